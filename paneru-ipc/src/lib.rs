@@ -0,0 +1,513 @@
+//! Types and a small client for talking to a running Paneru daemon over its Unix socket.
+//!
+//! Every request is a list of arguments, the same ones accepted by `paneru send-cmd`, framed as
+//! a little-endian `u32` byte count followed by the NUL-terminated arguments. Queries answer
+//! with a single JSON document and a newline, subscriptions keep the socket open and write one
+//! JSON event per line.
+//!
+//! ```no_run
+//! use paneru_ipc::{Client, StateEvent};
+//!
+//! let client = Client::new();
+//! client.send_command(["window", "focus", "east"])?;
+//!
+//! let active = client.query_active()?;
+//! println!("workspace {:?}", active.virtual_workspace_number);
+//!
+//! for event in client.subscribe()? {
+//!     if let StateEvent::WindowFocused { title, .. } = event? {
+//!         println!("focused {title:?}");
+//!     }
+//! }
+//! # Ok::<(), paneru_ipc::Error>(())
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{BufRead, BufReader, Lines, Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+
+/// The path of the Unix socket the daemon listens on.
+pub const SOCKET_PATH: &str = "/tmp/paneru.socket";
+
+/// Window identifier, as reported by the window server.
+pub type WinID = i32;
+/// Identifier of a native macOS Space.
+pub type WorkspaceId = u64;
+/// `CoreGraphics` display identifier.
+pub type DisplayId = u32;
+
+/// Errors returned by the client.
+#[derive(Debug)]
+pub enum Error {
+    /// Connecting to or talking over the socket failed.
+    Io(std::io::Error),
+    /// The daemon answered with something which is not the expected JSON.
+    Json(serde_json::Error),
+    /// The request does not fit into the `u32` length prefix.
+    RequestTooLarge(usize),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "socket error: {err}"),
+            Error::Json(err) => write!(f, "malformed response: {err}"),
+            Error::RequestTooLarge(size) => write!(f, "request of {size} bytes is too large"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Json(err) => Some(err),
+            Error::RequestTooLarge(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The parts of the state document a query can ask for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StateQueryKind {
+    State,
+    VirtualWorkspaces,
+    Active,
+    Windows,
+}
+
+impl StateQueryKind {
+    /// Returns the request arguments for this query.
+    #[must_use]
+    pub fn args(self) -> [&'static str; 3] {
+        match self {
+            StateQueryKind::State => ["query", "state", "--json"],
+            StateQueryKind::VirtualWorkspaces => ["query", "virtual-workspaces", "--json"],
+            StateQueryKind::Active => ["query", "active", "--json"],
+            StateQueryKind::Windows => ["query", "windows", "--json"],
+        }
+    }
+
+    /// Recognises a query request, with or without the trailing `--json`.
+    #[must_use]
+    pub fn from_args(argv: &[&str]) -> Option<Self> {
+        match argv {
+            ["query", "state", "--json"] | ["query", "state"] => Some(StateQueryKind::State),
+            ["query", "virtual-workspaces", "--json"] | ["query", "virtual-workspaces"] => {
+                Some(StateQueryKind::VirtualWorkspaces)
+            }
+            ["query", "active", "--json"] | ["query", "active"] => Some(StateQueryKind::Active),
+            ["query", "windows", "--json"] | ["query", "windows"] => Some(StateQueryKind::Windows),
+            _ => None,
+        }
+    }
+}
+
+/// Returns `true` if `argv` is a subscription request.
+#[must_use]
+pub fn is_subscribe_request(argv: &[&str]) -> bool {
+    matches!(argv, ["subscribe", "--json"] | ["subscribe"])
+}
+
+/// The complete state document returned by `query state`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PaneruQueryState {
+    pub version: u32,
+    pub timestamp: u64,
+    pub active: PaneruActiveState,
+    pub virtual_workspaces: Vec<PaneruVirtualWorkspaceState>,
+}
+
+impl PaneruQueryState {
+    /// Serializes the part of the document selected by `kind`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the `serde_json` error if serialization fails.
+    pub fn to_query_json(&self, kind: StateQueryKind) -> serde_json::Result<String> {
+        match kind {
+            StateQueryKind::State => serde_json::to_string(self),
+            StateQueryKind::VirtualWorkspaces => serde_json::to_string(&self.virtual_workspaces),
+            StateQueryKind::Active => serde_json::to_string(&self.active),
+            StateQueryKind::Windows => serde_json::to_string(&self.windows().collect::<Vec<_>>()),
+        }
+    }
+
+    /// Iterates over the windows of all virtual workspace rows.
+    pub fn windows(&self) -> impl Iterator<Item = &PaneruWindowState> {
+        self.virtual_workspaces
+            .iter()
+            .flat_map(|workspace| &workspace.windows)
+    }
+}
+
+/// The active display, workspace and focused window, returned by `query active`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct PaneruActiveState {
+    pub display_id: Option<DisplayId>,
+    pub native_workspace_id: Option<WorkspaceId>,
+    pub virtual_workspace_number: Option<u32>,
+    pub focused_window_id: Option<WinID>,
+    pub focused_bundle_id: Option<String>,
+    pub focused_app_name: Option<String>,
+    pub focused_window_title: Option<String>,
+}
+
+/// One virtual workspace row, returned by `query virtual-workspaces`.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PaneruVirtualWorkspaceState {
+    pub number: u32,
+    pub native_workspace_id: WorkspaceId,
+    pub active: bool,
+    pub windows: Vec<PaneruWindowState>,
+}
+
+/// A managed window inside a virtual workspace row.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PaneruWindowState {
+    pub window_id: WinID,
+    pub bundle_id: String,
+    pub app_name: String,
+    pub title: String,
+    pub focused: bool,
+    pub floating: bool,
+}
+
+impl PaneruWindowState {
+    /// Formats the window as a tab separated `id`, `app name`, `title` line for pickers like
+    /// `fzf`. The id comes first, so the picked line can go straight to `window focus-id`.
+    #[must_use]
+    pub fn picker_line(&self) -> String {
+        let clean = |text: &str| text.replace(['\t', '\n'], " ");
+        format!(
+            "{}\t{}\t{}",
+            self.window_id,
+            clean(&self.app_name),
+            clean(&self.title)
+        )
+    }
+}
+
+/// Summary of a configuration reload, carried by [`StateEvent::ConfigReloaded`].
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct ConfigReloadChanges {
+    pub options: Vec<String>,
+    pub bindings_added: Vec<String>,
+    pub bindings_removed: Vec<String>,
+    pub bindings_changed: Vec<String>,
+    pub sections: Vec<String>,
+    pub window_rules: usize,
+}
+
+/// An event line written to subscribers.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum StateEvent {
+    VirtualWorkspaceChanged {
+        active: PaneruActiveState,
+    },
+    WindowsChanged {
+        virtual_workspace_number: Option<u32>,
+        active: PaneruActiveState,
+    },
+    WindowFocused {
+        window_id: Option<WinID>,
+        bundle_id: Option<String>,
+        title: Option<String>,
+        virtual_workspace_number: Option<u32>,
+    },
+    WindowTitleChanged {
+        window_id: WinID,
+        title: String,
+    },
+    DisplayChanged {
+        display_id: Option<DisplayId>,
+    },
+    ConfigReloaded {
+        changes: ConfigReloadChanges,
+    },
+}
+
+/// Frames `args` into a socket request: a little-endian `u32` length and the NUL-terminated
+/// arguments.
+///
+/// # Errors
+///
+/// Returns `Error::RequestTooLarge` if the arguments exceed `u32::MAX` bytes.
+pub fn encode_request<I, S>(args: I) -> Result<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let payload = args
+        .into_iter()
+        .flat_map(|arg| [arg.as_ref().as_bytes(), &[0]].concat())
+        .collect::<Vec<_>>();
+    let size = u32::try_from(payload.len()).map_err(|_| Error::RequestTooLarge(payload.len()))?;
+    Ok([size.to_le_bytes().as_slice(), &payload].concat())
+}
+
+/// Splits a request payload, without its length prefix, back into arguments.
+#[must_use]
+pub fn decode_request(payload: &[u8]) -> Vec<String> {
+    payload
+        .split(|c| *c == 0)
+        .filter(|s| !s.is_empty())
+        .map(|s| String::from_utf8_lossy(s).to_string())
+        .collect()
+}
+
+/// A connection factory for the daemon socket. Every request opens its own connection.
+#[derive(Clone, Debug)]
+pub struct Client {
+    socket_path: PathBuf,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Client {
+    /// Creates a client for the default socket path.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_socket_path(SOCKET_PATH)
+    }
+
+    /// Creates a client for a socket at a custom path.
+    pub fn with_socket_path(path: impl AsRef<Path>) -> Self {
+        Self {
+            socket_path: path.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Sends a request and returns the open stream for reading the response.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Io` if the daemon is not running or the request can not be written.
+    pub fn request<I, S>(&self, args: I) -> Result<UnixStream>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let request = encode_request(args)?;
+        let mut stream = UnixStream::connect(&self.socket_path)?;
+        stream.write_all(&request)?;
+        Ok(stream)
+    }
+
+    /// Sends a command, using the same arguments as `paneru send-cmd`, e.g.
+    /// `["window", "focus", "east"]`.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::request`].
+    pub fn send_command<I, S>(&self, args: I) -> Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.request(args).map(|_| ())
+    }
+
+    /// Runs a query and returns the raw JSON response without the trailing newline.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Io` if the daemon can not be reached or the response can not be read.
+    pub fn query_json(&self, kind: StateQueryKind) -> Result<String> {
+        let mut output = String::new();
+        self.request(kind.args())?.read_to_string(&mut output)?;
+        output.truncate(output.trim_end().len());
+        Ok(output)
+    }
+
+    /// Returns the complete state document.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Error::Json` for an unexpected response, otherwise see [`Client::query_json`].
+    pub fn query_state(&self) -> Result<PaneruQueryState> {
+        Ok(serde_json::from_str(
+            &self.query_json(StateQueryKind::State)?,
+        )?)
+    }
+
+    /// Returns every virtual workspace row.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::query_state`].
+    pub fn query_virtual_workspaces(&self) -> Result<Vec<PaneruVirtualWorkspaceState>> {
+        Ok(serde_json::from_str(
+            &self.query_json(StateQueryKind::VirtualWorkspaces)?,
+        )?)
+    }
+
+    /// Returns the active display, workspace and focused window.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::query_state`].
+    pub fn query_active(&self) -> Result<PaneruActiveState> {
+        Ok(serde_json::from_str(
+            &self.query_json(StateQueryKind::Active)?,
+        )?)
+    }
+
+    /// Returns the windows of all virtual workspace rows.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::query_state`].
+    pub fn query_windows(&self) -> Result<Vec<PaneruWindowState>> {
+        Ok(serde_json::from_str(
+            &self.query_json(StateQueryKind::Windows)?,
+        )?)
+    }
+
+    /// Subscribes to state events. The returned iterator blocks until the next event arrives and
+    /// ends when the daemon closes the connection.
+    ///
+    /// # Errors
+    ///
+    /// See [`Client::request`].
+    pub fn subscribe(&self) -> Result<Subscription> {
+        let stream = self.request(["subscribe", "--json"])?;
+        Ok(Subscription {
+            lines: BufReader::new(stream).lines(),
+        })
+    }
+}
+
+/// A stream of [`StateEvent`]s, created by [`Client::subscribe`].
+pub struct Subscription {
+    lines: Lines<BufReader<UnixStream>>,
+}
+
+impl Iterator for Subscription {
+    type Item = Result<StateEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(err) => return Some(Err(err.into())),
+        };
+        Some(serde_json::from_str(&line).map_err(Error::from))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_framing_roundtrip() {
+        let request = encode_request(["window", "focus", "east"]).expect("request should encode");
+        let (size, payload) = request.split_at(4);
+        let size = u32::from_le_bytes(size.try_into().expect("four byte prefix"));
+
+        assert_eq!(size as usize, payload.len());
+        assert_eq!(payload, b"window\0focus\0east\0");
+        assert_eq!(decode_request(payload), ["window", "focus", "east"]);
+    }
+
+    #[test]
+    fn test_query_kind_args_roundtrip() {
+        for kind in [
+            StateQueryKind::State,
+            StateQueryKind::VirtualWorkspaces,
+            StateQueryKind::Active,
+            StateQueryKind::Windows,
+        ] {
+            assert_eq!(StateQueryKind::from_args(&kind.args()), Some(kind));
+            assert_eq!(StateQueryKind::from_args(&kind.args()[..2]), Some(kind));
+        }
+        assert_eq!(StateQueryKind::from_args(&["query", "displays"]), None);
+        assert!(is_subscribe_request(&["subscribe"]));
+        assert!(!is_subscribe_request(&["subscribe", "--yaml"]));
+    }
+
+    #[test]
+    fn test_windows_query_and_picker_lines() {
+        let window = |window_id, title: &str| PaneruWindowState {
+            window_id,
+            bundle_id: "com.apple.Terminal".to_string(),
+            app_name: "Terminal".to_string(),
+            title: title.to_string(),
+            focused: false,
+            floating: false,
+        };
+        let row = |number, windows| PaneruVirtualWorkspaceState {
+            number,
+            native_workspace_id: 1,
+            active: number == 1,
+            windows,
+        };
+        let state = PaneruQueryState {
+            version: 1,
+            timestamp: 0,
+            active: PaneruActiveState::default(),
+            virtual_workspaces: vec![
+                row(1, vec![window(10, "build\tlog")]),
+                row(2, vec![window(20, "notes")]),
+            ],
+        };
+
+        let json = state
+            .to_query_json(StateQueryKind::Windows)
+            .expect("windows should serialize");
+        let windows: Vec<PaneruWindowState> =
+            serde_json::from_str(&json).expect("windows should parse");
+        assert_eq!(windows, [window(10, "build\tlog"), window(20, "notes")]);
+        assert_eq!(windows[0].picker_line(), "10\tTerminal\tbuild log");
+    }
+
+    #[test]
+    fn test_state_events_parse() {
+        let focused = r#"{"event":"window_focused","window_id":321,"bundle_id":"com.apple.Terminal","title":"paneru","virtual_workspace_number":3}"#;
+        assert_eq!(
+            serde_json::from_str::<StateEvent>(focused).expect("event should parse"),
+            StateEvent::WindowFocused {
+                window_id: Some(321),
+                bundle_id: Some("com.apple.Terminal".to_string()),
+                title: Some("paneru".to_string()),
+                virtual_workspace_number: Some(3),
+            }
+        );
+
+        let display = r#"{"event":"display_changed","display_id":null}"#;
+        assert_eq!(
+            serde_json::from_str::<StateEvent>(display).expect("event should parse"),
+            StateEvent::DisplayChanged { display_id: None }
+        );
+
+        let reloaded = r#"{"event":"config_reloaded","changes":{"options":["animation_speed"],"bindings_added":[],"bindings_removed":[],"bindings_changed":[],"sections":["padding"],"window_rules":2}}"#;
+        let StateEvent::ConfigReloaded { changes } =
+            serde_json::from_str::<StateEvent>(reloaded).expect("event should parse")
+        else {
+            panic!("expected a config_reloaded event");
+        };
+        assert_eq!(changes.options, ["animation_speed"]);
+        assert_eq!(changes.window_rules, 2);
+    }
+}
@@ -0,0 +1,37 @@
+use objc2::MainThreadMarker;
+use objc2::runtime::AnyObject;
+use objc2_app_kit::{
+    NSAccessibilityAnnouncementKey, NSAccessibilityAnnouncementRequestedNotification,
+    NSAccessibilityPostNotificationWithUserInfo, NSAccessibilityPriorityKey,
+    NSAccessibilityPriorityLevel, NSApplication,
+};
+use objc2_foundation::{NSDictionary, NSNumber, NSString};
+
+/// Posts VoiceOver announcements. The notifications are sent on behalf of the application
+/// object, which is only accessible from the main thread.
+pub struct Announcer {
+    mtm: MainThreadMarker,
+}
+
+impl Announcer {
+    pub fn new(mtm: MainThreadMarker) -> Self {
+        Self { mtm }
+    }
+
+    pub fn announce(&self, message: &str) {
+        let app = NSApplication::sharedApplication(self.mtm);
+        let message = NSString::from_str(message);
+        let priority = NSNumber::new_isize(NSAccessibilityPriorityLevel::High.0);
+        let keys = unsafe { [NSAccessibilityAnnouncementKey, NSAccessibilityPriorityKey] };
+        let values: [&AnyObject; 2] = [message.as_ref(), priority.as_ref()];
+        let user_info = NSDictionary::from_slices(&keys, &values);
+
+        unsafe {
+            NSAccessibilityPostNotificationWithUserInfo(
+                &app,
+                NSAccessibilityAnnouncementRequestedNotification,
+                Some(&user_info),
+            );
+        }
+    }
+}
@@ -1,30 +1,49 @@
-use std::time::Duration;
+use std::os::unix::process::CommandExt as _;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
 use bevy::app::PreUpdate;
 use bevy::ecs::entity::{Entity, EntityHashSet};
 use bevy::ecs::hierarchy::ChildOf;
 use bevy::ecs::message::MessageReader;
 use bevy::ecs::query::{Has, With, Without};
-use bevy::ecs::system::{Commands, Query, Res, Single};
+use bevy::ecs::system::{Commands, Local, Query, Res, ResMut, Single};
 use bevy::math::IRect;
+use objc2_core_graphics::CGDirectDisplayID;
+use regex::Regex;
 use tracing::{Level, instrument};
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
 
+mod jump;
+mod preset;
 mod query;
+mod snapshot;
+mod strip;
+mod workspace;
+
+pub use jump::JumpBadges;
 
 use crate::config::Config;
 use crate::ecs::display::FloatingLayer;
 use crate::ecs::focus::FocusHistory;
+use crate::ecs::journal::EventJournal;
 use crate::ecs::layout::{Column, LayoutStrip, StackItem, clamp_origin_to_viewport};
 use crate::ecs::params::{ActiveDisplay, ActiveDisplayMut, Windows};
 use crate::ecs::{
-    ActiveDisplayMarker, ActiveWorkspaceMarker, Bounds, DockPosition, FocusedMarker,
-    FullWidthMarker, NativeFullscreenMarker, SelectedVirtualMarker, SendMessageTrigger,
-    SpawnCommandsExt, Timeout, Unmanaged,
+    ActiveDisplayMarker, ActiveWorkspaceMarker, BProcess, Bounds, DockPosition, FocusedAt,
+    FocusedMarker, FullWidthMarker, InsertPointMarker, MonocleMarker, NativeFullscreenMarker,
+    NextInsertion, OriginalFrame, OverviewFrame, OverviewMarker, PinnedMarker, PipMarker, Position,
+    SelectedVirtualMarker, SendMessageTrigger, SpawnCommandsExt, StickyMarker, Timeout, Unmanaged,
+    Wakeup, ZenMarker,
 };
 use crate::events::Event;
 use crate::manager::{Application, Display, Origin, Size, Window, WindowManager, origin_from};
-use crate::platform::WorkspaceId;
+use crate::platform::input::set_active_mode;
+use crate::platform::{WinID, WorkspaceId};
+use crate::util::{set_log_filter, spawn_and_reap};
+
+/// Smallest width or height `Operation::ResizeBy` will shrink a window to.
+const MIN_RESIZE_BY_EXTENT: i32 = 200;
 
 /// Represents a cardinal or directional choice for window manipulation.
 #[derive(Clone, Debug)]
@@ -57,6 +76,38 @@ pub enum ResizeDirection {
     Shrink,
 }
 
+/// Dimension of the focused window changed by `Operation::ResizeBy`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResizeAxis {
+    Width,
+    Height,
+}
+
+/// Signed amount by which `Operation::ResizeBy` grows or shrinks the focused window.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResizeAmount {
+    Pixels(i32),
+    /// Percentage of the viewport extent along the resized axis.
+    Percent(f64),
+}
+
+impl ResizeAmount {
+    /// Converts the amount to pixels, resolving percentages against `extent`.
+    pub fn pixels(self, extent: i32) -> i32 {
+        match self {
+            ResizeAmount::Pixels(pixels) => pixels,
+            ResizeAmount::Percent(percent) => (percent / 100.0 * f64::from(extent)).round() as i32,
+        }
+    }
+}
+
+/// Which way `Operation::CycleStack` moves through the focused stack.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CycleDirection {
+    Next,
+    Prev,
+}
+
 /// Controls whether focus follows the window after a move operation.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum MoveFocus {
@@ -64,31 +115,119 @@ pub enum MoveFocus {
     Stay,
 }
 
+/// Selects the display a window is moved to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DisplayTarget {
+    /// The next display after the active one.
+    Next,
+    /// The nearest display in the direction, judged by the display arrangement.
+    Direction(Direction),
+    /// The display with the one-based position in the arrangement, counted left to right, then
+    /// top to bottom.
+    Index(usize),
+}
+
 /// Defines the various operations that can be performed on windows.
 #[derive(Clone, Debug)]
 pub enum Operation {
     /// Focuses on a window in the specified `Direction`.
     Focus(Direction),
+    /// Focuses on a window in the specified `Direction`, always matching the stack row
+    /// of the neighbouring column, regardless of the `focus_skip_stacks` option.
+    FocusAny(Direction),
+    /// Focuses the next window in strip order, wrapping around at the end of the strip.
+    FocusNext,
+    /// Focuses the previous window in strip order, wrapping around at the start of the strip.
+    FocusPrev,
     /// Swaps the current window with another in the specified `Direction`.
     Swap(Direction),
     /// Centers the currently focused window on the display.
     Center,
+    /// Places the focused floating window into a cell of a virtual grid on the active display.
+    /// All values are ratios of the display size.
+    Grid {
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    },
     /// Resizes the focused window in the given direction.
     Resize(ResizeDirection),
     /// Resizes the focused window to an exact display-width ratio.
     SetWidth(f64),
+    /// Resizes the focused window to an exact width in pixels.
+    SetWidthPixels(i32),
+    /// Grows or shrinks the focused window along an axis by a fixed amount.
+    ResizeBy {
+        axis: ResizeAxis,
+        amount: ResizeAmount,
+    },
     /// Toggles the focused window to full width or a preset width.
     FullWidth,
+    /// Toggles the focused window to cover the whole padded viewport, restoring its previous
+    /// frame and strip position when toggled again.
+    Monocle,
+    /// Toggles zen mode: centers the focused window at the `zen_width` ratio of the viewport
+    /// and slides all other columns off-screen, restoring the strip when toggled again.
+    Zen,
+    /// Toggles an overview of the active strip: all of its windows shrink proportionally so the
+    /// whole strip fits on screen. Focus keys or a click pick a window, and toggling again (or
+    /// clicking) restores the real layout around it.
+    Overview,
+    /// Labels each visible column of the active strip with a badge and focuses the column whose
+    /// label key is pressed next.
+    Jump,
+    /// Slides the strip just enough to reveal the neighbouring column in the `Direction`
+    /// without moving focus, and slides it back after a few seconds or once the binding's
+    /// modifiers are released.
+    Peek(Direction),
     /// Moves the focused window to the next available display.
     ToNextDisplay(MoveFocus),
+    /// Moves the focused window to the display selected by the `DisplayTarget`.
+    ToDisplay(DisplayTarget, MoveFocus),
+    /// Moves the focused window to the native space of the active display with the one-based
+    /// number.
+    ToSpace(usize, MoveFocus),
     /// Distributes heights equally among windows in the focused stack.
     Equalize,
     /// Makes all columns in the active strip the same width as the focused window.
     Balance,
+    /// Divides the viewport width evenly among the visible columns of the active strip, or among
+    /// the columns of an inclusive, zero-based index range.
+    BalanceWidths(Option<(usize, usize)>),
+    /// Reverses the order of the columns in the active strip.
+    ReverseStrip,
+    /// Rotates the columns in the active strip by the given number of positions, wrapping
+    /// around at the ends. Negative values rotate towards the start of the strip.
+    RotateStrip(i32),
     /// Toggles the managed state of the focused window.
     Manage,
+    /// Minimizes the focused window into the Dock.
+    Minimize,
+    /// Restores the most recently minimized window and focuses it.
+    Deminimize,
+    /// Hides the application of the focused window. Used again, it unhides the application it
+    /// hid last, as long as that one is still hidden.
+    HideApp,
+    /// Moves the focused window back to the frame it had when Paneru first saw it.
+    RestoreOriginal,
     /// Stacks or unstacks a window. The boolean indicates whether to stack (`true`) or unstack (`false`).
     Stack(bool),
+    /// Toggles the focused window's stack between evenly split heights and accordion mode.
+    Accordion,
+    /// Focuses the next or previous window of the focused stack, wrapping around at its ends.
+    CycleStack(CycleDirection),
+    /// Moves the focused window to the top of its stack, handing it the tallest height share.
+    Promote,
+    /// Toggles pinning the focused column to the nearest edge of the viewport.
+    Pin,
+    /// Toggles marking the focused window as the point where the next new window opens.
+    SetInsertPoint,
+    /// Arms a one-shot directive placing the next created window relative to the focused column.
+    InsertNext(InsertNext),
+    /// Toggles whether the focused window follows the active space of its display. A tiled
+    /// window is floated first.
+    Sticky,
     /// Resizes and repositions the focused window to fit within the visible viewport
     /// (including edge padding).
     Snap,
@@ -104,6 +243,15 @@ pub enum Operation {
     FocusUnmanaged,
     /// Focuses the workspace's last-focused managed (tiled) window.
     FocusManaged,
+    /// Focuses the most recently focused window of an application, matched by its bundle id or
+    /// by a regular expression against its bundle id or name. With `launch` set, the
+    /// application is opened when it has no window yet.
+    FocusApp { matcher: Regex, launch: bool },
+    /// Focuses the window with the given id and scrolls it into view.
+    FocusId(WinID),
+    /// Steps focus through the recently used windows, Alt-Tab style. The step is committed
+    /// once the keybinding's modifiers are released.
+    CycleRecent,
     /// Raises all visible floating windows on the active display and focuses
     /// the last-floating window (idempotent — repeat presses behave the same).
     RaiseFloating,
@@ -111,6 +259,10 @@ pub enum Operation {
     /// Flips `FloatingLayer`, raises the other windows in the new top tier,
     /// and focuses the tier's last-focused window.
     ToggleFloatingLayer,
+    /// Saves the active strip's column order, stack shape and widths as a named layout preset.
+    LayoutSave(String),
+    /// Rearranges the active strip according to a previously saved layout preset.
+    LayoutApply(String),
 }
 
 /// Defines operations that can be performed on the mouse.
@@ -120,6 +272,77 @@ pub enum MouseMove {
     ToNextDisplay,
 }
 
+/// Low-level operations on the active `LayoutStrip`, targeting windows by their window id.
+/// Column indices are zero-based. Intended for external tools driving the layout over IPC.
+#[derive(Clone, Debug)]
+pub enum StripOperation {
+    /// Takes the window out of its column and inserts it as a new column at the index.
+    InsertAt(WinID, usize),
+    /// Moves the whole column containing the window to the index.
+    Move(WinID, usize),
+    /// Stacks the first window onto the column containing the second window.
+    Stack(WinID, WinID),
+    /// Resizes the column containing the window to a display-width ratio.
+    SetWidth(WinID, f64),
+    /// Pans the viewport west or east by the amount, leaving focus untouched.
+    Scroll(Direction, ResizeAmount),
+    /// Pans the viewport to the first or last column, leaving focus untouched.
+    ScrollTo(Direction),
+}
+
+/// Operations on snapshots of the whole managed state: displays, strips and window identities.
+#[derive(Clone, Debug)]
+pub enum StateOperation {
+    /// Writes the current state as JSON to the file.
+    Export(PathBuf),
+    /// Restores the state from a JSON file written by `Export`.
+    Import(PathBuf),
+}
+
+/// Operations on the active workspace as a whole.
+#[derive(Clone, Debug)]
+pub enum WorkspaceOperation {
+    /// Moves the active strip with all its windows onto the next display.
+    ToNextDisplay,
+}
+
+/// Operations involving more than one display.
+#[derive(Clone, Debug)]
+pub enum DisplayOperation {
+    /// Exchanges the windows of the active strip with those of the strip shown on the next
+    /// display.
+    SwapWorkspaces,
+}
+
+/// Operations on the native macOS spaces.
+#[derive(Clone, Debug)]
+pub enum SpaceOperation {
+    /// Switches the active display to its space with the one-based number.
+    Focus(usize),
+}
+
+/// Where `Operation::InsertNext` opens the next window, relative to the focused column.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InsertNext {
+    /// Stacked onto the focused column, below its windows.
+    Stacked,
+    /// In a new column to the right of the focused one.
+    Right,
+    /// In a new column to the left of the focused one.
+    Left,
+}
+
+/// Runtime adjustments of the gaps of the active display, like i3-gaps.
+#[derive(Clone, Debug)]
+pub enum GapsOperation {
+    /// Grows or shrinks the gap between columns and stacked windows by the given pixels.
+    Inner(i32),
+    /// Grows or shrinks the padding around the display edges by the given pixels.
+    Outer(i32),
+    /// Drops the adjustments, returning to the configured gaps.
+    Reset,
+}
+
 /// Represents a command that can be issued to the window manager.
 #[derive(Clone, Debug)]
 pub enum Command {
@@ -127,6 +350,29 @@ pub enum Command {
     Window(Operation),
     /// A command targeting the mouse with a specific `MouseOperation`.
     Mouse(MouseMove),
+    /// A command manipulating the active layout strip directly.
+    Strip(StripOperation),
+    /// A command exporting or importing a snapshot of the managed state.
+    State(StateOperation),
+    /// A command targeting the active workspace.
+    Workspace(WorkspaceOperation),
+    /// A command spanning the displays.
+    Display(DisplayOperation),
+    /// A command switching the native macOS spaces.
+    Space(SpaceOperation),
+    /// A command adjusting the gaps of the active display.
+    Gaps(GapsOperation),
+    /// Switches the active keybinding mode; `default` returns to `[bindings]`.
+    Mode(String),
+    /// Sets a configuration option of the running instance, without editing the file.
+    SetOption(String, String),
+    /// Starts an external program, detached from the window manager.
+    Exec(Vec<String>),
+    /// Replaces the log filter of the running instance, e.g. `debug` or `paneru=trace`.
+    LogLevel(String),
+    /// Writes the event journal to the given file, or to `paneru-journal.log` in the temporary
+    /// directory.
+    DumpJournal(Option<PathBuf>),
     /// A command to quit the window manager application.
     Quit,
     /// A command to restart the window manager service.
@@ -135,7 +381,12 @@ pub enum Command {
 }
 
 pub fn register_commands(app: &mut bevy::app::App) {
+    jump::register_jump_commands(app);
     query::register_query_commands(app);
+    strip::register_strip_commands(app);
+    snapshot::register_snapshot_commands(app);
+    preset::register_preset_commands(app);
+    workspace::register_workspace_commands(app);
     app.add_systems(
         PreUpdate,
         (
@@ -144,20 +395,51 @@ pub fn register_commands(app: &mut bevy::app::App) {
             print_internal_state_handler,
             mouse_to_next_display,
             resize_window,
-            command_center_window,
-            full_width_window,
+            resize_window_height,
+            (command_center_window, grid_window),
+            (
+                full_width_window,
+                pin_column,
+                set_insert_point,
+                arm_insert_next,
+                toggle_sticky,
+            ),
+            (monocle_window, zen_window, overview_strip),
             to_next_display,
             equalize_column,
+            toggle_accordion,
             balance_strip,
-            manage_window,
-            stack_windows_handler,
-            command_move_focus,
-            command_focus_unmanaged,
-            command_focus_managed,
-            command_raise_floating,
-            command_toggle_floating_layer,
-            command_swap_focus,
-            snap_window,
+            balance_column_widths,
+            reorder_strip,
+            (
+                manage_window,
+                minimize_window,
+                hide_application,
+                restore_original_frame,
+            ),
+            (stack_windows_handler, cycle_stack_focus, promote_in_stack),
+            (
+                command_move_focus,
+                command_focus_cycle,
+                command_focus_unmanaged,
+                command_focus_managed,
+                command_focus_app,
+                command_focus_id,
+                cycle_recent_windows,
+                command_raise_floating,
+                command_toggle_floating_layer,
+                command_swap_focus,
+                throw_pip_window,
+            ),
+            (snap_window, peek_neighbour),
+            (
+                command_mode_handler,
+                command_set_option_handler,
+                command_gaps_handler,
+                command_exec_handler,
+                command_log_level_handler,
+                dump_journal_handler,
+            ),
         ),
     );
 }
@@ -208,7 +490,7 @@ fn get_window_in_direction(
 
         Direction::North => match strip.get(index).ok()? {
             Column::Single(_) | Column::Tabs(_) | Column::Fullscren(_) => None,
-            Column::Stack(stack) => stack
+            Column::Stack(stack) | Column::Accordion(stack) => stack
                 .iter()
                 .enumerate()
                 .find(|(_, item)| item.contains(entity))
@@ -218,7 +500,7 @@ fn get_window_in_direction(
 
         Direction::South => match strip.get(index).ok()? {
             Column::Single(_) | Column::Tabs(_) | Column::Fullscren(_) => None,
-            Column::Stack(stack) => stack
+            Column::Stack(stack) | Column::Accordion(stack) => stack
                 .iter()
                 .enumerate()
                 .find(|(_, item)| item.contains(entity))
@@ -232,13 +514,30 @@ fn get_window_in_direction(
     }
 }
 
+/// Like `get_window_in_direction`, but treats stacked columns as a single unit: moving
+/// `West` or `East` lands on the top of the neighbouring column instead of the window at
+/// the same stack row. Other directions behave exactly as `get_window_in_direction`.
+fn get_column_in_direction(
+    direction: &Direction,
+    entity: Entity,
+    strip: &LayoutStrip,
+) -> Option<Entity> {
+    let index = strip.index_of(entity).ok()?;
+    let neighbour = match direction {
+        Direction::West => index.checked_sub(1)?,
+        Direction::East => index + 1,
+        _ => return get_window_in_direction(direction, entity, strip),
+    };
+    strip.get(neighbour).ok().and_then(|column| column.top())
+}
+
 /// 45° direction cone, closest by squared Euclidean distance.
 /// `First` / `Last` are strip-only and return `None`.
-fn pick_nearest_in_direction(
+fn pick_nearest_in_direction<T>(
     direction: &Direction,
     focused_center: bevy::math::IVec2,
-    candidates: impl IntoIterator<Item = (Entity, bevy::math::IVec2)>,
-) -> Option<Entity> {
+    candidates: impl IntoIterator<Item = (T, bevy::math::IVec2)>,
+) -> Option<T> {
     candidates
         .into_iter()
         .filter_map(|(entity, center)| {
@@ -322,11 +621,17 @@ fn command_move_focus(
     workspaces: Query<(&LayoutStrip, Entity, Option<&NativeFullscreenMarker>)>,
     active_display: ActiveDisplay,
     window_manager: Res<WindowManager>,
+    config: Res<Config>,
     mut commands: Commands,
 ) {
-    let Some(Operation::Focus(direction)) =
-        filter_window_operations(&mut messages, |op| matches!(op, Operation::Focus(_))).next()
-    else {
+    let Some((direction, skip_stacks)) = filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::Focus(_) | Operation::FocusAny(_))
+    })
+    .find_map(|op| match op {
+        Operation::Focus(direction) => Some((direction, config.focus_skip_stacks())),
+        Operation::FocusAny(direction) => Some((direction, false)),
+        _ => None,
+    }) else {
         return;
     };
 
@@ -381,7 +686,12 @@ fn command_move_focus(
     // be unable to leave that window. Enter the active strip from the
     // appropriate side so subsequent presses behave normally.
     let candidate = if active_strip.contains(focused_entity) {
-        get_window_in_direction(direction, focused_entity, active_strip).or_else(|| {
+        if skip_stacks {
+            get_column_in_direction(direction, focused_entity, active_strip)
+        } else {
+            get_window_in_direction(direction, focused_entity, active_strip)
+        }
+        .or_else(|| {
             // At the right edge going East, enter the fullscreen workspaces.
             (matches!(direction, Direction::East)
                 && active_strip.right_neighbour(focused_entity).is_none())
@@ -463,6 +773,43 @@ fn command_focus_unmanaged(
     }
 }
 
+/// Cycles focus through the windows of the active strip in order, descending into stacks
+/// and wrapping around at either end. Unlike directional focus, this never dead-ends at the
+/// strip edges. A window outside of the strip, e.g. a floating one, cycles from the ends.
+#[allow(clippy::needless_pass_by_value)]
+fn command_focus_cycle(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    mut commands: Commands,
+) {
+    let Some(forward) = filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::FocusNext | Operation::FocusPrev)
+    })
+    .map(|op| matches!(op, Operation::FocusNext))
+    .next() else {
+        return;
+    };
+
+    let order = active_display.active_strip().all_windows();
+    if order.is_empty() {
+        return;
+    }
+    let current = windows
+        .focused()
+        .and_then(|(_, focused)| order.iter().position(|entity| *entity == focused));
+    let index = match (current, forward) {
+        (Some(index), true) => (index + 1) % order.len(),
+        (Some(index), false) => (index + order.len() - 1) % order.len(),
+        (None, true) => 0,
+        (None, false) => order.len() - 1,
+    };
+
+    let entity = order[index];
+    commands.focus_entity(entity, true);
+    commands.reshuffle_around(entity);
+}
+
 #[allow(clippy::needless_pass_by_value)]
 fn command_focus_managed(
     mut messages: MessageReader<Event>,
@@ -491,6 +838,226 @@ fn command_focus_managed(
     }
 }
 
+/// Focuses the most recently focused managed window of the application picked by
+/// `Operation::FocusApp` and scrolls it into view. If the application has no window and
+/// `launch` is set, it is opened with `open -b` for bundle ids, or `open -a` otherwise.
+#[allow(clippy::needless_pass_by_value)]
+fn command_focus_app(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    apps: Query<&Application>,
+    focused_at: Query<&FocusedAt>,
+    mut commands: Commands,
+) {
+    let Some(Operation::FocusApp { matcher, launch }) =
+        filter_window_operations(&mut messages, |op| matches!(op, Operation::FocusApp { .. }))
+            .next()
+    else {
+        return;
+    };
+
+    let pattern = matcher.as_str();
+    let matches = |app: &Application| {
+        let bundle_id = app.bundle_id().unwrap_or_default();
+        bundle_id == pattern || matcher.is_match(&bundle_id) || matcher.is_match(app.name())
+    };
+    let target = windows
+        .managed_iter()
+        .filter(|(_, _, child_of)| apps.get(child_of.parent()).is_ok_and(|app| matches(app)))
+        .max_by_key(|(_, entity, _)| focused_at.get(*entity).ok().map(|at| at.0));
+
+    if let Some((window, entity, _)) = target {
+        debug!("focusing window {} {entity} of '{pattern}'.", window.id());
+        commands.focus_entity(entity, true);
+        commands.reshuffle_around(entity);
+    } else if *launch {
+        let flag = if pattern.contains('.') { "-b" } else { "-a" };
+        debug!("no window of '{pattern}', launching it.");
+        commands.trigger(SendMessageTrigger(Event::Command {
+            command: Command::Exec(vec!["open".into(), flag.into(), pattern.into()]),
+        }));
+    }
+}
+
+/// An in-progress `window cycle-recent` session. Keeps the windows in most recently used order
+/// as it was when the cycle started, together with their original focus times, and the
+/// position of the currently previewed window.
+struct RecentCycle {
+    order: Vec<(Entity, Instant)>,
+    index: usize,
+}
+
+/// Steps through the recently used windows on each `Operation::CycleRecent` and commits the
+/// selection on `Event::CycleModifiersReleased`. Previewed windows get focused right away, so
+/// on commit their focus times are restored, leaving only the selected window moved to the
+/// front of the history.
+#[allow(clippy::needless_pass_by_value)]
+fn cycle_recent_windows(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    focused_at: Query<(Entity, &FocusedAt)>,
+    mut cycle: Local<Option<RecentCycle>>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        match event {
+            Event::Command {
+                command: Command::Window(Operation::CycleRecent),
+            } => {
+                let focused = windows.focused().map(|(_, entity)| entity);
+                // Start over if focus moved elsewhere since the last step.
+                if cycle.as_ref().is_none_or(|cycle| {
+                    focused != cycle.order.get(cycle.index).map(|(entity, _)| *entity)
+                }) {
+                    let mut order = focused_at
+                        .iter()
+                        .filter(|(entity, _)| {
+                            windows
+                                .get_managed(*entity)
+                                .is_some_and(|(_, _, unmanaged)| {
+                                    matches!(unmanaged, None | Some(Unmanaged::Floating))
+                                })
+                        })
+                        .map(|(entity, at)| (entity, at.0))
+                        .collect::<Vec<_>>();
+                    order.sort_by(|a, b| b.1.cmp(&a.1));
+                    *cycle = Some(RecentCycle { order, index: 0 });
+                }
+
+                let Some(cycle) = cycle.as_mut().filter(|cycle| cycle.order.len() > 1) else {
+                    continue;
+                };
+                cycle.index = (cycle.index + 1) % cycle.order.len();
+                let (entity, _) = cycle.order[cycle.index];
+                debug!("cycling to recent window {entity}.");
+                commands.focus_entity(entity, true);
+            }
+            Event::CycleModifiersReleased => {
+                let Some(RecentCycle { order, index }) = cycle.take() else {
+                    continue;
+                };
+                for (position, (entity, at)) in order.into_iter().enumerate() {
+                    let at = if position == index {
+                        Instant::now()
+                    } else {
+                        at
+                    };
+                    if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                        entity_commands.try_insert(FocusedAt(at));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+}
+
+/// A strip slid aside by `Operation::Peek`, and the offset it returns to.
+#[derive(Clone, Copy)]
+struct Peek {
+    strip: Entity,
+    offset: Origin,
+    started: Instant,
+}
+
+/// Slides the strip just enough to fully reveal the column next to the focused one, leaving
+/// focus where it is. The strip returns to its previous offset after `peek_duration_ms`, or
+/// earlier when the modifiers of the peek binding are released. Peeking again while a peek is
+/// in progress keeps the original offset to return to.
+#[allow(clippy::needless_pass_by_value)]
+fn peek_neighbour(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    strips: Query<&Position, With<LayoutStrip>>,
+    config: Res<Config>,
+    mut peek: Local<Option<Peek>>,
+    mut wakeup: ResMut<Wakeup>,
+    mut commands: Commands,
+) {
+    let mut released = false;
+    for event in messages.read() {
+        match event {
+            Event::Command {
+                command: Command::Window(Operation::Peek(direction)),
+            } => {
+                let strip = active_display.active_strip();
+                let strip_entity = active_display.active_strip_entity();
+                let Some(neighbour) = windows
+                    .focused()
+                    .and_then(|(_, entity)| strip.index_of(entity).ok())
+                    .and_then(|index| match direction {
+                        Direction::West => index.checked_sub(1),
+                        Direction::East => Some(index + 1),
+                        Direction::First => Some(0),
+                        Direction::Last => strip.len().checked_sub(1),
+                        Direction::North | Direction::South => None,
+                    })
+                    .and_then(|index| strip.get(index).ok())
+                    .and_then(|column| column.top())
+                else {
+                    continue;
+                };
+                let (Some(size), Some(layout_position), Ok(Position(current))) = (
+                    windows.size(neighbour),
+                    windows.layout_position(neighbour),
+                    strips.get(strip_entity),
+                ) else {
+                    continue;
+                };
+
+                let viewport = active_display.actual_bounds(&config);
+                let origin = clamp_origin_to_viewport(layout_position.0 + *current, size, viewport);
+                let target = (origin - layout_position.0).with_y(current.y);
+                let offset = peek
+                    .as_ref()
+                    .filter(|peek| peek.strip == strip_entity)
+                    .map_or(*current, |peek| peek.offset);
+                debug!("peeking {direction:?} at {neighbour}.");
+                *peek = Some(Peek {
+                    strip: strip_entity,
+                    offset,
+                    started: Instant::now(),
+                });
+                commands.reposition_entity(strip_entity, target);
+            }
+            Event::CycleModifiersReleased => released = true,
+            _ => (),
+        }
+    }
+
+    if let Some(Peek {
+        strip,
+        offset,
+        started,
+    }) = *peek
+    {
+        if released || started.elapsed() >= config.peek_duration() {
+            debug!("peek ended.");
+            commands.reposition_entity(strip, offset);
+            *peek = None;
+        } else {
+            wakeup.at(started + config.peek_duration());
+        }
+    }
+}
+
+/// Focuses a window by its id, e.g. one picked from `query windows` by an external picker.
+#[allow(clippy::needless_pass_by_value)]
+fn command_focus_id(mut messages: MessageReader<Event>, windows: Windows, mut commands: Commands) {
+    let Some(Operation::FocusId(window_id)) =
+        filter_window_operations(&mut messages, |op| matches!(op, Operation::FocusId(_))).next()
+    else {
+        return;
+    };
+    let Some((_, entity)) = windows.find(*window_id) else {
+        warn!("focus-id: no window with id {window_id}.");
+        return;
+    };
+    commands.focus_entity(entity, true);
+    commands.reshuffle_around(entity);
+}
+
 #[allow(clippy::needless_pass_by_value)]
 fn command_raise_floating(
     mut messages: MessageReader<Event>,
@@ -632,6 +1199,7 @@ fn command_toggle_floating_layer(
 fn command_swap_focus(
     mut messages: MessageReader<Event>,
     windows: Windows,
+    pips: Query<&PipMarker>,
     mut active_display: ActiveDisplayMut,
     mut commands: Commands,
 ) {
@@ -640,6 +1208,13 @@ fn command_swap_focus(
     else {
         return;
     };
+    // Picture-in-picture windows are thrown around by `throw_pip_window` instead.
+    if windows
+        .focused()
+        .is_some_and(|(_, entity)| pips.contains(entity))
+    {
+        return;
+    }
 
     let active_strip = active_display.active_strip();
     let mut handler = || {
@@ -653,7 +1228,8 @@ fn command_swap_focus(
         );
 
         if index == new_index
-            && let Some(Column::Stack(stack)) = active_strip.get_column_mut(index)
+            && let Some(Column::Stack(stack) | Column::Accordion(stack)) =
+                active_strip.get_column_mut(index)
         {
             let pos_a = stack.iter().position(|i| i.contains(current))?;
             let pos_b = stack.iter().position(|i| i.contains(other_window))?;
@@ -744,6 +1320,47 @@ fn command_center_window(
     }
 }
 
+/// Moves and resizes the focused floating window into a grid cell of the active display.
+/// Tiled windows are left alone, the layout decides where they go.
+#[allow(clippy::needless_pass_by_value)]
+fn grid_window(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let Some(Operation::Grid {
+        x,
+        y,
+        width,
+        height,
+    }) = filter_window_operations(&mut messages, |op| matches!(op, Operation::Grid { .. })).next()
+    else {
+        return;
+    };
+    let Some((_, entity, Some(Unmanaged::Floating))) = windows
+        .focused()
+        .and_then(|(_, entity)| windows.get_managed(entity))
+    else {
+        return;
+    };
+
+    let bounds = active_display.actual_bounds(&config);
+    let scale = |length: i32, ratio: f64| (f64::from(length) * ratio) as i32;
+    let origin = Origin::new(
+        bounds.min.x + scale(bounds.width(), *x),
+        bounds.min.y + scale(bounds.height(), *y),
+    );
+    let size = Size::new(
+        scale(bounds.width(), *width),
+        scale(bounds.height(), *height),
+    );
+    debug!("placing {entity} into grid cell at {origin} with size {size}");
+    commands.reposition_entity(entity, origin);
+    commands.resize_entity(entity, size);
+}
+
 /// Resizes the focused window based on preset column widths.
 ///
 /// # Arguments
@@ -762,7 +1379,16 @@ fn resize_window(
     mut commands: Commands,
 ) {
     let Some(operation) = filter_window_operations(&mut messages, |op| {
-        matches!(op, Operation::Resize(_) | Operation::SetWidth(_))
+        matches!(
+            op,
+            Operation::Resize(_)
+                | Operation::SetWidth(_)
+                | Operation::SetWidthPixels(_)
+                | Operation::ResizeBy {
+                    axis: ResizeAxis::Width,
+                    ..
+                }
+        )
     })
     .next() else {
         return;
@@ -782,11 +1408,27 @@ fn resize_window(
 
     let viewport = active_display.actual_bounds(&config);
     let current_ratio = f64::from(frame.width()) / f64::from(viewport.width());
-    let widths = config.preset_column_widths();
+    let widths = config.preset_column_widths_for(viewport.width(), active_display.display().uuid());
     let fallback = *widths.first().unwrap_or(&0.5);
     let cycle = config.window_resize_cycle();
-    let next_ratio = match operation {
+    let operation = match operation {
+        Operation::Resize(direction) if config.window_resize_reverse() => {
+            Operation::Resize(match direction {
+                ResizeDirection::Grow => ResizeDirection::Shrink,
+                ResizeDirection::Shrink => ResizeDirection::Grow,
+            })
+        }
+        operation => operation.clone(),
+    };
+    let next_ratio = match &operation {
         Operation::SetWidth(ratio) if ratio.is_finite() && *ratio > 0.0 => *ratio,
+        Operation::SetWidthPixels(width) if *width > 0 => {
+            f64::from(*width) / f64::from(viewport.width())
+        }
+        Operation::ResizeBy { amount, .. } => {
+            let width = (frame.width() + amount.pixels(viewport.width())).max(MIN_RESIZE_BY_EXTENT);
+            f64::from(width) / f64::from(viewport.width())
+        }
         Operation::Resize(ResizeDirection::Grow) => widths
             .iter()
             .copied()
@@ -825,7 +1467,7 @@ fn resize_window(
 
     // Resize all windows in the column so stacked siblings share the new width.
     let strip = active_display.active_strip();
-    if let Some(Column::Stack(stack)) = strip
+    if let Some(Column::Stack(stack) | Column::Accordion(stack)) = strip
         .index_of(entity)
         .ok()
         .and_then(|idx| strip.get(idx).ok())
@@ -843,6 +1485,130 @@ fn resize_window(
     commands.reshuffle_around(entity);
 }
 
+/// Grows or shrinks the height of the focused window within its stack. The other windows of
+/// the stack give up or take over the difference when the layout is reflowed.
+#[allow(clippy::needless_pass_by_value)]
+fn resize_window_height(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let Some(Operation::ResizeBy { amount, .. }) = filter_window_operations(&mut messages, |op| {
+        matches!(
+            op,
+            Operation::ResizeBy {
+                axis: ResizeAxis::Height,
+                ..
+            }
+        )
+    })
+    .next() else {
+        return;
+    };
+
+    let Some((size, entity)) = windows
+        .focused()
+        .and_then(|(_, entity)| windows.size(entity).zip(Some(entity)))
+    else {
+        return;
+    };
+    let strip = active_display.active_strip();
+    let Ok(Column::Stack(_)) = strip.index_of(entity).and_then(|idx| strip.get(idx)) else {
+        debug!("only windows in a stack can change their height.");
+        return;
+    };
+
+    let viewport = active_display.actual_bounds(&config);
+    let height = (size.y + amount.pixels(viewport.height())).clamp(
+        MIN_RESIZE_BY_EXTENT,
+        viewport.height().max(MIN_RESIZE_BY_EXTENT),
+    );
+    commands.resize_entity(entity, size.with_y(height));
+    commands.reshuffle_around(entity);
+}
+
+/// Splits the viewport width evenly between the visible columns, or a range of columns.
+#[allow(clippy::needless_pass_by_value)]
+fn balance_column_widths(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let Some(Operation::BalanceWidths(range)) = filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::BalanceWidths(_))
+    })
+    .next() else {
+        return;
+    };
+
+    let viewport = active_display.actual_bounds(&config);
+    let strip = active_display.active_strip();
+    let columns = strip
+        .columns()
+        .enumerate()
+        .filter(|(index, column)| {
+            if matches!(column, Column::Fullscren(_)) {
+                return false;
+            }
+            match range {
+                Some((first, last)) => (*first..=*last).contains(index),
+                None => column
+                    .top()
+                    .and_then(|entity| windows.frame(entity))
+                    .is_some_and(|frame| {
+                        frame.max.x > viewport.min.x && frame.min.x < viewport.max.x
+                    }),
+            }
+        })
+        .map(|(_, column)| column)
+        .collect::<Vec<_>>();
+    let Ok(count) = i32::try_from(columns.len()) else {
+        return;
+    };
+    if count == 0 {
+        return;
+    }
+
+    let gap = config.gap_for(active_display.display().uuid());
+    let width = (viewport.width() - gap * (count - 1)) / count;
+    for column in columns {
+        strip::set_column_width(column, width, &windows, &mut commands);
+    }
+    if let Some((_, entity)) = windows.focused() {
+        commands.reshuffle_around(entity);
+    }
+}
+
+/// Reverses or rotates the columns of the active strip, keeping the focused window in view.
+#[allow(clippy::needless_pass_by_value)]
+fn reorder_strip(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    mut active_display: ActiveDisplayMut,
+    mut commands: Commands,
+) {
+    let mut reordered = false;
+    for operation in filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::ReverseStrip | Operation::RotateStrip(_))
+    }) {
+        let strip = active_display.active_strip();
+        match operation {
+            Operation::ReverseStrip => strip.reverse(),
+            Operation::RotateStrip(steps) => strip.rotate(*steps),
+            _ => continue,
+        }
+        reordered = true;
+    }
+
+    if reordered && let Some((_, entity)) = windows.focused() {
+        commands.reshuffle_around(entity);
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 fn full_width_window(
     mut messages: MessageReader<Event>,
@@ -877,7 +1643,7 @@ fn full_width_window(
             .index_of(entity)
             .ok()
             .and_then(|idx| strip.get(idx).ok())
-            .is_some_and(|col| matches!(col, Column::Stack(_)))
+            .is_some_and(|col| matches!(col, Column::Stack(_) | Column::Accordion(_)))
         {
             _ = strip.unstack(entity);
         }
@@ -891,14 +1657,454 @@ fn full_width_window(
     }
 }
 
-/// Toggles the managed state of the focused window.
-/// If the window is currently unmanaged, it becomes managed. If managed, it becomes unmanaged (floating).
-///
-/// # Arguments
-///
-/// * `focused_entity` - The `Entity` of the currently focused window.
-/// * `windows` - A mutable query for `Window` components, their `Entity`, and whether they have the `Unmanaged` marker.
-/// * `commands` - Bevy commands to modify entities.
+/// Toggles pinning of the focused column. A pinned column stays fully visible at the edge of
+/// the viewport it is closest to, while the other columns scroll past it.
+#[allow(clippy::needless_pass_by_value)]
+fn pin_column(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    pinned: Query<&PinnedMarker>,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::Pin))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    let strip = active_display.active_strip();
+    let Ok(column) = strip.index_of(entity).and_then(|index| strip.get(index)) else {
+        return;
+    };
+
+    let marker = if pinned.contains(entity) {
+        None
+    } else {
+        let viewport = active_display.actual_bounds(&config);
+        let center = windows.frame(entity).map(|frame| frame.center().x);
+        Some(if center.is_some_and(|x| x > viewport.center().x) {
+            PinnedMarker::Right
+        } else {
+            PinnedMarker::Left
+        })
+    };
+    debug!("pinning column of {entity}: {marker:?}");
+
+    for window in column.window_iter() {
+        let Ok(mut entity_commands) = commands.get_entity(window) else {
+            continue;
+        };
+        match marker {
+            Some(marker) => entity_commands.try_insert(marker),
+            None => entity_commands.try_remove::<PinnedMarker>(),
+        };
+    }
+    if marker.is_none() {
+        commands.reshuffle_around(entity);
+    }
+}
+
+/// Marks the focused window as the insertion point of the next new window, clearing any earlier
+/// mark. Used on the marked window again, it clears the mark.
+#[allow(clippy::needless_pass_by_value)]
+fn set_insert_point(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    marked: Query<Entity, With<InsertPointMarker>>,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::SetInsertPoint))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+    let Some((window, entity)) = windows.focused() else {
+        return;
+    };
+
+    let was_marked = marked.contains(entity);
+    for previous in &marked {
+        if let Ok(mut entity_commands) = commands.get_entity(previous) {
+            entity_commands.try_remove::<InsertPointMarker>();
+        }
+    }
+    if !was_marked && let Ok(mut entity_commands) = commands.get_entity(entity) {
+        debug!("next window opens after {} {entity}.", window.id());
+        entity_commands.try_insert(InsertPointMarker);
+    }
+}
+
+/// Arms the directive of `Operation::InsertNext` for the next created window, replacing one which
+/// is still pending.
+#[allow(clippy::needless_pass_by_value)]
+fn arm_insert_next(mut messages: MessageReader<Event>, mut next: ResMut<NextInsertion>) {
+    if let Some(Operation::InsertNext(directive)) =
+        filter_window_operations(&mut messages, |op| matches!(op, Operation::InsertNext(_))).last()
+    {
+        debug!("next window opens {directive:?}.");
+        next.0 = Some(*directive);
+    }
+}
+
+/// Toggles the sticky state of the focused window. A sticky window floats and is moved along
+/// whenever the active space of its display changes. Unsticking leaves the window floating.
+#[allow(clippy::needless_pass_by_value)]
+fn toggle_sticky(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    sticky: Query<&StickyMarker>,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::Sticky))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((window, entity, unmanaged)) = windows
+        .focused()
+        .and_then(|(_, entity)| windows.get_managed(entity))
+    else {
+        return;
+    };
+    let Ok(mut entity_commands) = commands.get_entity(entity) else {
+        return;
+    };
+    if sticky.contains(entity) {
+        debug!("window {} {entity} is no longer sticky.", window.id());
+        entity_commands.try_remove::<StickyMarker>();
+        return;
+    }
+
+    debug!("window {} {entity} is now sticky.", window.id());
+    entity_commands.try_insert(StickyMarker);
+    if unmanaged.is_none() {
+        entity_commands.try_insert(Unmanaged::Floating);
+    }
+}
+
+/// Throws the focused picture-in-picture window to a corner of the display. The direction
+/// picks the side to move to, while the other axis keeps the corner the window is closest to.
+#[allow(clippy::needless_pass_by_value)]
+fn throw_pip_window(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    pips: Query<&PipMarker>,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let Some(Operation::Swap(direction)) =
+        filter_window_operations(&mut messages, |op| matches!(op, Operation::Swap(_))).next()
+    else {
+        return;
+    };
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    let Some(frame) = windows.frame(entity).filter(|_| pips.contains(entity)) else {
+        return;
+    };
+
+    let margin = config.pip_margin();
+    let viewport = active_display.actual_bounds(&config);
+    let center = viewport.center();
+    let mut left = frame.center().x < center.x;
+    let mut top = frame.center().y < center.y;
+    match direction {
+        Direction::West => left = true,
+        Direction::East => left = false,
+        Direction::North => top = true,
+        Direction::South => top = false,
+        Direction::First | Direction::Last => return,
+    }
+
+    let x = if left {
+        viewport.min.x + margin
+    } else {
+        viewport.max.x - margin - frame.width()
+    };
+    let y = if top {
+        viewport.min.y + margin
+    } else {
+        viewport.max.y - margin - frame.height()
+    };
+    debug!("throwing pip window {entity} {direction:?} to ({x}, {y})");
+    commands.reposition_entity(entity, Origin::new(x, y));
+}
+
+/// Toggles monocle mode for the focused window.
+/// Entering expands the window over the whole padded viewport, taking it out of its stack if
+/// needed. Leaving puts it back into its original stack or column and restores its frame.
+#[allow(clippy::needless_pass_by_value)]
+fn monocle_window(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    monocles: Query<&MonocleMarker>,
+    mut active_display: ActiveDisplayMut,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::Monocle))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    let viewport = active_display.actual_bounds(&config);
+    let strip = active_display.active_strip();
+
+    if let Ok(marker) = monocles.get(entity) {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.try_remove::<MonocleMarker>();
+        }
+        match marker.stacked_with {
+            Some((sibling, row)) if strip.index_of(sibling).is_ok() => {
+                if strip.stack_onto(entity, sibling).is_ok()
+                    && let Ok(index) = strip.index_of(entity)
+                    && let Some(Column::Stack(items) | Column::Accordion(items)) =
+                        strip.get_column_mut(index)
+                    && let Some(item) = items.pop()
+                {
+                    items.insert(row.min(items.len()), item);
+                }
+            }
+            _ => {
+                if let Ok(from) = strip.index_of(entity) {
+                    strip.move_column(from, marker.index);
+                }
+            }
+        }
+        commands.resize_entity(entity, marker.frame.size());
+        commands.reshuffle_around(entity);
+        return;
+    }
+
+    let Some(frame) = windows.frame(entity) else {
+        return;
+    };
+    let Some((index, column)) = strip
+        .index_of(entity)
+        .ok()
+        .and_then(|index| strip.get(index).ok().map(|column| (index, column)))
+    else {
+        return;
+    };
+    let stacked_with = match &column {
+        Column::Fullscren(_) => return,
+        Column::Stack(_) | Column::Accordion(_) => column.position_of(entity).and_then(|row| {
+            column
+                .window_iter()
+                .find(|&sibling| sibling != entity)
+                .map(|sibling| (sibling, row))
+        }),
+        Column::Single(_) | Column::Tabs(_) => None,
+    };
+    if stacked_with.is_some() {
+        _ = strip.unstack(entity);
+    }
+
+    if let Ok(mut entity_commands) = commands.get_entity(entity) {
+        entity_commands.try_insert(MonocleMarker {
+            frame,
+            index,
+            stacked_with,
+        });
+    }
+    commands.reposition_entity(entity, Origin::new(viewport.min.x, viewport.min.y));
+    commands.resize_entity(entity, Size::new(viewport.width(), viewport.height()));
+    commands.reshuffle_around(entity);
+}
+
+/// Toggles zen mode for the focused window.
+/// Entering centers the window at `zen_width` of the viewport; the layout then slides every
+/// other column of the strip fully off-screen. Leaving restores the window's width and the
+/// strip offset it had before.
+#[allow(clippy::needless_pass_by_value)]
+fn zen_window(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    zens: Query<&ZenMarker>,
+    strips: Query<&Position, With<LayoutStrip>>,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::Zen))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    let strip_entity = active_display.active_strip_entity();
+
+    if let Ok(marker) = zens.get(entity) {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.try_remove::<ZenMarker>();
+        }
+        commands.resize_entity(entity, marker.frame.size());
+        commands.reposition_entity(strip_entity, marker.offset);
+        return;
+    }
+
+    if !active_display.active_strip().contains(entity) {
+        return;
+    }
+    let (Some(frame), Some(layout_position), Ok(Position(offset))) = (
+        windows.frame(entity),
+        windows.layout_position(entity),
+        strips.get(strip_entity),
+    ) else {
+        return;
+    };
+    let viewport = active_display.actual_bounds(&config);
+    let width = (f64::from(viewport.width()) * config.zen_width()).round() as i32;
+
+    if let Ok(mut entity_commands) = commands.get_entity(entity) {
+        entity_commands.try_insert(ZenMarker {
+            frame,
+            offset: *offset,
+        });
+    }
+    commands.resize_entity(entity, frame.size().with_x(width));
+    let strip_x = viewport.center().x - width / 2 - layout_position.0.x;
+    commands.reposition_entity(strip_entity, offset.with_x(strip_x));
+}
+
+/// Toggles the overview of the active strip.
+/// Entering scales every window of the strip by the same factor so that the whole strip fits
+/// into the viewport, and freezes its layout. While in overview, focus commands move between the
+/// shrunk windows; clicking one of them or toggling again restores the original sizes and
+/// reshuffles the strip around the focused window.
+#[allow(clippy::needless_pass_by_value)]
+fn overview_strip(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    frames: Query<(Entity, &OverviewFrame)>,
+    strip: Single<(Entity, Has<OverviewMarker>), With<ActiveWorkspaceMarker>>,
+    mut active_display: ActiveDisplayMut,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let (strip_entity, in_overview) = *strip;
+    let mut toggle = false;
+    let mut picked = None;
+    for event in messages.read() {
+        match event {
+            Event::Command {
+                command: Command::Window(Operation::Overview),
+            } => toggle = true,
+            Event::MouseDown { point, .. } if in_overview => {
+                let pointer = origin_from(*point);
+                picked = active_display
+                    .active_strip()
+                    .all_windows()
+                    .into_iter()
+                    .find(|&entity| {
+                        windows
+                            .frame(entity)
+                            .is_some_and(|frame| frame.contains(pointer))
+                    });
+            }
+            _ => (),
+        }
+    }
+
+    if in_overview {
+        if !toggle && picked.is_none() {
+            return;
+        }
+        if let Some(entity) = picked {
+            commands.focus_entity(entity, true);
+        }
+        if let Ok(mut entity_commands) = commands.get_entity(strip_entity) {
+            entity_commands.try_remove::<OverviewMarker>();
+        }
+        let strip = active_display.active_strip();
+        for (entity, frame) in frames.iter().filter(|(entity, _)| strip.contains(*entity)) {
+            commands.resize_entity(entity, frame.0);
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.try_remove::<OverviewFrame>();
+            }
+        }
+        // The layout was frozen while in overview, so recompute it from the restored sizes.
+        if let Some(entity) = picked.or_else(|| windows.focused().map(|(_, entity)| entity)) {
+            commands.reshuffle_around(entity);
+        }
+        return;
+    }
+    if !toggle {
+        return;
+    }
+
+    let viewport = active_display.actual_bounds(&config);
+    let layout = active_display
+        .active_strip()
+        .all_windows()
+        .into_iter()
+        .filter_map(|entity| {
+            Some((
+                entity,
+                windows.layout_position(entity)?.0,
+                windows.size(entity)?,
+            ))
+        })
+        .collect::<Vec<_>>();
+    let total_width = layout
+        .iter()
+        .map(|(_, origin, size)| origin.x + size.x)
+        .max()
+        .unwrap_or(0);
+    if total_width <= 0 {
+        return;
+    }
+
+    let scale = (f64::from(viewport.width()) / f64::from(total_width)).min(1.0);
+    let scaled = |value: i32| (f64::from(value) * scale).round() as i32;
+    let margin = Origin::new(
+        (viewport.width() - scaled(total_width)) / 2,
+        (viewport.height() - scaled(viewport.height())) / 2,
+    );
+    debug!("overview of {} windows at scale {scale:.2}.", layout.len());
+    for (entity, origin, size) in layout {
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.try_insert(OverviewFrame(size));
+        }
+        commands.reposition_entity(
+            entity,
+            viewport.min + margin + Origin::new(scaled(origin.x), scaled(origin.y)),
+        );
+        commands.resize_entity(entity, Size::new(scaled(size.x), scaled(size.y)));
+    }
+    if let Ok(mut entity_commands) = commands.get_entity(strip_entity) {
+        entity_commands.try_insert(OverviewMarker);
+    }
+}
+
+/// Toggles the managed state of the focused window.
+/// If the window is currently unmanaged, it becomes managed. If managed, it becomes unmanaged (floating).
+///
+/// # Arguments
+///
+/// * `focused_entity` - The `Entity` of the currently focused window.
+/// * `windows` - A mutable query for `Window` components, their `Entity`, and whether they have the `Unmanaged` marker.
+/// * `commands` - Bevy commands to modify entities.
 #[allow(clippy::needless_pass_by_value)]
 fn manage_window(
     mut messages: MessageReader<Event>,
@@ -927,7 +2133,8 @@ fn manage_window(
     let was_unmanaged = unmanaged.is_some();
     if let Ok(mut entity_commands) = commands.get_entity(entity) {
         if was_unmanaged {
-            entity_commands.try_remove::<Unmanaged>();
+            // A managed window is tiled on its own space, so it can no longer be sticky.
+            entity_commands.try_remove::<(Unmanaged, StickyMarker)>();
         } else {
             entity_commands.try_insert(Unmanaged::Floating);
         }
@@ -951,7 +2158,163 @@ fn manage_window(
     }
 }
 
-/// Moves the focused window to the next available display.
+/// Minimizes the focused window, or restores the most recently minimized one. The resulting
+/// minimize notifications from macOS take the window out of, and back into, the layout.
+/// The most recently minimized window is the minimized one which was focused last, as windows
+/// are nearly always minimized while focused.
+#[allow(clippy::needless_pass_by_value)]
+fn minimize_window(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    focused_at: Query<&FocusedAt>,
+    mut commands: Commands,
+) {
+    let Some(operation) = filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::Minimize | Operation::Deminimize)
+    })
+    .next() else {
+        return;
+    };
+
+    if matches!(operation, Operation::Minimize) {
+        if let Some((window, entity)) = windows.focused() {
+            debug!("minimizing window {} {entity}.", window.id());
+            window.set_minimized(true);
+        }
+        return;
+    }
+
+    let Some((window, entity)) = windows
+        .iter()
+        .filter(|(_, entity)| {
+            matches!(
+                windows.get_managed(*entity),
+                Some((_, _, Some(Unmanaged::Minimized)))
+            )
+        })
+        .max_by_key(|(_, entity)| focused_at.get(*entity).ok().map(|at| at.0))
+    else {
+        return;
+    };
+    debug!("restoring minimized window {} {entity}.", window.id());
+    window.set_minimized(false);
+    commands.focus_entity(entity, true);
+}
+
+/// Toggles the hidden state of the frontmost application. The application of the focused
+/// window gets hidden, unless the application hidden by the previous toggle is still hidden,
+/// in which case that one is brought back. The workspace notifications about hidden and
+/// visible applications take care of the layout.
+#[allow(clippy::needless_pass_by_value)]
+fn hide_application(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    apps: Query<&ChildOf, With<Application>>,
+    processes: Query<&BProcess>,
+    mut last_hidden: Local<Option<Entity>>,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::HideApp))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    if let Some(process) = last_hidden
+        .take()
+        .and_then(|entity| processes.get(entity).ok())
+        && process.is_hidden()
+    {
+        debug!("unhiding application {}.", process.name());
+        process.set_hidden(false);
+        return;
+    }
+
+    let Some((entity, process)) = windows
+        .focused()
+        .and_then(|(window, _)| windows.find_parent(window.id()))
+        .and_then(|(_, _, app)| apps.get(app).ok())
+        .and_then(|child_of| {
+            let entity = child_of.parent();
+            processes.get(entity).ok().map(|process| (entity, process))
+        })
+    else {
+        return;
+    };
+    debug!("hiding application {}.", process.name());
+    process.set_hidden(true);
+    *last_hidden = Some(entity);
+}
+
+/// Moves the focused window back to its `OriginalFrame`. A tiled window is floated first, so
+/// the layout does not pull it back into the strip.
+#[allow(clippy::needless_pass_by_value)]
+fn restore_original_frame(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    original_frames: Query<&OriginalFrame>,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::RestoreOriginal))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((window, entity, unmanaged)) = windows
+        .focused()
+        .and_then(|(_, entity)| windows.get_managed(entity))
+    else {
+        return;
+    };
+    let Ok(OriginalFrame(frame)) = original_frames.get(entity) else {
+        return;
+    };
+    debug!("restoring window {} to {frame:?}.", window.id());
+    if unmanaged.is_none()
+        && let Ok(mut entity_commands) = commands.get_entity(entity)
+    {
+        entity_commands.try_insert(Unmanaged::Floating);
+    }
+    commands.reposition_entity(entity, frame.min);
+    commands.resize_entity(entity, frame.size());
+}
+
+/// Resolves a `DisplayTarget` to the id of a display other than the active one. Directions
+/// compare display centers, the same way directional focus compares floating windows.
+fn target_display(
+    target: &DisplayTarget,
+    active_display: &mut ActiveDisplayMut,
+) -> Option<CGDirectDisplayID> {
+    let active_id = active_display.display().id();
+    let active_bounds = active_display.bounds();
+    let others = active_display
+        .other()
+        .map(|display| (display.id(), display.bounds()))
+        .collect::<Vec<_>>();
+    match target {
+        DisplayTarget::Next => others.first().map(|(id, _)| *id),
+        DisplayTarget::Direction(direction) => pick_nearest_in_direction(
+            direction,
+            active_bounds.center(),
+            others.iter().map(|(id, bounds)| (*id, bounds.center())),
+        ),
+        DisplayTarget::Index(index) => {
+            let mut arranged = others;
+            arranged.push((active_id, active_bounds));
+            arranged.sort_by_key(|(_, bounds)| (bounds.min.x, bounds.min.y));
+            index
+                .checked_sub(1)
+                .and_then(|index| arranged.get(index))
+                .map(|(id, _)| *id)
+                .filter(|id| *id != active_id)
+        }
+    }
+}
+
+/// Moves the focused window to another display: the next available one, or the one selected by
+/// the `DisplayTarget` of `Operation::ToDisplay`.
 /// The window will be repositioned to the center of the new display.
 ///
 /// # Arguments
@@ -973,12 +2336,14 @@ fn to_next_display(
     config: Res<Config>,
     mut commands: Commands,
 ) {
-    let Some(Operation::ToNextDisplay(move_focus)) =
-        filter_window_operations(&mut messages, |op| {
-            matches!(op, Operation::ToNextDisplay(_))
-        })
-        .next()
-    else {
+    let Some((target, move_focus)) = filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::ToNextDisplay(_) | Operation::ToDisplay(..))
+    })
+    .find_map(|op| match op {
+        Operation::ToNextDisplay(move_focus) => Some((DisplayTarget::Next, *move_focus)),
+        Operation::ToDisplay(target, move_focus) => Some((target.clone(), *move_focus)),
+        _ => None,
+    }) else {
         return;
     };
 
@@ -999,8 +2364,14 @@ fn to_next_display(
     // for on both the source and target displays.
     let source_viewport_width = active_display.actual_bounds(&config).width();
 
-    let Some(other) = active_display.other().next() else {
-        debug!("no other display to move window to.");
+    let Some(target_display_id) = target_display(&target, &mut active_display) else {
+        debug!("no display to move window to for {target:?}.");
+        return;
+    };
+    let Some(other) = active_display
+        .other()
+        .find(|display| display.id() == target_display_id)
+    else {
         return;
     };
 
@@ -1011,7 +2382,6 @@ fn to_next_display(
         other.width() / 2,
     );
     let center = other.bounds().center().x;
-    let target_display_id = other.id();
 
     let Some(size) = windows.size(entity) else {
         return;
@@ -1185,6 +2555,117 @@ fn equalize_column(
     }
 }
 
+/// Switches the focused window's stack between a regular stack and an accordion, where the
+/// windows overlap and only slivers of the unfocused ones stay visible.
+#[allow(clippy::needless_pass_by_value)]
+fn toggle_accordion(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    mut active_display: ActiveDisplayMut,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::Accordion))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    match active_display.active_strip().toggle_accordion(entity) {
+        Ok(()) => commands.reshuffle_around(entity),
+        Err(err) => debug!("toggling accordion: {err}"),
+    }
+}
+
+/// Rotates focus through the windows of the focused stack, wrapping around at the ends.
+#[allow(clippy::needless_pass_by_value)]
+fn cycle_stack_focus(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    mut commands: Commands,
+) {
+    let Some(Operation::CycleStack(direction)) =
+        filter_window_operations(&mut messages, |op| matches!(op, Operation::CycleStack(_))).next()
+    else {
+        return;
+    };
+
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    let strip = active_display.active_strip();
+    let Some(Column::Stack(stack) | Column::Accordion(stack)) = strip
+        .index_of(entity)
+        .ok()
+        .and_then(|index| strip.get(index).ok())
+    else {
+        return;
+    };
+    let Some(position) = stack.iter().position(|item| item.contains(entity)) else {
+        return;
+    };
+
+    let next = match direction {
+        CycleDirection::Next => (position + 1) % stack.len(),
+        CycleDirection::Prev => (position + stack.len() - 1) % stack.len(),
+    };
+    if let Some(target) = stack.get(next).and_then(StackItem::top)
+        && target != entity
+    {
+        commands.focus_entity(target, true);
+        commands.reshuffle_around(target);
+    }
+}
+
+/// Moves the focused window to the top of its stack. It swaps heights with the tallest window
+/// of the stack, so a "main + pile" column keeps its main window large.
+#[allow(clippy::needless_pass_by_value)]
+fn promote_in_stack(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    mut active_display: ActiveDisplayMut,
+    mut commands: Commands,
+) {
+    if filter_window_operations(&mut messages, |op| matches!(op, Operation::Promote))
+        .next()
+        .is_none()
+    {
+        return;
+    }
+
+    let Some((_, entity)) = windows.focused() else {
+        return;
+    };
+    let strip = active_display.active_strip();
+    let Some(column) = strip
+        .index_of(entity)
+        .ok()
+        .and_then(|index| strip.get_column_mut(index))
+    else {
+        return;
+    };
+    if !column.promote(entity) {
+        return;
+    }
+
+    let tallest = column
+        .window_iter()
+        .filter_map(|other| windows.size(other).map(|size| (other, size)))
+        .max_by_key(|(_, size)| size.y);
+    if let Some((tallest, tallest_size)) = tallest
+        && tallest != entity
+        && let Some(size) = windows.size(entity)
+    {
+        commands.resize_entity(entity, size.with_y(tallest_size.y));
+        commands.resize_entity(tallest, tallest_size.with_y(size.y));
+    }
+    commands.reshuffle_around(entity);
+}
+
 /// Makes all columns in the active strip the same width as the focused window.
 #[allow(clippy::needless_pass_by_value)]
 fn balance_strip(
@@ -1341,6 +2822,209 @@ pub fn command_quit_handler(
     }
 }
 
+/// Applies `config set <key> <value>` commands to the live configuration.
+#[allow(clippy::needless_pass_by_value)]
+fn command_set_option_handler(
+    mut messages: MessageReader<Event>,
+    mut config: ResMut<Config>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::SetOption(key, value),
+        } = event
+        else {
+            continue;
+        };
+
+        match config.set_option(key, value) {
+            Ok(changes) => {
+                info!("Configuration option '{key}' set: {changes}");
+                commands.trigger(SendMessageTrigger(Event::ConfigReloaded(changes)));
+            }
+            Err(err) => error!("setting option '{key}' to '{value}': {err}"),
+        }
+    }
+}
+
+/// Applies `gaps` commands to the live configuration of the active display. The adjusted
+/// values are kept as runtime overrides, so `gaps reset` can return to the configured ones.
+#[allow(clippy::needless_pass_by_value)]
+fn command_gaps_handler(
+    mut messages: MessageReader<Event>,
+    active_display: Query<&Display, With<ActiveDisplayMarker>>,
+    active_workspace: Query<&LayoutStrip, With<ActiveWorkspaceMarker>>,
+    mut config: ResMut<Config>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::Gaps(operation),
+        } = event
+        else {
+            continue;
+        };
+        let uuid = active_display.iter().next().and_then(Display::uuid);
+        let workspace = active_workspace.single().ok().map(LayoutStrip::id);
+        // Displays without a UUID fall back to the global `[padding]` section.
+        let key = |name: &str| match uuid {
+            Some(uuid) if name == "gap" => format!("displays.{uuid}.gap"),
+            Some(uuid) => format!("displays.{uuid}.padding_{name}"),
+            None => format!("padding.{name}"),
+        };
+        let sides = ["top", "right", "bottom", "left"];
+        let workspace_key =
+            |workspace: WorkspaceId, side: &str| format!("workspaces.{workspace}.padding_{side}");
+
+        let result = match operation {
+            GapsOperation::Inner(delta) => {
+                let gap = (config.gap_for(uuid) + delta).max(0);
+                config.set_option(&key("gap"), &gap.to_string())
+            }
+            GapsOperation::Outer(delta) => {
+                let (top, right, bottom, left) = config.edge_padding_for(uuid, workspace);
+                // Sides set in `[workspaces.<id>]` win over the display, so they are adjusted there.
+                let overridden = config
+                    .workspace_options(workspace)
+                    .map(|options| {
+                        [
+                            options.padding_top,
+                            options.padding_right,
+                            options.padding_bottom,
+                            options.padding_left,
+                        ]
+                        .map(|side| side.is_some())
+                    })
+                    .unwrap_or_default();
+                let options = sides
+                    .into_iter()
+                    .zip([top, right, bottom, left])
+                    .zip(overridden)
+                    .map(|((side, padding), overridden)| {
+                        let key = match workspace {
+                            Some(workspace) if overridden => workspace_key(workspace, side),
+                            _ => key(side),
+                        };
+                        (key, (padding + delta).max(0).to_string())
+                    })
+                    .collect::<Vec<_>>();
+                config.set_options(&options)
+            }
+            GapsOperation::Reset => {
+                let keys = sides
+                    .into_iter()
+                    .chain(["gap"])
+                    .map(key)
+                    .chain(
+                        workspace
+                            .into_iter()
+                            .flat_map(|workspace| sides.map(|side| workspace_key(workspace, side))),
+                    )
+                    .collect::<Vec<_>>();
+                config.reset_options(&keys)
+            }
+        };
+        match result {
+            Ok(changes) => {
+                debug!("gaps {operation:?}: {changes}");
+                commands.trigger(SendMessageTrigger(Event::ConfigReloaded(changes)));
+            }
+            Err(err) => error!("adjusting gaps: {err}"),
+        }
+    }
+}
+
+/// Starts the program of `exec` commands in its own process group, so it outlives the daemon
+/// and does not receive its signals. Children are reaped on a background thread.
+fn command_exec_handler(mut messages: MessageReader<Event>) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::Exec(argv),
+        } = event
+        else {
+            continue;
+        };
+        let Some((program, args)) = argv.split_first() else {
+            continue;
+        };
+
+        debug!("exec: {argv:?}");
+        if let Err(err) = spawn_and_reap(
+            std::process::Command::new(program)
+                .args(args)
+                .stdin(std::process::Stdio::null())
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .process_group(0),
+        ) {
+            error!("unable to exec '{program}': {err}");
+        }
+    }
+}
+
+/// Swaps the log filter on `loglevel <filter>` commands, so a trace can be captured without
+/// restarting the service.
+fn command_log_level_handler(mut messages: MessageReader<Event>) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::LogLevel(filter),
+        } = event
+        else {
+            continue;
+        };
+
+        match set_log_filter(filter) {
+            Ok(()) => info!("log filter set to '{filter}'."),
+            Err(err) => error!("unable to set log filter: {err}"),
+        }
+    }
+}
+
+/// Writes the `EventJournal` to a file on `dump-journal [path]` commands.
+#[allow(clippy::needless_pass_by_value)]
+fn dump_journal_handler(mut messages: MessageReader<Event>, journal: Res<EventJournal>) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::DumpJournal(path),
+        } = event
+        else {
+            continue;
+        };
+
+        let path = path
+            .clone()
+            .unwrap_or_else(|| std::env::temp_dir().join("paneru-journal.log"));
+        match journal.dump(&path) {
+            Ok(()) => info!("event journal written to {}.", path.display()),
+            Err(err) => error!("unable to write event journal to {}: {err}", path.display()),
+        }
+    }
+}
+
+/// Switches the keybinding mode on `mode <name>` commands and flashes the new mode's name.
+#[allow(clippy::needless_pass_by_value)]
+fn command_mode_handler(
+    mut messages: MessageReader<Event>,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::Mode(mode),
+        } = event
+        else {
+            continue;
+        };
+
+        if set_active_mode(mode, &config) {
+            debug!("keybinding mode '{mode}' active.");
+            commands.flash_message(mode.clone(), 1.0);
+        } else {
+            warn!("keybinding mode '{mode}' is not configured.");
+        }
+    }
+}
+
 #[instrument(level = Level::DEBUG, skip_all)]
 #[allow(clippy::needless_pass_by_value)]
 pub fn command_restart_handler(mut messages: MessageReader<Event>) {
@@ -1435,7 +3119,8 @@ fn print_internal_state_handler(
 
             let display_id = display.id();
             info!(
-                "Display {display_id}{}, workspace id {} ({strip_entity}){}{}: {strip}:\n{}",
+                "Display {display_id} ({}){}, workspace id {} ({strip_entity}){}{}: {strip}:\n{}",
+                display.uuid().unwrap_or_default(),
                 if active { ", active" } else { "" },
                 strip.id(),
                 if active_workspace { ", active" } else { "" },
@@ -1559,6 +3244,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_get_column_in_direction_skips_stack_rows() {
+        // Layout: [Stack(e0, e1), Stack(e2, e3)]
+        let mut world = World::new();
+        let entities = world
+            .spawn_batch(vec![(), (), (), ()])
+            .collect::<Vec<Entity>>();
+
+        let mut strip = LayoutStrip::default();
+        strip.append(entities[0]);
+        strip.append(entities[1]);
+        strip.append(entities[2]);
+        strip.append(entities[3]);
+        strip.stack(entities[1]).unwrap();
+        strip.stack(entities[3]).unwrap();
+
+        let east = Direction::East;
+        let west = Direction::West;
+        let north = Direction::North;
+
+        // From e1 (bottom-left), east lands on the top of the next column.
+        assert_eq!(
+            get_column_in_direction(&east, entities[1], &strip),
+            Some(entities[2])
+        );
+        // From e3 (bottom-right), west lands on the top of the previous column.
+        assert_eq!(
+            get_column_in_direction(&west, entities[3], &strip),
+            Some(entities[0])
+        );
+        // Edges have no neighbour.
+        assert_eq!(get_column_in_direction(&west, entities[0], &strip), None);
+        assert_eq!(get_column_in_direction(&east, entities[2], &strip), None);
+        // Vertical movement inside the stack is unaffected.
+        assert_eq!(
+            get_column_in_direction(&north, entities[3], &strip),
+            Some(entities[2])
+        );
+    }
+
     #[test]
     fn pick_nearest_in_direction_east_picks_closer() {
         let mut world = World::new();
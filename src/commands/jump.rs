@@ -0,0 +1,94 @@
+use bevy::app::{App, PreUpdate};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Commands, Res, ResMut};
+use bevy::math::{IRect, IVec2};
+use tracing::debug;
+
+use super::{Command, Operation};
+use crate::config::{Config, jump_keys};
+use crate::ecs::SpawnCommandsExt;
+use crate::ecs::layout::Column;
+use crate::ecs::params::{ActiveDisplay, Windows};
+use crate::events::Event;
+use crate::platform::input::set_jump_pending;
+
+/// Side length of the square badges labelling the columns.
+const JUMP_BADGE_SIZE: i32 = 64;
+
+/// A badge labelling a visible column while `window jump` waits for a key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JumpBadge {
+    /// The badge rect in absolute screen coordinates, centered on the visible part of the column.
+    pub frame: IRect,
+    pub label: &'static str,
+    pub keycode: u8,
+    /// The top window of the labelled column.
+    pub entity: Entity,
+}
+
+/// Badges of an in-progress `window jump`, empty otherwise.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct JumpBadges(pub Vec<JumpBadge>);
+
+pub(super) fn register_jump_commands(app: &mut App) {
+    app.init_resource::<JumpBadges>()
+        .add_systems(PreUpdate, jump_to_column);
+}
+
+/// Handles `window jump`: labels every visible column of the active strip with a badge, then
+/// focuses the column whose label is typed next. Any other key cancels the jump.
+#[allow(clippy::needless_pass_by_value)]
+fn jump_to_column(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    config: Res<Config>,
+    mut badges: ResMut<JumpBadges>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        match event {
+            Event::Command {
+                command: Command::Window(Operation::Jump),
+            } => {
+                let viewport = active_display.actual_bounds(&config);
+                // Slivers and columns barely peeking in are too narrow to carry a badge.
+                let columns = active_display
+                    .active_strip()
+                    .columns()
+                    .filter_map(Column::top)
+                    .filter_map(|entity| {
+                        let visible = windows.frame(entity)?.intersect(viewport);
+                        (visible.width() >= JUMP_BADGE_SIZE).then_some((entity, visible))
+                    });
+                badges.0 = columns
+                    .zip(jump_keys())
+                    .map(|((entity, visible), &(label, keycode))| JumpBadge {
+                        frame: IRect::from_center_size(
+                            visible.center(),
+                            IVec2::splat(JUMP_BADGE_SIZE),
+                        ),
+                        label,
+                        keycode,
+                        entity,
+                    })
+                    .collect();
+                debug!("jump: labelled {} columns.", badges.0.len());
+            }
+            Event::JumpKey { keycode } if !badges.0.is_empty() => {
+                if let Some(badge) = badges.0.iter().find(|badge| badge.keycode == *keycode) {
+                    commands.focus_entity(badge.entity, true);
+                }
+                badges.0.clear();
+            }
+            _ => (),
+        }
+    }
+    // The event tap only swallows the next key while there are badges to pick from, so that a
+    // jump without any labelled column or one which already ended leaves the keybindings alone.
+    if badges.is_changed() {
+        set_jump_pending(!badges.0.is_empty());
+    }
+}
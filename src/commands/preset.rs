@@ -0,0 +1,269 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::app::{App, PreUpdate};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::system::{Commands, Query, Res};
+use serde::{Deserialize, Serialize};
+use stdext::function_name;
+use tracing::{debug, warn};
+
+use super::strip::set_column_width;
+use super::{Operation, filter_window_operations};
+use crate::config::Config;
+use crate::ecs::SpawnCommandsExt;
+use crate::ecs::layout::{Column, LayoutStrip};
+use crate::ecs::params::{ActiveDisplayMut, Windows};
+use crate::ecs::restore::{
+    PlannedColumn, PlannedStackItem, RestorePlanner, apply_planned_columns,
+    current_window_identities,
+};
+use crate::ecs::state::{PaneruState, SavedColumn, SavedStrip, SavedWorkspace};
+use crate::errors::{Error, Result};
+use crate::events::Event;
+use crate::manager::Application;
+
+const LAYOUTS_DIRECTORY: &str = "layouts";
+
+/// A named snapshot of a strip: the column order and stack shape by window identity, together
+/// with each column's width as a ratio of the viewport.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct LayoutPreset {
+    pub columns: Vec<PresetColumn>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PresetColumn {
+    pub width: f64,
+    pub column: SavedColumn,
+}
+
+impl LayoutPreset {
+    /// Returns the file name of the preset, rejecting names which could escape the layouts directory.
+    fn file_name(name: &str) -> Result<String> {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(Error::InvalidInput(format!(
+                "{}: invalid layout name '{name}'.",
+                function_name!()
+            )));
+        }
+        Ok(format!("{LAYOUTS_DIRECTORY}/{name}.json"))
+    }
+
+    fn save(&self, name: &str) -> Result<PathBuf> {
+        let path = xdg::BaseDirectories::with_prefix("paneru")
+            .place_config_file(Self::file_name(name)?)
+            .map_err(|err| Error::IO(format!("{}: {err}", function_name!())))?;
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|err| Error::Generic(format!("{}: {err}", function_name!())))?;
+        fs::write(&path, json).map_err(|err| Error::IO(format!("{}: {err}", function_name!())))?;
+        Ok(path)
+    }
+
+    fn load(name: &str) -> Result<Self> {
+        let path = xdg::BaseDirectories::with_prefix("paneru")
+            .find_config_file(Self::file_name(name)?)
+            .ok_or(Error::NotFound(format!(
+                "{}: no layout named '{name}'.",
+                function_name!()
+            )))?;
+        let data = fs::read_to_string(&path)
+            .map_err(|err| Error::IO(format!("{}: {err}", function_name!())))?;
+        serde_json::from_str(&data)
+            .map_err(|err| Error::InvalidConfig(format!("{}: {err}", function_name!())))
+    }
+
+    /// Wraps the preset into a single-strip state, so the session restore planner can match it.
+    fn to_state(&self, strip: &LayoutStrip) -> PaneruState {
+        PaneruState {
+            version: 0,
+            timestamp: 0,
+            active_display_id: None,
+            unmanaged: Vec::new(),
+            displays: Vec::new(),
+            workspaces: vec![SavedWorkspace {
+                workspace_id: strip.id(),
+                display_id: None,
+                active_virtual_index: Some(strip.virtual_index),
+                strips: vec![SavedStrip {
+                    virtual_index: strip.virtual_index,
+                    columns: self.saved_columns(),
+                }],
+            }],
+        }
+    }
+
+    fn saved_columns(&self) -> Vec<SavedColumn> {
+        self.columns
+            .iter()
+            .map(|preset| preset.column.clone())
+            .collect()
+    }
+}
+
+pub(super) fn register_preset_commands(app: &mut App) {
+    app.add_systems(PreUpdate, layout_preset_handler);
+}
+
+/// Handles `window layout-save <name>` and `window layout-apply <name>`.
+#[allow(clippy::needless_pass_by_value)]
+fn layout_preset_handler(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    apps: Query<&Application>,
+    mut active_display: ActiveDisplayMut,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let viewport_width = active_display.actual_bounds(&config).width();
+
+    for operation in filter_window_operations(&mut messages, |op| {
+        matches!(op, Operation::LayoutSave(_) | Operation::LayoutApply(_))
+    }) {
+        let strip = active_display.active_strip();
+        let result = match operation {
+            Operation::LayoutSave(name) => {
+                let preset = extract_preset(strip, viewport_width, &windows, &apps);
+                preset.save(name).map(|path| {
+                    debug!("layout '{name}' saved to {}.", path.display());
+                    commands.flash_message(format!("Saved layout {name}"), 1.0);
+                })
+            }
+            Operation::LayoutApply(name) => LayoutPreset::load(name).and_then(|preset| {
+                apply_preset(
+                    &preset,
+                    strip,
+                    viewport_width,
+                    &windows,
+                    &apps,
+                    &mut commands,
+                )
+            }),
+            _ => continue,
+        };
+
+        if let Err(err) = result {
+            warn!("{operation:?}: {err}");
+        }
+    }
+}
+
+/// Snapshots the columns of `strip`, skipping columns whose windows can no longer be resolved.
+fn extract_preset(
+    strip: &LayoutStrip,
+    viewport_width: i32,
+    windows: &Windows,
+    apps: &Query<&Application>,
+) -> LayoutPreset {
+    let columns = strip
+        .columns()
+        .filter_map(|column| {
+            let width = column
+                .top()
+                .and_then(|entity| windows.size(entity))
+                .map(|size| f64::from(size.x) / f64::from(viewport_width))?;
+            SavedColumn::from_column(column, windows, apps)
+                .map(|column| PresetColumn { width, column })
+        })
+        .collect();
+    LayoutPreset { columns }
+}
+
+/// Rearranges the matched windows of `strip` into the preset's columns, placed at the start of
+/// the strip in preset order and resized to their saved widths. Unmatched windows keep their
+/// relative order after them.
+fn apply_preset(
+    preset: &LayoutPreset,
+    strip: &mut LayoutStrip,
+    viewport_width: i32,
+    windows: &Windows,
+    apps: &Query<&Application>,
+    commands: &mut Commands,
+) -> Result<()> {
+    if strip
+        .columns()
+        .any(|column| matches!(column, Column::Fullscren(_)))
+    {
+        return Err(Error::InvalidInput(format!(
+            "{}: layouts can not be applied to a fullscreen strip.",
+            function_name!()
+        )));
+    }
+
+    let state = preset.to_state(strip);
+    let current = current_window_identities(windows, apps, &state)
+        .into_iter()
+        .filter(|identity| strip.contains(identity.entity))
+        .collect::<Vec<_>>();
+    let (planned, widths): (Vec<_>, Vec<_>) = RestorePlanner::new(&state)
+        .plan_columns(&preset.saved_columns(), &current)
+        .into_iter()
+        .zip(&preset.columns)
+        .filter_map(|(planned, preset)| planned.map(|planned| (planned, preset.width)))
+        .unzip();
+    if planned.is_empty() {
+        return Err(Error::NotFound(format!(
+            "{}: no windows of the layout are in the current strip.",
+            function_name!()
+        )));
+    }
+
+    for entity in planned.iter().flat_map(planned_entities) {
+        strip.remove(entity);
+    }
+    let remaining = strip.len();
+    apply_planned_columns(strip, &planned);
+    for index in 0..planned.len() {
+        strip.move_column(remaining + index, index);
+    }
+
+    for (index, ratio) in widths.into_iter().enumerate() {
+        let column = strip.get(index)?;
+        let width = (ratio * f64::from(viewport_width)).round() as i32;
+        set_column_width(&column, width, windows, commands);
+    }
+
+    if let Some(entity) = strip.get(0)?.top() {
+        commands.reshuffle_around(entity);
+    }
+    Ok(())
+}
+
+fn planned_entities(column: &PlannedColumn) -> Vec<Entity> {
+    match column {
+        PlannedColumn::Single(entity) | PlannedColumn::Fullscreen(entity) => vec![*entity],
+        PlannedColumn::Tabs(entities) => entities.clone(),
+        PlannedColumn::Stack(items) => items
+            .iter()
+            .flat_map(|item| match item {
+                PlannedStackItem::Single(entity) => vec![*entity],
+                PlannedStackItem::Tabs(entities) => entities.clone(),
+            })
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_preset_file_name() {
+        assert_eq!(
+            LayoutPreset::file_name("work").unwrap(),
+            "layouts/work.json"
+        );
+        assert_eq!(
+            LayoutPreset::file_name("deep_focus-2").unwrap(),
+            "layouts/deep_focus-2.json"
+        );
+        assert!(LayoutPreset::file_name("").is_err());
+        assert!(LayoutPreset::file_name("../state").is_err());
+        assert!(LayoutPreset::file_name("a/b").is_err());
+    }
+}
@@ -11,18 +11,22 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::io::Write;
 use std::os::unix::net::UnixStream;
 use std::sync::{Arc, Mutex};
-use tracing::warn;
+use tracing::{debug, warn};
 
 use super::{Command, Operation};
+use crate::config::{Config, ConfigChanges};
 use crate::ecs::layout::LayoutStrip;
 use crate::ecs::params::Windows;
-use crate::ecs::state::{PaneruActiveState, PaneruQueryState, PaneruVirtualWorkspaceState};
+use crate::ecs::state::{
+    PaneruActiveState, PaneruQueryState, PaneruVirtualWorkspaceState, extract_query_state,
+};
 use crate::ecs::{
     ActiveDisplayMarker, ActiveWorkspaceMarker, FocusedMarker, SelectedVirtualMarker, Unmanaged,
 };
 use crate::events::Event;
 use crate::manager::{Application, Display, WindowManager};
 use crate::platform::WinID;
+use crate::util::spawn_and_reap;
 
 #[derive(Default, Resource)]
 struct StateSubscribers {
@@ -89,6 +93,7 @@ struct StateBroadcastIntent {
     title_changes: BTreeSet<WinID>,
     display_changes: Vec<Option<u32>>,
     active_display_changed: bool,
+    config_reloaded: Option<ConfigChanges>,
 }
 
 impl StateBroadcastIntent {
@@ -120,6 +125,9 @@ impl StateBroadcastIntent {
                             | Operation::VirtualMoveNumber(_, _)
                             | Operation::Swap(_),
                         ),
+                }
+                | Event::Command {
+                    command: Command::Strip(_) | Command::Workspace(_) | Command::Display(_),
                 } => intent.windows_changed = true,
                 Event::WindowFocused { .. } => intent.window_focused = true,
                 Event::WindowTitleChanged { window_id } => {
@@ -138,6 +146,9 @@ impl StateBroadcastIntent {
                 Event::DisplayChanged => {
                     intent.active_display_changed = true;
                 }
+                Event::ConfigReloaded(changes) => {
+                    intent.config_reloaded = Some(changes.clone());
+                }
                 _ => {}
             }
         }
@@ -153,7 +164,10 @@ impl StateBroadcastIntent {
     }
 
     fn is_empty(&self) -> bool {
-        !self.requires_state() && self.title_changes.is_empty() && self.display_changes.is_empty()
+        !self.requires_state()
+            && self.title_changes.is_empty()
+            && self.display_changes.is_empty()
+            && self.config_reloaded.is_none()
     }
 }
 
@@ -191,7 +205,7 @@ fn state_query_handler(
         };
 
         let response =
-            PaneruQueryState::extract(&workspaces, &displays, &windows, &apps, &window_manager)
+            extract_query_state(&workspaces, &displays, &windows, &apps, &window_manager)
                 .map_err(|err| err.to_string())
                 .and_then(|state| state.to_query_json(*kind).map_err(|err| err.to_string()))
                 .unwrap_or_else(|err| json!({ "error": err }).to_string());
@@ -243,8 +257,15 @@ fn collect_state_broadcast_events_for_intent(
         title_changes.insert(*window_id, title_for_window(*window_id).unwrap_or_default());
     }
 
+    let mut outgoing = Vec::new();
+    if let Some(changes) = &intent.config_reloaded {
+        outgoing.push(json!({
+            "event": "config_reloaded",
+            "changes": changes,
+        }));
+    }
+
     let Some(state) = state else {
-        let mut outgoing = Vec::new();
         for (window_id, title) in title_changes {
             if cache.titles.get(&window_id) == Some(&title) {
                 continue;
@@ -265,8 +286,6 @@ fn collect_state_broadcast_events_for_intent(
         return outgoing;
     };
 
-    let mut outgoing = Vec::new();
-
     if intent.virtual_workspace_changed {
         let workspace = WorkspaceBroadcastSnapshot::from(&state.active);
         if cache.workspace.as_ref() != Some(&workspace)
@@ -328,6 +347,75 @@ fn collect_state_broadcast_events_for_intent(
     outgoing
 }
 
+/// Maps a broadcast event name to its `[on_event]` hook name.
+fn event_hook_name(event: &Value) -> Option<&'static str> {
+    match event["event"].as_str()? {
+        "window_focused" => Some("window_focused"),
+        "virtual_workspace_changed" => Some("space_changed"),
+        "windows_changed" => Some("layout_changed"),
+        _ => None,
+    }
+}
+
+/// Builds the environment passed to an `[on_event]` hook from a broadcast event.
+/// Values missing from the event itself are taken from its `active` state, if present.
+fn event_hook_environment(hook: &str, event: &Value) -> Vec<(&'static str, String)> {
+    let lookup = |key: &str, active_key: &str| {
+        [&event[key], &event["active"][active_key]]
+            .into_iter()
+            .find_map(|value| match value {
+                Value::String(value) => Some(value.clone()),
+                Value::Number(value) => Some(value.to_string()),
+                _ => None,
+            })
+    };
+
+    let mut environment = vec![("PANERU_EVENT", hook.to_string())];
+    for (name, key, active_key) in [
+        ("PANERU_WINDOW_ID", "window_id", "focused_window_id"),
+        ("PANERU_WINDOW_TITLE", "title", "focused_window_title"),
+        ("PANERU_BUNDLE_ID", "bundle_id", "focused_bundle_id"),
+        (
+            "PANERU_WORKSPACE",
+            "virtual_workspace_number",
+            "virtual_workspace_number",
+        ),
+        (
+            "PANERU_NATIVE_WORKSPACE",
+            "native_workspace_id",
+            "native_workspace_id",
+        ),
+    ] {
+        if let Some(value) = lookup(key, active_key) {
+            environment.push((name, value));
+        }
+    }
+    environment
+}
+
+/// Spawns the configured `[on_event]` shell commands for the outgoing broadcast events.
+/// Children are reaped on a background thread so slow hooks never stall the event loop.
+fn run_event_hooks(outgoing: &[Value], config: &Config) {
+    for event in outgoing {
+        let Some(hook) = event_hook_name(event) else {
+            continue;
+        };
+        let Some(command) = config.on_event_command(hook) else {
+            continue;
+        };
+
+        debug!("running {hook} hook: {command}");
+        if let Err(err) = spawn_and_reap(
+            std::process::Command::new("/bin/sh")
+                .arg("-c")
+                .arg(&command)
+                .envs(event_hook_environment(hook, event)),
+        ) {
+            warn!("unable to run {hook} hook '{command}': {err}");
+        }
+    }
+}
+
 #[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 fn state_event_broadcast_handler(
     mut messages: MessageReader<Event>,
@@ -345,10 +433,11 @@ fn state_event_broadcast_handler(
     windows: Windows,
     apps: Query<&Application>,
     window_manager: Res<WindowManager>,
+    config: Res<Config>,
 ) {
     let events = messages.read().collect::<Vec<_>>();
 
-    if subscribers.streams.is_empty() {
+    if subscribers.streams.is_empty() && !config.has_event_hooks() {
         return;
     }
 
@@ -371,7 +460,7 @@ fn state_event_broadcast_handler(
     }
 
     let state = if intent.requires_state() {
-        match PaneruQueryState::extract(&workspaces, &displays, &windows, &apps, &window_manager) {
+        match extract_query_state(&workspaces, &displays, &windows, &apps, &window_manager) {
             Ok(state) => Some(state),
             Err(err) => {
                 warn!("extracting query state for broadcast: {err}");
@@ -396,6 +485,11 @@ fn state_event_broadcast_handler(
         return;
     }
 
+    run_event_hooks(&outgoing, &config);
+    if subscribers.streams.is_empty() {
+        return;
+    }
+
     let mut payload = outgoing
         .into_iter()
         .map(|event| event.to_string())
@@ -517,6 +611,13 @@ mod tests {
         assert_eq!(outgoing[0]["event"], "windows_changed");
         assert_eq!(outgoing[0]["virtual_workspace_number"], 2);
         assert_eq!(outgoing[0]["active"]["focused_window_id"], 26_261);
+        assert!(matches!(
+            serde_json::from_value(outgoing[0].clone()),
+            Ok(paneru_ipc::StateEvent::WindowsChanged {
+                virtual_workspace_number: Some(2),
+                ..
+            })
+        ));
 
         let duplicate =
             collect_state_broadcast_events(events.iter(), &state, &mut cache, |_| None, signals);
@@ -616,4 +717,84 @@ mod tests {
         assert!(intent.requires_state());
         assert!(!intent.is_empty());
     }
+
+    #[test]
+    fn test_state_broadcast_reports_config_reload_without_state() {
+        let changes = ConfigChanges {
+            options: vec!["animation_speed".to_string()],
+            bindings_added: vec!["window_center".to_string()],
+            window_rules: 2,
+            ..ConfigChanges::default()
+        };
+        let intent = StateBroadcastIntent::from_events(
+            [PaneruEvent::ConfigReloaded(changes)].iter(),
+            StateBroadcastSignals::default(),
+        );
+        assert!(!intent.is_empty());
+        assert!(!intent.requires_state());
+
+        let outgoing = collect_state_broadcast_events_for_intent(
+            &intent,
+            None,
+            &mut StateBroadcastCache::default(),
+            |_| None,
+        );
+
+        assert_eq!(outgoing.len(), 1);
+        assert_eq!(outgoing[0]["event"], "config_reloaded");
+        assert_eq!(
+            outgoing[0]["changes"]["options"],
+            json!(["animation_speed"])
+        );
+        assert_eq!(
+            outgoing[0]["changes"]["bindings_added"],
+            json!(["window_center"])
+        );
+        assert_eq!(outgoing[0]["changes"]["window_rules"], 2);
+
+        // Client tools read the same line through the typed IPC events.
+        let event = serde_json::from_value::<paneru_ipc::StateEvent>(outgoing[0].clone())
+            .expect("event should match the IPC types");
+        assert!(matches!(
+            event,
+            paneru_ipc::StateEvent::ConfigReloaded { changes } if changes.window_rules == 2
+        ));
+    }
+
+    #[test]
+    fn test_event_hook_environment_from_broadcast_events() {
+        let state =
+            query_state_with_active_window(26_261, "com.cmuxterm.app", "term", 2, vec![26_261]);
+        let mut cache = StateBroadcastCache::default();
+        let signals = StateBroadcastSignals {
+            virtual_workspace_changed: true,
+            windows_changed: true,
+            window_focused: true,
+        };
+
+        let outgoing =
+            collect_state_broadcast_events([].iter(), &state, &mut cache, |_| None, signals);
+        let hooks = outgoing
+            .iter()
+            .filter_map(event_hook_name)
+            .collect::<Vec<_>>();
+        assert_eq!(hooks, ["space_changed", "layout_changed", "window_focused"]);
+
+        let environment = event_hook_environment("space_changed", &outgoing[0]);
+        assert_eq!(
+            environment,
+            [
+                ("PANERU_EVENT", "space_changed".to_string()),
+                ("PANERU_WINDOW_ID", "26261".to_string()),
+                ("PANERU_WINDOW_TITLE", "term".to_string()),
+                ("PANERU_BUNDLE_ID", "com.cmuxterm.app".to_string()),
+                ("PANERU_WORKSPACE", "2".to_string()),
+                ("PANERU_NATIVE_WORKSPACE", "10".to_string()),
+            ]
+        );
+
+        let environment = event_hook_environment("window_focused", &outgoing[2]);
+        assert!(environment.contains(&("PANERU_WINDOW_ID", "26261".to_string())));
+        assert!(environment.contains(&("PANERU_WORKSPACE", "2".to_string())));
+    }
 }
@@ -0,0 +1,106 @@
+use bevy::app::{App, PreUpdate};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::Has;
+use bevy::ecs::system::{Commands, Query, Res};
+use bevy::math::IRect;
+use tracing::{info, warn};
+
+use super::{Command, StateOperation};
+use crate::config::Config;
+use crate::ecs::layout::LayoutStrip;
+use crate::ecs::params::Windows;
+use crate::ecs::restore::SessionRestore;
+use crate::ecs::state::{PaneruState, SavedUnmanagedKind};
+use crate::ecs::{
+    ActiveDisplayMarker, ActiveWorkspaceMarker, RestoreWindowState, SpawnCommandsExt, Unmanaged,
+};
+use crate::events::Event;
+use crate::manager::{Application, Display};
+
+pub(super) fn register_snapshot_commands(app: &mut App) {
+    app.add_systems(PreUpdate, state_snapshot_handler);
+}
+
+/// Handles `state export <file>` and `state import <file>`.
+/// Exports write the same JSON as the session state file. Imports run it through the session
+/// restore, regardless of `restore_enabled`, and float the windows recorded as floating.
+#[allow(clippy::needless_pass_by_value)]
+fn state_snapshot_handler(
+    mut messages: MessageReader<Event>,
+    workspaces: Query<(Option<&ChildOf>, &LayoutStrip, Has<ActiveWorkspaceMarker>)>,
+    displays: Query<(&Display, Entity, Has<ActiveDisplayMarker>)>,
+    windows: Windows,
+    apps: Query<&Application>,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::State(operation),
+        } = event
+        else {
+            continue;
+        };
+
+        match operation {
+            StateOperation::Export(path) => {
+                let state = PaneruState::extract(&workspaces, &displays, &windows, &apps);
+                match state.save_to_file(path) {
+                    Ok(()) => info!("state exported to {}.", path.display()),
+                    Err(err) => warn!("unable to export state to {}: {err}", path.display()),
+                }
+            }
+            StateOperation::Import(path) => {
+                let Some(state) = PaneruState::load_from_file(path) else {
+                    warn!("unable to import state from {}.", path.display());
+                    continue;
+                };
+                info!("importing state from {}.", path.display());
+                float_unmanaged_windows(&state, &windows, &apps, &mut commands);
+                commands
+                    .insert_resource(SessionRestore::new(state, config.restore_startup_grace()));
+                commands.trigger(RestoreWindowState);
+            }
+        }
+    }
+}
+
+/// Floats the windows which were floating in `state` and moves them back to their saved frames.
+/// Minimized and hidden windows are left alone, since their visibility is owned by the user.
+fn float_unmanaged_windows(
+    state: &PaneruState,
+    windows: &Windows,
+    apps: &Query<&Application>,
+    commands: &mut Commands,
+) {
+    for saved in &state.unmanaged {
+        if saved.kind != SavedUnmanagedKind::Floating {
+            continue;
+        }
+        let Some((window, entity, app_entity)) = windows.find_parent(saved.window.window_id) else {
+            continue;
+        };
+        let Some(bundle_id) = apps
+            .get(app_entity)
+            .ok()
+            .map(|app| app.bundle_id().unwrap_or_default().clone())
+        else {
+            continue;
+        };
+        let Ok(pid) = window.pid() else {
+            continue;
+        };
+        if !saved.window.hard_match(window.id(), pid, &bundle_id) {
+            continue;
+        }
+
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            entity_commands.try_insert(Unmanaged::Floating);
+        }
+        let frame = IRect::from(saved.frame);
+        commands.reposition_entity(entity, frame.min);
+        commands.resize_entity(entity, frame.size());
+    }
+}
@@ -0,0 +1,190 @@
+use bevy::app::{App, PreUpdate};
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::With;
+use bevy::ecs::system::{Commands, Query, Res};
+use stdext::function_name;
+use tracing::{debug, warn};
+
+use super::{Command, Direction, StripOperation};
+use crate::config::Config;
+use crate::ecs::layout::{Column, LayoutStrip};
+use crate::ecs::params::{ActiveDisplay, ActiveDisplayMut, Windows};
+use crate::ecs::scroll::clamp_viewport_offset;
+use crate::ecs::{FullWidthMarker, Position, RepositionMarker, SpawnCommandsExt};
+use crate::errors::Error;
+use crate::events::Event;
+use crate::platform::WinID;
+
+pub(super) fn register_strip_commands(app: &mut App) {
+    app.add_systems(PreUpdate, (strip_command_handler, strip_scroll_handler));
+}
+
+/// Applies `strip ...` IPC commands to the active `LayoutStrip`.
+/// Each operation targets windows by their window id, so external tools do not depend on focus.
+#[allow(clippy::needless_pass_by_value)]
+fn strip_command_handler(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    mut active_display: ActiveDisplayMut,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let viewport_width = active_display.actual_bounds(&config).width();
+
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::Strip(operation),
+        } = event
+        else {
+            continue;
+        };
+
+        let find = |window_id: WinID| {
+            windows
+                .find_managed(window_id)
+                .map(|(_, entity)| entity)
+                .ok_or(Error::NotFound(format!(
+                    "{}: window {window_id} is not managed.",
+                    function_name!()
+                )))
+        };
+        let strip = active_display.active_strip();
+
+        let result = match operation {
+            StripOperation::InsertAt(window_id, index) => find(*window_id).and_then(|entity| {
+                strip.index_of(entity)?;
+                strip.remove(entity);
+                strip.insert_at(*index, entity);
+                Ok(entity)
+            }),
+            StripOperation::Move(window_id, index) => find(*window_id).and_then(|entity| {
+                let from = strip.index_of(entity)?;
+                strip.move_column(from, *index);
+                Ok(entity)
+            }),
+            StripOperation::Stack(window_id, target_id) => find(*window_id)
+                .and_then(|entity| Ok((entity, find(*target_id)?)))
+                .and_then(|(entity, target)| {
+                    strip.index_of(entity)?;
+                    strip.stack_onto(entity, target)?;
+                    Ok(entity)
+                }),
+            StripOperation::SetWidth(window_id, ratio) => find(*window_id).and_then(|entity| {
+                if !ratio.is_finite() || *ratio <= 0.0 {
+                    return Err(Error::InvalidInput(format!(
+                        "{}: invalid width ratio {ratio}.",
+                        function_name!()
+                    )));
+                }
+                let column = strip.get(strip.index_of(entity)?)?;
+                let width = (ratio * f64::from(viewport_width)).round() as i32;
+                set_column_width(&column, width, &windows, &mut commands);
+                Ok(entity)
+            }),
+            StripOperation::Scroll(..) | StripOperation::ScrollTo(_) => continue,
+        };
+
+        match result {
+            Ok(entity) => {
+                debug!("strip {operation:?} applied.");
+                commands.reshuffle_around(entity);
+            }
+            Err(err) => warn!("strip {operation:?}: {err}"),
+        }
+    }
+}
+
+/// Pans the active strip for `strip scroll` and `strip scroll-to`, clamped to the same range as
+/// a swipe. Focus stays where it is, so no reshuffle is requested.
+#[allow(clippy::needless_pass_by_value)]
+fn strip_scroll_handler(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    strips: Query<(&Position, Option<&RepositionMarker>), With<LayoutStrip>>,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let viewport = active_display.actual_bounds(&config);
+    let strip = active_display.active_strip();
+    let strip_entity = active_display.active_strip_entity();
+    let column_edges = |column: Result<Column, Error>| {
+        column
+            .ok()
+            .and_then(|column| column.top())
+            .and_then(|entity| {
+                let x = windows.layout_position(entity)?.0.x;
+                Some((x, x + windows.size(entity)?.x))
+            })
+    };
+    let mut pending = None;
+
+    for event in messages.read() {
+        let Event::Command {
+            command: Command::Strip(operation),
+        } = event
+        else {
+            continue;
+        };
+        let Ok((position, reposition)) = strips.get(strip_entity) else {
+            return;
+        };
+        // Successive scrolls build on the target of the running animation.
+        let offset = pending
+            .or(reposition.map(|marker| marker.target.x))
+            .unwrap_or(position.0.x);
+
+        let target = match operation {
+            StripOperation::Scroll(Direction::West, amount) => {
+                offset + amount.pixels(viewport.width())
+            }
+            StripOperation::Scroll(_, amount) => offset - amount.pixels(viewport.width()),
+            StripOperation::ScrollTo(Direction::First) => {
+                let Some((left, _)) = column_edges(strip.first()) else {
+                    continue;
+                };
+                viewport.min.x - left
+            }
+            StripOperation::ScrollTo(_) => {
+                let Some((_, right)) = column_edges(strip.last()) else {
+                    continue;
+                };
+                viewport.max.x - right
+            }
+            _ => continue,
+        };
+        let get_window_frame = |entity| windows.moving_frame(entity);
+        let Some(target) = clamp_viewport_offset(
+            target,
+            strip,
+            &windows,
+            &get_window_frame,
+            &viewport,
+            &config,
+        ) else {
+            continue;
+        };
+        debug!("strip {operation:?} to offset {target}.");
+        pending = Some(target);
+        commands.reposition_entity(strip_entity, position.0.with_x(target));
+    }
+}
+
+/// Resizes every window of a column to `width`, dropping any full-width toggle state.
+pub(super) fn set_column_width(
+    column: &Column,
+    width: i32,
+    windows: &Windows,
+    commands: &mut Commands,
+) {
+    for entity in column.window_iter() {
+        if windows.full_width(entity).is_some()
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.try_remove::<FullWidthMarker>();
+        }
+        if let Some(size) = windows.size(entity) {
+            commands.resize_entity(entity, size.with_x(width));
+        }
+    }
+}
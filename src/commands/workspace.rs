@@ -0,0 +1,386 @@
+use bevy::app::{App, PreUpdate};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::hierarchy::ChildOf;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::{Has, With, Without};
+use bevy::ecs::system::{Commands, Query, Res};
+use tracing::{debug, warn};
+
+use objc2_core_graphics::CGDirectDisplayID;
+
+use super::{
+    Command, DisplayOperation, MoveFocus, Operation, SpaceOperation, WorkspaceOperation,
+    filter_window_operations,
+};
+use crate::config::Config;
+use crate::ecs::layout::LayoutStrip;
+use crate::ecs::params::{ActiveDisplayMut, Windows};
+use crate::ecs::{
+    ActiveDisplayMarker, ActiveWorkspaceMarker, DockPosition, SelectedVirtualMarker,
+    SpawnCommandsExt,
+};
+use crate::events::Event;
+use crate::manager::{Display, WindowManager};
+use crate::platform::WorkspaceId;
+
+pub(super) fn register_workspace_commands(app: &mut App) {
+    app.add_systems(
+        PreUpdate,
+        (
+            workspace_to_next_display,
+            swap_display_workspaces,
+            focus_space,
+            window_to_space,
+        ),
+    );
+}
+
+/// Handles `workspace tonextdisplay`: carries the active strip over to the next display, as a new
+/// virtual workspace of that display's current space. The windows are moved to the space through
+/// `SkyLight` and keep their width relative to the display. The source space gets an empty strip
+/// in place of the moved one.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+fn workspace_to_next_display(
+    mut messages: MessageReader<Event>,
+    mut strips: Query<(
+        Entity,
+        &mut LayoutStrip,
+        &ChildOf,
+        Has<ActiveWorkspaceMarker>,
+    )>,
+    displays: Query<(Entity, &Display, Option<&DockPosition>)>,
+    windows: Windows,
+    window_manager: Res<WindowManager>,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    if !messages.read().any(|event| {
+        matches!(
+            event,
+            Event::Command {
+                command: Command::Workspace(WorkspaceOperation::ToNextDisplay)
+            }
+        )
+    }) {
+        return;
+    }
+
+    let Some((strip_entity, source_entity)) = strips
+        .iter()
+        .find_map(|(entity, _, child, active)| active.then_some((entity, child.parent())))
+    else {
+        return;
+    };
+    let Ok((_, source, source_dock)) = displays.get(source_entity) else {
+        return;
+    };
+    let Some((target_entity, target, target_dock)) = displays
+        .iter()
+        .find(|(entity, _, _)| *entity != source_entity)
+    else {
+        debug!("no other display to move the workspace to.");
+        return;
+    };
+    let Ok(target_space) = window_manager.active_display_space(target.id()) else {
+        return;
+    };
+
+    let source_width = source.actual_display_bounds(source_dock, &config).width();
+    let target_width = target.actual_display_bounds(target_dock, &config).width();
+    let virtual_index = strips
+        .iter()
+        .filter(|(_, strip, _, _)| strip.id() == target_space)
+        .map(|(_, strip, _, _)| strip.virtual_index + 1)
+        .max()
+        .unwrap_or(0);
+
+    let Ok((_, mut strip, _, _)) = strips.get_mut(strip_entity) else {
+        return;
+    };
+    let (source_space, source_virtual_index) = (strip.id(), strip.virtual_index);
+    strip.move_to_workspace(target_space, virtual_index);
+    debug!(
+        "moving workspace {source_space} to display {} as {target_space} ({virtual_index}).",
+        target.id()
+    );
+
+    let moved = strip.all_windows();
+    carry_windows(
+        &moved,
+        target_space,
+        (source_width, target_width),
+        &windows,
+        &window_manager,
+        &mut commands,
+    );
+
+    // Re-adding the active marker hides the strips which were showing on the target space.
+    if let Ok(mut cmd) = commands.get_entity(strip_entity) {
+        cmd.try_remove::<(ActiveWorkspaceMarker, ChildOf)>()
+            .try_insert((ChildOf(target_entity), ActiveWorkspaceMarker));
+    }
+    commands.reposition_entity(strip_entity, target.bounds().min);
+    commands.spawn_layout_strip(
+        LayoutStrip::new(source_space, source_virtual_index),
+        source.bounds().min,
+        source_entity,
+        false,
+    );
+
+    if let Ok(mut cmd) = commands.get_entity(target_entity) {
+        cmd.try_insert(ActiveDisplayMarker);
+    }
+    window_manager.warp_mouse(target.bounds().center());
+    if let Some(entity) = windows
+        .focused()
+        .map(|(_, entity)| entity)
+        .filter(|focused| moved.contains(focused))
+        .or_else(|| moved.first().copied())
+    {
+        commands.reshuffle_around(entity);
+    }
+}
+
+/// Handles `display swap-workspaces`: exchanges the windows of the active strip with those of the
+/// strip shown on the next display. Both strips stay on their spaces; the windows move across.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+fn swap_display_workspaces(
+    mut messages: MessageReader<Event>,
+    mut strips: Query<(
+        Entity,
+        &mut LayoutStrip,
+        &ChildOf,
+        Has<ActiveWorkspaceMarker>,
+        Has<SelectedVirtualMarker>,
+    )>,
+    displays: Query<(Entity, &Display, Option<&DockPosition>)>,
+    windows: Windows,
+    window_manager: Res<WindowManager>,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    if !messages.read().any(|event| {
+        matches!(
+            event,
+            Event::Command {
+                command: Command::Display(DisplayOperation::SwapWorkspaces)
+            }
+        )
+    }) {
+        return;
+    }
+
+    let Some((active_entity, active_display)) = strips
+        .iter()
+        .find_map(|(entity, _, child, active, _)| active.then_some((entity, child.parent())))
+    else {
+        return;
+    };
+    let Some((other_display, other, other_dock)) = displays
+        .iter()
+        .find(|(entity, _, _)| *entity != active_display)
+    else {
+        debug!("no other display to swap workspaces with.");
+        return;
+    };
+    let Ok(other_space) = window_manager.active_display_space(other.id()) else {
+        return;
+    };
+    let Some(other_entity) = strips
+        .iter()
+        .find_map(|(entity, strip, child, _, selected)| {
+            (selected && strip.id() == other_space && child.parent() == other_display)
+                .then_some(entity)
+        })
+    else {
+        return;
+    };
+    let Ok((_, display, dock)) = displays.get(active_display) else {
+        return;
+    };
+    let active_width = display.actual_display_bounds(dock, &config).width();
+    let other_width = other.actual_display_bounds(other_dock, &config).width();
+
+    let Ok([(_, mut active_strip, ..), (_, mut other_strip, ..)]) =
+        strips.get_many_mut([active_entity, other_entity])
+    else {
+        return;
+    };
+    debug!(
+        "swapping windows of workspaces {} and {}.",
+        active_strip.id(),
+        other_strip.id()
+    );
+    active_strip.swap_columns(&mut other_strip);
+
+    let incoming = active_strip.all_windows();
+    let outgoing = other_strip.all_windows();
+    carry_windows(
+        &incoming,
+        active_strip.id(),
+        (other_width, active_width),
+        &windows,
+        &window_manager,
+        &mut commands,
+    );
+    carry_windows(
+        &outgoing,
+        other_strip.id(),
+        (active_width, other_width),
+        &windows,
+        &window_manager,
+        &mut commands,
+    );
+
+    for (strip, entities) in [(active_entity, &incoming), (other_entity, &outgoing)] {
+        if let Some(&entity) = entities.first() {
+            commands.reshuffle_around(entity);
+        } else if let Ok((_, _, child, _, _)) = strips.get(strip)
+            && let Ok((_, display, _)) = displays.get(child.parent())
+        {
+            commands.reposition_entity(strip, display.bounds().min);
+        }
+    }
+    if let Some(&entity) = incoming.first() {
+        commands.focus_entity(entity, false);
+    }
+}
+
+/// Handles `space focus <n>`: switches the active display to its n-th native space. The strips
+/// follow once macOS reports the space change.
+#[allow(clippy::needless_pass_by_value)]
+fn focus_space(
+    mut messages: MessageReader<Event>,
+    active_display: Query<&Display, With<ActiveDisplayMarker>>,
+    window_manager: Res<WindowManager>,
+) {
+    let Some(number) = messages.read().find_map(|event| match event {
+        Event::Command {
+            command: Command::Space(SpaceOperation::Focus(number)),
+        } => Some(*number),
+        _ => None,
+    }) else {
+        return;
+    };
+    let Ok(display) = active_display.single() else {
+        return;
+    };
+
+    let Some(space_id) = display_space(&window_manager, display.id(), number) else {
+        debug!("display {} has no space {number}.", display.id());
+        return;
+    };
+    _ = window_manager
+        .focus_space(display.id(), space_id)
+        .inspect_err(|err| warn!("switching to space {number}: {err}"));
+}
+
+/// Handles `window tospace <n> [--follow]`: moves the focused window onto the n-th native space
+/// of the active display. With `--follow` the display switches to that space and the window keeps
+/// the focus, otherwise the focus stays on the current space.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+fn window_to_space(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    mut active_display: ActiveDisplayMut,
+    mut other_workspaces: Query<
+        &mut LayoutStrip,
+        (With<SelectedVirtualMarker>, Without<ActiveWorkspaceMarker>),
+    >,
+    window_manager: Res<WindowManager>,
+    mut commands: Commands,
+) {
+    let Some(&Operation::ToSpace(number, move_focus)) =
+        filter_window_operations(&mut messages, |op| matches!(op, Operation::ToSpace(..))).next()
+    else {
+        return;
+    };
+    let Some((window, entity, None)) = windows
+        .focused()
+        .and_then(|(_, entity)| windows.get_managed(entity))
+    else {
+        return;
+    };
+    let display_id = active_display.display().id();
+    let Some(space_id) = display_space(&window_manager, display_id, number) else {
+        debug!("display {display_id} has no space {number}.");
+        return;
+    };
+    if active_display.active_strip().id() == space_id {
+        return;
+    }
+
+    debug!(
+        "moving window (id {}, {entity}) to space {space_id}.",
+        window.id()
+    );
+    let source_neighbour = active_display
+        .active_strip()
+        .left_neighbour(entity)
+        .or_else(|| active_display.active_strip().right_neighbour(entity));
+    active_display.active_strip().remove(entity);
+    if let Some(neighbour) = source_neighbour {
+        commands.reshuffle_around(neighbour);
+    }
+    window_manager.move_window_to_space(window.id(), space_id);
+
+    // Without a strip for the space yet, the window is picked up once the space is shown.
+    if let Some(mut target_strip) = other_workspaces
+        .iter_mut()
+        .find(|strip| strip.id() == space_id)
+    {
+        target_strip.append(entity);
+        commands.reshuffle_around(entity);
+    }
+
+    match move_focus {
+        MoveFocus::Follow => {
+            _ = window_manager
+                .focus_space(display_id, space_id)
+                .inspect_err(|err| warn!("switching to space {number}: {err}"));
+            commands.focus_entity(entity, false);
+        }
+        MoveFocus::Stay => {
+            if let Some(neighbour) = source_neighbour {
+                commands.focus_entity(neighbour, false);
+            }
+        }
+    }
+}
+
+/// Returns the id of the one-based `number`-th desktop of a display. Fullscreen spaces are not
+/// counted, matching the desktop numbers of Mission Control.
+fn display_space(
+    window_manager: &WindowManager,
+    display_id: CGDirectDisplayID,
+    number: usize,
+) -> Option<WorkspaceId> {
+    window_manager
+        .display_desktops(display_id)
+        .ok()
+        .and_then(|desktops| desktops.get(number.checked_sub(1)?).copied())
+}
+
+/// Moves `entities` onto the space `space_id` and scales their widths by the ratio of the
+/// `(source, target)` viewport widths, so they keep their share of the display.
+fn carry_windows(
+    entities: &[Entity],
+    space_id: WorkspaceId,
+    (source_width, target_width): (i32, i32),
+    windows: &Windows,
+    window_manager: &WindowManager,
+    commands: &mut Commands,
+) {
+    for &entity in entities {
+        if let Some(window) = windows.get(entity) {
+            window_manager.move_window_to_space(window.id(), space_id);
+        }
+        if let Some(size) = windows.size(entity)
+            && source_width > 0
+        {
+            let ratio = f64::from(size.x) / f64::from(source_width);
+            let width = (ratio * f64::from(target_width)).round() as i32;
+            commands.resize_entity(entity, size.with_x(width));
+        }
+    }
+}
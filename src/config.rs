@@ -2,11 +2,12 @@ use arc_swap::{ArcSwap, Guard};
 use bevy::ecs::resource::Resource;
 use objc2_core_foundation::{CFData, CFString};
 use regex::Regex;
-use serde::{Deserialize, Deserializer, de};
+use serde::{Deserialize, Deserializer, Serialize, de};
 use std::{
     collections::HashMap,
     env,
     ffi::c_void,
+    fmt,
     fs::{OpenOptions, create_dir_all, read_to_string},
     io::{ErrorKind, Write},
     path::{Path, PathBuf},
@@ -18,39 +19,53 @@ use stdext::function_name;
 use tracing::{error, info, warn};
 
 use self::decorations::BorderRadiusOption;
+use self::hot_corners::{HotCornerOptions, HotCornerRegion};
 use self::swipe::SwipeGestureDirection;
 use crate::{
-    commands::{Command, Direction, MouseMove, MoveFocus, Operation, ResizeDirection},
+    commands::{
+        Command, CycleDirection, Direction, DisplayOperation, DisplayTarget, GapsOperation,
+        InsertNext, MouseMove, MoveFocus, Operation, ResizeAmount, ResizeAxis, ResizeDirection,
+        SpaceOperation, StateOperation, StripOperation, WorkspaceOperation,
+    },
     manager::ProcessApi,
-    platform::{Modifiers, OSStatus, macos_major_version},
+    platform::{Modifiers, OSStatus, WorkspaceId, macos_major_version},
 };
 use crate::{
     errors::{Error, Result},
     util::MacResult,
 };
-use crate::{platform::CFStringRef, util::AXUIWrapper};
+use crate::{
+    platform::CFStringRef,
+    util::{AXUIWrapper, expand_env, expand_path},
+};
 
 pub mod decorations;
+pub mod displays;
+pub mod hot_corners;
 pub mod padding;
 pub mod swipe;
+pub mod workspaces;
 
 /// A `LazyLock` that determines the path to the application's configuration file.
 /// It checks the `PANERU_CONFIG` environment variable first, then standard XDG locations and user home directory.
-/// If no configuration file is found, a minimal one is created in the user's
+/// If no configuration file is found, a commented default one is created in the user's
 /// XDG configuration directory so a fresh app installation can start with the
-/// built-in defaults.
+/// built-in defaults. If that fails too, the path is returned anyway and
+/// Paneru runs with the built-in defaults.
 pub static CONFIGURATION_FILE: LazyLock<PathBuf> = LazyLock::new(|| {
     discover_configuration_file().unwrap_or_else(|| {
         create_default_configuration_file().unwrap_or_else(|error| {
-            panic!(
-                "{}: Unable to create default configuration: {error}",
+            error!(
+                "{}: Unable to create default configuration, using built-in defaults: {error}",
                 function_name!()
-            )
+            );
+            default_configuration_file().unwrap_or_else(|_| PathBuf::from("paneru.toml"))
         })
     })
 });
 
-const DEFAULT_CONFIGURATION: &str = "# Paneru configuration\n\n[options]\n\n[bindings]\n";
+/// A fully commented configuration showing the default values.
+const DEFAULT_CONFIGURATION: &str = include_str!("config/default.toml");
 
 fn default_configuration_file() -> std::io::Result<PathBuf> {
     let config_home = env::var_os("XDG_CONFIG_HOME")
@@ -74,6 +89,31 @@ fn create_default_configuration_file() -> std::io::Result<PathBuf> {
     Ok(path)
 }
 
+/// Writes the commented default configuration to the XDG configuration directory.
+/// An existing file is only replaced when `force` is set.
+///
+/// # Returns
+///
+/// The path of the written file, or an `AlreadyExists` error.
+pub fn write_default_configuration(force: bool) -> std::io::Result<PathBuf> {
+    let path = default_configuration_file()?;
+    if force {
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        std::fs::write(&path, DEFAULT_CONFIGURATION)?;
+    } else if !create_configuration_file_at(&path)? {
+        return Err(std::io::Error::new(
+            ErrorKind::AlreadyExists,
+            format!(
+                "{} already exists, use --force to replace it",
+                path.display()
+            ),
+        ));
+    }
+    Ok(path)
+}
+
 fn create_configuration_file_at(path: &Path) -> std::io::Result<bool> {
     let parent = path.parent().ok_or_else(|| {
         std::io::Error::new(ErrorKind::InvalidInput, "configuration path has no parent")
@@ -94,7 +134,7 @@ fn create_configuration_file_at(path: &Path) -> std::io::Result<bool> {
 /// Unlike [`CONFIGURATION_FILE`], this does not panic when no file is found.
 pub fn discover_configuration_file() -> Option<PathBuf> {
     if let Ok(path_str) = env::var("PANERU_CONFIG") {
-        let path = PathBuf::from(path_str);
+        let path = expand_path(&path_str);
         if path.exists() {
             return Some(path);
         }
@@ -157,6 +197,110 @@ pub fn deprecated_options_in_input(input: &str) -> Result<Vec<String>> {
         .collect())
 }
 
+/// Sets the dotted option `key` in the raw TOML document. A bare key refers to `[options]`, and
+/// `value` is parsed as a TOML value, falling back to a plain string.
+fn set_raw_option(raw: &mut toml::Table, key: &str, value: &str) -> Result<()> {
+    let mut path = key.split('.').collect::<Vec<_>>();
+    if path.len() == 1 {
+        path.insert(0, "options");
+    }
+    let Some((last, sections)) = path.split_last() else {
+        return Err(Error::InvalidConfig(format!(
+            "{}: empty option key.",
+            function_name!()
+        )));
+    };
+
+    let parsed = toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_string()));
+
+    let mut table = raw;
+    for section in sections {
+        table = table
+            .entry(*section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .ok_or(Error::InvalidConfig(format!(
+                "{}: '{section}' in '{key}' is not a section.",
+                function_name!()
+            )))?;
+    }
+    table.insert((*last).to_string(), parsed);
+    Ok(())
+}
+
+/// Reads the configuration file at `path` and merges the files listed in its top-level
+/// `include` array into it. Relative paths are resolved against the directory of `path` and
+/// `~` expands to the home directory. Settings in `path` win over included ones.
+///
+/// # Returns
+///
+/// The merged configuration as TOML and the resolved paths of the included files.
+fn read_with_includes(path: &Path) -> Result<(String, Vec<PathBuf>)> {
+    let input = read_to_string(path)?;
+    let mut table: toml::Table = toml::from_str(&input)?;
+    let Some(include) = table.remove("include") else {
+        return Ok((input, Vec::new()));
+    };
+
+    let base = path.parent().unwrap_or(Path::new("."));
+    let invalid = || {
+        Error::InvalidConfig(format!(
+            "{}: include must be a list of paths",
+            function_name!()
+        ))
+    };
+    let includes = include
+        .as_array()
+        .ok_or_else(invalid)?
+        .iter()
+        .map(|value| {
+            value
+                .as_str()
+                .map(|include| resolve_include(include, base))
+                .ok_or_else(invalid)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for include in &includes {
+        let input = read_to_string(include).map_err(|err| {
+            Error::InvalidConfig(format!("including '{}': {err}", include.display()))
+        })?;
+        let mut included: toml::Table = toml::from_str(&input)?;
+        if included.remove("include").is_some() {
+            warn!(
+                "{}: nested include in '{}' is ignored.",
+                function_name!(),
+                include.display()
+            );
+        }
+        merge_tables(&mut table, included);
+    }
+    Ok((toml::to_string(&table)?, includes))
+}
+
+fn resolve_include(include: &str, base: &Path) -> PathBuf {
+    base.join(expand_path(include))
+}
+
+/// Merges `source` into `target`. Tables are merged key by key, other values already present in
+/// `target` are kept.
+fn merge_tables(target: &mut toml::Table, source: toml::Table) {
+    for (key, value) in source {
+        match (target.get_mut(&key), value) {
+            (Some(toml::Value::Table(target)), toml::Value::Table(source)) => {
+                merge_tables(target, source);
+            }
+            (Some(_), _) => {}
+            (None, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
 /// Returns deprecated top-level `[options]` keys present in the config file.
 pub fn deprecated_options_in_file(path: &Path) -> Result<Vec<String>> {
     let input = read_to_string(path)?;
@@ -219,6 +363,74 @@ fn parse_resize_direction(direction: &str) -> Result<ResizeDirection> {
     })
 }
 
+/// Parses the arguments of `resize width +100` or `resize height -5%` into an axis and amount.
+fn parse_resize_by(axis: &str, amount: &str) -> Result<Operation> {
+    let err = || {
+        Error::InvalidConfig(format!(
+            "{}: Invalid resize '{axis} {amount}'",
+            function_name!()
+        ))
+    };
+    let axis = match axis {
+        "width" => ResizeAxis::Width,
+        "height" => ResizeAxis::Height,
+        _ => return Err(err()),
+    };
+    let amount = parse_amount(amount).ok_or_else(err)?;
+    Ok(Operation::ResizeBy { axis, amount })
+}
+
+/// Parses a signed amount in pixels (`+100`) or in percent of the viewport (`-5%`).
+fn parse_amount(amount: &str) -> Option<ResizeAmount> {
+    match amount.strip_suffix('%') {
+        Some(percent) => percent
+            .parse::<f64>()
+            .ok()
+            .filter(|percent| percent.is_finite())
+            .map(ResizeAmount::Percent),
+        None => amount.parse::<i32>().ok().map(ResizeAmount::Pixels),
+    }
+}
+
+/// Parses `0.4` as a ratio of the padded display width, or `900px` as an exact pixel width.
+fn parse_width(width: &str) -> Result<Operation> {
+    let operation = match width.strip_suffix("px") {
+        Some(pixels) => pixels
+            .parse::<i32>()
+            .ok()
+            .filter(|pixels| *pixels > 0)
+            .map(Operation::SetWidthPixels),
+        None => width
+            .parse::<f64>()
+            .ok()
+            .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+            .map(Operation::SetWidth),
+    };
+    operation.ok_or(Error::InvalidConfig(format!(
+        "{}: Invalid width '{width}'",
+        function_name!()
+    )))
+}
+
+/// Parses `2x2:0:1` as a single grid cell, or `2x2:0:1:2:1` and `2:2:0:1:2:1` as a cell with a
+/// span, into a grid placement.
+fn parse_grid(grid: &str) -> Result<Operation> {
+    let mut spec = grid.replacen('x', ":", 1);
+    if spec.split(':').count() == 4 {
+        spec.push_str(":1:1");
+    }
+    let (x, y, width, height) = parse_grid_ratios(&spec).ok_or(Error::InvalidConfig(format!(
+        "{}: Invalid grid '{grid}'",
+        function_name!()
+    )))?;
+    Ok(Operation::Grid {
+        x,
+        y,
+        width,
+        height,
+    })
+}
+
 /// Parses a command argument vector into an `Operation` enum.
 ///
 /// # Arguments
@@ -239,8 +451,24 @@ fn parse_operation(argv: &[&str]) -> Result<Operation> {
         "focus" => match *argv.get(1).ok_or(err.clone())? {
             "unmanaged" => Operation::FocusUnmanaged,
             "managed" => Operation::FocusManaged,
+            "next" => Operation::FocusNext,
+            "prev" => Operation::FocusPrev,
+            "any" => Operation::FocusAny(parse_direction(argv.get(2).ok_or(err)?)?),
             dir => Operation::Focus(parse_direction(dir)?),
         },
+        "focus-id" => Operation::FocusId(argv.get(1).and_then(|id| id.parse().ok()).ok_or(err)?),
+        "focusapp" => {
+            let matcher = argv
+                .get(1)
+                .and_then(|pattern| Regex::new(pattern).ok())
+                .ok_or(err.clone())?;
+            let launch = match argv.get(2).copied() {
+                None => false,
+                Some("launch") => true,
+                Some(_) => return Err(err),
+            };
+            Operation::FocusApp { matcher, launch }
+        }
         "raise" => match *argv.get(1).ok_or(err.clone())? {
             "floating" => Operation::RaiseFloating,
             _ => return Err(err),
@@ -248,20 +476,99 @@ fn parse_operation(argv: &[&str]) -> Result<Operation> {
         "togglefloatlayer" => Operation::ToggleFloatingLayer,
         "swap" => Operation::Swap(parse_direction(argv.get(1).ok_or(err)?)?),
         "center" => Operation::Center,
-        "resize" => Operation::Resize(
-            argv.get(1)
-                .map_or(Ok(ResizeDirection::Grow), |arg| parse_resize_direction(arg))?,
-        ),
+        "resize" => match (argv.get(1), argv.get(2)) {
+            (Some(axis), Some(amount)) => parse_resize_by(axis, amount)?,
+            (direction, _) => Operation::Resize(
+                direction.map_or(Ok(ResizeDirection::Grow), |arg| parse_resize_direction(arg))?,
+            ),
+        },
         "grow" => Operation::Resize(ResizeDirection::Grow),
-        "shrink" => Operation::Resize(ResizeDirection::Shrink),
+        "shrink" | "resizeback" => Operation::Resize(ResizeDirection::Shrink),
+        "width" => parse_width(argv.get(1).ok_or(err)?)?,
+        "grid" => parse_grid(argv.get(1).ok_or(err)?)?,
         "fullwidth" => Operation::FullWidth,
+        "monocle" => Operation::Monocle,
+        "zen" => Operation::Zen,
+        "overview" => Operation::Overview,
+        "jump" => Operation::Jump,
+        "peek" => Operation::Peek(parse_direction(argv.get(1).ok_or(err)?)?),
         "manage" => Operation::Manage,
+        "minimize" => Operation::Minimize,
+        "deminimize" => Operation::Deminimize,
+        "hideapp" => Operation::HideApp,
+        "cycle-recent" => Operation::CycleRecent,
+        "restore-original" => Operation::RestoreOriginal,
         "equalize" => Operation::Equalize,
+        "accordion" => Operation::Accordion,
+        "promote" => Operation::Promote,
+        "pin" => Operation::Pin,
+        "setinsertpoint" => Operation::SetInsertPoint,
+        "insertnext" => Operation::InsertNext(match argv.get(1).copied() {
+            Some("stacked") => InsertNext::Stacked,
+            Some("right") => InsertNext::Right,
+            Some("left") => InsertNext::Left,
+            _ => return Err(err),
+        }),
+        "sticky" => Operation::Sticky,
+        "cyclestack" => Operation::CycleStack(match argv.get(1).copied() {
+            None | Some("next") => CycleDirection::Next,
+            Some("prev") => CycleDirection::Prev,
+            Some(_) => return Err(err),
+        }),
         "balance" => Operation::Balance,
+        "balancewidths" => match (argv.get(1), argv.get(2)) {
+            (None, _) => Operation::BalanceWidths(None),
+            (Some(first), Some(last)) => {
+                // Columns are numbered from 1 on the command line.
+                let column = |arg: &str| arg.parse::<usize>().ok().and_then(|n| n.checked_sub(1));
+                match (column(first), column(last)) {
+                    (Some(first), Some(last)) if first <= last => {
+                        Operation::BalanceWidths(Some((first, last)))
+                    }
+                    _ => return Err(err),
+                }
+            }
+            (Some(_), None) => return Err(err),
+        },
+        "reverse" => Operation::ReverseStrip,
+        "rotate" => Operation::RotateStrip(
+            argv.get(1)
+                .map_or(Ok(1), |steps| steps.parse::<i32>())
+                .map_err(|_| err)?,
+        ),
+        "layout-save" => Operation::LayoutSave((*argv.get(1).ok_or(err)?).to_string()),
+        "layout-apply" => Operation::LayoutApply((*argv.get(1).ok_or(err)?).to_string()),
         "stack" => Operation::Stack(true),
         "unstack" => Operation::Stack(false),
         "nextdisplay" => Operation::ToNextDisplay(MoveFocus::Follow),
         "nextdisplaysend" => Operation::ToNextDisplay(MoveFocus::Stay),
+        "todisplay" => {
+            let move_focus = match argv.get(2).copied() {
+                None => MoveFocus::Stay,
+                Some("--follow") => MoveFocus::Follow,
+                Some(_) => return Err(err),
+            };
+            let target = match argv.get(1).map(|arg| (arg, arg.parse::<usize>())) {
+                Some((_, Ok(number @ 1..))) => DisplayTarget::Index(number),
+                Some((arg, Err(_))) => match parse_direction(arg) {
+                    Ok(Direction::First | Direction::Last) | Err(_) => return Err(err),
+                    Ok(direction) => DisplayTarget::Direction(direction),
+                },
+                _ => return Err(err),
+            };
+            Operation::ToDisplay(target, move_focus)
+        }
+        "tospace" => {
+            let move_focus = match argv.get(2).copied() {
+                None => MoveFocus::Stay,
+                Some("--follow") => MoveFocus::Follow,
+                Some(_) => return Err(err),
+            };
+            match argv.get(1).and_then(|arg| arg.parse::<usize>().ok()) {
+                Some(number @ 1..) => Operation::ToSpace(number, move_focus),
+                _ => return Err(err),
+            }
+        }
         "snap" => Operation::Snap,
         "virtual" => {
             let target = argv.get(1).ok_or(err)?;
@@ -329,6 +636,53 @@ fn parse_mouse_move(argv: &[&str]) -> Result<MouseMove> {
     Ok(out)
 }
 
+/// Parses a `strip` command argument vector into a `StripOperation` enum.
+/// Window arguments are window ids and column indices are zero-based, e.g.
+/// `["insert-at", "1234", "0"]` or `["set-width", "1234", "0.5"]`.
+fn parse_strip_operation(argv: &[&str]) -> Result<StripOperation> {
+    let err = || {
+        Error::InvalidConfig(format!(
+            "{}: Invalid strip command '{argv:?}'",
+            function_name!()
+        ))
+    };
+    let window_id = |index: usize| {
+        argv.get(index)
+            .and_then(|arg| arg.parse::<WinID>().ok())
+            .ok_or_else(err)
+    };
+    let column = || {
+        argv.get(2)
+            .and_then(|arg| arg.parse::<usize>().ok())
+            .ok_or_else(err)
+    };
+
+    let out = match argv {
+        ["insert-at", _, _] => StripOperation::InsertAt(window_id(1)?, column()?),
+        ["move", _, _] => StripOperation::Move(window_id(1)?, column()?),
+        ["stack", _, _] => StripOperation::Stack(window_id(1)?, window_id(2)?),
+        ["set-width", _, ratio] => {
+            StripOperation::SetWidth(window_id(1)?, ratio.parse::<f64>().map_err(|_| err())?)
+        }
+        ["scroll", direction, rest @ ..] if rest.len() <= 1 => {
+            let direction = match *direction {
+                "left" => Direction::West,
+                "right" => Direction::East,
+                _ => return Err(err()),
+            };
+            let amount = match rest.first() {
+                Some(amount) => parse_amount(amount).ok_or_else(err)?,
+                None => ResizeAmount::Percent(50.0),
+            };
+            StripOperation::Scroll(direction, amount)
+        }
+        ["scroll-to", "first"] => StripOperation::ScrollTo(Direction::First),
+        ["scroll-to", "last"] => StripOperation::ScrollTo(Direction::Last),
+        _ => return Err(err()),
+    };
+    Ok(out)
+}
+
 /// Parses a command argument vector into a `Command` enum.
 ///
 /// # Arguments
@@ -346,6 +700,86 @@ pub fn parse_command(argv: &[&str]) -> Result<Command> {
         "printstate" => Command::PrintState,
         "window" => Command::Window(parse_operation(&argv[1..])?),
         "mouse" => Command::Mouse(parse_mouse_move(&argv[1..])?),
+        "strip" => Command::Strip(parse_strip_operation(&argv[1..])?),
+        "mode" => Command::Mode(
+            (*argv.get(1).ok_or(Error::InvalidConfig(format!(
+                "{}: missing mode name in '{argv:?}'",
+                function_name!()
+            )))?)
+            .to_string(),
+        ),
+        "config" => match argv.get(1..) {
+            Some(["set", key, value @ ..]) if !value.is_empty() => {
+                Command::SetOption((*key).to_string(), value.join(" "))
+            }
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "{}: expected 'config set <key> <value>' in '{argv:?}'",
+                    function_name!()
+                )));
+            }
+        },
+        "exec" if argv.len() > 1 => {
+            Command::Exec(argv[1..].iter().map(ToString::to_string).collect())
+        }
+        "loglevel" => Command::LogLevel(
+            (*argv.get(1).ok_or(Error::InvalidConfig(format!(
+                "{}: missing log filter in '{argv:?}'",
+                function_name!()
+            )))?)
+            .to_string(),
+        ),
+        "state" => match argv.get(1..) {
+            Some(["export", path]) => Command::State(StateOperation::Export(expand_path(path))),
+            Some(["import", path]) => Command::State(StateOperation::Import(expand_path(path))),
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "{}: expected 'state export|import <file>' in '{argv:?}'",
+                    function_name!()
+                )));
+            }
+        },
+        "workspace" => match argv.get(1..) {
+            Some(["tonextdisplay"]) => Command::Workspace(WorkspaceOperation::ToNextDisplay),
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "{}: expected 'workspace tonextdisplay' in '{argv:?}'",
+                    function_name!()
+                )));
+            }
+        },
+        "display" => match argv.get(1..) {
+            Some(["swap-workspaces"]) => Command::Display(DisplayOperation::SwapWorkspaces),
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "{}: expected 'display swap-workspaces' in '{argv:?}'",
+                    function_name!()
+                )));
+            }
+        },
+        "space" => match (argv.get(1..), argv.get(2).and_then(|arg| arg.parse().ok())) {
+            (Some(["focus", _]), Some(number @ 1..)) => {
+                Command::Space(SpaceOperation::Focus(number))
+            }
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "{}: expected 'space focus <number>' in '{argv:?}'",
+                    function_name!()
+                )));
+            }
+        },
+        "gaps" => match (argv.get(1..), argv.get(2).and_then(|arg| arg.parse().ok())) {
+            (Some(["inner", _]), Some(delta)) => Command::Gaps(GapsOperation::Inner(delta)),
+            (Some(["outer", _]), Some(delta)) => Command::Gaps(GapsOperation::Outer(delta)),
+            (Some(["reset"]), _) => Command::Gaps(GapsOperation::Reset),
+            _ => {
+                return Err(Error::InvalidConfig(format!(
+                    "{}: expected 'gaps inner|outer <+/-pixels>' or 'gaps reset' in '{argv:?}'",
+                    function_name!()
+                )));
+            }
+        },
+        "dump-journal" => Command::DumpJournal(argv.get(1).copied().map(expand_path)),
         "quit" => Command::Quit,
         "restart" => Command::Restart,
         _ => {
@@ -376,9 +810,11 @@ impl Config {
     ///
     /// `Ok(Self)` if the configuration is loaded successfully, otherwise `Err(Error)` with an error message.
     pub fn new(path: &Path) -> Result<Self> {
-        let input = read_to_string(path)?;
+        let (input, includes) = read_with_includes(path)?;
+        let mut inner = InnerConfig::new(&input)?;
+        inner.includes = includes;
         Ok(Config {
-            inner: Arc::new(ArcSwap::from_pointee(InnerConfig::new(&input)?)),
+            inner: Arc::new(ArcSwap::from_pointee(inner)),
         })
     }
 
@@ -390,12 +826,79 @@ impl Config {
     ///
     /// # Returns
     ///
-    /// `Ok(())` if the configuration is reloaded successfully, otherwise `Err(Error)` with an error message.
-    pub fn reload_config(&mut self, path: &Path) -> Result<()> {
-        let input = read_to_string(path)?;
-        let new = InnerConfig::new(&input)?;
+    /// `Ok(ConfigChanges)` summarizing the differences to the previous configuration, otherwise `Err(Error)` with an error message.
+    pub fn reload_config(&mut self, path: &Path) -> Result<ConfigChanges> {
+        let (input, includes) = read_with_includes(path)?;
+        let mut new = InnerConfig::new(&input)?;
+        new.includes = includes;
+        let changes = ConfigChanges::between(&self.inner().raw, &new.raw);
         self.inner.store(Arc::new(new));
-        Ok(())
+        Ok(changes)
+    }
+
+    /// Sets a single option of the live configuration, without touching the file on disk.
+    /// `key` is a dotted path, e.g. `padding.top`; a bare key refers to `[options]`. `value`
+    /// is parsed as a TOML value, falling back to a plain string.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(ConfigChanges)` summarizing the change, otherwise `Err(Error)` if the key is invalid
+    /// or the resulting configuration does not parse.
+    pub fn set_option(&mut self, key: &str, value: &str) -> Result<ConfigChanges> {
+        self.set_options(&[(key.to_string(), value.to_string())])
+    }
+
+    /// Sets several options of the live configuration at once, as with `set_option`, so that
+    /// they are applied as a single change.
+    pub fn set_options(&mut self, options: &[(String, String)]) -> Result<ConfigChanges> {
+        let current = self.inner();
+        let mut raw = current.raw.clone();
+        for (key, value) in options {
+            set_raw_option(&mut raw, key, value)?;
+        }
+        let mut overrides = current.overrides.clone();
+        overrides.retain(|(set, _)| options.iter().all(|(key, _)| key != set));
+        overrides.extend_from_slice(options);
+        self.store_raw(current, &raw, overrides)
+    }
+
+    /// Drops the runtime overrides of `keys`, returning them to their values from the file.
+    pub fn reset_options(&mut self, keys: &[String]) -> Result<ConfigChanges> {
+        let current = self.inner();
+        let mut overrides = current.overrides.clone();
+        overrides.retain(|(set, _)| !keys.contains(set));
+        let mut raw = current.loaded.clone();
+        for (key, value) in &overrides {
+            set_raw_option(&mut raw, key, value)?;
+        }
+        self.store_raw(current, &raw, overrides)
+    }
+
+    /// Replaces the live configuration with one parsed from `raw`, keeping the file state.
+    fn store_raw(
+        &mut self,
+        current: Guard<Arc<InnerConfig>>,
+        raw: &toml::Table,
+        overrides: Vec<(String, String)>,
+    ) -> Result<ConfigChanges> {
+        let mut new = InnerConfig::new(&toml::to_string(raw)?)?;
+        new.includes.clone_from(&current.includes);
+        new.loaded.clone_from(&current.loaded);
+        new.overrides = overrides;
+        let changes = ConfigChanges::between(&current.raw, &new.raw);
+        drop(current);
+        self.inner.store(Arc::new(new));
+        Ok(changes)
+    }
+
+    /// Returns the `(key, value)` pairs set with `config set` since the file was last loaded.
+    pub fn runtime_overrides(&self) -> Vec<(String, String)> {
+        self.inner().overrides.clone()
+    }
+
+    /// Returns the files merged into the configuration by its `include` directive.
+    pub fn included_files(&self) -> Vec<PathBuf> {
+        self.inner().includes.clone()
     }
 
     /// Returns a read guard to the inner `InnerConfig` for read-only access.
@@ -416,13 +919,25 @@ impl Config {
         self.inner().options.clone()
     }
 
+    /// Returns the curve of window move and resize animations, `ease_out` unless
+    /// `animation_easing` is set.
+    pub fn animation_easing(&self) -> AnimationEasing {
+        self.options().animation_easing.unwrap_or_default()
+    }
+
     // Exponential ease-out decay rate (per second) consumed by the animation
     // systems as `t = 1 - e^(-rate*dt)`. Higher values feel snappier; very large
     // values collapse to an instant snap.
     // Suggested range: 8..20 for a fluid feel. Unset = instant (no animation),
     pub fn animation_speed(&self) -> f64 {
-        self.options()
-            .animation_speed
+        self.animation_speed_on(None)
+    }
+
+    /// Returns the animation speed on `workspace`, falling back to `animation_speed`.
+    pub fn animation_speed_on(&self, workspace: Option<WorkspaceId>) -> f64 {
+        self.workspace_options(workspace)
+            .and_then(|workspace| workspace.animation_speed)
+            .or(self.options().animation_speed)
             // If unset, set it to something high, so the move happens immediately,
             // effectively disabling animation.
             .unwrap_or(1_000_000.0)
@@ -440,30 +955,69 @@ impl Config {
     ///
     /// `Some(Command)` if a matching keybinding is found, otherwise `None`.
     pub fn find_keybind(&self, keycode: u8, mask: Modifiers) -> Option<Command> {
+        find_in_bindings(&self.inner().bindings, keycode, mask, false)
+    }
+
+    /// Finds a keybinding of the named `[modes.<mode>]` table.
+    pub fn find_mode_keybind(&self, mode: &str, keycode: u8, mask: Modifiers) -> Option<Command> {
         let config = self.inner();
-        config
-            .bindings
-            .values()
-            .flat_map(|binds| binds.all())
-            .find_map(|bind| {
-                (bind.code == keycode && bind.modifiers.matches(mask))
-                    .then_some(bind.command.clone())
-            })
+        let bindings = config.modes.as_ref()?.get(mode)?;
+        find_in_bindings(bindings, keycode, mask, false)
+    }
+
+    /// Returns `true` if a `[modes.<mode>]` table with this name is configured.
+    pub fn has_mode(&self, mode: &str) -> bool {
+        self.inner()
+            .modes
+            .as_ref()
+            .is_some_and(|modes| modes.contains_key(mode))
+    }
+
+    /// Returns the resolved leader key, if one is configured.
+    pub fn leader_key(&self) -> Option<(u8, Modifiers)> {
+        self.inner().parsed_leader
+    }
+
+    pub fn leader_timeout(&self) -> Duration {
+        Duration::from_millis(self.options().leader_timeout_ms.unwrap_or(1000))
+    }
+
+    /// Returns how long `window peek` keeps the neighbouring column revealed.
+    pub fn peek_duration(&self) -> Duration {
+        Duration::from_millis(self.options().peek_duration_ms.unwrap_or(2000))
+    }
+
+    pub fn reshuffle_debounce(&self) -> Duration {
+        Duration::from_millis(self.options().reshuffle_debounce_ms.unwrap_or(50))
+    }
+
+    /// Finds a two-step keybinding whose second key matches `keycode` and `mask`.
+    /// Only consulted right after the leader key was pressed.
+    pub fn find_leader_keybind(&self, keycode: u8, mask: Modifiers) -> Option<Command> {
+        find_in_bindings(&self.inner().bindings, keycode, mask, true)
     }
 
-    /// Finds window properties for a given `title` and `bundle_id`.
+    /// Finds window properties for a given `title`, `bundle_id`, `role` and `subrole`.
     /// It iterates through configured window parameters and returns all matching rules.
-    /// A rule matches when its bundle ID (if any) and title regex match.
+    /// A rule matches when its bundle ID, role and subrole (if any) and title regex match.
     ///
     /// # Arguments
     ///
     /// * `title` - The title of the window to match.
     /// * `bundle_id` - The bundle identifier of the application owning the window.
+    /// * `role` - The accessibility role of the window, e.g. `AXWindow`.
+    /// * `subrole` - The accessibility subrole of the window, e.g. `AXDialog`.
     ///
     /// # Returns
     ///
     /// A `Vec<WindowParams>` containing all matching window rules.
-    pub fn find_window_properties(&self, title: &str, bundle_id: &str) -> Vec<WindowParams> {
+    pub fn find_window_properties(
+        &self,
+        title: &str,
+        bundle_id: &str,
+        role: &str,
+        subrole: &str,
+    ) -> Vec<WindowParams> {
         self.inner()
             .windows
             .as_ref()
@@ -471,9 +1025,13 @@ impl Config {
                 windows
                     .values()
                     .filter(|params| {
-                        let bundle_match =
-                            params.bundle_id.as_ref().map(|id| id.as_str() == bundle_id);
-                        bundle_match.is_none_or(|m| m) && params.title.is_match(title)
+                        let matches = |pattern: &Option<String>, value: &str| {
+                            pattern.as_ref().is_none_or(|pattern| pattern == value)
+                        };
+                        matches(&params.bundle_id, bundle_id)
+                            && matches(&params.role, role)
+                            && matches(&params.subrole, subrole)
+                            && params.title.is_match(title)
                     })
                     .cloned()
                     .collect::<Vec<_>>()
@@ -484,47 +1042,214 @@ impl Config {
     /// Returns `true` if any window rule for the given bundle ID requests that the
     /// process be forcibly managed even when macOS reports it as unobservable.
     pub fn should_force_manage_process(&self, process: &dyn ProcessApi) -> bool {
-        self.inner().windows.as_ref().is_some_and(|windows| {
-            let Some(bundle_id) = process
-                .application()
-                .as_ref()
-                .and_then(|app| app.bundleIdentifier())
-                .map(|id| id.to_string())
-            else {
-                return false;
-            };
-            windows.values().any(|params| {
-                params
-                    .bundle_id
-                    .as_ref()
-                    .is_some_and(|id| id.as_str() == bundle_id)
-                    && params.manage.is_some_and(|manage| manage)
-            })
-        })
+        self.process_manage_rules(process).any(|(manage, _)| manage)
+    }
+
+    /// Returns `true` if the process is listed in `ignore_apps`, by bundle ID or name, or if a
+    /// window rule sets `manage = false` for all windows of its bundle ID and no rule sets
+    /// `manage = true`. Rules scoped by title, role or subrole leave the process observed.
+    /// Such processes are skipped before any accessibility observers are created for them.
+    pub fn should_ignore_process(&self, process: &dyn ProcessApi) -> bool {
+        let bundle_id = process.bundle_id();
+        if self
+            .options()
+            .ignore_apps
+            .iter()
+            .flatten()
+            .any(|app| bundle_id.as_deref() == Some(app.as_str()) || process.name() == app.as_str())
+        {
+            return true;
+        }
+        let rules = self.process_manage_rules(process).collect::<Vec<_>>();
+        rules.iter().any(|(manage, app_wide)| !manage && *app_wide)
+            && rules.iter().all(|(manage, _)| !manage)
+    }
+
+    /// Returns the `manage` settings of the window rules matching the bundle ID of `process`,
+    /// together with whether each rule applies to all of its windows.
+    fn process_manage_rules(&self, process: &dyn ProcessApi) -> impl Iterator<Item = (bool, bool)> {
+        let bundle_id = process.bundle_id();
+        self.inner()
+            .windows
+            .iter()
+            .flat_map(|windows| windows.values())
+            .filter(|params| bundle_id.is_some() && params.bundle_id.as_ref() == bundle_id.as_ref())
+            .filter_map(|params| params.manage.map(|manage| (manage, params.is_app_wide())))
+            .collect::<Vec<_>>()
+            .into_iter()
     }
 
     pub fn sliver_height(&self) -> f64 {
-        self.options().sliver_height.unwrap_or(1.0).clamp(0.1, 1.0)
+        self.sliver_height_for(None)
     }
 
     pub fn sliver_width(&self) -> i32 {
-        i32::from(self.options().sliver_width.unwrap_or(5)).max(1)
+        self.sliver_width_for(None)
+    }
+
+    /// Returns the `[displays."<uuid>"]` overrides of the display with `uuid`, if any.
+    fn display_options(&self, uuid: Option<&str>) -> Option<displays::DisplayOptions> {
+        let uuid = uuid?;
+        self.inner()
+            .displays
+            .as_ref()?
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(uuid))
+            .map(|(_, options)| options.clone())
+    }
+
+    /// Returns the `[workspaces.<id>]` overrides of `workspace`, if any.
+    pub fn workspace_options(
+        &self,
+        workspace: Option<WorkspaceId>,
+    ) -> Option<workspaces::WorkspaceOptions> {
+        let workspace = workspace?.to_string();
+        self.inner().workspaces.as_ref()?.get(&workspace).cloned()
+    }
+
+    /// Returns the sliver height on the display with `uuid`, falling back to `sliver_height`.
+    pub fn sliver_height_for(&self, uuid: Option<&str>) -> f64 {
+        self.display_options(uuid)
+            .and_then(|display| display.sliver_height)
+            .or(self.options().sliver_height)
+            .unwrap_or(1.0)
+            .clamp(0.1, 1.0)
+    }
+
+    /// Returns how long the pointer rests on a sliver before its column peeks into view, or
+    /// `None` when peeking is off. Focus follows mouse focuses the sliver instead, so it
+    /// disables peeking.
+    pub fn sliver_peek_delay(&self) -> Option<Duration> {
+        if self.focus_follows_mouse() {
+            return None;
+        }
+        self.options()
+            .sliver_peek_delay_ms
+            .map(Duration::from_millis)
+    }
+
+    /// Returns the sliver width on the display with `uuid`, falling back to `sliver_width`.
+    pub fn sliver_width_for(&self, uuid: Option<&str>) -> i32 {
+        let width = self
+            .display_options(uuid)
+            .and_then(|display| display.sliver_width)
+            .or(self.options().sliver_width)
+            .unwrap_or(5);
+        i32::from(width).max(1)
+    }
+
+    /// Returns `true` if the log should also be written to files in `~/Library/Logs/paneru/`.
+    pub fn log_file(&self) -> bool {
+        self.options().log_file.unwrap_or(false)
+    }
+
+    pub fn log_format(&self) -> LogFormat {
+        self.options().log_format.unwrap_or_default()
+    }
+
+    pub fn log_rotation(&self) -> LogRotation {
+        self.options().log_rotation.unwrap_or_default()
+    }
+
+    /// Returns the opacity of windows which do not have focus, or `None` to leave them opaque.
+    pub fn unfocused_opacity(&self) -> Option<f32> {
+        self.options()
+            .unfocused_opacity
+            .map(|opacity| opacity.clamp(0.0, 1.0))
     }
 
     pub fn edge_padding(&self) -> (i32, i32, i32, i32) {
+        self.edge_padding_for(None, None)
+    }
+
+    /// Returns the `(top, right, bottom, left)` edge padding on the display with `uuid` while
+    /// `workspace` is laid out. Each side of a `[workspaces.<id>]` section overrides the
+    /// `[displays."<uuid>"]` section, which in turn overrides the global padding.
+    pub fn edge_padding_for(
+        &self,
+        uuid: Option<&str>,
+        workspace: Option<WorkspaceId>,
+    ) -> (i32, i32, i32, i32) {
+        let display = self.display_options(uuid).unwrap_or_default();
+        let workspace = self.workspace_options(workspace).unwrap_or_default();
         let config = self.inner();
         let o = &config.options;
         let p = config.padding.as_ref();
+        let side = |workspace: Option<u16>,
+                    display: Option<u16>,
+                    padding: Option<u16>,
+                    option: Option<u16>| {
+            i32::from(workspace.or(display).or(padding).or(option).unwrap_or(0))
+        };
         (
-            i32::from(p.and_then(|p| p.top).or(o.padding_top).unwrap_or(0)),
-            i32::from(p.and_then(|p| p.right).or(o.padding_right).unwrap_or(0)),
-            i32::from(p.and_then(|p| p.bottom).or(o.padding_bottom).unwrap_or(0)),
-            i32::from(p.and_then(|p| p.left).or(o.padding_left).unwrap_or(0)),
+            side(
+                workspace.padding_top,
+                display.padding_top,
+                p.and_then(|p| p.top),
+                o.padding_top,
+            ),
+            side(
+                workspace.padding_right,
+                display.padding_right,
+                p.and_then(|p| p.right),
+                o.padding_right,
+            ),
+            side(
+                workspace.padding_bottom,
+                display.padding_bottom,
+                p.and_then(|p| p.bottom),
+                o.padding_bottom,
+            ),
+            side(
+                workspace.padding_left,
+                display.padding_left,
+                p.and_then(|p| p.left),
+                o.padding_left,
+            ),
         )
     }
 
-    pub fn preset_column_widths(&self) -> Vec<f64> {
-        self.options().preset_column_widths
+    /// Returns the gap between adjacent columns, and between the windows of a stack, on the
+    /// display with `uuid`. A `[displays."<uuid>"]` gap overrides the `[padding]` one.
+    pub fn gap_for(&self, uuid: Option<&str>) -> i32 {
+        let gap = self
+            .display_options(uuid)
+            .and_then(|display| display.gap)
+            .or_else(|| {
+                self.inner()
+                    .padding
+                    .as_ref()
+                    .and_then(|padding| padding.gap)
+            })
+            .unwrap_or(0);
+        i32::from(gap)
+    }
+
+    /// Returns the preset column widths as ratios of `display_width`, taken from the first
+    /// `display_column_widths` bucket matching the width, or `preset_column_widths` otherwise.
+    /// The `preset_column_widths` of a `[displays."<uuid>"]` section take precedence.
+    pub fn preset_column_widths_for(&self, display_width: i32, uuid: Option<&str>) -> Vec<f64> {
+        let options = self.options();
+        let widths = self
+            .display_options(uuid)
+            .and_then(|display| display.preset_column_widths)
+            .or_else(|| {
+                options
+                    .display_column_widths
+                    .iter()
+                    .flatten()
+                    .find(|bucket| bucket.matches(display_width))
+                    .map(|bucket| bucket.widths.clone())
+            })
+            .unwrap_or(options.preset_column_widths);
+
+        let mut widths = widths
+            .iter()
+            .map(|width| width.ratio(display_width))
+            .filter(|ratio| ratio.is_finite() && *ratio > 0.0)
+            .collect::<Vec<_>>();
+        widths.sort_by(f64::total_cmp);
+        widths
     }
 
     pub fn swipe_gesture_direction(&self) -> SwipeGestureDirection {
@@ -711,6 +1436,23 @@ impl Config {
             .clamp(1.0, 10.0)
     }
 
+    /// Returns the command and dwell time configured for a hot corner or edge.
+    pub fn hot_corner(&self, region: HotCornerRegion) -> Option<(Command, Duration)> {
+        const DEFAULT_DWELL_MS: u64 = 300;
+        let config = self.inner();
+        let hot_corner = config.hot_corners.as_ref()?.get(&region)?;
+        let dwell = Duration::from_millis(hot_corner.dwell.unwrap_or(DEFAULT_DWELL_MS));
+        hot_corner.parsed.clone().map(|command| (command, dwell))
+    }
+
+    /// Returns `true` if at least one hot corner or edge is configured.
+    pub fn has_hot_corners(&self) -> bool {
+        self.inner()
+            .hot_corners
+            .as_ref()
+            .is_some_and(|hot_corners| !hot_corners.is_empty())
+    }
+
     pub fn mouse_resize_modifier(&self) -> Option<Modifiers> {
         self.options().mouse_resize_modifier
     }
@@ -741,6 +1483,27 @@ impl Config {
             .unwrap_or(MissingWindowBehavior::Ignore)
     }
 
+    /// Returns the shell command configured in `[on_event]` for the given hook name, if any.
+    pub fn on_event_command(&self, event: &str) -> Option<String> {
+        let inner = self.inner();
+        let on_event = inner.on_event.as_ref()?;
+        match event {
+            "window_focused" => on_event.window_focused.clone(),
+            "space_changed" => on_event.space_changed.clone(),
+            "layout_changed" => on_event.layout_changed.clone(),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if at least one `[on_event]` hook is configured.
+    pub fn has_event_hooks(&self) -> bool {
+        self.inner().on_event.as_ref().is_some_and(|on_event| {
+            on_event.window_focused.is_some()
+                || on_event.space_changed.is_some()
+                || on_event.layout_changed.is_some()
+        })
+    }
+
     pub fn swipe_scroll_modifier(&self) -> Modifiers {
         let config = self.inner();
         config
@@ -805,8 +1568,33 @@ impl Config {
         self.options().window_resize_cycle.unwrap_or(true)
     }
 
+    pub fn window_resize_reverse(&self) -> bool {
+        self.options().window_resize_reverse.unwrap_or(false)
+    }
+
     pub fn auto_center(&self) -> bool {
-        self.options().auto_center.is_some_and(|center| center)
+        self.auto_center_on(None)
+    }
+
+    /// Returns whether the focused window is centered on `workspace`, falling back to
+    /// `auto_center` and then to a `center` scroll policy.
+    pub fn auto_center_on(&self, workspace: Option<WorkspaceId>) -> bool {
+        self.workspace_options(workspace)
+            .and_then(|workspace| workspace.auto_center)
+            .or(self.options().auto_center)
+            .unwrap_or(self.options().scroll_policy == Some(ScrollPolicy::Center))
+    }
+
+    /// Returns how the strip of `workspace` scrolls to a focused window. Centering enabled by
+    /// `auto_center` takes precedence over `scroll_policy`.
+    pub fn scroll_policy_on(&self, workspace: Option<WorkspaceId>) -> ScrollPolicy {
+        if self.auto_center_on(workspace) {
+            return ScrollPolicy::Center;
+        }
+        match self.options().scroll_policy.unwrap_or_default() {
+            ScrollPolicy::Center => ScrollPolicy::Minimal,
+            policy => policy,
+        }
     }
 
     pub fn horizontal_mouse_warp(&self) -> Option<i16> {
@@ -827,6 +1615,28 @@ impl Config {
         self.options().mouse_follows_focus.is_none_or(|mff| mff)
     }
 
+    /// Returns `true` if the mouse cursor should warp to newly created windows once they are focused.
+    /// If the configuration option is not set, it defaults to `false`.
+    pub fn mouse_follows_new_window(&self) -> bool {
+        self.options().mouse_follows_new_window.unwrap_or(false)
+    }
+
+    pub fn mouse_warp(&self) -> MouseWarp {
+        self.options().mouse_warp.unwrap_or_default()
+    }
+
+    /// Returns the offset applied to a floating window which would open on top of a sibling.
+    /// If the configuration option is not set, it defaults to 32 pixels.
+    pub fn floating_cascade_step(&self) -> i32 {
+        self.options().floating_cascade_step.unwrap_or(32).max(0)
+    }
+
+    /// Returns the gap between a picture-in-picture window and its display corner.
+    /// If the configuration option is not set, it defaults to 16 pixels.
+    pub fn pip_margin(&self) -> i32 {
+        self.options().pip_margin.unwrap_or(16).max(0)
+    }
+
     pub fn horizontal_mouse_warp_offset(&self) -> i32 {
         self.options().horizontal_mouse_warp_offset.unwrap_or(0)
     }
@@ -876,6 +1686,77 @@ impl Config {
             .insert_windows_mid_strip
             .is_some_and(|enabled| enabled)
     }
+
+    pub fn focus_skip_stacks(&self) -> bool {
+        self.options()
+            .focus_skip_stacks
+            .is_some_and(|enabled| enabled)
+    }
+
+    /// Returns whether workspaces showing a single window drop their padding and gaps.
+    pub fn smart_gaps(&self) -> bool {
+        self.options().smart_gaps.is_some_and(|enabled| enabled)
+    }
+
+    pub fn stack_tabs(&self) -> bool {
+        self.options().stack_tabs.is_some_and(|enabled| enabled)
+    }
+
+    pub fn stack_main_ratio(&self) -> Option<f64> {
+        self.options()
+            .stack_main_ratio
+            .filter(|ratio| *ratio > 0.0 && *ratio < 1.0)
+    }
+
+    pub fn empty_workspace(&self) -> EmptyWorkspaceBehavior {
+        self.options().empty_workspace.unwrap_or_default()
+    }
+
+    /// Returns the maximum number of columns in a strip, or `None` when unlimited.
+    pub fn max_columns(&self) -> Option<usize> {
+        self.options().max_columns.filter(|max| *max > 0)
+    }
+
+    /// Returns where new windows open in the active strip. Defaults to `after_focused`.
+    pub fn insert_position(&self) -> InsertPosition {
+        self.options().insert_position.unwrap_or_default()
+    }
+
+    /// Returns whether new windows of the focused application join its column. Off by default.
+    pub fn auto_stack_same_app(&self) -> bool {
+        self.options()
+            .auto_stack_same_app
+            .is_some_and(|enabled| enabled)
+    }
+
+    pub fn auto_float_dialogs(&self) -> bool {
+        self.options().auto_float_dialogs.unwrap_or(true)
+    }
+
+    /// Returns the viewport width ratio of a window in zen mode.
+    pub fn zen_width(&self) -> f64 {
+        self.options()
+            .zen_width
+            .filter(|ratio| *ratio > 0.0 && *ratio <= 1.0)
+            .unwrap_or(0.6)
+    }
+
+    pub fn max_columns_policy(&self) -> MaxColumnsPolicy {
+        self.options().max_columns_policy.unwrap_or_default()
+    }
+
+    /// Returns how managed windows moved by another tool are handled, or `None` to leave them
+    /// until the next reshuffle.
+    pub fn external_move_policy(&self) -> Option<ExternalMovePolicy> {
+        self.options().external_move_policy
+    }
+
+    /// Returns `true` if focus changes and workspace switches should be announced to VoiceOver.
+    pub fn accessibility_announcements(&self) -> bool {
+        self.options()
+            .accessibility_announcements
+            .is_some_and(|enabled| enabled)
+    }
 }
 
 fn parse_hex_color(hex: &str) -> (f64, f64, f64) {
@@ -951,17 +1832,60 @@ impl OneOrMore {
     }
 }
 
+/// Name of the implicit keybinding mode using the `[bindings]` table.
+pub const DEFAULT_MODE: &str = "default";
+
+fn find_in_bindings(
+    bindings: &HashMap<String, OneOrMore>,
+    keycode: u8,
+    mask: Modifiers,
+    leader: bool,
+) -> Option<Command> {
+    bindings
+        .values()
+        .flat_map(|binds| binds.all())
+        .find_map(|bind| {
+            (bind.leader == leader && bind.code == keycode && bind.modifiers.matches(mask))
+                .then_some(bind.command.clone())
+        })
+}
+
 /// `InnerConfig` holds the actual configuration data parsed from a file, including options, keybindings, and window parameters.
 /// It is typically accessed via an `Arc<RwLock<InnerConfig>>` within the `Config` struct.
 #[derive(Deserialize, Debug, Default)]
 struct InnerConfig {
     options: MainOptions,
     bindings: HashMap<String, OneOrMore>,
+    /// Named binding tables (`[modes.<name>]`) which replace `[bindings]` while the mode is active.
+    modes: Option<HashMap<String, HashMap<String, OneOrMore>>>,
     windows: Option<HashMap<String, WindowParams>>,
     decorations: Option<decorations::DecorationsOptions>,
     swipe: Option<swipe::SwipeOptions>,
     padding: Option<padding::PaddingOptions>,
+    /// Per-display overrides keyed by display UUID (`[displays."<uuid>"]`).
+    displays: Option<HashMap<String, displays::DisplayOptions>>,
+    /// Per-workspace overrides keyed by space ID (`[workspaces.<id>]`).
+    workspaces: Option<HashMap<String, workspaces::WorkspaceOptions>>,
+    hot_corners: Option<HashMap<HotCornerRegion, HotCornerOptions>>,
     restore: Option<RestoreOptions>,
+    on_event: Option<OnEventOptions>,
+    /// Programs run by `exec_<name>` bindings (`[exec]`).
+    exec: Option<HashMap<String, ExecProgram>>,
+    /// Resolved `(keycode, modifiers)` pair of `options.leader`.
+    #[serde(skip)]
+    parsed_leader: Option<(u8, Modifiers)>,
+    /// The raw TOML document, kept to summarize the changes on reload.
+    #[serde(skip)]
+    raw: toml::Table,
+    /// The raw TOML document as loaded from the file, without the runtime overrides.
+    #[serde(skip)]
+    loaded: toml::Table,
+    /// Files merged in by the top-level `include` directive.
+    #[serde(skip)]
+    includes: Vec<PathBuf>,
+    /// `(key, value)` pairs set with `config set` since the file was last loaded.
+    #[serde(skip)]
+    overrides: Vec<(String, String)>,
 }
 
 impl InnerConfig {
@@ -989,13 +1913,23 @@ impl InnerConfig {
     ///
     /// `Ok(InnerConfig)` if the parsing is successful, otherwise `Err(Error)` with an error message.
     fn parse_config(input: &str) -> Result<InnerConfig> {
-        let config: InnerConfig = toml::from_str(input)?;
-        if !config.needs_virtual_keys() {
-            return Ok(config);
+        let mut config: InnerConfig = toml::from_str(input)?;
+        if config.needs_virtual_keys() {
+            let virtual_keys = generate_virtual_keymap();
+            config = Self::parse_config_with_virtual_keys(input, &virtual_keys)?;
         }
-
-        let virtual_keys = generate_virtual_keymap();
-        Self::parse_config_with_virtual_keys(input, &virtual_keys)
+        for (region, hot_corner) in config.hot_corners.iter_mut().flatten() {
+            let argv = hot_corner.command.split_whitespace().collect::<Vec<_>>();
+            hot_corner.parsed = Some(parse_command(&argv).map_err(|err| {
+                Error::InvalidConfig(format!(
+                    "{}: hot corner {region:?}: {err}",
+                    function_name!()
+                ))
+            })?);
+        }
+        config.raw = toml::from_str(input)?;
+        config.loaded = config.raw.clone();
+        Ok(config)
     }
 
     fn parse_config_with_virtual_keys(
@@ -1004,18 +1938,14 @@ impl InnerConfig {
     ) -> Result<InnerConfig> {
         let mut config: InnerConfig = toml::from_str(input)?;
 
-        for (command, bindings) in &mut config.bindings {
-            let argv = command.split('_').collect::<Vec<_>>();
-            for binding in bindings.all_mut() {
-                binding.command = parse_command(&argv)?;
+        let exec = config.exec.as_ref();
+        Self::resolve_bindings(&mut config.bindings, exec, virtual_keys)?;
+        for bindings in config.modes.iter_mut().flat_map(HashMap::values_mut) {
+            Self::resolve_bindings(bindings, exec, virtual_keys)?;
+        }
 
-                if let Some(code) = keycode_for_key_name(&binding.key, virtual_keys) {
-                    binding.code = code;
-                    info!("bind: {binding:?}");
-                } else {
-                    error!("{}: invalid key '{}'", function_name!(), &binding.key);
-                }
-            }
+        if let Some(leader) = &config.options.leader {
+            config.parsed_leader = Some(resolve_keybinding_str(leader, virtual_keys)?);
         }
 
         // Resolve passthrough keybinding strings into (keycode, modifiers) pairs.
@@ -1033,8 +1963,41 @@ impl InnerConfig {
         Ok(config)
     }
 
+    fn resolve_bindings(
+        bindings: &mut HashMap<String, OneOrMore>,
+        exec: Option<&HashMap<String, ExecProgram>>,
+        virtual_keys: &[(String, u8)],
+    ) -> Result<()> {
+        for (command, bindings) in bindings {
+            // `exec_<name>` runs the `[exec]` program called `<name>`, or `<name>` itself.
+            let argv = match command.split_once('_') {
+                Some(("exec", name)) => vec!["exec", name],
+                _ => command.split('_').collect::<Vec<_>>(),
+            };
+            let parsed = match (argv.as_slice(), exec) {
+                (["exec", name], Some(exec)) if exec.contains_key(*name) => {
+                    Command::Exec(exec[*name].argv())
+                }
+                _ => parse_command(&argv)?,
+            };
+            for binding in bindings.all_mut() {
+                binding.command = parsed.clone();
+
+                if let Some(code) = keycode_for_key_name(&binding.key, virtual_keys) {
+                    binding.code = code;
+                    info!("bind: {binding:?}");
+                } else {
+                    error!("{}: invalid key '{}'", function_name!(), &binding.key);
+                }
+            }
+        }
+        Ok(())
+    }
+
     fn needs_virtual_keys(&self) -> bool {
         !self.bindings.is_empty()
+            || self.options.leader.is_some()
+            || self.modes.as_ref().is_some_and(|modes| !modes.is_empty())
             || self.windows.as_ref().is_some_and(|windows| {
                 windows
                     .values()
@@ -1043,12 +2006,217 @@ impl InnerConfig {
     }
 }
 
+/// Summary of what a configuration reload changed, reported in the logs and to state subscribers.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ConfigChanges {
+    /// `[options]` keys which were added, removed or changed.
+    pub options: Vec<String>,
+    pub bindings_added: Vec<String>,
+    pub bindings_removed: Vec<String>,
+    pub bindings_changed: Vec<String>,
+    /// Other top-level sections (e.g. `padding`, `swipe`) which changed.
+    pub sections: Vec<String>,
+    /// Number of `[windows]` rules in the new configuration.
+    pub window_rules: usize,
+}
+
+impl ConfigChanges {
+    fn between(old: &toml::Table, new: &toml::Table) -> Self {
+        let (added, removed, changed) = table_diff(old.get("options"), new.get("options"));
+        let mut options = [added, removed, changed].concat();
+        options.sort();
+        let (bindings_added, bindings_removed, bindings_changed) =
+            table_diff(old.get("bindings"), new.get("bindings"));
+        let mut sections = old
+            .keys()
+            .chain(new.keys())
+            .filter(|section| {
+                !matches!(section.as_str(), "options" | "bindings")
+                    && old.get(*section) != new.get(*section)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+        sections.sort();
+        sections.dedup();
+
+        Self {
+            options,
+            bindings_added,
+            bindings_removed,
+            bindings_changed,
+            sections,
+            window_rules: new
+                .get("windows")
+                .and_then(toml::Value::as_table)
+                .map_or(0, toml::Table::len),
+        }
+    }
+}
+
+impl fmt::Display for ConfigChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lists = [
+            ("options changed", &self.options),
+            ("bindings added", &self.bindings_added),
+            ("bindings removed", &self.bindings_removed),
+            ("bindings changed", &self.bindings_changed),
+            ("sections changed", &self.sections),
+        ];
+        for (label, keys) in lists {
+            if !keys.is_empty() {
+                write!(f, "{label}: {}; ", keys.join(", "))?;
+            }
+        }
+        write!(f, "{} window rules", self.window_rules)
+    }
+}
+
+/// Compares the keys of two TOML tables, returning the `(added, removed, changed)` keys.
+fn table_diff(
+    old: Option<&toml::Value>,
+    new: Option<&toml::Value>,
+) -> (Vec<String>, Vec<String>, Vec<String>) {
+    let empty = toml::Table::new();
+    let old = old.and_then(toml::Value::as_table).unwrap_or(&empty);
+    let new = new.and_then(toml::Value::as_table).unwrap_or(&empty);
+
+    let mut added = Vec::new();
+    let mut changed = Vec::new();
+    for (key, value) in new {
+        match old.get(key) {
+            None => added.push(key.clone()),
+            Some(previous) if previous != value => changed.push(key.clone()),
+            Some(_) => {}
+        }
+    }
+    let removed = old
+        .keys()
+        .filter(|key| !new.contains_key(*key))
+        .cloned()
+        .collect();
+    (added, removed, changed)
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum MissingWindowBehavior {
     Ignore,
 }
 
+/// Behaviour when the last window of the active virtual workspace closes.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EmptyWorkspaceBehavior {
+    /// Keep the empty workspace active.
+    #[default]
+    Stay,
+    /// Switch to the virtual workspace above the empty one.
+    Previous,
+    /// Switch to the next virtual workspace that still has windows, wrapping around.
+    NextNonEmpty,
+}
+
+/// Easing curve used to animate window moves and resizes.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AnimationEasing {
+    /// Fast start which slows down towards the target.
+    #[default]
+    EaseOut,
+    /// Slow start and end, fastest in the middle.
+    EaseInOut,
+    /// Overshoots the target slightly and settles back.
+    Spring,
+}
+
+/// Where `mouse_follows_focus` moves the pointer when focus changes.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MouseWarp {
+    /// Warps to the center of the focused window.
+    #[default]
+    Center,
+    /// Moves the pointer just inside the closest edge of the focused window.
+    NearestEdge,
+    /// Leaves the pointer alone if it is already over the focused window, centers it otherwise.
+    OffIfVisible,
+}
+
+/// Format of the log lines written to the `log_file`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// The same single line format as the console output.
+    #[default]
+    Compact,
+    /// One JSON object per line, for log processors.
+    Json,
+}
+
+/// How often the `log_file` is rotated.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogRotation {
+    Hourly,
+    #[default]
+    Daily,
+    /// Keeps appending to a single file.
+    Never,
+}
+
+/// What happens to the least recently focused column once a strip exceeds `max_columns`.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MaxColumnsPolicy {
+    /// Stack the column onto its left neighbour.
+    #[default]
+    Stack,
+    /// Move the column's window to the next virtual workspace.
+    Overflow,
+    /// Stack new windows onto the last column, so the strip stops growing.
+    StackLast,
+}
+
+/// Where a new window opens in the active strip.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum InsertPosition {
+    /// To the right of the focused column.
+    #[default]
+    AfterFocused,
+    /// To the left of the focused column.
+    BeforeFocused,
+    /// At the beginning of the strip.
+    Start,
+    /// At the end of the strip.
+    End,
+}
+
+/// How the strip scrolls to expose a newly focused window.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ScrollPolicy {
+    /// Scroll only as far as needed to bring a hidden window into view.
+    #[default]
+    Minimal,
+    /// Center the focused column on the display.
+    Center,
+    /// Align the focused column with the nearer viewport edge.
+    Edge,
+}
+
+/// How a managed window moved by another tool is handled.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExternalMovePolicy {
+    /// Move the window straight back to its place in the strip.
+    SnapBack,
+    /// Float the window at the position it was moved to.
+    Float,
+    /// Move the window's column to the strip slot nearest to where it was moved.
+    Reslot,
+}
+
 #[derive(Clone, Debug, Deserialize, Default)]
 pub struct RestoreOptions {
     pub enabled: Option<bool>,
@@ -1056,6 +2224,32 @@ pub struct RestoreOptions {
     pub missing_windows: Option<MissingWindowBehavior>,
 }
 
+/// A program started by an `exec_<name>` binding: either a shell command line or an argument
+/// vector which is run without a shell.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+pub enum ExecProgram {
+    Shell(String),
+    Argv(Vec<String>),
+}
+
+impl ExecProgram {
+    fn argv(&self) -> Vec<String> {
+        match self {
+            ExecProgram::Shell(line) => vec!["/bin/sh".to_string(), "-c".to_string(), line.clone()],
+            ExecProgram::Argv(argv) => argv.iter().map(|arg| expand_env(arg)).collect(),
+        }
+    }
+}
+
+/// Shell commands spawned by the daemon when the corresponding state change is broadcast.
+#[derive(Clone, Debug, Deserialize, Default)]
+pub struct OnEventOptions {
+    pub window_focused: Option<String>,
+    pub space_changed: Option<String>,
+    pub layout_changed: Option<String>,
+}
+
 /// `MainOptions` represents the primary configuration options for the window manager.
 /// These options control various behaviors such as mouse focus, gesture recognition, and window animation.
 #[derive(Deserialize, Clone, Debug, Default)]
@@ -1064,6 +2258,17 @@ pub struct MainOptions {
     pub focus_follows_mouse: Option<bool>,
     /// Enables or disables mouse follows focus behavior.
     pub mouse_follows_focus: Option<bool>,
+    /// Warps the mouse to newly created windows when they get focused, even if
+    /// `mouse_follows_focus` is disabled. Off by default.
+    pub mouse_follows_new_window: Option<bool>,
+    /// Where the pointer is warped to when it follows focus. Default: `center`.
+    pub mouse_warp: Option<MouseWarp>,
+    /// Pixel step used to cascade floating windows of the same application which open at the
+    /// same position. Set to 0 to let them stack on top of each other.
+    pub floating_cascade_step: Option<i32>,
+    /// Pixel gap kept between a picture-in-picture window and the display corner it is thrown
+    /// to.
+    pub pip_margin: Option<i32>,
     /// Warps the mouse to the closest screen when at the edge.
     pub horizontal_mouse_warp: Option<i16>,
     /// Vertical pixel offset applied to the warp landing position, signed by
@@ -1072,13 +2277,24 @@ pub struct MainOptions {
     /// higher or lower than the laptop). When warping downward (target below
     /// source) the offset is added; when warping upward, subtracted.
     pub horizontal_mouse_warp_offset: Option<i32>,
-    /// A list of preset column widths (as ratios) used for resizing windows.
+    /// A list of preset column widths used for resizing windows, as ratios (`0.5`) or pixels
+    /// (`"800px"`).
     #[serde(default = "default_preset_column_widths")]
-    pub preset_column_widths: Vec<f64>,
+    pub preset_column_widths: Vec<ColumnWidth>,
+    /// Preset column widths for specific display widths. The first matching bucket
+    /// replaces `preset_column_widths` on that display.
+    pub display_column_widths: Option<Vec<DisplayColumnWidths>>,
     /// The animation speed for window movements in pixels per second.
     pub animation_speed: Option<f64>,
+    /// The easing curve of window animations: "ease_out" (default), "ease_in_out" or "spring".
+    pub animation_easing: Option<AnimationEasing>,
     /// Automatically center the window when switching focus with keyboard.
     pub auto_center: Option<bool>,
+    /// How the strip scrolls to a focused window: "minimal" (default), "center" or "edge".
+    /// `auto_center = true` selects "center".
+    pub scroll_policy: Option<ScrollPolicy>,
+    /// Drops the edge padding and gaps while a workspace shows a single window.
+    pub smart_gaps: Option<bool>,
     /// Height of off-screen window slivers as a ratio (0.0–1.0) of the display height.
     /// Lower values hide the window's corner radius at screen edges.
     /// Default: 1.0 (full height).
@@ -1086,6 +2302,22 @@ pub struct MainOptions {
     /// Width of off-screen window slivers in pixels.
     /// Default: 5 pixels.
     pub sliver_width: Option<u16>,
+    /// Milliseconds the pointer has to rest on an off-screen sliver before its column slides
+    /// into view, without taking focus. Only used with `focus_follows_mouse` off. Unset
+    /// disables peeking.
+    pub sliver_peek_delay_ms: Option<u64>,
+    /// Applications, by bundle ID or name, which are never observed or managed.
+    pub ignore_apps: Option<Vec<String>>,
+    /// Opacity (0.0–1.0) of all windows except the focused one.
+    /// Default: unset (windows stay opaque).
+    pub unfocused_opacity: Option<f32>,
+    /// Writes the log to rotating files in `~/Library/Logs/paneru/` in addition to stderr.
+    /// Read once at startup.
+    pub log_file: Option<bool>,
+    /// Format of the lines in the log file. Default: compact.
+    pub log_format: Option<LogFormat>,
+    /// How often the log file is rotated. Default: daily.
+    pub log_rotation: Option<LogRotation>,
     /// Legacy top-level padding (deprecated; use `[padding]`).
     pub padding_top: Option<u16>,
     pub padding_bottom: Option<u16>,
@@ -1127,6 +2359,11 @@ pub struct MainOptions {
     /// Default: true (cycles). Set to false to stop at the limits.
     pub window_resize_cycle: Option<bool>,
 
+    /// Reverses the order grow/shrink step through the presets, so `window resize` steps down
+    /// and `window resizeback` steps up.
+    /// Default: false.
+    pub window_resize_reverse: Option<bool>,
+
     /// If enabled, an empty virtual workspace will be removed.
     /// Default: true.
     pub reap_empty_workspaces: Option<bool>,
@@ -1146,11 +2383,136 @@ pub struct MainOptions {
     /// shifting the rest) instead of appending it to the end of the strip.
     /// Off by default.
     pub insert_windows_mid_strip: Option<bool>,
+
+    /// When enabled, `focus east/west` treats a stacked column as a single unit
+    /// and lands on the top of the neighbouring column instead of the window at
+    /// the same stack row. `focus any` keeps the row-matching behaviour.
+    /// Off by default.
+    pub focus_skip_stacks: Option<bool>,
+    /// When enabled, stacked columns reserve a tab bar at their top listing the window titles,
+    /// with the focused one highlighted. Clicking a tab focuses its window. Off by default.
+    pub stack_tabs: Option<bool>,
+    /// Share (0.0–1.0) of a stacked column's height given to its top window, the others split
+    /// the rest evenly. Unset by default, which keeps the heights of the stacked windows.
+    pub stack_main_ratio: Option<f64>,
+    /// What to do when the last window of the active virtual workspace closes.
+    /// Defaults to `stay`.
+    pub empty_workspace: Option<EmptyWorkspaceBehavior>,
+    /// Maximum number of columns in a strip. Once exceeded, the least recently focused column
+    /// is stacked or moved away according to `max_columns_policy`. Unlimited by default.
+    pub max_columns: Option<usize>,
+    /// How to get a strip back under `max_columns`. Defaults to `stack`.
+    pub max_columns_policy: Option<MaxColumnsPolicy>,
+    /// Where new windows open when no window rule sets an `index`. Defaults to `after_focused`.
+    pub insert_position: Option<InsertPosition>,
+    /// Stacks new windows into the focused column when they belong to the same application.
+    pub auto_stack_same_app: Option<bool>,
+    /// Floats windows which look like dialogs, sheets or utility panels without needing a window
+    /// rule. Default: true.
+    pub auto_float_dialogs: Option<bool>,
+    /// Width (0.0–1.0) of the viewport given to the window centered by `window zen`. Defaults
+    /// to 0.6.
+    pub zen_width: Option<f64>,
+    /// Announces focus changes and virtual workspace switches to VoiceOver, e.g.
+    /// "Focused Safari, column 3 of 7". Off by default.
+    pub accessibility_announcements: Option<bool>,
+    /// What to do when another tool (a different window manager, an AppleScript) moves a managed
+    /// window. Unset keeps the window where it landed until the next reshuffle.
+    pub external_move_policy: Option<ExternalMovePolicy>,
+    /// A leader key (e.g. `"ctrl-space"`) starting two-step bindings. Bindings written as
+    /// `"leader-h"` or `"leader+shift-h"` fire when their key follows the leader.
+    pub leader: Option<String>,
+    /// How long after the leader key the second key is accepted, in milliseconds.
+    /// Default: 1000.
+    pub leader_timeout_ms: Option<u64>,
+    /// How long `window peek` reveals the neighbouring column before the strip slides back, in
+    /// milliseconds. Default: 2000.
+    pub peek_duration_ms: Option<u64>,
+    /// Reshuffles of a strip within this many milliseconds of the previous one are coalesced.
+    /// Default: 50.
+    pub reshuffle_debounce_ms: Option<u64>,
+}
+
+/// A preset column width, either a ratio of the display width or an absolute width in pixels.
+/// Ratios are written as numbers (`0.5`), pixels as strings (`"800px"`).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColumnWidth {
+    Ratio(f64),
+    Pixels(u32),
+}
+
+impl ColumnWidth {
+    /// Converts the width to a ratio of `display_width`.
+    pub fn ratio(self, display_width: i32) -> f64 {
+        match self {
+            ColumnWidth::Ratio(ratio) => ratio,
+            ColumnWidth::Pixels(pixels) => f64::from(pixels) / f64::from(display_width.max(1)),
+        }
+    }
+
+    /// Converts the width to pixels on a display `display_width` wide.
+    pub fn pixels(self, display_width: i32) -> i32 {
+        match self {
+            ColumnWidth::Ratio(ratio) => (f64::from(display_width) * ratio).round() as i32,
+            ColumnWidth::Pixels(pixels) => i32::try_from(pixels).unwrap_or(i32::MAX),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ColumnWidth {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawColumnWidth {
+            Ratio(f64),
+            Pixels(String),
+        }
+
+        match RawColumnWidth::deserialize(deserializer)? {
+            RawColumnWidth::Ratio(ratio) => Ok(ColumnWidth::Ratio(ratio)),
+            RawColumnWidth::Pixels(input) => input
+                .trim()
+                .strip_suffix("px")
+                .and_then(|pixels| pixels.trim().parse().ok())
+                .map(ColumnWidth::Pixels)
+                .ok_or_else(|| {
+                    de::Error::custom(format!(
+                        "Invalid column width {input:?}, expected a ratio or pixels like \"800px\""
+                    ))
+                }),
+        }
+    }
+}
+
+/// A set of preset column widths used on displays whose width falls within the bounds.
+#[derive(Clone, Debug, Deserialize)]
+pub struct DisplayColumnWidths {
+    /// Smallest display width (inclusive) this bucket applies to.
+    pub min_display_width: Option<i32>,
+    /// Largest display width (inclusive) this bucket applies to.
+    pub max_display_width: Option<i32>,
+    pub widths: Vec<ColumnWidth>,
+}
+
+impl DisplayColumnWidths {
+    fn matches(&self, display_width: i32) -> bool {
+        self.min_display_width
+            .is_none_or(|min| display_width >= min)
+            && self
+                .max_display_width
+                .is_none_or(|max| display_width <= max)
+    }
 }
 
 /// Returns a default set of column widths.
-pub fn default_preset_column_widths() -> Vec<f64> {
-    vec![0.25, 0.33333, 0.50, 0.66667, 0.75, 1.0, 1.5, 2.0]
+pub fn default_preset_column_widths() -> Vec<ColumnWidth> {
+    [0.25, 0.33333, 0.50, 0.66667, 0.75, 1.0, 1.5, 2.0]
+        .into_iter()
+        .map(ColumnWidth::Ratio)
+        .collect()
 }
 
 /// `Keybinding` represents a keyboard shortcut and the command it triggers.
@@ -1161,11 +2523,13 @@ pub struct Keybinding {
     pub code: u8,
     pub modifiers: Modifiers,
     pub command: Command,
+    /// Set for two-step bindings, which only fire after the leader key.
+    pub leader: bool,
 }
 
 impl<'de> Deserialize<'de> for Keybinding {
     /// Deserializes a `Keybinding` from a string input. The input string is expected to be in a format like "`modifier+modifier-key`" or "`key`".
-    /// Examples: "`ctrl+alt-q`", "`shift-tab`", "`h`".
+    /// Examples: "`ctrl+alt-q`", "`shift-tab`", "`h`", "`leader-h`".
     ///
     /// # Arguments
     ///
@@ -1186,8 +2550,24 @@ impl<'de> Deserialize<'de> for Keybinding {
             return Err(de::Error::custom(format!("Too many dashes: {input:?}")));
         }
 
+        let mut leader = false;
         let modifiers = match parts.pop() {
-            Some(modifiers) => parse_modifiers(modifiers).map_err(de::Error::custom)?,
+            Some(modifiers) => {
+                let modifiers = modifiers
+                    .split('+')
+                    .map(str::trim)
+                    .filter(|modifier| {
+                        let is_leader = *modifier == "leader";
+                        leader |= is_leader;
+                        !is_leader
+                    })
+                    .collect::<Vec<_>>();
+                if modifiers.is_empty() {
+                    Modifiers::empty()
+                } else {
+                    parse_modifiers(&modifiers.join("+")).map_err(de::Error::custom)?
+                }
+            }
             None => Modifiers::empty(),
         };
 
@@ -1196,10 +2576,20 @@ impl<'de> Deserialize<'de> for Keybinding {
             code: 0,
             modifiers,
             command: Command::Quit,
+            leader,
         })
     }
 }
 
+/// Selects a display, either by its position in the display arrangement (counted from 1, left to
+/// right, then top to bottom) or by its UUID.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum DisplaySelector {
+    Index(usize),
+    Uuid(String),
+}
+
 /// `WindowParams` defines rules and properties for specific windows based on their title or bundle ID.
 /// These parameters can override default window management behavior, such as forcing a window to float or setting its initial index.
 #[derive(Clone, Debug, Deserialize)]
@@ -1209,8 +2599,17 @@ pub struct WindowParams {
     title: Regex,
     /// An optional bundle identifier to match against the application's bundle ID.
     bundle_id: Option<String>,
+    /// An optional accessibility role to match against, e.g. `AXWindow`.
+    role: Option<String>,
+    /// An optional accessibility subrole to match against, e.g. `AXDialog` or `AXSystemDialog`.
+    subrole: Option<String>,
     /// If `true`, the window will be managed as a floating window (not tiled).
     pub floating: Option<bool>,
+    /// If `true`, the window floats and follows the active space of its display.
+    pub sticky: Option<bool>,
+    /// If `true`, the window floats as picture-in-picture: `window swap` throws it between the
+    /// corners of the display.
+    pub pip: Option<bool>,
     /// If `true`, force the process/window to be managed even if macOS reports it as
     /// unobservable or the window does not look like a standard window.
     pub manage: Option<bool>,
@@ -1219,15 +2618,31 @@ pub struct WindowParams {
     pub vertical_padding: Option<i32>,
     pub horizontal_padding: Option<i32>,
     pub dont_focus: Option<bool>,
+    /// If `true`, new windows of the focused window's application are stacked into its column.
+    pub auto_stack_same_app: Option<bool>,
     /// An optional positive initial width ratio relative to the display width.
     /// Values above 1.0 create an oversized, horizontally scrollable window.
     /// Overrides the default column width when the window is first managed.
     pub width: Option<f64>,
+    /// The narrowest the window may be resized to, as a ratio of the display width or in pixels.
+    pub min_width: Option<ColumnWidth>,
+    /// The widest the window may be resized to, as a ratio of the display width or in pixels.
+    pub max_width: Option<ColumnWidth>,
     /// Grid placement for floating windows: "cols:rows:x:y:w:h".
     /// Divides the display into a grid and positions the window at the given cell/span.
     pub grid: Option<String>,
     /// Per-window override for the active window border corner radius.
     pub border_radius: Option<f64>,
+    /// If `false`, moves and resizes of the window are applied instantly instead of animated.
+    pub animate: Option<bool>,
+    /// If `false`, leaves the application's `AXEnhancedUserInterface` alone while moving or
+    /// resizing its windows. By default it is turned off for the duration, so apps like Electron
+    /// or Chromium based browsers do not animate on their own and fight the layout.
+    pub disable_enhanced_ui: Option<bool>,
+    /// Opacity (0.0–1.0) of the window.
+    pub opacity: Option<f32>,
+    /// The display new windows are placed on, by index or UUID.
+    pub display: Option<DisplaySelector>,
     /// Keyboard shortcuts that should be passed through to this app instead of
     /// being intercepted by paneru. Uses the same `"modifier+modifier-key"`
     /// format as `[bindings]` (e.g. `"ctrl+alt-h"`).
@@ -1244,15 +2659,26 @@ impl WindowParams {
         Self {
             title: Regex::new(title).unwrap(),
             bundle_id,
+            role: None,
+            subrole: None,
             floating: None,
             manage: None,
+            sticky: None,
+            pip: None,
             index: None,
             vertical_padding: None,
             horizontal_padding: None,
             dont_focus: None,
+            auto_stack_same_app: None,
             width: None,
+            min_width: None,
+            max_width: None,
             grid: None,
             border_radius: None,
+            animate: None,
+            disable_enhanced_ui: None,
+            opacity: None,
+            display: None,
             bindings_passthrough: Vec::new(),
             parsed_passthrough: Vec::new(),
         }
@@ -1263,24 +2689,36 @@ impl WindowParams {
         &self.parsed_passthrough
     }
 
+    /// Returns `true` if the rule applies to every window of its application: the title pattern
+    /// matches any title and there is no role or subrole filter.
+    fn is_app_wide(&self) -> bool {
+        matches!(self.title.as_str(), "" | ".*" | "^.*" | ".*$" | "^.*$")
+            && self.role.is_none()
+            && self.subrole.is_none()
+    }
+
     /// Parses the grid string into `(x_ratio, y_ratio, w_ratio, h_ratio)`, all 0.0–1.0.
     pub fn grid_ratios(&self) -> Option<(f64, f64, f64, f64)> {
-        let grid = self.grid.as_ref()?;
-        let parts: Vec<f64> = grid.split(':').filter_map(|s| s.parse().ok()).collect();
-        if parts.len() != 6 {
-            return None;
-        }
-        let (cols, rows) = (parts[0], parts[1]);
-        if cols <= 0.0 || rows <= 0.0 {
-            return None;
-        }
-        Some((
-            parts[2] / cols,
-            parts[3] / rows,
-            parts[4] / cols,
-            parts[5] / rows,
-        ))
+        parse_grid_ratios(self.grid.as_ref()?)
+    }
+}
+
+/// Parses a `cols:rows:x:y:w:h` grid string into `(x_ratio, y_ratio, w_ratio, h_ratio)`.
+fn parse_grid_ratios(grid: &str) -> Option<(f64, f64, f64, f64)> {
+    let parts: Vec<f64> = grid.split(':').filter_map(|s| s.parse().ok()).collect();
+    if parts.len() != 6 {
+        return None;
+    }
+    let (cols, rows) = (parts[0], parts[1]);
+    if cols <= 0.0 || rows <= 0.0 {
+        return None;
     }
+    Some((
+        parts[2] / cols,
+        parts[3] / rows,
+        parts[4] / cols,
+        parts[5] / rows,
+    ))
 }
 
 /// Deserializes a regular expression from a string for window titles.
@@ -1536,6 +2974,18 @@ fn virtual_keycode() -> impl Iterator<Item = &'static (&'static str, u8)> {
     VIRTUAL_KEYCODE.iter()
 }
 
+/// Keys labelling the columns of `window jump`, in the order the columns get them.
+const JUMP_KEYS: [&str; 19] = [
+    "1", "2", "3", "4", "5", "6", "7", "8", "9", "0", "a", "s", "d", "f", "g", "h", "j", "k", "l",
+];
+
+/// Returns the keys labelling the columns of `window jump`, together with their keycodes.
+pub fn jump_keys() -> impl Iterator<Item = &'static (&'static str, u8)> {
+    JUMP_KEYS
+        .iter()
+        .filter_map(|key| virtual_keycode().find(|(name, _)| name == key))
+}
+
 /// Returns an iterator over static tuples of literal key names and their corresponding keycodes.
 /// These keycodes are for keys that are independent of the keyboard layout (e.g., Return, Tab, Space).
 ///
@@ -1796,7 +3246,12 @@ index = 1
         Some(Command::Window(Operation::Resize(ResizeDirection::Shrink)))
     ));
 
-    let props = config.find_window_properties("picture in picture", "com.something.apple");
+    let props = config.find_window_properties(
+        "picture in picture",
+        "com.something.apple",
+        "AXWindow",
+        "AXStandardWindow",
+    );
     assert_eq!(props[0].floating, Some(true));
     assert_eq!(props[0].index, Some(1));
 
@@ -1812,13 +3267,82 @@ index = 1
 }
 
 #[test]
-fn test_config_parsing_absolute_virtual_workspace_bindings() {
-    let input = r#"
+fn test_config_changes_between() {
+    let old: toml::Table = toml::from_str(
+        r#"
 [options]
+focus_follows_mouse = true
+animation_speed = 10.0
 
 [bindings]
-window_virtualnum_3 = "cmd + alt - 3"
-window_virtualsendnum_3 = "cmd + alt + shift - 3"
+window_focus_west = "alt-h"
+window_center = "alt-c"
+
+[padding]
+top = 4
+"#,
+    )
+    .unwrap();
+    let new: toml::Table = toml::from_str(
+        r#"
+[options]
+animation_speed = 12.0
+auto_center = true
+
+[bindings]
+window_focus_west = "alt-j"
+window_manage = "alt-t"
+
+[padding]
+top = 4
+
+[swipe]
+
+[windows.pip]
+title = "Picture in Picture"
+floating = true
+"#,
+    )
+    .unwrap();
+
+    let changes = ConfigChanges::between(&old, &new);
+
+    assert_eq!(
+        changes.options,
+        vec!["animation_speed", "auto_center", "focus_follows_mouse"]
+    );
+    assert_eq!(changes.bindings_added, vec!["window_manage"]);
+    assert_eq!(changes.bindings_removed, vec!["window_center"]);
+    assert_eq!(changes.bindings_changed, vec!["window_focus_west"]);
+    assert_eq!(changes.sections, vec!["swipe", "windows"]);
+    assert_eq!(changes.window_rules, 1);
+    assert_eq!(
+        changes.to_string(),
+        "options changed: animation_speed, auto_center, focus_follows_mouse; \
+         bindings added: window_manage; bindings removed: window_center; \
+         bindings changed: window_focus_west; sections changed: swipe, windows; \
+         1 window rules"
+    );
+    assert_eq!(
+        ConfigChanges::between(&new, &new),
+        ConfigChanges {
+            window_rules: 1,
+            ..ConfigChanges::default()
+        }
+    );
+}
+
+#[test]
+fn test_keybinding_modes() {
+    let input = r#"
+[bindings]
+mode_resize = "alt-r"
+window_focus_west = "alt-h"
+
+[modes.resize]
+window_shrink = "h"
+window_grow = "l"
+mode_default = "return"
 "#;
     let virtual_keys = test_virtual_keymap();
     let config = Config {
@@ -1827,289 +3351,1600 @@ window_virtualsendnum_3 = "cmd + alt + shift - 3"
                 .expect("Failed to parse config"),
         )),
     };
-    let find_key = |k| {
+    let find_key = |k: &str| {
         virtual_keycode()
-            .find_map(|(s, v)| (format!("{k}") == *s).then_some(*v))
+            .chain(literal_keycode())
+            .find_map(|(s, v)| (k == *s).then_some(*v))
             .unwrap()
     };
-    let keycode = find_key('3');
-    assert!(matches!(
-        config.find_keybind(keycode, Modifiers::CMD | Modifiers::ALT),
-        Some(Command::Window(Operation::VirtualNumber(2)))
-    ));
+
+    assert!(config.has_mode("resize"));
+    assert!(!config.has_mode("move"));
     assert!(matches!(
-        config.find_keybind(keycode, Modifiers::CMD | Modifiers::ALT | Modifiers::SHIFT),
-        Some(Command::Window(Operation::VirtualMoveNumber(
-            2,
-            MoveFocus::Stay
-        )))
+        config.find_keybind(find_key("r"), Modifiers::LALT),
+        Some(Command::Mode(mode)) if mode == "resize"
     ));
-}
 
-#[test]
-fn test_parse_resize_commands() {
+    // Mode tables only apply while the mode is active.
+    let h = find_key("h");
+    assert!(config.find_keybind(h, Modifiers::empty()).is_none());
     assert!(matches!(
-        parse_command(&["window", "resize"]).unwrap(),
-        Command::Window(Operation::Resize(ResizeDirection::Grow))
+        config.find_mode_keybind("resize", h, Modifiers::empty()),
+        Some(Command::Window(Operation::Resize(ResizeDirection::Shrink)))
     ));
     assert!(matches!(
-        parse_command(&["window", "grow"]).unwrap(),
-        Command::Window(Operation::Resize(ResizeDirection::Grow))
+        config.find_mode_keybind("resize", find_key("l"), Modifiers::empty()),
+        Some(Command::Window(Operation::Resize(ResizeDirection::Grow)))
     ));
     assert!(matches!(
-        parse_command(&["window", "resize", "shrink"]).unwrap(),
-        Command::Window(Operation::Resize(ResizeDirection::Shrink))
+        config.find_mode_keybind("resize", find_key("return"), Modifiers::empty()),
+        Some(Command::Mode(mode)) if mode == DEFAULT_MODE
+    ));
+    assert!(
+        config
+            .find_mode_keybind("resize", h, Modifiers::LALT)
+            .is_none()
+    );
+    assert!(
+        config
+            .find_mode_keybind("move", h, Modifiers::empty())
+            .is_none()
+    );
+    assert!(parse_command(&["mode"]).is_err());
+}
+
+#[test]
+fn test_exec_bindings() {
+    let input = r#"
+[options]
+
+[bindings]
+exec_terminal = "ctrl+alt-t"
+exec_ghostty = "ctrl+alt-g"
+exec_notes = "ctrl+alt-n"
+
+[exec]
+terminal = "open -na Terminal"
+notes = ["open", "-a", "Notes"]
+"#;
+    let virtual_keys = test_virtual_keymap();
+    let config = Config {
+        inner: Arc::new(ArcSwap::from_pointee(
+            InnerConfig::parse_config_with_virtual_keys(input, &virtual_keys)
+                .expect("Failed to parse config"),
+        )),
+    };
+    let find_key = |k| {
+        virtual_keycode()
+            .find_map(|(s, v)| (format!("{k}") == *s).then_some(*v))
+            .unwrap()
+    };
+    let exec = |key| match config.find_keybind(find_key(key), Modifiers::CTRL | Modifiers::ALT) {
+        Some(Command::Exec(argv)) => argv,
+        other => panic!("expected an exec binding, got {other:?}"),
+    };
+
+    assert_eq!(exec('t'), vec!["/bin/sh", "-c", "open -na Terminal"]);
+    assert_eq!(exec('n'), vec!["open", "-a", "Notes"]);
+    // Without an `[exec]` entry the binding name is the program.
+    assert_eq!(exec('g'), vec!["ghostty"]);
+
+    assert!(matches!(
+        parse_command(&["exec", "open", "-a", "Safari"]),
+        Ok(Command::Exec(argv)) if argv == ["open", "-a", "Safari"]
+    ));
+    assert!(parse_command(&["exec"]).is_err());
+}
+
+#[test]
+fn test_parse_log_level_command() {
+    assert!(matches!(
+        parse_command(&["loglevel", "debug"]),
+        Ok(Command::LogLevel(filter)) if filter == "debug"
+    ));
+    assert!(parse_command(&["loglevel"]).is_err());
+}
+
+#[test]
+fn test_parse_dump_journal_command() {
+    assert!(matches!(
+        parse_command(&["dump-journal"]),
+        Ok(Command::DumpJournal(None))
+    ));
+    assert!(matches!(
+        parse_command(&["dump-journal", "/tmp/trace.log"]),
+        Ok(Command::DumpJournal(Some(path))) if path == Path::new("/tmp/trace.log")
+    ));
+}
+
+#[test]
+fn test_parse_state_command() {
+    assert!(matches!(
+        parse_command(&["state", "export", "/tmp/state.json"]),
+        Ok(Command::State(StateOperation::Export(path))) if path == Path::new("/tmp/state.json")
+    ));
+    assert!(matches!(
+        parse_command(&["state", "import", "/tmp/state.json"]),
+        Ok(Command::State(StateOperation::Import(path))) if path == Path::new("/tmp/state.json")
+    ));
+    assert!(parse_command(&["state", "export"]).is_err());
+    assert!(parse_command(&["state", "reset", "/tmp/state.json"]).is_err());
+}
+
+#[test]
+fn test_parse_workspace_command() {
+    assert!(matches!(
+        parse_command(&["workspace", "tonextdisplay"]),
+        Ok(Command::Workspace(WorkspaceOperation::ToNextDisplay))
+    ));
+    assert!(parse_command(&["workspace"]).is_err());
+    assert!(parse_command(&["workspace", "tonextdisplay", "now"]).is_err());
+}
+
+#[test]
+fn test_parse_display_command() {
+    assert!(matches!(
+        parse_command(&["display", "swap-workspaces"]),
+        Ok(Command::Display(DisplayOperation::SwapWorkspaces))
+    ));
+    assert!(parse_command(&["display", "swap"]).is_err());
+}
+
+#[test]
+fn test_parse_space_command() {
+    assert!(matches!(
+        parse_command(&["space", "focus", "3"]),
+        Ok(Command::Space(SpaceOperation::Focus(3)))
+    ));
+    assert!(parse_command(&["space", "focus", "0"]).is_err());
+    assert!(parse_command(&["space", "focus"]).is_err());
+}
+
+#[test]
+fn test_parse_insert_next() {
+    assert!(matches!(
+        parse_command(&["window", "insertnext", "stacked"]),
+        Ok(Command::Window(Operation::InsertNext(InsertNext::Stacked)))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "insertnext", "left"]),
+        Ok(Command::Window(Operation::InsertNext(InsertNext::Left)))
+    ));
+    assert!(parse_command(&["window", "insertnext"]).is_err());
+    assert!(parse_command(&["window", "insertnext", "below"]).is_err());
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_parse_zen_command() {
+    assert!(matches!(
+        parse_command(&["window", "zen"]),
+        Ok(Command::Window(Operation::Zen))
+    ));
+    assert_eq!(Config::default().zen_width(), 0.6);
+}
+
+#[test]
+fn test_parse_overview_command() {
+    assert!(matches!(
+        parse_command(&["window", "overview"]),
+        Ok(Command::Window(Operation::Overview))
+    ));
+}
+
+#[test]
+fn test_parse_jump_command() {
+    assert!(matches!(
+        parse_command(&["window", "jump"]),
+        Ok(Command::Window(Operation::Jump))
+    ));
+    let keys = jump_keys().collect::<Vec<_>>();
+    assert_eq!(keys.len(), JUMP_KEYS.len());
+    assert_eq!(keys.first(), Some(&&("1", 0x12)));
+}
+
+#[test]
+fn test_parse_peek_command() {
+    assert!(matches!(
+        parse_command(&["window", "peek", "east"]),
+        Ok(Command::Window(Operation::Peek(Direction::East)))
+    ));
+    assert!(parse_command(&["window", "peek"]).is_err());
+    assert!(parse_command(&["window", "peek", "up"]).is_err());
+}
+
+#[test]
+fn test_parse_gaps_command() {
+    assert!(matches!(
+        parse_command(&["gaps", "inner", "+5"]),
+        Ok(Command::Gaps(GapsOperation::Inner(5)))
+    ));
+    assert!(matches!(
+        parse_command(&["gaps", "outer", "-5"]),
+        Ok(Command::Gaps(GapsOperation::Outer(-5)))
+    ));
+    assert!(matches!(
+        parse_command(&["gaps", "reset"]),
+        Ok(Command::Gaps(GapsOperation::Reset))
+    ));
+    assert!(parse_command(&["gaps", "inner"]).is_err());
+    assert!(parse_command(&["gaps", "outer", "wide"]).is_err());
+}
+
+#[test]
+fn test_parse_window_to_space() {
+    assert!(matches!(
+        parse_command(&["window", "tospace", "2"]),
+        Ok(Command::Window(Operation::ToSpace(2, MoveFocus::Stay)))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "tospace", "2", "--follow"]),
+        Ok(Command::Window(Operation::ToSpace(2, MoveFocus::Follow)))
+    ));
+    assert!(parse_command(&["window", "tospace", "0"]).is_err());
+    assert!(parse_command(&["window", "tospace", "2", "--stay"]).is_err());
+}
+
+#[test]
+fn test_parse_window_to_display() {
+    assert!(matches!(
+        parse_command(&["window", "todisplay", "west"]),
+        Ok(Command::Window(Operation::ToDisplay(
+            DisplayTarget::Direction(Direction::West),
+            MoveFocus::Stay
+        )))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "todisplay", "north", "--follow"]),
+        Ok(Command::Window(Operation::ToDisplay(
+            DisplayTarget::Direction(Direction::North),
+            MoveFocus::Follow
+        )))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "todisplay", "2"]),
+        Ok(Command::Window(Operation::ToDisplay(
+            DisplayTarget::Index(2),
+            MoveFocus::Stay
+        )))
+    ));
+    assert!(parse_command(&["window", "todisplay"]).is_err());
+    assert!(parse_command(&["window", "todisplay", "0"]).is_err());
+    assert!(parse_command(&["window", "todisplay", "first"]).is_err());
+    assert!(parse_command(&["window", "todisplay", "east", "--stay"]).is_err());
+}
+
+#[test]
+fn test_set_option_updates_live_config() {
+    let mut config = Config::try_from(
+        r#"
+[options]
+focus_follows_mouse = true
+
+[bindings]
+"#,
+    )
+    .expect("config should parse");
+
+    assert!(matches!(
+        parse_command(&["config", "set", "animation_speed", "20"]),
+        Ok(Command::SetOption(key, value)) if key == "animation_speed" && value == "20"
+    ));
+    assert!(parse_command(&["config", "set", "animation_speed"]).is_err());
+    assert!(parse_command(&["config", "get", "animation_speed"]).is_err());
+
+    let changes = config
+        .set_option("focus_follows_mouse", "false")
+        .expect("option should be set");
+    assert_eq!(changes.options, vec!["focus_follows_mouse".to_string()]);
+    assert!(!config.focus_follows_mouse());
+
+    config.set_option("animation_speed", "20").unwrap();
+    assert_eq!(config.animation_speed(), 20.0);
+
+    config.set_option("padding.top", "12").unwrap();
+    assert_eq!(config.edge_padding().0, 12);
+
+    // Values of the wrong type leave the configuration untouched.
+    assert!(config.set_option("animation_speed", "fast").is_err());
+    assert_eq!(config.animation_speed(), 20.0);
+}
+
+#[test]
+fn test_leader_keybindings() {
+    let input = r#"
+[options]
+leader = "ctrl-space"
+leader_timeout_ms = 750
+
+[bindings]
+window_focus_west = ["leader-h", "alt-h"]
+window_swap_west = "leader+shift-h"
+"#;
+    let virtual_keys = test_virtual_keymap();
+    let config = Config {
+        inner: Arc::new(ArcSwap::from_pointee(
+            InnerConfig::parse_config_with_virtual_keys(input, &virtual_keys)
+                .expect("Failed to parse config"),
+        )),
+    };
+    let find_key = |k: &str| {
+        virtual_keycode()
+            .find_map(|(s, v)| (k == *s).then_some(*v))
+            .unwrap()
+    };
+
+    assert_eq!(
+        config.leader_key(),
+        Some((find_key("space"), Modifiers::CTRL))
+    );
+    assert_eq!(config.leader_timeout(), Duration::from_millis(750));
+
+    let h = find_key("h");
+    assert!(config.find_keybind(h, Modifiers::empty()).is_none());
+    assert!(matches!(
+        config.find_keybind(h, Modifiers::LALT),
+        Some(Command::Window(Operation::Focus(Direction::West)))
+    ));
+    assert!(matches!(
+        config.find_leader_keybind(h, Modifiers::empty()),
+        Some(Command::Window(Operation::Focus(Direction::West)))
+    ));
+    assert!(matches!(
+        config.find_leader_keybind(h, Modifiers::LSHIFT),
+        Some(Command::Window(Operation::Swap(Direction::West)))
+    ));
+    assert!(config.find_leader_keybind(h, Modifiers::LALT).is_none());
+
+    assert!(Config::default().leader_key().is_none());
+    assert_eq!(Config::default().leader_timeout(), Duration::from_secs(1));
+}
+
+#[test]
+fn test_config_parsing_absolute_virtual_workspace_bindings() {
+    let input = r#"
+[options]
+
+[bindings]
+window_virtualnum_3 = "cmd + alt - 3"
+window_virtualsendnum_3 = "cmd + alt + shift - 3"
+"#;
+    let virtual_keys = test_virtual_keymap();
+    let config = Config {
+        inner: Arc::new(ArcSwap::from_pointee(
+            InnerConfig::parse_config_with_virtual_keys(input, &virtual_keys)
+                .expect("Failed to parse config"),
+        )),
+    };
+    let find_key = |k| {
+        virtual_keycode()
+            .find_map(|(s, v)| (format!("{k}") == *s).then_some(*v))
+            .unwrap()
+    };
+    let keycode = find_key('3');
+    assert!(matches!(
+        config.find_keybind(keycode, Modifiers::CMD | Modifiers::ALT),
+        Some(Command::Window(Operation::VirtualNumber(2)))
+    ));
+    assert!(matches!(
+        config.find_keybind(keycode, Modifiers::CMD | Modifiers::ALT | Modifiers::SHIFT),
+        Some(Command::Window(Operation::VirtualMoveNumber(
+            2,
+            MoveFocus::Stay
+        )))
+    ));
+}
+
+#[test]
+fn test_parse_resize_commands() {
+    assert!(matches!(
+        parse_command(&["window", "resize"]).unwrap(),
+        Command::Window(Operation::Resize(ResizeDirection::Grow))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "grow"]).unwrap(),
+        Command::Window(Operation::Resize(ResizeDirection::Grow))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "resize", "shrink"]).unwrap(),
+        Command::Window(Operation::Resize(ResizeDirection::Shrink))
     ));
     assert!(matches!(
         parse_command(&["window", "shrink"]).unwrap(),
         Command::Window(Operation::Resize(ResizeDirection::Shrink))
     ));
+    assert!(matches!(
+        parse_command(&["window", "resizeback"]).unwrap(),
+        Command::Window(Operation::Resize(ResizeDirection::Shrink))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "resize", "width", "+100"]).unwrap(),
+        Command::Window(Operation::ResizeBy {
+            axis: ResizeAxis::Width,
+            amount: ResizeAmount::Pixels(100)
+        })
+    ));
+    assert!(matches!(
+        parse_command(&["window", "resize", "height", "-5%"]).unwrap(),
+        Command::Window(Operation::ResizeBy {
+            axis: ResizeAxis::Height,
+            amount: ResizeAmount::Percent(percent)
+        }) if (percent + 5.0).abs() < f64::EPSILON
+    ));
+    assert!(parse_command(&["window", "resize", "depth", "+100"]).is_err());
+    assert!(parse_command(&["window", "resize", "width", "wide"]).is_err());
 }
 
 #[test]
-fn test_parse_restart_command() {
+fn test_parse_width_command() {
     assert!(matches!(
-        parse_command(&["restart"]).unwrap(),
-        Command::Restart
+        parse_command(&["window", "width", "0.4"]).unwrap(),
+        Command::Window(Operation::SetWidth(ratio)) if (ratio - 0.4).abs() < f64::EPSILON
+    ));
+    assert!(matches!(
+        parse_command(&["window", "width", "900px"]).unwrap(),
+        Command::Window(Operation::SetWidthPixels(900))
     ));
+    assert!(parse_command(&["window", "width"]).is_err());
+    assert!(parse_command(&["window", "width", "0"]).is_err());
+    assert!(parse_command(&["window", "width", "-10px"]).is_err());
 }
 
 #[test]
-fn test_parse_absolute_virtual_workspace_commands() {
+#[allow(clippy::float_cmp)]
+fn test_parse_grid_command() {
+    let grid = |spec| match parse_command(&["window", "grid", spec]) {
+        Ok(Command::Window(Operation::Grid {
+            x,
+            y,
+            width,
+            height,
+        })) => Some((x, y, width, height)),
+        _ => None,
+    };
+    assert_eq!(grid("2x2:0:1"), Some((0.0, 0.5, 0.5, 0.5)));
+    assert_eq!(grid("3x2:1:0:2:1"), Some((1.0 / 3.0, 0.0, 2.0 / 3.0, 0.5)));
+    assert_eq!(grid("2:2:1:1:1:1"), Some((0.5, 0.5, 0.5, 0.5)));
+    assert_eq!(grid("0x2:0:0"), None);
+    assert_eq!(grid("2x2:0"), None);
+    assert!(parse_command(&["window", "grid"]).is_err());
+}
+
+#[test]
+fn test_parse_balance_widths_command() {
     assert!(matches!(
-        parse_command(&["window", "virtualnum", "3"]).unwrap(),
-        Command::Window(Operation::VirtualNumber(2))
+        parse_command(&["window", "balancewidths"]).unwrap(),
+        Command::Window(Operation::BalanceWidths(None))
     ));
-    assert!(parse_command(&["workspace", "virtual", "3"]).is_err());
     assert!(matches!(
-        parse_command(&["window", "virtualmove", "3"]).unwrap(),
-        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Follow))
+        parse_command(&["window", "balancewidths", "2", "4"]).unwrap(),
+        Command::Window(Operation::BalanceWidths(Some((1, 3))))
     ));
+    assert!(parse_command(&["window", "balancewidths", "2"]).is_err());
+    assert!(parse_command(&["window", "balancewidths", "0", "2"]).is_err());
+    assert!(parse_command(&["window", "balancewidths", "3", "2"]).is_err());
+}
+
+#[test]
+fn test_parse_cycle_stack_commands() {
     assert!(matches!(
-        parse_command(&["window", "virtualsend", "3"]).unwrap(),
-        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Stay))
+        parse_command(&["window", "cyclestack"]).unwrap(),
+        Command::Window(Operation::CycleStack(CycleDirection::Next))
     ));
     assert!(matches!(
-        parse_command(&["window", "virtualmovenum", "3"]).unwrap(),
-        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Follow))
+        parse_command(&["window", "cyclestack", "next"]).unwrap(),
+        Command::Window(Operation::CycleStack(CycleDirection::Next))
     ));
     assert!(matches!(
-        parse_command(&["window", "virtualsendnum", "3"]).unwrap(),
-        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Stay))
+        parse_command(&["window", "cyclestack", "prev"]).unwrap(),
+        Command::Window(Operation::CycleStack(CycleDirection::Prev))
     ));
+    assert!(parse_command(&["window", "cyclestack", "north"]).is_err());
+}
+
+#[test]
+fn test_parse_promote_command() {
+    assert!(matches!(
+        parse_command(&["window", "promote"]).unwrap(),
+        Command::Window(Operation::Promote)
+    ));
+}
+
+#[test]
+fn test_parse_pin_command() {
+    assert!(matches!(
+        parse_command(&["window", "pin"]).unwrap(),
+        Command::Window(Operation::Pin)
+    ));
+}
+
+#[test]
+fn test_parse_sticky_command() {
+    assert!(matches!(
+        parse_command(&["window", "sticky"]).unwrap(),
+        Command::Window(Operation::Sticky)
+    ));
+}
+
+#[test]
+fn test_parse_minimize_commands() {
+    assert!(matches!(
+        parse_command(&["window", "minimize"]).unwrap(),
+        Command::Window(Operation::Minimize)
+    ));
+    assert!(matches!(
+        parse_command(&["window", "deminimize"]).unwrap(),
+        Command::Window(Operation::Deminimize)
+    ));
+}
+
+#[test]
+fn test_parse_hideapp_command() {
+    assert!(matches!(
+        parse_command(&["window", "hideapp"]).unwrap(),
+        Command::Window(Operation::HideApp)
+    ));
+}
+
+#[test]
+fn test_parse_focusapp_command() {
+    assert!(matches!(
+        parse_command(&["window", "focusapp", "com.apple.Safari"]).unwrap(),
+        Command::Window(Operation::FocusApp { matcher, launch: false })
+            if matcher.as_str() == "com.apple.Safari"
+    ));
+    assert!(matches!(
+        parse_command(&["window", "focusapp", "^Term", "launch"]).unwrap(),
+        Command::Window(Operation::FocusApp { launch: true, .. })
+    ));
+    assert!(parse_command(&["window", "focusapp"]).is_err());
+    assert!(parse_command(&["window", "focusapp", "(", "launch"]).is_err());
+}
+
+#[test]
+fn test_parse_focus_id_command() {
+    assert!(matches!(
+        parse_command(&["window", "focus-id", "321"]).unwrap(),
+        Command::Window(Operation::FocusId(321))
+    ));
+    assert!(parse_command(&["window", "focus-id", "abc"]).is_err());
+}
+
+#[test]
+fn test_parse_focus_next_prev_commands() {
+    assert!(matches!(
+        parse_command(&["window", "focus", "next"]).unwrap(),
+        Command::Window(Operation::FocusNext)
+    ));
+    assert!(matches!(
+        parse_command(&["window", "focus", "prev"]).unwrap(),
+        Command::Window(Operation::FocusPrev)
+    ));
+}
+
+#[test]
+fn test_parse_cycle_recent_command() {
+    assert!(matches!(
+        parse_command(&["window", "cycle-recent"]).unwrap(),
+        Command::Window(Operation::CycleRecent)
+    ));
+}
+
+#[test]
+fn test_parse_restore_original_command() {
+    assert!(matches!(
+        parse_command(&["window", "restore-original"]).unwrap(),
+        Command::Window(Operation::RestoreOriginal)
+    ));
+}
+
+#[test]
+fn test_parse_reorder_strip_commands() {
+    assert!(matches!(
+        parse_command(&["window", "reverse"]).unwrap(),
+        Command::Window(Operation::ReverseStrip)
+    ));
+    assert!(matches!(
+        parse_command(&["window", "rotate"]).unwrap(),
+        Command::Window(Operation::RotateStrip(1))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "rotate", "-2"]).unwrap(),
+        Command::Window(Operation::RotateStrip(-2))
+    ));
+    assert!(parse_command(&["window", "rotate", "left"]).is_err());
+}
+
+#[test]
+fn test_parse_focus_any_command() {
+    assert!(matches!(
+        parse_command(&["window", "focus", "any", "east"]).unwrap(),
+        Command::Window(Operation::FocusAny(Direction::East))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "focus", "west"]).unwrap(),
+        Command::Window(Operation::Focus(Direction::West))
+    ));
+    assert!(parse_command(&["window", "focus", "any"]).is_err());
+}
+
+#[test]
+fn test_parse_layout_preset_commands() {
+    assert!(matches!(
+        parse_command(&["window", "layout-save", "work"]).unwrap(),
+        Command::Window(Operation::LayoutSave(name)) if name == "work"
+    ));
+    assert!(matches!(
+        parse_command(&["window", "layout-apply", "work"]).unwrap(),
+        Command::Window(Operation::LayoutApply(name)) if name == "work"
+    ));
+    assert!(parse_command(&["window", "layout-apply"]).is_err());
+}
+
+#[test]
+fn test_parse_strip_commands() {
+    assert!(matches!(
+        parse_command(&["strip", "insert-at", "42", "0"]).unwrap(),
+        Command::Strip(StripOperation::InsertAt(42, 0))
+    ));
+    assert!(matches!(
+        parse_command(&["strip", "move", "42", "3"]).unwrap(),
+        Command::Strip(StripOperation::Move(42, 3))
+    ));
+    assert!(matches!(
+        parse_command(&["strip", "stack", "42", "7"]).unwrap(),
+        Command::Strip(StripOperation::Stack(42, 7))
+    ));
+    assert!(matches!(
+        parse_command(&["strip", "set-width", "42", "0.5"]).unwrap(),
+        Command::Strip(StripOperation::SetWidth(42, width)) if (width - 0.5).abs() < f64::EPSILON
+    ));
+    assert!(parse_command(&["strip", "move", "42"]).is_err());
+    assert!(parse_command(&["strip", "move", "window", "1"]).is_err());
+}
+
+#[test]
+fn test_parse_strip_scroll_commands() {
+    assert!(matches!(
+        parse_command(&["strip", "scroll", "left"]).unwrap(),
+        Command::Strip(StripOperation::Scroll(Direction::West, ResizeAmount::Percent(percent)))
+            if (percent - 50.0).abs() < f64::EPSILON
+    ));
+    assert!(matches!(
+        parse_command(&["strip", "scroll", "right", "300"]).unwrap(),
+        Command::Strip(StripOperation::Scroll(
+            Direction::East,
+            ResizeAmount::Pixels(300)
+        ))
+    ));
+    assert!(matches!(
+        parse_command(&["strip", "scroll-to", "last"]).unwrap(),
+        Command::Strip(StripOperation::ScrollTo(Direction::Last))
+    ));
+    assert!(parse_command(&["strip", "scroll", "up"]).is_err());
+    assert!(parse_command(&["strip", "scroll", "left", "far"]).is_err());
+    assert!(parse_command(&["strip", "scroll-to", "middle"]).is_err());
+}
+
+#[test]
+fn test_parse_restart_command() {
+    assert!(matches!(
+        parse_command(&["restart"]).unwrap(),
+        Command::Restart
+    ));
+}
+
+#[test]
+fn test_parse_absolute_virtual_workspace_commands() {
+    assert!(matches!(
+        parse_command(&["window", "virtualnum", "3"]).unwrap(),
+        Command::Window(Operation::VirtualNumber(2))
+    ));
+    assert!(parse_command(&["workspace", "virtual", "3"]).is_err());
+    assert!(matches!(
+        parse_command(&["window", "virtualmove", "3"]).unwrap(),
+        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Follow))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "virtualsend", "3"]).unwrap(),
+        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Stay))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "virtualmovenum", "3"]).unwrap(),
+        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Follow))
+    ));
+    assert!(matches!(
+        parse_command(&["window", "virtualsendnum", "3"]).unwrap(),
+        Command::Window(Operation::VirtualMoveNumber(2, MoveFocus::Stay))
+    ));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_grid_ratios() {
+    use regex::Regex;
+
+    let make = |grid: Option<&str>| WindowParams {
+        title: Regex::new(".*").unwrap(),
+        bundle_id: None,
+        role: None,
+        subrole: None,
+        floating: None,
+        sticky: None,
+        pip: None,
+        manage: None,
+        index: None,
+        vertical_padding: None,
+        horizontal_padding: None,
+        dont_focus: None,
+        auto_stack_same_app: None,
+        width: None,
+        min_width: None,
+        max_width: None,
+        grid: grid.map(Into::into),
+        border_radius: None,
+        animate: None,
+        disable_enhanced_ui: None,
+        opacity: None,
+        display: None,
+        bindings_passthrough: vec![],
+        parsed_passthrough: vec![],
+    };
+
+    // Standard 2x2 grid, cell (1,1), span 1x1 → bottom-right quarter.
+    assert_eq!(
+        make(Some("2:2:1:1:1:1")).grid_ratios(),
+        Some((0.5, 0.5, 0.5, 0.5))
+    );
+
+    // 3x3 grid, cell (0,0), span 2x1 → top-left, 2/3 width, 1/3 height.
+    assert_eq!(
+        make(Some("3:3:0:0:2:1")).grid_ratios(),
+        Some((0.0, 0.0, 2.0 / 3.0, 1.0 / 3.0))
+    );
+
+    // Full screen: 1x1 grid, cell (0,0), span 1x1.
+    assert_eq!(
+        make(Some("1:1:0:0:1:1")).grid_ratios(),
+        Some((0.0, 0.0, 1.0, 1.0))
+    );
+
+    // Invalid: too few parts.
+    assert_eq!(make(Some("2:2:1:1")).grid_ratios(), None);
+
+    // Invalid: zero columns.
+    assert_eq!(make(Some("0:2:0:0:1:1")).grid_ratios(), None);
+
+    // No grid set.
+    assert_eq!(make(None).grid_ratios(), None);
+}
+
+#[test]
+fn test_parse_hex_color_valid() {
+    assert_eq!(
+        parse_hex_color("#89b4fa"),
+        (
+            f64::from(0x89) / 255.0,
+            f64::from(0xb4) / 255.0,
+            f64::from(0xfa) / 255.0
+        )
+    );
+    assert_eq!(parse_hex_color("#000000"), (0.0, 0.0, 0.0));
+    assert_eq!(parse_hex_color("#FFFFFF"), (1.0, 1.0, 1.0));
+    assert_eq!(parse_hex_color("#FF0000"), (1.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_parse_hex_color_no_hash() {
+    assert_eq!(
+        parse_hex_color("89b4fa"),
+        (
+            f64::from(0x89) / 255.0,
+            f64::from(0xb4) / 255.0,
+            f64::from(0xfa) / 255.0
+        )
+    );
+    assert_eq!(parse_hex_color("FF0000"), (1.0, 0.0, 0.0));
+}
+
+#[test]
+fn test_parse_hex_color_invalid_length() {
+    // Short strings fall back to white.
+    assert_eq!(parse_hex_color("#FFF"), (1.0, 1.0, 1.0));
+    assert_eq!(parse_hex_color(""), (1.0, 1.0, 1.0));
+    assert_eq!(parse_hex_color("#FF"), (1.0, 1.0, 1.0));
+}
+
+#[test]
+fn test_parse_hex_color_malformed_hex() {
+    // Non-hex digits fall back to 255 per channel.
+    assert_eq!(parse_hex_color("ZZZZZZ"), (1.0, 1.0, 1.0));
+    assert_eq!(parse_hex_color("GG0000"), (1.0, 0.0, 0.0));
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_config_defaults() {
+    let config = Config::default();
+    assert_eq!(config.dim_inactive_opacity(), 0.0);
+    assert_eq!(config.dim_inactive_color(), (0.0, 0.0, 0.0));
+    assert!(!config.border_active_window());
+    assert_eq!(config.border_color(), (1.0, 1.0, 1.0));
+    assert_eq!(config.border_opacity(), 1.0);
+    assert_eq!(config.border_width(), 2.0);
+    assert_eq!(config.border_radius(), BorderRadiusOption::Auto);
+    assert_eq!(config.menubar_height(), None);
+}
+
+#[test]
+fn test_first_launch_creates_parseable_config_without_overwriting_it() {
+    let unique = format!(
+        "paneru-first-launch-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    let directory = std::env::temp_dir().join(unique);
+    let path = directory.join("paneru.toml");
+
+    assert!(create_configuration_file_at(&path).unwrap());
+    Config::new(&path).expect("generated configuration should parse");
+
+    let custom = "[options]\nauto_center = false\n\n[bindings]\n";
+    std::fs::write(&path, custom).unwrap();
+    assert!(!create_configuration_file_at(&path).unwrap());
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), custom);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_default_configuration_settings_parse_when_uncommented() {
+    let uncommented = DEFAULT_CONFIGURATION
+        .lines()
+        .map(|line| match line.strip_prefix("# ") {
+            Some(setting) if setting.starts_with('[') || setting.contains(" = ") => setting,
+            _ => line,
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let config = Config::try_from(uncommented.as_str()).expect("uncommented defaults should parse");
+    assert_eq!(config.sliver_width(), 5);
+}
+
+#[test]
+fn test_config_paths_expand_home_and_variables() {
+    let home = env::var("HOME").expect("HOME should be set");
+    let base = Path::new("/etc/paneru");
+
+    let expected = PathBuf::from(&home).join("paneru/rules.toml");
+    assert_eq!(resolve_include("~/paneru/rules.toml", base), expected);
+    assert_eq!(resolve_include("$HOME/paneru/rules.toml", base), expected);
+    assert_eq!(resolve_include("${HOME}/paneru/rules.toml", base), expected);
+    assert_eq!(
+        resolve_include("rules.toml", base),
+        PathBuf::from("/etc/paneru/rules.toml")
+    );
+    // Unset variables and a `~` inside a name are kept as written.
+    assert_eq!(
+        crate::util::expand_env("$PANERU_UNSET_VARIABLE/a~b"),
+        "$PANERU_UNSET_VARIABLE/a~b"
+    );
+
+    let program = ExecProgram::Argv(vec!["~/bin/term".to_string(), "$HOME".to_string()]);
+    assert_eq!(program.argv(), vec![format!("{home}/bin/term"), home]);
+}
+
+#[test]
+fn test_include_merges_files() {
+    let unique = format!(
+        "paneru-include-{}-{}",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    );
+    let directory = std::env::temp_dir().join(unique);
+    std::fs::create_dir_all(&directory).unwrap();
+    let path = directory.join("paneru.toml");
+    let rules = directory.join("rules.toml");
+
+    std::fs::write(
+        &path,
+        "include = [\"rules.toml\"]\n\n[options]\nauto_center = true\n\n[bindings]\n",
+    )
+    .unwrap();
+    std::fs::write(
+        &rules,
+        "[options]\nauto_center = false\nsliver_width = 9\n\n[windows.finder]\ntitle = \".*\"\nbundle_id = \"com.apple.finder\"\nfloating = true\n",
+    )
+    .unwrap();
+
+    let config = Config::new(&path).expect("configuration with includes should parse");
+    assert_eq!(config.included_files(), vec![rules.clone()]);
+    // The including file wins over included settings.
+    assert!(config.auto_center());
+    assert_eq!(config.sliver_width(), 9);
+    let props =
+        config.find_window_properties("Finder", "com.apple.finder", "AXWindow", "AXStandardWindow");
+    assert_eq!(props.len(), 1);
+
+    std::fs::remove_file(&rules).unwrap();
+    assert!(Config::new(&path).is_err());
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_window_rules_manage() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.btt_main]
+bundle_id = "com.hegenberg.BetterTouchTool"
+title = "BetterTouchTool"
+manage = true
+
+[windows.btt_floating]
+bundle_id = "com.hegenberg.BetterTouchTool"
+title = "Screenshot.*"
+floating = true
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+
+    // Main window should match the manage rule.
+    let props = config.find_window_properties(
+        "BetterTouchTool",
+        "com.hegenberg.BetterTouchTool",
+        "AXWindow",
+        "AXStandardWindow",
+    );
+    assert_eq!(props.len(), 1);
+    assert_eq!(props[0].manage, Some(true));
+
+    // Screenshot window matches the floating rule.
+    let props = config.find_window_properties(
+        "Screenshot 1",
+        "com.hegenberg.BetterTouchTool",
+        "AXWindow",
+        "AXStandardWindow",
+    );
+    assert_eq!(props.len(), 1);
+    assert_eq!(props[0].floating, Some(true));
+}
+
+#[test]
+fn test_window_rules_match_role_and_subrole() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.finder_dialogs]
+bundle_id = "com.apple.finder"
+title = ".*"
+subrole = "AXDialog"
+floating = true
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+
+    let props = config.find_window_properties("Copy", "com.apple.finder", "AXWindow", "AXDialog");
+    assert_eq!(props.len(), 1);
+    assert_eq!(props[0].floating, Some(true));
+
+    // A standard window of the same app does not match.
+    let props = config.find_window_properties(
+        "Downloads",
+        "com.apple.finder",
+        "AXWindow",
+        "AXStandardWindow",
+    );
+    assert!(props.is_empty());
+
+    // Neither does a dialog of another app.
+    let props = config.find_window_properties("Copy", "com.apple.Safari", "AXWindow", "AXDialog");
+    assert!(props.is_empty());
+}
+
+#[test]
+fn test_window_rules_display() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.slack]
+bundle_id = "com.tinyspeck.slackmacgap"
+title = ".*"
+display = 2
+
+[windows.music]
+bundle_id = "com.apple.Music"
+title = ".*"
+display = "37D8832A-2D66-02CA-B9F7-8F30A301B230"
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+
+    let props = config.find_window_properties(
+        "Slack",
+        "com.tinyspeck.slackmacgap",
+        "AXWindow",
+        "AXStandardWindow",
+    );
+    assert_eq!(props[0].display, Some(DisplaySelector::Index(2)));
+
+    let props =
+        config.find_window_properties("Music", "com.apple.Music", "AXWindow", "AXStandardWindow");
+    assert_eq!(
+        props[0].display,
+        Some(DisplaySelector::Uuid(
+            "37D8832A-2D66-02CA-B9F7-8F30A301B230".to_string()
+        ))
+    );
+}
+
+#[test]
+fn test_window_width_limits_parse_pixels_and_ratios() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.terminal]
+bundle_id = "com.apple.Terminal"
+title = ".*"
+min_width = "640px"
+max_width = 1
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+
+    let props = config.find_window_properties(
+        "Terminal",
+        "com.apple.Terminal",
+        "AXWindow",
+        "AXStandardWindow",
+    );
+    assert_eq!(props[0].min_width, Some(ColumnWidth::Pixels(640)));
+    // A bare number is always a ratio, even without a fractional part.
+    assert_eq!(props[0].max_width, Some(ColumnWidth::Ratio(1.0)));
+    assert_eq!(ColumnWidth::Pixels(640).pixels(1000), 640);
+    assert_eq!(ColumnWidth::Ratio(0.5).pixels(1000), 500);
+
+    let negative = r#"
+[options]
+
+[bindings]
+
+[windows.terminal]
+title = ".*"
+min_width = "-640px"
+"#;
+    assert!(Config::try_from(negative).is_err());
+}
+
+#[test]
+fn test_disable_enhanced_ui_rule() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.slack]
+bundle_id = "com.tinyspeck.slackmacgap"
+title = ".*"
+disable_enhanced_ui = false
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+
+    let props = config.find_window_properties(
+        "Slack",
+        "com.tinyspeck.slackmacgap",
+        "AXWindow",
+        "AXStandardWindow",
+    );
+    assert_eq!(props[0].disable_enhanced_ui, Some(false));
+}
+
+#[test]
+fn test_process_manage_rules_use_bundle_id() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.btt]
+bundle_id = "com.hegenberg.BetterTouchTool"
+title = ".*"
+manage = true
+
+[windows.helper]
+bundle_id = "com.example.Helper"
+title = ".*"
+manage = false
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+    let process = |bundle_id: Option<&str>| {
+        let mut process = crate::manager::MockProcessApi::new();
+        process
+            .expect_bundle_id()
+            .return_const(bundle_id.map(str::to_string));
+        process
+    };
+
+    let btt = process(Some("com.hegenberg.BetterTouchTool"));
+    assert!(config.should_force_manage_process(&btt));
+    assert!(!config.should_ignore_process(&btt));
+
+    let helper = process(Some("com.example.Helper"));
+    assert!(!config.should_force_manage_process(&helper));
+    assert!(config.should_ignore_process(&helper));
+
+    let unknown = process(None);
+    assert!(!config.should_force_manage_process(&unknown));
+    assert!(!config.should_ignore_process(&unknown));
+}
+
+#[test]
+fn test_scoped_manage_false_rule_keeps_process_observed() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[windows.prefs]
+bundle_id = "com.example.Editor"
+title = "Preferences"
+manage = false
+
+[windows.dialogs]
+bundle_id = "com.example.Viewer"
+title = ".*"
+subrole = "AXDialog"
+manage = false
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+    let process = |bundle_id: &str| {
+        let mut process = crate::manager::MockProcessApi::new();
+        process
+            .expect_bundle_id()
+            .return_const(Some(bundle_id.to_string()));
+        process.expect_name().return_const(String::new());
+        process
+    };
+
+    assert!(!config.should_ignore_process(&process("com.example.Editor")));
+    assert!(!config.should_ignore_process(&process("com.example.Viewer")));
+}
+
+#[test]
+fn test_ignore_apps_option() {
+    let input = r#"
+[options]
+ignore_apps = ["com.example.Helper", "Stickies"]
+
+[bindings]
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+    let process = |bundle_id: Option<&str>, name: &str| {
+        let mut process = crate::manager::MockProcessApi::new();
+        process
+            .expect_bundle_id()
+            .return_const(bundle_id.map(str::to_string));
+        process.expect_name().return_const(name.to_string());
+        process
+    };
+
+    assert!(config.should_ignore_process(&process(Some("com.example.Helper"), "Helper")));
+    assert!(config.should_ignore_process(&process(None, "Stickies")));
+    assert!(!config.should_ignore_process(&process(Some("com.apple.Terminal"), "Terminal")));
+}
+
+#[test]
+fn test_hot_corners_parse_commands() {
+    let input = r#"
+[options]
+
+[bindings]
+
+[hot_corners]
+top_right = { command = "window fullwidth", dwell = 150 }
+left = { command = "window focus west" }
+"#;
+    let config = Config::try_from(input).expect("config should parse");
+    assert!(config.has_hot_corners());
+
+    let (command, dwell) = config
+        .hot_corner(HotCornerRegion::TopRight)
+        .expect("top right corner should be bound");
+    assert!(matches!(command, Command::Window(Operation::FullWidth)));
+    assert_eq!(dwell, Duration::from_millis(150));
+
+    let (_, dwell) = config
+        .hot_corner(HotCornerRegion::Left)
+        .expect("left edge should be bound");
+    assert_eq!(dwell, Duration::from_millis(300));
+    assert!(config.hot_corner(HotCornerRegion::Bottom).is_none());
+
+    let input = "[options]\n\n[bindings]\n\n[hot_corners]\ntop = { command = \"window nope\" }\n";
+    assert!(Config::try_from(input).is_err());
+}
+
+#[test]
+fn test_restore_config_defaults() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+
+    assert!(config.restore_enabled());
+    assert_eq!(config.restore_startup_grace(), Duration::from_secs(2));
+    assert_eq!(
+        config.restore_missing_windows(),
+        MissingWindowBehavior::Ignore
+    );
 }
 
 #[test]
-#[allow(clippy::float_cmp)]
-fn test_grid_ratios() {
-    use regex::Regex;
+fn test_restore_config_explicit_values() {
+    let config = Config::try_from(
+        r#"
+[options]
 
-    let make = |grid: Option<&str>| WindowParams {
-        title: Regex::new(".*").unwrap(),
-        bundle_id: None,
-        floating: None,
-        manage: None,
-        index: None,
-        vertical_padding: None,
-        horizontal_padding: None,
-        dont_focus: None,
-        width: None,
-        grid: grid.map(Into::into),
-        border_radius: None,
-        bindings_passthrough: vec![],
-        parsed_passthrough: vec![],
-    };
+[restore]
+enabled = false
+startup_grace_ms = 750
+missing_windows = "ignore"
 
-    // Standard 2x2 grid, cell (1,1), span 1x1 → bottom-right quarter.
-    assert_eq!(
-        make(Some("2:2:1:1:1:1")).grid_ratios(),
-        Some((0.5, 0.5, 0.5, 0.5))
-    );
+[bindings]
+"#,
+    )
+    .expect("config should parse");
 
-    // 3x3 grid, cell (0,0), span 2x1 → top-left, 2/3 width, 1/3 height.
+    assert!(!config.restore_enabled());
+    assert_eq!(config.restore_startup_grace(), Duration::from_millis(750));
     assert_eq!(
-        make(Some("3:3:0:0:2:1")).grid_ratios(),
-        Some((0.0, 0.0, 2.0 / 3.0, 1.0 / 3.0))
+        config.restore_missing_windows(),
+        MissingWindowBehavior::Ignore
     );
+}
 
-    // Full screen: 1x1 grid, cell (0,0), span 1x1.
-    assert_eq!(
-        make(Some("1:1:0:0:1:1")).grid_ratios(),
-        Some((0.0, 0.0, 1.0, 1.0))
-    );
+#[test]
+fn test_restore_config_rejects_unsupported_missing_window_policy() {
+    let err = Config::try_from(
+        r#"
+[options]
 
-    // Invalid: too few parts.
-    assert_eq!(make(Some("2:2:1:1")).grid_ratios(), None);
+[restore]
+missing_windows = "reserve"
 
-    // Invalid: zero columns.
-    assert_eq!(make(Some("0:2:0:0:1:1")).grid_ratios(), None);
+[bindings]
+"#,
+    )
+    .expect_err("unsupported restore missing-window policy should fail");
 
-    // No grid set.
-    assert_eq!(make(None).grid_ratios(), None);
+    assert!(err.to_string().contains("unknown variant"));
 }
 
 #[test]
-fn test_parse_hex_color_valid() {
+fn test_on_event_config() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+    assert!(!config.has_event_hooks());
+    assert_eq!(config.on_event_command("window_focused"), None);
+
+    let config = Config::try_from(
+        r#"
+[options]
+
+[on_event]
+window_focused = "sketchybar --trigger paneru_focus"
+layout_changed = "sketchybar --trigger paneru_layout"
+
+[bindings]
+"#,
+    )
+    .expect("config should parse");
+
+    assert!(config.has_event_hooks());
     assert_eq!(
-        parse_hex_color("#89b4fa"),
-        (
-            f64::from(0x89) / 255.0,
-            f64::from(0xb4) / 255.0,
-            f64::from(0xfa) / 255.0
-        )
+        config.on_event_command("window_focused").as_deref(),
+        Some("sketchybar --trigger paneru_focus")
+    );
+    assert_eq!(config.on_event_command("space_changed"), None);
+    assert_eq!(
+        config.on_event_command("layout_changed").as_deref(),
+        Some("sketchybar --trigger paneru_layout")
     );
-    assert_eq!(parse_hex_color("#000000"), (0.0, 0.0, 0.0));
-    assert_eq!(parse_hex_color("#FFFFFF"), (1.0, 1.0, 1.0));
-    assert_eq!(parse_hex_color("#FF0000"), (1.0, 0.0, 0.0));
 }
 
 #[test]
-fn test_parse_hex_color_no_hash() {
+fn test_empty_workspace_option() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+    assert_eq!(config.empty_workspace(), EmptyWorkspaceBehavior::Stay);
+
+    let config =
+        Config::try_from("[options]\nempty_workspace = \"next_non_empty\"\n\n[bindings]\n")
+            .expect("config should parse");
     assert_eq!(
-        parse_hex_color("89b4fa"),
-        (
-            f64::from(0x89) / 255.0,
-            f64::from(0xb4) / 255.0,
-            f64::from(0xfa) / 255.0
-        )
+        config.empty_workspace(),
+        EmptyWorkspaceBehavior::NextNonEmpty
     );
-    assert_eq!(parse_hex_color("FF0000"), (1.0, 0.0, 0.0));
+
+    assert!(Config::try_from("[options]\nempty_workspace = \"close\"\n\n[bindings]\n").is_err());
 }
 
 #[test]
-fn test_parse_hex_color_invalid_length() {
-    // Short strings fall back to white.
-    assert_eq!(parse_hex_color("#FFF"), (1.0, 1.0, 1.0));
-    assert_eq!(parse_hex_color(""), (1.0, 1.0, 1.0));
-    assert_eq!(parse_hex_color("#FF"), (1.0, 1.0, 1.0));
+fn test_max_columns_options() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+    assert_eq!(config.max_columns(), None);
+    assert_eq!(config.max_columns_policy(), MaxColumnsPolicy::Stack);
+
+    let config = Config::try_from(
+        "[options]\nmax_columns = 4\nmax_columns_policy = \"overflow\"\n\n[bindings]\n",
+    )
+    .expect("config should parse");
+    assert_eq!(config.max_columns(), Some(4));
+    assert_eq!(config.max_columns_policy(), MaxColumnsPolicy::Overflow);
+
+    let config = Config::try_from("[options]\nmax_columns = 0\n\n[bindings]\n")
+        .expect("config should parse");
+    assert_eq!(config.max_columns(), None);
 }
 
 #[test]
-fn test_parse_hex_color_malformed_hex() {
-    // Non-hex digits fall back to 255 per channel.
-    assert_eq!(parse_hex_color("ZZZZZZ"), (1.0, 1.0, 1.0));
-    assert_eq!(parse_hex_color("GG0000"), (1.0, 0.0, 0.0));
+fn test_animation_easing_option() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+    assert_eq!(config.animation_easing(), AnimationEasing::EaseOut);
+
+    let config = Config::try_from("[options]\nanimation_easing = \"spring\"\n\n[bindings]\n")
+        .expect("config should parse");
+    assert_eq!(config.animation_easing(), AnimationEasing::Spring);
 }
 
 #[test]
-#[allow(clippy::float_cmp)]
-fn test_config_defaults() {
-    let config = Config::default();
-    assert_eq!(config.dim_inactive_opacity(), 0.0);
-    assert_eq!(config.dim_inactive_color(), (0.0, 0.0, 0.0));
-    assert!(!config.border_active_window());
-    assert_eq!(config.border_color(), (1.0, 1.0, 1.0));
-    assert_eq!(config.border_opacity(), 1.0);
-    assert_eq!(config.border_width(), 2.0);
-    assert_eq!(config.border_radius(), BorderRadiusOption::Auto);
-    assert_eq!(config.menubar_height(), None);
+fn test_log_file_options() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+    assert!(!config.log_file());
+    assert_eq!(config.log_format(), LogFormat::Compact);
+    assert_eq!(config.log_rotation(), LogRotation::Daily);
+
+    let config = Config::try_from(
+        "[options]\nlog_file = true\nlog_format = \"json\"\nlog_rotation = \"hourly\"\n\n[bindings]\n",
+    )
+    .expect("config should parse");
+    assert!(config.log_file());
+    assert_eq!(config.log_format(), LogFormat::Json);
+    assert_eq!(config.log_rotation(), LogRotation::Hourly);
+
+    assert!(Config::try_from("[options]\nlog_rotation = \"weekly\"\n\n[bindings]\n").is_err());
 }
 
 #[test]
-fn test_first_launch_creates_parseable_config_without_overwriting_it() {
-    let unique = format!(
-        "paneru-first-launch-{}-{}",
-        std::process::id(),
-        std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_nanos()
+fn test_external_move_policy_option() {
+    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+    assert_eq!(config.external_move_policy(), None);
+
+    let config =
+        Config::try_from("[options]\nexternal_move_policy = \"snap_back\"\n\n[bindings]\n")
+            .expect("config should parse");
+    assert_eq!(
+        config.external_move_policy(),
+        Some(ExternalMovePolicy::SnapBack)
     );
-    let directory = std::env::temp_dir().join(unique);
-    let path = directory.join("paneru.toml");
 
-    assert!(create_configuration_file_at(&path).unwrap());
-    Config::new(&path).expect("generated configuration should parse");
+    assert!(
+        Config::try_from("[options]\nexternal_move_policy = \"ignore\"\n\n[bindings]\n").is_err()
+    );
+}
 
-    let custom = "[options]\nauto_center = false\n\n[bindings]\n";
-    std::fs::write(&path, custom).unwrap();
-    assert!(!create_configuration_file_at(&path).unwrap());
-    assert_eq!(std::fs::read_to_string(&path).unwrap(), custom);
+#[test]
+fn test_display_column_widths() {
+    let config = Config::try_from(
+        r#"
+[options]
+preset_column_widths = [0.5, 1.0]
 
-    std::fs::remove_dir_all(directory).unwrap();
+[[options.display_column_widths]]
+min_display_width = 3000
+widths = [0.25, 0.33, 0.5]
+
+[[options.display_column_widths]]
+max_display_width = 1600
+widths = [1.0, "800px"]
+
+[bindings]
+"#,
+    )
+    .expect("config should parse");
+
+    assert_eq!(
+        config.preset_column_widths_for(5120, None),
+        vec![0.25, 0.33, 0.5]
+    );
+    assert_eq!(config.preset_column_widths_for(1600, None), vec![0.5, 1.0]);
+    assert_eq!(
+        config.preset_column_widths_for(1200, None)[0],
+        800.0 / 1200.0
+    );
+    // Displays between the buckets use the plain preset widths.
+    assert_eq!(config.preset_column_widths_for(2560, None), vec![0.5, 1.0]);
+
+    let pixels = Config::try_from(
+        "[options]\npreset_column_widths = [\"1280px\", 0.25]\n[displays.\"37D8832A-2D66-02CA-B9F7-8F30A301B230\"]\npreset_column_widths = [\"640px\"]\n[bindings]\n",
+    )
+    .expect("config should parse");
+    assert_eq!(pixels.preset_column_widths_for(2560, None), vec![0.25, 0.5]);
+    assert_eq!(
+        pixels.preset_column_widths_for(2560, Some("37D8832A-2D66-02CA-B9F7-8F30A301B230")),
+        vec![0.25]
+    );
+
+    assert!(
+        Config::try_from(
+            "[options]\n[[options.display_column_widths]]\nwidths = [\"wide\"]\n[bindings]\n"
+        )
+        .is_err()
+    );
 }
 
 #[test]
-fn test_window_rules_manage() {
-    let input = r#"
+fn test_per_display_overrides() {
+    let config = Config::try_from(
+        r#"
 [options]
+preset_column_widths = [0.5, 1.0]
+sliver_width = 5
+padding_left = 10
+padding_right = 10
+
+[displays."37D8832A-2D66-02CA-B9F7-8F30A301B230"]
+padding_left = 40
+sliver_width = 20
+sliver_height = 0.5
+preset_column_widths = [0.25, 0.5]
 
 [bindings]
+"#,
+    )
+    .expect("config should parse");
 
-[windows.btt_main]
-bundle_id = "com.hegenberg.BetterTouchTool"
-title = "BetterTouchTool"
-manage = true
+    let ultrawide = Some("37d8832a-2d66-02ca-b9f7-8f30a301b230");
+    assert_eq!(config.sliver_width_for(ultrawide), 20);
+    assert_eq!(config.sliver_height_for(ultrawide), 0.5);
+    assert_eq!(config.edge_padding_for(ultrawide, None), (0, 10, 0, 40));
+    assert_eq!(
+        config.preset_column_widths_for(5120, ultrawide),
+        vec![0.25, 0.5]
+    );
 
-[windows.btt_floating]
-bundle_id = "com.hegenberg.BetterTouchTool"
-title = "Screenshot.*"
-floating = true
-"#;
-    let config = Config::try_from(input).expect("config should parse");
+    // Other displays keep the global values.
+    let laptop = Some("00000000-0000-0000-0000-000000000000");
+    assert_eq!(config.sliver_width_for(laptop), 5);
+    assert_eq!(config.sliver_height_for(None), 1.0);
+    assert_eq!(config.edge_padding_for(laptop, None), (0, 10, 0, 10));
+    assert_eq!(
+        config.preset_column_widths_for(1440, laptop),
+        vec![0.5, 1.0]
+    );
+}
 
-    // Main window should match the manage rule.
-    let props = config.find_window_properties("BetterTouchTool", "com.hegenberg.BetterTouchTool");
-    assert_eq!(props.len(), 1);
-    assert_eq!(props[0].manage, Some(true));
+#[test]
+fn test_per_workspace_overrides() {
+    let config = Config::try_from(
+        r#"
+[options]
+auto_center = false
+animation_speed = 12.0
+padding_left = 10
 
-    // Screenshot window matches the floating rule.
-    let props = config.find_window_properties("Screenshot 1", "com.hegenberg.BetterTouchTool");
-    assert_eq!(props.len(), 1);
-    assert_eq!(props[0].floating, Some(true));
+[displays."37D8832A-2D66-02CA-B9F7-8F30A301B230"]
+padding_left = 40
+padding_top = 8
+
+[workspaces.3]
+padding_left = 100
+auto_center = true
+animation_speed = 20.0
+
+[bindings]
+"#,
+    )
+    .expect("config should parse");
+
+    let ultrawide = Some("37D8832A-2D66-02CA-B9F7-8F30A301B230");
+    assert!(config.auto_center_on(Some(3)));
+    assert_eq!(config.animation_speed_on(Some(3)), 20.0);
+    // Workspace padding wins over the display padding, unset sides fall back to it.
+    assert_eq!(config.edge_padding_for(ultrawide, Some(3)), (8, 0, 0, 100));
+
+    assert!(!config.auto_center_on(Some(4)));
+    assert_eq!(config.animation_speed_on(Some(4)), 12.0);
+    assert_eq!(config.edge_padding_for(None, Some(4)), (0, 0, 0, 10));
 }
 
 #[test]
-fn test_restore_config_defaults() {
-    let config = Config::try_from("[options]\n\n[bindings]\n").expect("config should parse");
+fn test_scroll_policy() {
+    let parse = |options: &str| {
+        Config::try_from(format!("[options]\n{options}\n\n[bindings]\n").as_str())
+            .expect("config should parse")
+    };
 
-    assert!(config.restore_enabled());
-    assert_eq!(config.restore_startup_grace(), Duration::from_secs(2));
+    assert_eq!(parse("").scroll_policy_on(None), ScrollPolicy::Minimal);
     assert_eq!(
-        config.restore_missing_windows(),
-        MissingWindowBehavior::Ignore
+        parse("scroll_policy = \"edge\"").scroll_policy_on(None),
+        ScrollPolicy::Edge
     );
+
+    let config = parse("scroll_policy = \"center\"");
+    assert!(config.auto_center());
+    assert_eq!(config.scroll_policy_on(None), ScrollPolicy::Center);
+
+    // auto_center keeps working and wins over the policy.
+    let config = parse("auto_center = true\nscroll_policy = \"edge\"");
+    assert_eq!(config.scroll_policy_on(None), ScrollPolicy::Center);
+    let config = parse("auto_center = false\nscroll_policy = \"center\"");
+    assert_eq!(config.scroll_policy_on(None), ScrollPolicy::Minimal);
 }
 
 #[test]
-fn test_restore_config_explicit_values() {
+fn test_gap_for_display() {
     let config = Config::try_from(
         r#"
 [options]
 
-[restore]
-enabled = false
-startup_grace_ms = 750
-missing_windows = "ignore"
+[padding]
+gap = 8
+
+[displays."37D8832A-2D66-02CA-B9F7-8F30A301B230"]
+gap = 20
 
 [bindings]
 "#,
     )
     .expect("config should parse");
 
-    assert!(!config.restore_enabled());
-    assert_eq!(config.restore_startup_grace(), Duration::from_millis(750));
     assert_eq!(
-        config.restore_missing_windows(),
-        MissingWindowBehavior::Ignore
+        config.gap_for(Some("37D8832A-2D66-02CA-B9F7-8F30A301B230")),
+        20
     );
+    assert_eq!(config.gap_for(None), 8);
+    assert_eq!(Config::default().gap_for(None), 0);
 }
 
 #[test]
-fn test_restore_config_rejects_unsupported_missing_window_policy() {
-    let err = Config::try_from(
+fn test_reset_options_restores_file_values() {
+    const UUID: &str = "37D8832A-2D66-02CA-B9F7-8F30A301B230";
+    let mut config = Config::try_from(
         r#"
 [options]
 
-[restore]
-missing_windows = "reserve"
+[padding]
+gap = 8
 
 [bindings]
 "#,
     )
-    .expect_err("unsupported restore missing-window policy should fail");
+    .expect("config should parse");
 
-    assert!(err.to_string().contains("unknown variant"));
+    let gap = format!("displays.{UUID}.gap");
+    config
+        .set_options(&[
+            (gap.clone(), "14".into()),
+            ("padding.top".into(), "4".into()),
+        ])
+        .unwrap();
+    assert_eq!(config.gap_for(Some(UUID)), 14);
+
+    let changes = config.reset_options(std::slice::from_ref(&gap)).unwrap();
+    assert_eq!(changes.sections, vec!["displays".to_string()]);
+    assert_eq!(config.gap_for(Some(UUID)), 8);
+    // Overrides of other keys survive the reset.
+    assert_eq!(config.edge_padding().0, 4);
+    assert_eq!(
+        config.runtime_overrides(),
+        vec![("padding.top".to_string(), "4".to_string())]
+    );
 }
 
 #[test]
@@ -0,0 +1,23 @@
+use serde::Deserialize;
+
+use super::ColumnWidth;
+
+/// Options overridden on a single display, keyed by the display UUID in `[displays."<uuid>"]`.
+/// Unset values fall back to the global `[options]` and `[padding]` settings.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct DisplayOptions {
+    /// Padding applied at the edges of this display (in pixels).
+    pub padding_top: Option<u16>,
+    pub padding_bottom: Option<u16>,
+    pub padding_left: Option<u16>,
+    pub padding_right: Option<u16>,
+    /// Gap between the columns and stacked windows on this display (in pixels).
+    pub gap: Option<u16>,
+
+    /// Part of off-screen windows kept visible on this display.
+    pub sliver_height: Option<f64>,
+    pub sliver_width: Option<u16>,
+
+    /// Preset column widths used on this display, as ratios of its width or pixels (`"800px"`).
+    pub preset_column_widths: Option<Vec<ColumnWidth>>,
+}
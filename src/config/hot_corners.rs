@@ -0,0 +1,31 @@
+use serde::Deserialize;
+
+use crate::commands::Command;
+
+/// A screen corner or edge which can trigger a command.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum HotCornerRegion {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Top,
+    Bottom,
+    Left,
+    Right,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct HotCornerOptions {
+    /// The command to run, in the same format as `paneru send-cmd`, e.g. "window fullwidth".
+    pub command: String,
+
+    /// How long the pointer has to rest in the corner before the command runs, in
+    /// milliseconds. Default: 300.
+    pub dwell: Option<u64>,
+
+    /// The parsed `command`, resolved when the configuration is loaded.
+    #[serde(skip)]
+    pub parsed: Option<Command>,
+}
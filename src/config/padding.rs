@@ -8,4 +8,7 @@ pub struct PaddingOptions {
     pub bottom: Option<u16>,
     pub left: Option<u16>,
     pub right: Option<u16>,
+    /// Gap between adjacent columns and between the windows of a stack (in pixels).
+    /// Default: 0.
+    pub gap: Option<u16>,
 }
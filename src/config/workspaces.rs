@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+/// Layout options overridden on a single workspace, keyed by the space ID in `[workspaces.<id>]`.
+/// Unset values fall back to the display and global settings.
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct WorkspaceOptions {
+    /// Padding applied at the edges of the display while this workspace is laid out (in pixels).
+    pub padding_top: Option<u16>,
+    pub padding_bottom: Option<u16>,
+    pub padding_left: Option<u16>,
+    pub padding_right: Option<u16>,
+
+    /// Center the focused window on this workspace.
+    pub auto_center: Option<bool>,
+
+    /// Speed of window animations on this workspace.
+    pub animation_speed: Option<f64>,
+}
@@ -9,8 +9,9 @@ use bevy::ecs::lifecycle::RemovedComponents;
 use bevy::ecs::message::Messages;
 use bevy::ecs::query::{Added, Changed, With};
 use bevy::ecs::resource::Resource;
-use bevy::ecs::schedule::common_conditions::{not, resource_exists};
+use bevy::ecs::schedule::common_conditions::{not, resource_changed, resource_exists};
 use bevy::ecs::system::{Commands, EntityCommands, Query, Res, SystemId};
+use bevy::ecs::world::EntityWorldMut;
 use bevy::prelude::Event as BevyEvent;
 use bevy::tasks::Task;
 use bevy::time::Timer;
@@ -18,26 +19,29 @@ use bevy::time::common_conditions::on_timer;
 use bevy::time::{Time, Virtual};
 use bevy::{
     app::Update,
-    ecs::{component::Component, entity::Entity, schedule::IntoScheduleConfigs},
+    ecs::{component::Component, entity::Entity, schedule::IntoScheduleConfigs, world::World},
+    math::IRect,
 };
 use derive_more::{Deref, DerefMut};
-use tracing::{Level, instrument};
+use tracing::{Level, instrument, warn};
 
-use crate::commands::register_commands;
-use crate::config::{CONFIGURATION_FILE, Config, WindowParams};
-use crate::ecs::layout::LayoutStrip;
-use crate::ecs::state::PaneruState;
+use crate::announcer::Announcer;
+use crate::commands::{InsertNext, JumpBadges, register_commands};
+use crate::config::{CONFIGURATION_FILE, Config, DisplaySelector, WindowParams};
+use crate::ecs::layout::{LayoutStrip, StackTabBars};
+use crate::ecs::state::{PaneruState, RuntimeState};
 use crate::errors::Result;
 use crate::events::{Event, EventSender};
 use crate::manager::{
     Application, Origin, ProcessApi, Size, Window, WindowManager, WindowManagerApi, WindowManagerOS,
 };
 use crate::menubar::MenuBarManager;
-use crate::overlay::{FlashMessageManager, OverlayManager};
+use crate::overlay::{FlashMessageManager, JumpBadgesManager, OverlayManager, StackTabsManager};
 use crate::platform::{Modifiers, PlatformCallbacks, WinID, WorkspaceId};
 
 pub mod display;
 pub mod focus;
+pub mod journal;
 pub mod layout;
 pub mod mouse;
 pub mod params;
@@ -58,6 +62,7 @@ pub mod workspace;
 #[allow(clippy::too_many_lines)]
 pub fn register_systems(app: &mut bevy::app::App) {
     const LOW_POWER_MODE_CHECK_SEC: u64 = 60;
+    const UNRESPONSIVE_PROBE_SEC: u64 = 2;
 
     let not_swiping = |scrolling: Query<&Scrolling, With<ActiveWorkspaceMarker>>| {
         scrolling
@@ -87,6 +92,9 @@ pub fn register_systems(app: &mut bevy::app::App) {
     let native_tabs_enabled =
         |config: Option<Res<Config>>| config.is_none_or(|config| config.native_tabs_enabled());
 
+    app.world_mut()
+        .get_resource_or_init::<Wakeup>()
+        .every(Duration::from_secs(UNRESPONSIVE_PROBE_SEC));
     app.add_systems(
         Startup,
         (systems::gather_displays, systems::gather_initial_processes).chain(),
@@ -114,14 +122,18 @@ pub fn register_systems(app: &mut bevy::app::App) {
             systems::add_launched_process,
             systems::add_launched_application,
             systems::fresh_marker_cleanup,
+            systems::window_title_changed,
             systems::timeout_ticker,
             systems::retry_front_switch,
+            systems::probe_unresponsive_windows
+                .run_if(on_timer(Duration::from_secs(UNRESPONSIVE_PROBE_SEC))),
             systems::update_low_power_state
                 .run_if(resource_exists::<LowPowerMode>)
                 .run_if(on_timer(Duration::from_secs(LOW_POWER_MODE_CHECK_SEC))),
             (
                 systems::window_resized_update_frame,
                 systems::window_moved_update_frame,
+                systems::apply_external_move_policy,
             )
                 .chain()
                 .run_if(not_swiping),
@@ -129,22 +141,21 @@ pub fn register_systems(app: &mut bevy::app::App) {
             restore::tick_restore_grace,
             state::periodic_state_save.run_if(on_timer(Duration::from_secs(300))),
             state::cleanup_on_exit,
+            state::restore_focus_history
+                .run_if(resource_exists::<RuntimeState>)
+                .run_if(not(resource_exists::<Initializing>)),
         ),
     );
     app.add_systems(
         PostUpdate,
         (
             (
-                systems::animate_entities,
-                systems::commit_window_position.run_if(not(resource_exists::<Initializing>)),
+                (systems::animate_entities, systems::animate_resize_entities),
+                systems::commit_window_frames.run_if(not(resource_exists::<Initializing>)),
+                systems::watch_window_convergence.run_if(not(resource_exists::<Initializing>)),
                 systems::verify_window_position.run_if(not(resource_exists::<Initializing>)),
             )
                 .chain(),
-            (
-                systems::animate_resize_entities,
-                systems::commit_window_size.run_if(not(resource_exists::<Initializing>)),
-            )
-                .chain(),
             (
                 systems::update_overlays
                     .after(systems::animate_entities)
@@ -152,6 +163,10 @@ pub fn register_systems(app: &mut bevy::app::App) {
                     .run_if(dimming_enabled)
                     .run_if(overlay_dirty),
                 systems::update_flash_messages,
+                systems::update_stack_tabs.run_if(
+                    resource_changed::<StackTabBars>.or(resource_changed::<MissionControlActive>),
+                ),
+                systems::update_jump_badges.run_if(resource_changed::<JumpBadges>),
             )
                 .chain(),
             crate::menubar::update_menu_bar,
@@ -161,6 +176,7 @@ pub fn register_systems(app: &mut bevy::app::App) {
 
 /// Registers all the event triggers for the window manager.
 pub fn register_triggers(app: &mut bevy::app::App) {
+    app.init_resource::<NextInsertion>();
     app.add_systems(
         Update,
         (
@@ -171,6 +187,7 @@ pub fn register_triggers(app: &mut bevy::app::App) {
             triggers::dispatch_application_messages,
             triggers::window_destroyed_trigger,
             triggers::refresh_configuration_trigger,
+            triggers::config_reloaded_trigger,
             triggers::theme_change_trigger,
             triggers::window_resize_verifier,
         ),
@@ -189,6 +206,15 @@ pub fn register_triggers(app: &mut bevy::app::App) {
 #[derive(Component)]
 pub struct FocusedMarker;
 
+/// Records when a window last gained focus, used to find the least recently focused column.
+#[derive(Component)]
+pub struct FocusedAt(pub Instant);
+
+/// The frame a window had when Paneru first saw it, before the layout moved it anywhere.
+/// Restored on exit, on a panic and with `window restore-original`.
+#[derive(Component)]
+pub struct OriginalFrame(pub IRect);
+
 #[derive(Component)]
 pub struct ActiveWorkspaceMarker;
 
@@ -206,17 +232,64 @@ pub struct ActiveDisplayMarker;
 #[derive(Component)]
 pub struct FreshMarker;
 
+/// Marks windows created while Paneru runs, until the next focus change. Lets
+/// `mouse_follows_new_window` tell the first focus of a new window apart from later ones.
+#[derive(Component)]
+pub struct NewWindowMarker;
+
 /// Marker component used to gather existing processes and windows during initialization.
 #[derive(Component)]
 pub struct ExistingMarker;
 
+/// Marks a window matched by a window rule with `animate = false`. Its repositions and resizes
+/// are applied instantly.
+#[derive(Component)]
+pub struct NoAnimationMarker;
+
+/// The opacity set for a window by the `opacity` option of a window rule.
+#[derive(Component)]
+pub struct WindowOpacity(pub f32);
+
 /// Component representing a request to reposition a window.
-#[derive(Component, Debug, Deref, DerefMut)]
-pub struct RepositionMarker(pub Origin);
+/// The start position is captured on the first animation frame, so that eased curves can
+/// interpolate over the whole move.
+#[derive(Component, Debug)]
+pub struct RepositionMarker {
+    pub target: Origin,
+    pub start: Option<Origin>,
+    /// Seconds since the animation started.
+    pub elapsed: f64,
+}
+
+impl RepositionMarker {
+    pub fn new(target: Origin) -> Self {
+        Self {
+            target,
+            start: None,
+            elapsed: 0.0,
+        }
+    }
+}
 
 /// Component representing a request to resize a window.
-#[derive(Component, Debug, Deref, DerefMut)]
-pub struct ResizeMarker(pub Size);
+/// Like `RepositionMarker`, it records where the animation started and how long it ran.
+#[derive(Component, Debug)]
+pub struct ResizeMarker {
+    pub target: Size,
+    pub start: Option<Size>,
+    /// Seconds since the animation started.
+    pub elapsed: f64,
+}
+
+impl ResizeMarker {
+    pub fn new(target: Size) -> Self {
+        Self {
+            target,
+            start: None,
+            elapsed: 0.0,
+        }
+    }
+}
 
 /// Marker component indicating that windows around the marked entity need to be reshuffled.
 #[derive(Component)]
@@ -280,6 +353,95 @@ pub struct FullWidthMarker {
     pub width_ratio: f64,
 }
 
+/// Marks the windows of a column pinned by `Operation::Pin` to one edge of the viewport. They
+/// stay fully visible there while the rest of the strip scrolls past.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum PinnedMarker {
+    Left,
+    Right,
+}
+
+/// The smallest height the application of a window accepts. Learned when a resize ends taller
+/// than requested, so stacks stop packing the window below it.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MinimumHeight(pub i32);
+
+/// Width bounds in pixels from the `min_width` / `max_width` window rules, resolved against
+/// the display the window was first managed on.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct WidthLimits {
+    pub min: Option<i32>,
+    pub max: Option<i32>,
+}
+
+impl WidthLimits {
+    /// Clamps `width` into the bounds. The minimum wins if the bounds overlap.
+    pub fn clamp(&self, width: i32) -> i32 {
+        let width = self.max.map_or(width, |max| width.min(max));
+        self.min.map_or(width, |min| width.max(min))
+    }
+}
+
+/// Marks the window set by `Operation::SetInsertPoint`. The next new window in its strip opens
+/// to the right of it, and the marker is removed.
+#[derive(Component)]
+pub struct InsertPointMarker;
+
+/// The one-shot directive armed by `Operation::InsertNext`. The next created window takes it,
+/// which clears it again.
+#[derive(Default, Resource)]
+pub struct NextInsertion(pub Option<InsertNext>);
+
+/// Carries the `Operation::InsertNext` directive taken by a new window until it is placed.
+#[derive(Component)]
+pub struct InsertNextMarker(pub InsertNext);
+
+/// Marks a floating window that follows the active space of its display, so it stays visible
+/// on every workspace. Set by the `sticky` window rule or toggled with `Operation::Sticky`.
+#[derive(Component)]
+pub struct StickyMarker;
+
+/// Marks a window which looked like a dialog, sheet or utility panel when it was spawned, so
+/// `auto_float_dialogs` floats it. Only checked while that option is enabled.
+#[derive(Component)]
+pub struct DialogMarker;
+
+/// Marks a floating picture-in-picture window, set by the `pip` window rule. Direction swaps
+/// throw it between the corners of its display instead of being ignored.
+#[derive(Component)]
+pub struct PipMarker;
+
+/// Marks a window temporarily expanded to the whole viewport by `Operation::Monocle`.
+/// Holds what is needed to put the window back where it was when the mode is toggled off.
+#[derive(Component, Debug)]
+pub struct MonocleMarker {
+    /// The window frame before it was expanded.
+    pub frame: IRect,
+    /// The strip column index the window occupied.
+    pub index: usize,
+    /// Another window of the stack the window was part of, and the window's row in that stack.
+    pub stacked_with: Option<(Entity, usize)>,
+}
+
+/// Marks the window centered by `Operation::Zen`. While present, the other columns of its strip
+/// are laid out fully off-screen.
+#[derive(Component, Debug)]
+pub struct ZenMarker {
+    /// The window frame before zen mode was entered.
+    pub frame: IRect,
+    /// The strip offset before zen mode was entered.
+    pub offset: Origin,
+}
+
+/// Marks a strip zoomed out by `Operation::Overview`. Its layout is frozen until the overview
+/// ends.
+#[derive(Component, Debug)]
+pub struct OverviewMarker;
+
+/// The size a window had before `Operation::Overview` shrank it.
+#[derive(Component, Debug)]
+pub struct OverviewFrame(pub Size);
+
 /// Enum component indicating the unmanaged state of a window.
 #[derive(Component, Debug)]
 pub enum Unmanaged {
@@ -289,13 +451,21 @@ pub enum Unmanaged {
     Minimized,
     /// The window is hidden.
     Hidden,
+    /// The window's application stopped honouring reposition requests. The window is kept out
+    /// of the layout until it follows a reposition again.
+    Unresponsive,
 }
 
+/// Remembers where a window sat in the layout before it was minimized or floated, so it can be
+/// put back into the same slot once it is managed again.
 #[derive(Clone, Component, Copy, Debug)]
 pub struct PreviousManagedStrip {
     pub workspace_id: WorkspaceId,
     pub virtual_index: u32,
     pub index: usize,
+    /// The width ratio the window had while tiled. Only kept for floated windows, whose size
+    /// usually changes while they float.
+    pub width_ratio: Option<f64>,
 }
 
 /// Wrapper component for a `ProcessApi` trait object, enabling dynamic dispatch for process-related operations within Bevy.
@@ -386,6 +556,16 @@ impl RefreshWindowSizes {
     }
 }
 
+/// Marks a strip whose display changed its width. The next `RefreshWindowSizes` pass sizes the
+/// windows from their `WidthRatio` instead of keeping whatever width macOS left them with.
+#[derive(Component)]
+pub struct RescaleWidthsMarker;
+
+/// Marks a managed window which was moved by another tool, awaiting the configured
+/// `external_move_policy`.
+#[derive(Component)]
+pub struct ExternallyMovedMarker;
+
 #[derive(Component)]
 pub struct VerifyWindowPosition {
     remaining: u8,
@@ -404,9 +584,87 @@ impl VerifyWindowPosition {
     }
 }
 
+/// Counts the resizes re-issued to a window which did not end up at its requested size, e.g.
+/// because the application applied the request late. Removed once the window converges or its
+/// actual size is adopted.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct ResizeRetries(pub u8);
+
+impl ResizeRetries {
+    pub const LIMIT: u8 = 2;
+}
+
+/// Counts consecutive `VerifyWindowPosition` rounds in which a window did not end up at its
+/// layout position. Removed as soon as the window converges.
+#[derive(Clone, Component, Debug, Default)]
+pub struct ConvergenceFailures {
+    count: u8,
+    last_origin: Option<Origin>,
+}
+
+impl ConvergenceFailures {
+    const LIMIT: u8 = 3;
+
+    /// Records a failed verification round with the origin the window was found at, or `None`
+    /// if its frame could not be read. A window which moved somewhere else since the previous
+    /// failure (e.g. clamped by macOS) is still responding, so the count restarts.
+    ///
+    /// # Returns
+    ///
+    /// `true` once the window has stalled for enough consecutive rounds to be considered unresponsive.
+    pub fn record(&mut self, origin: Option<Origin>) -> bool {
+        if origin.is_some() && self.last_origin.is_some() && origin != self.last_origin {
+            self.count = 0;
+        }
+        self.count = self.count.saturating_add(1);
+        self.last_origin = origin.or(self.last_origin);
+        self.count >= Self::LIMIT
+    }
+}
+
 #[derive(Deref, DerefMut, Resource)]
 pub struct LowPowerMode(pub bool);
 
+/// Bounds how long `pump_events` may sleep while idle. `on_timer` pollers register their
+/// interval once, and systems waiting for a dwell to pass request a wakeup every frame.
+#[derive(Default, Resource)]
+pub struct Wakeup {
+    /// The shortest interval of the registered periodic systems.
+    interval: Option<Duration>,
+    /// The earliest pending dwell deadline, consumed by the next sleep.
+    deadline: Option<Instant>,
+}
+
+impl Wakeup {
+    /// Registers a system polled every `interval`.
+    pub fn every(&mut self, interval: Duration) {
+        self.interval = Some(
+            self.interval
+                .map_or(interval, |current| current.min(interval)),
+        );
+    }
+
+    /// Requests a wakeup no later than `deadline`.
+    pub fn at(&mut self, deadline: Instant) {
+        self.deadline = Some(
+            self.deadline
+                .map_or(deadline, |current| current.min(deadline)),
+        );
+    }
+
+    /// Returns the longest sleep that neither misses a periodic system nor a pending deadline.
+    pub fn take_limit(&mut self) -> Option<Duration> {
+        let deadline = self
+            .deadline
+            .take()
+            .map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (self.interval, deadline) {
+            (Some(interval), Some(deadline)) => Some(interval.min(deadline)),
+            (interval, deadline) => interval.or(deadline),
+        }
+    }
+}
+
 #[derive(Resource)]
 pub struct SystemTheme {
     pub is_dark: bool,
@@ -471,8 +729,16 @@ pub trait SpawnCommandsExt {
 impl SpawnCommandsExt for Commands<'_, '_> {
     #[instrument(level = Level::TRACE, skip(self))]
     fn reposition_entity(&mut self, entity: Entity, origin: Origin) {
+        // Retargeting a running animation keeps its start and elapsed time, so eased curves
+        // carry on smoothly while the target keeps moving (drags, swipes, strip scrolling).
         if let Ok(mut entity_commands) = self.get_entity(entity) {
-            entity_commands.try_insert(RepositionMarker(origin));
+            entity_commands.queue_silenced(move |mut entity: EntityWorldMut| {
+                if let Some(mut marker) = entity.get_mut::<RepositionMarker>() {
+                    marker.target = origin;
+                } else {
+                    entity.insert(RepositionMarker::new(origin));
+                }
+            });
         }
     }
 
@@ -482,7 +748,13 @@ impl SpawnCommandsExt for Commands<'_, '_> {
             return;
         }
         if let Ok(mut entity_commands) = self.get_entity(entity) {
-            entity_commands.try_insert(ResizeMarker(size));
+            entity_commands.queue_silenced(move |mut entity: EntityWorldMut| {
+                if let Some(mut marker) = entity.get_mut::<ResizeMarker>() {
+                    marker.target = size;
+                } else {
+                    entity.insert(ResizeMarker::new(size));
+                }
+            });
         }
     }
 
@@ -532,9 +804,23 @@ impl SpawnCommandsExt for Commands<'_, '_> {
     }
 }
 
+/// Moves every window back to its `OriginalFrame`. Called when the app unwinds from a panic,
+/// where the regular exit cleanup systems never get to run.
+pub fn restore_original_frames(world: &mut World) {
+    let mut windows = world.query::<(&mut Window, &OriginalFrame)>();
+    for (mut window, OriginalFrame(frame)) in windows.iter_mut(world) {
+        window.resize(frame.size());
+        window.reposition(frame.min);
+    }
+}
+
 pub fn setup_bevy_app(sender: EventSender, receiver: Receiver<Event>) -> Result<BevyApp> {
     let window_manager: Box<dyn WindowManagerApi> = Box::new(WindowManagerOS::new(sender.clone()));
-    let watcher = window_manager.setup_config_watcher(CONFIGURATION_FILE.as_path())?;
+    // Without a configuration file there is nothing to watch, the built-in defaults are used.
+    let watcher = window_manager
+        .setup_config_watcher(CONFIGURATION_FILE.as_path())
+        .inspect_err(|err| warn!("not watching the configuration: {err}"))
+        .ok();
 
     let mut app = BevyApp::new();
 
@@ -549,13 +835,13 @@ pub fn setup_bevy_app(sender: EventSender, receiver: Receiver<Event>) -> Result<
         .insert_resource(MissionControlActive(false))
         .insert_resource(FocusFollowsMouse(None))
         .insert_resource(Initializing)
-        .insert_non_send_resource(watcher)
         .add_plugins(mouse::MouseEventsPlugin)
         .add_plugins(scroll::ScrollEventsPlugin)
         .add_plugins(workspace::WorkspaceEventsPlugin)
         .add_plugins(layout::LayoutEventsPlugin)
         .add_plugins(focus::FocusEventsPlugin)
         .add_plugins(display::DisplayEventsPlugin)
+        .add_plugins(journal::JournalPlugin)
         .add_plugins((register_triggers, register_systems, register_commands));
 
     let menu_events = sender.clone();
@@ -564,18 +850,30 @@ pub fn setup_bevy_app(sender: EventSender, receiver: Receiver<Event>) -> Result<
     let mtm = platform_callbacks.main_thread_marker;
     let overlay_manager = OverlayManager::new(mtm);
     let flash_message_manager = FlashMessageManager::new(mtm);
+    let stack_tabs_manager = StackTabsManager::new(mtm);
+    let jump_badges_manager = JumpBadgesManager::new(mtm);
+    let announcer = Announcer::new(mtm);
     let menu_bar_manager = MenuBarManager::new(mtm, menu_events);
     app.insert_non_send_resource(platform_callbacks)
         .insert_non_send_resource(overlay_manager)
         .insert_non_send_resource(flash_message_manager)
+        .insert_non_send_resource(stack_tabs_manager)
+        .insert_non_send_resource(jump_badges_manager)
+        .insert_non_send_resource(announcer)
         .insert_non_send_resource(menu_bar_manager)
         .insert_non_send_resource(receiver);
+    if let Some(watcher) = watcher {
+        app.insert_non_send_resource(watcher);
+    }
 
     if let Some(previous_state) =
         PaneruState::load_from_file(&PaneruState::default_state_file_path())
     {
         app.insert_resource(previous_state);
     }
+    if let Some(runtime_state) = RuntimeState::load_from_file(&RuntimeState::default_file_path()) {
+        app.insert_resource(runtime_state);
+    }
 
     // Do not insert this in mocks.
     app.insert_resource(LowPowerMode(false));
@@ -583,22 +881,76 @@ pub fn setup_bevy_app(sender: EventSender, receiver: Receiver<Event>) -> Result<
     Ok(app)
 }
 
+/// Subroles of dialogs and utility panels.
+const DIALOG_SUBROLES: [&str; 3] = ["AXDialog", "AXSystemDialog", "AXFloatingWindow"];
+/// Windows smaller than this in both dimensions are popups rather than documents.
+const TINY_WINDOW_SIZE: Size = Size::new(240, 160);
+
+/// Recognizes dialogs, sheets and utility panels by their subrole, a missing close button, a
+/// fixed or a tiny size.
+fn looks_like_dialog(window: &Window) -> bool {
+    let size = window.frame().size();
+    window.child_role().unwrap_or(false)
+        || window
+            .subrole()
+            .is_ok_and(|subrole| DIALOG_SUBROLES.contains(&subrole.as_str()))
+        || !window.has_close_button()
+        || !window.is_resizable()
+        || (size.x > 0 && size.y > 0 && size.cmplt(TINY_WINDOW_SIZE).all())
+}
+
 struct WindowProperties {
     params: Vec<WindowParams>,
+    /// Set when `auto_float_dialogs` is enabled and no rule decides the floating state, so
+    /// `floating` falls back to the `DialogMarker` of the window.
+    auto_float: bool,
 }
 
 impl WindowProperties {
     pub fn new(app: &Application, window: &Window, config: &Config) -> Self {
         let bundle_id = app.bundle_id().unwrap_or_default();
         let title = window.title().unwrap_or_default();
-        let params = config.find_window_properties(&title, &bundle_id);
-        Self { params }
+        let role = window.role().unwrap_or_default();
+        let subrole = window.subrole().unwrap_or_default();
+        let params = config.find_window_properties(&title, &bundle_id, &role, &subrole);
+        // Rules setting `floating` either way or forcing `manage` take precedence.
+        let auto_float = config.auto_float_dialogs()
+            && params
+                .iter()
+                .all(|props| props.floating.is_none() && props.manage != Some(true));
+        Self { params, auto_float }
+    }
+
+    /// Sticky and picture-in-picture windows are always floating. Without a rule, windows
+    /// carrying a `DialogMarker` (`dialog`) float too.
+    pub fn floating(&self, dialog: bool) -> bool {
+        self.sticky()
+            || self.pip()
+            || self
+                .params
+                .iter()
+                .find_map(|props| props.floating)
+                .unwrap_or(self.auto_float && dialog)
+    }
+
+    pub fn disable_enhanced_ui(&self) -> bool {
+        self.params
+            .iter()
+            .find_map(|props| props.disable_enhanced_ui)
+            .unwrap_or(true)
     }
 
-    pub fn floating(&self) -> bool {
+    pub fn sticky(&self) -> bool {
         self.params
             .iter()
-            .find_map(|props| props.floating)
+            .find_map(|props| props.sticky)
+            .unwrap_or(false)
+    }
+
+    pub fn pip(&self) -> bool {
+        self.params
+            .iter()
+            .find_map(|props| props.pip)
             .unwrap_or(false)
     }
 
@@ -606,6 +958,15 @@ impl WindowProperties {
         self.params.iter().find_map(|props| props.index)
     }
 
+    /// Returns whether the window is stacked into the focused column of its application, falling
+    /// back to the global `auto_stack_same_app` option.
+    pub fn auto_stack_same_app(&self, config: &Config) -> bool {
+        self.params
+            .iter()
+            .find_map(|props| props.auto_stack_same_app)
+            .unwrap_or_else(|| config.auto_stack_same_app())
+    }
+
     pub fn dont_focus(&self) -> bool {
         self.params
             .iter()
@@ -617,6 +978,24 @@ impl WindowProperties {
         self.params.iter().find_map(|p| p.border_radius)
     }
 
+    pub fn animate(&self) -> bool {
+        self.params
+            .iter()
+            .find_map(|props| props.animate)
+            .unwrap_or(true)
+    }
+
+    pub fn display(&self) -> Option<DisplaySelector> {
+        self.params.iter().find_map(|props| props.display.clone())
+    }
+
+    pub fn opacity(&self) -> Option<f32> {
+        self.params
+            .iter()
+            .find_map(|props| props.opacity)
+            .map(|opacity| opacity.clamp(0.0, 1.0))
+    }
+
     pub fn grid_ratios(&self) -> Option<(f64, f64, f64, f64)> {
         self.params.iter().find_map(WindowParams::grid_ratios)
     }
@@ -632,6 +1011,16 @@ impl WindowProperties {
         self.params.iter().find_map(|props| props.width)
     }
 
+    /// Resolves the `min_width` / `max_width` rules against `display_width`.
+    pub fn width_limits(&self, display_width: i32) -> Option<WidthLimits> {
+        let min = self.params.iter().find_map(|props| props.min_width);
+        let max = self.params.iter().find_map(|props| props.max_width);
+        (min.is_some() || max.is_some()).then(|| WidthLimits {
+            min: min.map(|limit| limit.pixels(display_width)),
+            max: max.map(|limit| limit.pixels(display_width)),
+        })
+    }
+
     pub fn vertical_padding(&self) -> i32 {
         self.params
             .iter()
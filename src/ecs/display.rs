@@ -6,7 +6,8 @@ use bevy::ecs::lifecycle::Add;
 use bevy::ecs::message::{MessageReader, MessageWriter};
 use bevy::ecs::observer::On;
 use bevy::ecs::query::{Has, With};
-use bevy::ecs::system::{Commands, Local, NonSend, Query, Res};
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Commands, Local, NonSend, Query, Res, ResMut};
 use bevy::math::IRect;
 use bevy::platform::collections::HashSet;
 use objc2_app_kit::NSScreen;
@@ -18,21 +19,40 @@ use tracing::{Level, debug, error, instrument, warn};
 
 use crate::config::Config;
 use crate::ecs::layout::LayoutStrip;
+use crate::ecs::params::Windows;
+use crate::ecs::restore::SessionRestore;
+use crate::ecs::state::{PaneruState, SavedColumn, SavedStrip, SavedWorkspace};
 use crate::ecs::{
     ActiveDisplayMarker, ActiveWorkspaceMarker, ReadDisplayProperties, RefreshWindowSizes,
-    SendMessageTrigger, SpawnCommandsExt, Timeout,
+    RescaleWidthsMarker, RestoreWindowState, SendMessageTrigger, SpawnCommandsExt, Timeout,
 };
 use crate::events::Event;
-use crate::manager::{Display, WindowManager, irect_from};
+use crate::manager::{Application, Display, WindowManager, irect_from};
 use crate::platform::{PlatformCallbacks, WorkspaceId};
 use crate::util::read_screen_property;
 
 const ORPHANED_SPACES_TIMEOUT_SEC: u64 = 30;
 
+/// Layouts of disconnected displays, keyed by display UUID. Reattaching the same monitor restores
+/// its strips, even once the orphaned strips timed out or macOS handed it new space ids.
+#[derive(Debug, Default, Resource)]
+pub struct DetachedLayouts(HashMap<String, Vec<SavedWorkspace>>);
+
+impl DetachedLayouts {
+    /// The key a display's layout is stored under: its UUID, or its id when Core Graphics could
+    /// not provide one.
+    fn key(display: &Display) -> String {
+        display
+            .uuid()
+            .map_or_else(|| display.id().to_string(), str::to_owned)
+    }
+}
+
 pub struct DisplayEventsPlugin;
 
 impl Plugin for DisplayEventsPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<DetachedLayouts>();
         app.add_systems(PreUpdate, display_change_handler);
         app.add_systems(Update, reconcile_displays)
             .add_observer(read_display_properties_trigger)
@@ -107,7 +127,11 @@ pub(crate) fn reconcile_displays(
     workspaces: Query<(&LayoutStrip, Entity, Option<&ChildOf>)>,
     mut displays: Query<(&mut Display, Entity)>,
     active_strips: Query<Entity, (With<LayoutStrip>, With<ActiveWorkspaceMarker>)>,
+    windows: Windows,
+    apps: Query<&Application>,
     window_manager: Res<WindowManager>,
+    config: Res<Config>,
+    mut detached: ResMut<DetachedLayouts>,
     mut retries: Local<u8>,
     mut commands: Commands,
 ) {
@@ -170,6 +194,14 @@ pub(crate) fn reconcile_displays(
             error!("Unable to find removed display: {display_id}");
             continue;
         };
+        detach_layout(
+            display,
+            &workspaces,
+            &displays,
+            &windows,
+            &apps,
+            &mut detached,
+        );
         remove_display(display, &workspaces, &displays, &mut commands);
     }
 
@@ -179,7 +211,19 @@ pub(crate) fn reconcile_displays(
             error!("Unable to find added display: {display_id}");
             continue;
         };
+        let layout = detached.0.remove(&DetachedLayouts::key(&display));
+        let display_id = display.id();
         add_display(display, &workspace_ids, &workspaces, &mut commands);
+        if let Some(layout) = layout {
+            reattach_layout(
+                display_id,
+                layout,
+                &workspace_ids,
+                &workspaces,
+                &config,
+                &mut commands,
+            );
+        }
     }
 
     // Displays that are still present: refresh their bounds (resolution or
@@ -228,6 +272,106 @@ fn add_display(
     );
 }
 
+/// Remembers the strips of a display which is about to be removed, so they can be restored when
+/// the same monitor is reattached.
+fn detach_layout(
+    display: &Display,
+    workspaces: &Query<(&LayoutStrip, Entity, Option<&ChildOf>)>,
+    displays: &Query<(&mut Display, Entity)>,
+    windows: &Windows,
+    apps: &Query<&Application>,
+    detached: &mut DetachedLayouts,
+) {
+    let Some((_, display_entity)) = displays
+        .into_iter()
+        .find(|(present, _)| present.id() == display.id())
+    else {
+        return;
+    };
+
+    let mut saved: Vec<SavedWorkspace> = Vec::new();
+    for (strip, _, _) in workspaces
+        .into_iter()
+        .filter(|(_, _, child)| child.is_some_and(|child| child.parent() == display_entity))
+    {
+        let columns = strip
+            .columns()
+            .filter_map(|column| SavedColumn::from_column(column, windows, apps))
+            .collect::<Vec<_>>();
+        if columns.is_empty() {
+            continue;
+        }
+        let saved_strip = SavedStrip {
+            virtual_index: strip.virtual_index,
+            columns,
+        };
+        if let Some(workspace) = saved.iter_mut().find(|ws| ws.workspace_id == strip.id()) {
+            workspace.strips.push(saved_strip);
+        } else {
+            saved.push(SavedWorkspace {
+                workspace_id: strip.id(),
+                display_id: Some(display.id()),
+                active_virtual_index: None,
+                strips: vec![saved_strip],
+            });
+        }
+    }
+    if saved.is_empty() {
+        return;
+    }
+
+    saved.sort_by_key(|workspace| workspace.workspace_id);
+    for workspace in &mut saved {
+        workspace.strips.sort_by_key(|strip| strip.virtual_index);
+    }
+    debug!(
+        "remembering {} workspaces of display {}.",
+        saved.len(),
+        display.id()
+    );
+    detached.0.insert(DetachedLayouts::key(display), saved);
+}
+
+/// Restores the strips remembered for a reattached display onto its current spaces, pairing the
+/// saved workspaces with `workspace_ids` in order. Skipped when the display got its old spaces
+/// back, because the orphaned strips are then re-parented as they are.
+fn reattach_layout(
+    display_id: CGDirectDisplayID,
+    layout: Vec<SavedWorkspace>,
+    workspace_ids: &[WorkspaceId],
+    existing_strips: &Query<(&LayoutStrip, Entity, Option<&ChildOf>)>,
+    config: &Config,
+    commands: &mut Commands,
+) {
+    if existing_strips
+        .iter()
+        .any(|(strip, _, _)| workspace_ids.contains(&strip.id()))
+    {
+        return;
+    }
+
+    let workspaces = layout
+        .into_iter()
+        .zip(workspace_ids)
+        .map(|(workspace, &workspace_id)| SavedWorkspace {
+            workspace_id,
+            display_id: Some(display_id),
+            ..workspace
+        })
+        .collect::<Vec<_>>();
+    if workspaces.is_empty() {
+        return;
+    }
+
+    debug!(
+        "restoring {} workspaces of reattached display {display_id}.",
+        workspaces.len()
+    );
+    let state = PaneruState::from_workspaces(workspaces);
+    commands.insert_resource(SessionRestore::new(state, config.restore_startup_grace()));
+    commands.trigger(RestoreWindowState);
+}
+
 #[instrument(level = Level::DEBUG, skip_all, fields(display_id))]
 fn remove_display(
     display: &Display,
@@ -299,6 +443,18 @@ fn move_display(
     else {
         return;
     };
+    if display.bounds().width() != moved_display.bounds().width() {
+        // A resolution or scale change: keep the windows at the same share of the display.
+        debug!("display {display_id} resized, rescaling its windows.");
+        for (_, entity, _) in existing_strips
+            .iter()
+            .filter(|(_, _, child)| child.is_some_and(|child| child.parent() == display_entity))
+        {
+            if let Ok(mut cmd) = commands.get_entity(entity) {
+                cmd.try_insert((RefreshWindowSizes::default(), RescaleWidthsMarker));
+            }
+        }
+    }
     *display = moved_display;
     commands.trigger(ReadDisplayProperties(display_entity));
 
@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bevy::app::{App, Plugin, PostUpdate};
 use bevy::ecs::entity::Entity;
@@ -9,20 +9,23 @@ use bevy::ecs::observer::On;
 use bevy::ecs::query::{Added, Has, With};
 use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::IntoScheduleConfigs as _;
-use bevy::ecs::system::{Commands, Populated, Query, Res, Single};
+use bevy::ecs::system::{Commands, NonSend, Populated, Query, Res, Single};
+use bevy::math::IRect;
 use bevy::prelude::Event as BevyEvent;
 use bevy::time::common_conditions::on_timer;
 use tracing::{Level, debug, error, instrument, trace, warn};
 
-use super::{FocusedMarker, MouseHeldMarker, SystemTheme, Unmanaged};
-use crate::config::Config;
+use super::{FocusedAt, FocusedMarker, MouseHeldMarker, NewWindowMarker, SystemTheme, Unmanaged};
+use crate::announcer::Announcer;
+use crate::config::{Config, MouseWarp, ScrollPolicy};
 use crate::ecs::layout::LayoutStrip;
 use crate::ecs::params::{ActiveDisplay, GlobalState, Windows};
 use crate::ecs::{
-    ActiveWorkspaceMarker, Scrolling, SendMessageTrigger, SpawnCommandsExt, StrayFocusEvent,
+    ActiveWorkspaceMarker, OverviewMarker, Scrolling, SendMessageTrigger, SpawnCommandsExt,
+    StrayFocusEvent, Wakeup, WindowOpacity,
 };
 use crate::events::Event;
-use crate::manager::{Application, Display, Window, WindowManager};
+use crate::manager::{Application, Display, Origin, Window, WindowManager, origin_from};
 use crate::platform::WorkspaceId;
 
 const REFRESH_WINDOW_CHECK_FREQ_MS: u64 = 1000;
@@ -83,6 +86,18 @@ impl FocusHistory {
     pub fn forget_workspace(&mut self, workspace: WorkspaceId) {
         self.by_workspace.remove(&workspace);
     }
+
+    /// Returns the remembered windows of every workspace, used to persist the history.
+    pub fn workspaces(&self) -> impl Iterator<Item = (WorkspaceId, &TierMemory)> {
+        self.by_workspace
+            .iter()
+            .map(|(workspace, memory)| (*workspace, memory))
+    }
+
+    /// Replaces the remembered windows of `workspace`, e.g. with a persisted history.
+    pub fn restore(&mut self, workspace: WorkspaceId, memory: TierMemory) {
+        self.by_workspace.insert(workspace, memory);
+    }
 }
 
 pub struct FocusEventsPlugin;
@@ -90,6 +105,9 @@ pub struct FocusEventsPlugin;
 impl Plugin for FocusEventsPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<FocusHistory>();
+        app.world_mut()
+            .get_resource_or_init::<Wakeup>()
+            .every(Duration::from_millis(REFRESH_WINDOW_CHECK_FREQ_MS));
         app.add_systems(
             PostUpdate,
             (
@@ -98,10 +116,15 @@ impl Plugin for FocusEventsPlugin {
                 recover_lost_focus.run_if(on_timer(Duration::from_millis(
                     REFRESH_WINDOW_CHECK_FREQ_MS,
                 ))),
+                announce_focus_changes.run_if(|config: Option<Res<Config>>| {
+                    config.is_some_and(|config| config.accessibility_announcements())
+                }),
             ),
         );
         app.add_observer(dim_remove_window_trigger)
             .add_observer(dim_window_trigger)
+            .add_observer(opacity_window_trigger)
+            .add_observer(opacity_remove_window_trigger)
             .add_observer(maintain_focus_singleton)
             .add_observer(virtual_strip_activated)
             .add_observer(stray_focus_observer)
@@ -124,6 +147,9 @@ fn maintain_focus_singleton(
     mut commands: Commands,
 ) {
     let focused_entity = trigger.event().entity;
+    if let Ok(mut entity_commands) = commands.get_entity(focused_entity) {
+        entity_commands.try_insert(FocusedAt(Instant::now()));
+    }
 
     for (entity, focused) in windows {
         if focused
@@ -151,6 +177,7 @@ fn autocenter_window_on_focus(
     windows: Windows,
     global_state: GlobalState,
     active_display: ActiveDisplay,
+    overviews: Query<(), With<OverviewMarker>>,
     config: Res<Config>,
     mut commands: Commands,
 ) {
@@ -159,16 +186,30 @@ fn autocenter_window_on_focus(
     if global_state.skip_reshuffle() || global_state.initializing() || !mouse_held.is_empty() {
         return;
     }
-    if active_display.active_strip().tabbed(entity) {
+    // The overview keeps its shrunk windows in place until a window is picked.
+    if active_display.active_strip().tabbed(entity)
+        || overviews.contains(active_display.active_strip_entity())
+    {
         return;
     }
-    if config.auto_center()
+    let policy = config.scroll_policy_on(Some(active_display.active_strip().id()));
+    if policy != ScrollPolicy::Minimal
         && let Some((_, _, None)) = windows.get_managed(entity)
         && let Some(size) = windows.size(entity)
         && let Some(mut origin) = windows.origin(entity)
     {
-        let center = active_display.bounds().center();
-        origin.x = center.x - size.x / 2;
+        if policy == ScrollPolicy::Center {
+            let center = active_display.bounds().center();
+            origin.x = center.x - size.x / 2;
+        } else {
+            // Align with the edge nearer to the window's center.
+            let viewport = active_display.actual_bounds(&config);
+            origin.x = if origin.x + size.x / 2 < viewport.center().x {
+                viewport.min.x
+            } else {
+                viewport.max.x - size.x
+            };
+        }
         commands.reposition_entity(entity, origin);
     }
     commands.reshuffle_around(entity);
@@ -189,8 +230,18 @@ fn mouse_follows_focus(
         Option<&Scrolling>,
         Has<ActiveWorkspaceMarker>,
     )>,
+    new_windows: Query<Entity, With<NewWindowMarker>>,
+    mut commands: Commands,
 ) {
     let entity = *focused;
+    // Only the first focus change after a window was created counts as "new". Whichever window
+    // got focus, the markers are spent, so a window focused much later is no longer new.
+    let fresh = new_windows.contains(entity);
+    for new_window in &new_windows {
+        if let Ok(mut entity_commands) = commands.get_entity(new_window) {
+            entity_commands.try_remove::<NewWindowMarker>();
+        }
+    }
     let Some(window) = windows.get(entity) else {
         return;
     };
@@ -209,7 +260,7 @@ fn mouse_follows_focus(
         global_state.skip_reshuffle(),
         global_state.ffm_flag()
     );
-    if config.mouse_follows_focus()
+    if (config.mouse_follows_focus() || (fresh && config.mouse_follows_new_window()))
         && !global_state.skip_reshuffle()
         && global_state.ffm_flag().is_none_or(|id| id != window.id())
         && let Some(frame) = windows.moving_frame(entity)
@@ -223,10 +274,30 @@ fn mouse_follows_focus(
         // If the overlap is smaller than 50x50, the window is probably hidden
         // off screen, so do not move the mouse.
         if visible.size().length_squared() > 5000 {
-            let origin = visible.center();
-            debug!("centering on {} {origin}", window.id());
-            window_manager.warp_mouse(origin);
+            let cursor = window_manager.cursor_position().map(origin_from);
+            if let Some(origin) = mouse_warp_target(config.mouse_warp(), visible, cursor) {
+                debug!("warping to {origin} on {}", window.id());
+                window_manager.warp_mouse(origin);
+            }
+        }
+    }
+}
+
+/// Returns where the pointer at `cursor` moves to when focusing a window whose visible part is
+/// `visible`, or `None` if it stays put.
+fn mouse_warp_target(mode: MouseWarp, visible: IRect, cursor: Option<Origin>) -> Option<Origin> {
+    // Keeps the pointer a few pixels inside the window, so it is not on its resize border.
+    const EDGE_INSET: i32 = 10;
+
+    match (mode, cursor) {
+        (MouseWarp::Center, _) | (_, None) => Some(visible.center()),
+        (_, Some(cursor)) if visible.contains(cursor) => None,
+        (MouseWarp::NearestEdge, Some(cursor)) => {
+            let inset = visible.inflate(-EDGE_INSET);
+            let inset = if inset.is_empty() { visible } else { inset };
+            Some(cursor.clamp(inset.min, inset.max))
         }
+        (MouseWarp::OffIfVisible, Some(_)) => Some(visible.center()),
     }
 }
 
@@ -275,6 +346,58 @@ fn dim_remove_window_trigger(
     }
 }
 
+/// Returns the opacity a window should have, given the `opacity` of its window rule and
+/// whether it is focused, or `None` when neither the rule nor `unfocused_opacity` is set.
+/// Unfocused windows never become more opaque than their rule allows.
+pub(super) fn window_opacity(config: &Config, rule: Option<f32>, focused: bool) -> Option<f32> {
+    let unfocused = config.unfocused_opacity();
+    if rule.is_none() && unfocused.is_none() {
+        return None;
+    }
+    let opacity = rule.unwrap_or(1.0);
+    Some(if focused {
+        opacity
+    } else {
+        unfocused.map_or(opacity, |unfocused| unfocused.min(opacity))
+    })
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn opacity_window_trigger(
+    trigger: On<Add, FocusedMarker>,
+    windows: Windows,
+    opacities: Query<&WindowOpacity>,
+    window_manager: Res<WindowManager>,
+    config: Res<Config>,
+) {
+    let entity = trigger.event().entity;
+    let Some(window) = windows.get(entity) else {
+        return;
+    };
+    let rule = opacities.get(entity).ok().map(|opacity| opacity.0);
+    if let Some(opacity) = window_opacity(&config, rule, true) {
+        window_manager.set_window_opacity(window.id(), opacity);
+    }
+}
+
+#[allow(clippy::needless_pass_by_value)]
+fn opacity_remove_window_trigger(
+    trigger: On<Remove, FocusedMarker>,
+    windows: Windows,
+    opacities: Query<&WindowOpacity>,
+    window_manager: Res<WindowManager>,
+    config: Res<Config>,
+) {
+    let entity = trigger.event().entity;
+    let Some(window) = windows.get(entity) else {
+        return;
+    };
+    let rule = opacities.get(entity).ok().map(|opacity| opacity.0);
+    if let Some(opacity) = window_opacity(&config, rule, false) {
+        window_manager.set_window_opacity(window.id(), opacity);
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[instrument(level = Level::DEBUG, skip_all, fields(trigger))]
 fn virtual_strip_activated(
@@ -355,11 +478,107 @@ pub(super) fn stray_focus_observer(
         });
 }
 
+/// Tells VoiceOver users where focus landed, since the scrolling layout keeps most columns
+/// off-screen.
+#[allow(clippy::needless_pass_by_value)]
+fn announce_focus_changes(
+    focused: Query<Entity, Added<FocusedMarker>>,
+    switched: Query<&LayoutStrip, Added<ActiveWorkspaceMarker>>,
+    windows: Windows,
+    apps: Query<&Application>,
+    active_display: ActiveDisplay,
+    announcer: Option<NonSend<Announcer>>,
+) {
+    let Some(announcer) = announcer else {
+        return;
+    };
+
+    let workspace = switched
+        .iter()
+        .next()
+        .map(|strip| format!("Workspace {}", strip.virtual_index + 1));
+    let window = focused.iter().next().and_then(|entity| {
+        let window = windows.get(entity)?;
+        let (_, _, parent) = windows.find_parent(window.id())?;
+        let app = apps.get(parent).ok()?;
+        let strip = active_display.active_strip();
+        let column = strip
+            .index_of(entity)
+            .ok()
+            .map(|index| (index + 1, strip.len()));
+        let floating = matches!(
+            windows.get_managed(entity),
+            Some((_, _, Some(Unmanaged::Floating)))
+        );
+        Some(focus_announcement(app.name(), column, floating))
+    });
+
+    let message = workspace.into_iter().chain(window).collect::<Vec<_>>();
+    if !message.is_empty() {
+        announcer.announce(&message.join(", "));
+    }
+}
+
+/// Formats the announcement for a focused window, with its one-based column position if tiled.
+fn focus_announcement(app_name: &str, column: Option<(usize, usize)>, floating: bool) -> String {
+    match column {
+        _ if floating => format!("Focused {app_name}, floating"),
+        Some((column, columns)) => format!("Focused {app_name}, column {column} of {columns}"),
+        None => format!("Focused {app_name}"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use bevy::ecs::world::World;
 
+    #[test]
+    fn focus_announcement_mentions_column_position() {
+        assert_eq!(
+            focus_announcement("Safari", Some((3, 7)), false),
+            "Focused Safari, column 3 of 7"
+        );
+        assert_eq!(
+            focus_announcement("Finder", None, true),
+            "Focused Finder, floating"
+        );
+        assert_eq!(focus_announcement("Mail", None, false), "Focused Mail");
+    }
+
+    #[test]
+    fn mouse_warp_target_modes() {
+        let visible = IRect::new(100, 100, 500, 400);
+        let outside = Some(Origin::new(700, 50));
+        let inside = Some(Origin::new(120, 390));
+
+        assert_eq!(
+            mouse_warp_target(MouseWarp::Center, visible, inside),
+            Some(Origin::new(300, 250))
+        );
+        assert_eq!(
+            mouse_warp_target(MouseWarp::NearestEdge, visible, outside),
+            Some(Origin::new(490, 110))
+        );
+        assert_eq!(
+            mouse_warp_target(MouseWarp::NearestEdge, visible, inside),
+            None
+        );
+        assert_eq!(
+            mouse_warp_target(MouseWarp::OffIfVisible, visible, inside),
+            None
+        );
+        assert_eq!(
+            mouse_warp_target(MouseWarp::OffIfVisible, visible, outside),
+            Some(Origin::new(300, 250))
+        );
+        // Without a known pointer position every mode centers.
+        assert_eq!(
+            mouse_warp_target(MouseWarp::NearestEdge, visible, None),
+            Some(Origin::new(300, 250))
+        );
+    }
+
     #[test]
     fn record_and_read_per_tier() {
         let mut world = World::new();
@@ -0,0 +1,118 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bevy::app::{App, Plugin, PostUpdate, PreUpdate};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::Changed;
+use bevy::ecs::resource::Resource;
+use bevy::ecs::system::{Query, ResMut};
+
+use crate::ecs::layout::LayoutStrip;
+use crate::errors::Result;
+use crate::events::Event;
+
+/// Number of entries kept by the `EventJournal`, older ones are dropped.
+const JOURNAL_CAPACITY: usize = 2000;
+
+/// Ring buffer of recently received events and the layout changes they caused. Dumped with
+/// `dump-journal`, so a trace of layout corruption can be attached to a bug report.
+#[derive(Debug, Default, Resource)]
+pub struct EventJournal {
+    entries: VecDeque<String>,
+}
+
+impl EventJournal {
+    /// Appends a timestamped entry of the given `kind`, dropping the oldest one when full.
+    fn record(&mut self, kind: &str, entry: impl Display) {
+        if self.entries.len() == JOURNAL_CAPACITY {
+            self.entries.pop_front();
+        }
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        self.entries.push_back(format!(
+            "{}.{:03} {kind:<6} {entry}",
+            now.as_secs(),
+            now.subsec_millis()
+        ));
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    /// Writes the journal to `path`, one entry per line, oldest first.
+    pub fn dump(&self, path: &Path) -> Result<()> {
+        let mut out = self.entries().collect::<Vec<_>>().join("\n");
+        out.push('\n');
+        fs::write(path, out)?;
+        Ok(())
+    }
+}
+
+pub struct JournalPlugin;
+
+impl Plugin for JournalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EventJournal>()
+            .add_systems(PreUpdate, record_events)
+            .add_systems(PostUpdate, record_layout_changes);
+    }
+}
+
+/// Records every incoming event, except for pointer motion which would flush out everything
+/// else within seconds.
+#[allow(clippy::needless_pass_by_value)]
+fn record_events(mut messages: MessageReader<Event>, mut journal: ResMut<EventJournal>) {
+    for event in messages.read() {
+        if matches!(event, Event::MouseMoved { .. } | Event::MouseDragged { .. }) {
+            continue;
+        }
+        journal.record("event", format_args!("{event:?}"));
+    }
+}
+
+/// Records the new column arrangement of every layout strip changed during this frame.
+#[allow(clippy::needless_pass_by_value)]
+fn record_layout_changes(
+    strips: Query<(Entity, &LayoutStrip), Changed<LayoutStrip>>,
+    mut journal: ResMut<EventJournal>,
+) {
+    for (entity, strip) in &strips {
+        journal.record(
+            "layout",
+            format_args!("strip {entity} workspace {}: {strip}", strip.id()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn journal_keeps_latest_entries_and_dumps_them_in_order() {
+        let mut journal = EventJournal::default();
+        for index in 0..JOURNAL_CAPACITY + 2 {
+            journal.record("event", index);
+        }
+
+        let entries = journal.entries().collect::<Vec<_>>();
+        assert_eq!(entries.len(), JOURNAL_CAPACITY);
+        assert!(entries[0].ends_with("event  2"));
+        assert!(
+            entries[JOURNAL_CAPACITY - 1].ends_with(&format!("event  {}", JOURNAL_CAPACITY + 1))
+        );
+
+        let path = std::env::temp_dir().join(format!("paneru-journal-{}.log", std::process::id()));
+        journal.dump(&path).expect("journal should be written");
+        let dumped = fs::read_to_string(&path).expect("journal should be readable");
+        _ = fs::remove_file(&path);
+        assert_eq!(dumped.lines().count(), JOURNAL_CAPACITY);
+        assert_eq!(dumped.lines().next(), Some(entries[0]));
+    }
+}
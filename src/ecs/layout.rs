@@ -3,27 +3,73 @@ use bevy::ecs::change_detection::{DetectChanges, DetectChangesMut, Ref};
 use bevy::ecs::component::Component;
 use bevy::ecs::entity::{Entity, EntityHashMap, EntityHashSet};
 use bevy::ecs::hierarchy::ChildOf;
-use bevy::ecs::query::{Changed, Has, Or, With, Without};
+use bevy::ecs::lifecycle::RemovedComponents;
+use bevy::ecs::message::MessageReader;
+use bevy::ecs::query::{Added, Changed, Has, Or, With, Without};
+use bevy::ecs::resource::Resource;
 use bevy::ecs::schedule::IntoScheduleConfigs as _;
 use bevy::ecs::schedule::common_conditions::{not, resource_exists};
-use bevy::ecs::system::{Commands, ParamSet, Populated, Query, Res};
+use bevy::ecs::system::{Commands, Local, ParamSet, Populated, Query, Res, ResMut};
 use bevy::math::IRect;
+use bevy::time::Time;
+use std::cmp::Ordering;
 use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
 use stdext::function_name;
 use tracing::{Level, instrument, trace};
 
 use crate::config::Config;
 use crate::ecs::params::Windows;
 use crate::ecs::{
-    ActiveWorkspaceMarker, Bounds, DockPosition, EnsureVisibleMarker, Initializing, LayoutPosition,
-    Position, RepositionMarker, ReshuffleAroundMarker, Scrolling, SpawnCommandsExt,
+    ActiveWorkspaceMarker, Bounds, DockPosition, EnsureVisibleMarker, FocusedMarker, Initializing,
+    LayoutPosition, MinimumHeight, OverviewFrame, OverviewMarker, PinnedMarker, Position,
+    RepositionMarker, ReshuffleAroundMarker, Scrolling, SpawnCommandsExt, ZenMarker,
 };
 use crate::errors::{Error, Result};
+use crate::events::Event;
 use crate::manager::{Display, Origin, Size, Window};
 use crate::platform::WorkspaceId;
 
 pub struct LayoutEventsPlugin;
 
+/// The visible height of each window tucked behind the focused one in an accordion column,
+/// roughly a macOS title bar.
+pub const ACCORDION_SLIVER_HEIGHT: i32 = 28;
+
+/// The height of the tab bar reserved at the top of a stacked column when `stack_tabs` is enabled.
+pub const STACK_TAB_BAR_HEIGHT: i32 = 22;
+
+/// How `Column::Stack` columns are laid out vertically, and how far apart the columns sit.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct StackLayout {
+    /// Height reserved at the top of the column for its tab bar.
+    pub tab_bar_height: i32,
+    /// Share of the column height given to the top window, if set.
+    pub main_ratio: Option<f64>,
+    /// Gap between adjacent columns and between the windows of a stack.
+    pub gap: i32,
+}
+
+/// The frames computed by the previous layout pass of a strip, one entry per column.
+#[derive(Debug, Default)]
+pub struct StripLayoutCache {
+    height: i32,
+    stack_layout: StackLayout,
+    columns: Vec<CachedColumn>,
+}
+
+/// A column together with the window sizes and minimum heights it was laid out from and the
+/// resulting frames.
+#[derive(Debug)]
+struct CachedColumn {
+    column: Column,
+    sizes: Vec<Option<Size>>,
+    min_heights: Vec<Option<i32>>,
+    left_edge: i32,
+    width: Option<i32>,
+    frames: Vec<(Entity, IRect)>,
+}
+
 /// Clamp a window origin to the range where it still touches both viewport
 /// edges. For an oversized window this range is reversed: from right-aligned
 /// to left-aligned, which lets the strip pan across the hidden content.
@@ -44,18 +90,25 @@ impl Plugin for LayoutEventsPlugin {
                 (
                     sync_tab_group_frames,
                     layout_sizes_changed,
+                    relayout_on_padding_change,
                     layout_strip_changed,
                     reshuffle_layout_strip,
                     ensure_visible_in_strip,
                     position_layout_strips,
+                    pin_layout_windows,
+                    leave_zen_on_focus_change,
+                    zen_layout_windows,
+                    overview_layout_windows,
                     position_layout_windows,
+                    update_stack_tab_bars,
                 )
                     .chain()
                     .after(super::systems::finish_setup)
                     .before(super::workspace::show_active_workspace)
                     .run_if(not(resource_exists::<Initializing>)),
             ),
-        );
+        )
+        .init_resource::<StackTabBars>();
     }
 }
 
@@ -120,7 +173,7 @@ impl DoubleEndedIterator for StackItemIter<'_> {
 }
 
 /// Represents a single panel within a `LayoutStrip`, which can either hold a single window, a stack of items, or a group of tabs.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Column {
     /// A panel containing a single window, identified by its `Entity`.
     Single(Entity),
@@ -129,16 +182,30 @@ pub enum Column {
     /// A panel containing a group of native tabs.
     Tabs(Vec<Entity>),
     Fullscren(Entity),
+    /// A stack whose windows overlap vertically, cascaded by `ACCORDION_SLIVER_HEIGHT` so only
+    /// a title-bar-height sliver of the windows behind the focused one stays visible.
+    Accordion(Vec<StackItem>),
 }
 
 impl Column {
+    /// Builds a stack column from `items`, as an `Accordion` if `accordion` is set.
+    fn stacked(items: Vec<StackItem>, accordion: bool) -> Column {
+        if accordion {
+            Column::Accordion(items)
+        } else {
+            Column::Stack(items)
+        }
+    }
+
     /// Returns the top window entity in the panel.
     /// For a `Single` panel, it's the contained window.
     /// For a `Stack` or `Tabs`, it's the first window in the vector.
     pub fn top(&self) -> Option<Entity> {
         match self {
             Column::Single(id) | Column::Fullscren(id) => Some(*id),
-            Column::Stack(stack) => stack.first().and_then(StackItem::top),
+            Column::Stack(stack) | Column::Accordion(stack) => {
+                stack.first().and_then(StackItem::top)
+            }
             Column::Tabs(tabs) => tabs.first().copied(),
         }
     }
@@ -150,7 +217,7 @@ impl Column {
                 ColumnWindowIter::Single(std::iter::once(*entity))
             }
             Column::Tabs(tabs) => ColumnWindowIter::Tabs(tabs.iter().copied()),
-            Column::Stack(items) => {
+            Column::Stack(items) | Column::Accordion(items) => {
                 ColumnWindowIter::Stack(items.iter().flat_map(StackItem::window_iter))
             }
         }
@@ -170,7 +237,7 @@ impl Column {
     pub fn at_or_last(&self, index: usize) -> Option<Entity> {
         match self {
             Column::Single(id) | Column::Fullscren(id) => Some(*id),
-            Column::Stack(stack) => stack
+            Column::Stack(stack) | Column::Accordion(stack) => stack
                 .get(index)
                 .or_else(|| stack.last())
                 .and_then(StackItem::top),
@@ -182,17 +249,35 @@ impl Column {
     pub fn position_of(&self, entity: Entity) -> Option<usize> {
         match self {
             Column::Single(id) | Column::Fullscren(id) => (*id == entity).then_some(0),
-            Column::Stack(stack) => stack.iter().position(|item| item.contains(entity)),
+            Column::Stack(stack) | Column::Accordion(stack) => {
+                stack.iter().position(|item| item.contains(entity))
+            }
             Column::Tabs(tabs) => tabs.contains(&entity).then_some(0),
         }
     }
 
+    /// Moves the stack item holding `entity` to the top of a stacked column, keeping the order
+    /// of the others. Returns `false` if the column is not a stack or the item is already on top.
+    pub fn promote(&mut self, entity: Entity) -> bool {
+        let (Column::Stack(stack) | Column::Accordion(stack)) = self else {
+            return false;
+        };
+        match stack.iter().position(|item| item.contains(entity)) {
+            Some(position) if position > 0 => {
+                let item = stack.remove(position);
+                stack.insert(0, item);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Moves the specified entity to the front of stack-local ordering.
     /// Native tab ordering is stable; the focused tab is tracked by `FocusedMarker`.
     pub fn move_to_front(&mut self, entity: Entity) {
         match self {
             Column::Single(_) | Column::Fullscren(_) => {}
-            Column::Stack(stack) => {
+            Column::Stack(stack) | Column::Accordion(stack) => {
                 if let Some(StackItem::Tabs(tabs)) =
                     stack.iter_mut().find(|item| item.contains(entity))
                     && let Some(pos) = tabs.iter().position(|&e| e == entity)
@@ -276,7 +361,9 @@ impl LayoutStrip {
             .iter()
             .position(|column| match column {
                 Column::Single(id) | Column::Fullscren(id) => *id == entity,
-                Column::Stack(stack) => stack.iter().any(|item| item.contains(entity)),
+                Column::Stack(stack) | Column::Accordion(stack) => {
+                    stack.iter().any(|item| item.contains(entity))
+                }
                 Column::Tabs(stack) => stack.contains(&entity),
             })
             .ok_or(Error::NotFound(format!(
@@ -289,7 +376,9 @@ impl LayoutStrip {
     pub fn contains(&self, entity: Entity) -> bool {
         self.columns.iter().any(|column| match column {
             Column::Single(id) | Column::Fullscren(id) => *id == entity,
-            Column::Stack(stack) => stack.iter().any(|item| item.contains(entity)),
+            Column::Stack(stack) | Column::Accordion(stack) => {
+                stack.iter().any(|item| item.contains(entity))
+            }
             Column::Tabs(stack) => stack.contains(&entity),
         })
     }
@@ -367,11 +456,12 @@ impl LayoutStrip {
         self.remove(follower);
         let index = self.index_of(leader)?;
         let column = self.columns.remove(index).unwrap();
+        let accordion = matches!(column, Column::Accordion(_));
         match column {
             Column::Single(id) | Column::Fullscren(id) => {
                 self.columns.insert(index, Column::Tabs(vec![follower, id]));
             }
-            Column::Stack(mut items) => {
+            Column::Stack(mut items) | Column::Accordion(mut items) => {
                 if let Some(pos) = items.iter().position(|item| item.contains(leader)) {
                     match &mut items[pos] {
                         StackItem::Single(id) => {
@@ -385,7 +475,8 @@ impl LayoutStrip {
                         }
                     }
                 }
-                self.columns.insert(index, Column::Stack(items));
+                self.columns
+                    .insert(index, Column::stacked(items, accordion));
             }
             Column::Tabs(mut tabs) => {
                 if !tabs.contains(&follower) {
@@ -411,11 +502,12 @@ impl LayoutStrip {
             .and_then(|index| self.columns.remove(index).zip(Some(index)));
 
         if let Some((column, index)) = removed {
+            let accordion = matches!(column, Column::Accordion(_));
             match column {
                 Column::Single(_) | Column::Fullscren(_) => {
                     // Already removed from self.columns.
                 }
-                Column::Stack(mut stack) => {
+                Column::Stack(mut stack) | Column::Accordion(mut stack) => {
                     for item in &mut stack {
                         match item {
                             StackItem::Single(_) => {}
@@ -429,7 +521,8 @@ impl LayoutStrip {
                         StackItem::Tabs(tabs) => !tabs.is_empty(),
                     });
                     if stack.len() > 1 {
-                        self.columns.insert(index, Column::Stack(stack));
+                        self.columns
+                            .insert(index, Column::stacked(stack, accordion));
                     } else if let Some(remaining_item) = stack.first() {
                         match remaining_item {
                             StackItem::Single(id) => {
@@ -491,6 +584,16 @@ impl LayoutStrip {
         self.columns.len()
     }
 
+    /// Returns `true` if the strip has one column which shows a single window, i.e. does not
+    /// split its height between stacked windows.
+    pub fn shows_single_window(&self) -> bool {
+        match self.columns.as_slice() {
+            [Column::Single(_) | Column::Fullscren(_) | Column::Tabs(_)] => true,
+            [Column::Stack(items) | Column::Accordion(items)] => items.len() == 1,
+            _ => false,
+        }
+    }
+
     /// Returns the first `Panel` in the pane.
     ///
     /// # Returns
@@ -555,7 +658,7 @@ impl LayoutStrip {
             Column::Fullscren(_) => return Ok(()),
             Column::Single(id) => vec![StackItem::Single(id)],
             Column::Tabs(tabs) => vec![StackItem::Tabs(tabs)],
-            Column::Stack(items) => items,
+            Column::Stack(items) | Column::Accordion(items) => items,
         };
 
         let target_column = self.columns.remove(index - 1).unwrap();
@@ -568,12 +671,116 @@ impl LayoutStrip {
                 Column::Stack([vec![StackItem::Tabs(tabs)], items_to_stack].concat())
             }
             Column::Stack(items) => Column::Stack([items, items_to_stack].concat()),
+            Column::Accordion(items) => Column::Accordion([items, items_to_stack].concat()),
         };
 
         self.columns.insert(index - 1, new_column);
         Ok(())
     }
 
+    /// Stacks the window onto the bottom of the column containing `target`, taking it out of
+    /// its current column first. Unlike `stack`, the target column does not have to be adjacent.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - Entity of the window to stack.
+    /// * `target` - Entity of a window in the column to stack onto.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the stacking is successful, otherwise `Err(Error)` if the target is not found,
+    /// is the window itself or is a fullscreen column.
+    pub fn stack_onto(&mut self, entity: Entity, target: Entity) -> Result<()> {
+        if entity == target {
+            return Err(Error::InvalidInput(format!(
+                "{}: can not stack {entity} onto itself.",
+                function_name!()
+            )));
+        }
+        if matches!(self.get(self.index_of(target)?)?, Column::Fullscren(_)) {
+            return Err(Error::InvalidInput(format!(
+                "{}: can not stack onto a fullscreen column.",
+                function_name!()
+            )));
+        }
+
+        self.remove(entity);
+        let index = self.index_of(target)?;
+        let new_column = match self.columns.remove(index).unwrap() {
+            Column::Single(id) | Column::Fullscren(id) => {
+                Column::Stack(vec![StackItem::Single(id), StackItem::Single(entity)])
+            }
+            Column::Tabs(tabs) => {
+                Column::Stack(vec![StackItem::Tabs(tabs), StackItem::Single(entity)])
+            }
+            Column::Stack(mut items) => {
+                items.push(StackItem::Single(entity));
+                Column::Stack(items)
+            }
+            Column::Accordion(mut items) => {
+                items.push(StackItem::Single(entity));
+                Column::Accordion(items)
+            }
+        };
+        self.columns.insert(index, new_column);
+        Ok(())
+    }
+
+    /// Switches the column containing `entity` between `Stack` and `Accordion`.
+    /// Columns which are not stacks are left unchanged.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - Entity of a window in the column to toggle.
+    ///
+    /// # Returns
+    ///
+    /// `Ok(())` if the window was found, otherwise `Err(Error)`.
+    pub fn toggle_accordion(&mut self, entity: Entity) -> Result<()> {
+        let index = self.index_of(entity)?;
+        if let Some(column) = self.columns.get_mut(index) {
+            *column = match std::mem::replace(column, Column::Stack(Vec::new())) {
+                Column::Stack(items) => Column::Accordion(items),
+                Column::Accordion(items) => Column::Stack(items),
+                other => other,
+            };
+        }
+        Ok(())
+    }
+
+    /// Moves the column at `from` to the position `to`, shifting the columns in between.
+    /// `to` is clamped to the last column.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The index of the column to move.
+    /// * `to` - The destination index.
+    pub fn move_column(&mut self, from: usize, to: usize) {
+        if let Some(column) = self.columns.remove(from) {
+            let to = to.min(self.columns.len());
+            self.columns.insert(to, column);
+        }
+    }
+
+    /// Reverses the order of all columns in the strip.
+    pub fn reverse(&mut self) {
+        self.columns.make_contiguous().reverse();
+    }
+
+    /// Rotates the columns by `steps` positions. Positive values move every column to the right,
+    /// wrapping the last columns around to the front; negative values rotate to the left.
+    pub fn rotate(&mut self, steps: i32) {
+        let Ok(len) = i32::try_from(self.columns.len()) else {
+            return;
+        };
+        if len == 0 {
+            return;
+        }
+        if let Ok(steps) = usize::try_from(steps.rem_euclid(len)) {
+            self.columns.rotate_right(steps);
+        }
+    }
+
     /// Unstacks the window with the given ID from its entity stack.
     /// If the window is in a single panel, no action is taken.
     ///
@@ -587,8 +794,9 @@ impl LayoutStrip {
     pub fn unstack(&mut self, entity: Entity) -> Result<()> {
         let index = self.index_of(entity)?;
         let column = self.columns.remove(index).unwrap();
+        let accordion = matches!(column, Column::Accordion(_));
 
-        if let Column::Stack(mut items) = column {
+        if let Column::Stack(mut items) | Column::Accordion(mut items) = column {
             let item_index = items
                 .iter()
                 .position(|item| item.contains(entity))
@@ -611,7 +819,7 @@ impl LayoutStrip {
                         StackItem::Tabs(tabs) => Column::Tabs(tabs),
                     }
                 } else {
-                    Column::Stack(items)
+                    Column::stacked(items, accordion)
                 };
                 self.columns.insert(index, new_column);
             }
@@ -634,7 +842,9 @@ impl LayoutStrip {
             .iter()
             .flat_map(|column| match column {
                 Column::Single(entity) | Column::Fullscren(entity) => vec![*entity],
-                Column::Stack(items) => items.iter().flat_map(StackItem::window_iter).collect(),
+                Column::Stack(items) | Column::Accordion(items) => {
+                    items.iter().flat_map(StackItem::window_iter).collect()
+                }
                 Column::Tabs(ids) => ids.clone(),
             })
             .collect()
@@ -652,6 +862,17 @@ impl LayoutStrip {
         self.id
     }
 
+    /// Exchanges the columns of two strips, leaving their workspaces as they are.
+    pub fn swap_columns(&mut self, other: &mut LayoutStrip) {
+        std::mem::swap(&mut self.columns, &mut other.columns);
+    }
+
+    /// Moves the strip onto another workspace, e.g. when it is carried over to another display.
+    pub fn move_to_workspace(&mut self, id: WorkspaceId, virtual_index: u32) {
+        self.id = id;
+        self.virtual_index = virtual_index;
+    }
+
     pub fn columns(&self) -> impl Iterator<Item = &Column> {
         self.columns.iter()
     }
@@ -665,67 +886,127 @@ impl LayoutStrip {
     where
         W: Fn(Entity) -> Option<IRect>,
     {
-        const MIN_WINDOW_HEIGHT: i32 = 200;
+        self.relative_positions_with(
+            layout_strip_height,
+            StackLayout::default(),
+            get_window_frame,
+        )
+    }
 
-        self.column_positions(get_window_frame)
+    /// Like `relative_positions`, but lays out `Column::Stack` columns according to `stack_layout`.
+    pub fn relative_positions_with<W>(
+        &self,
+        layout_strip_height: i32,
+        stack_layout: StackLayout,
+        get_window_frame: &W,
+    ) -> impl Iterator<Item = (Entity, IRect)>
+    where
+        W: Fn(Entity) -> Option<IRect>,
+    {
+        self.column_positions(stack_layout.gap, get_window_frame)
             .filter_map(move |(column, position)| {
-                let items: Vec<StackItem> = match column {
-                    Column::Single(entity) | Column::Fullscren(entity) => {
-                        vec![StackItem::Single(*entity)]
-                    }
-                    Column::Stack(stack) => stack.clone(),
-                    Column::Tabs(tabs) => vec![StackItem::Tabs(tabs.clone())],
-                };
+                column_frames(
+                    column,
+                    position,
+                    layout_strip_height,
+                    stack_layout,
+                    get_window_frame,
+                    &|_| None,
+                )
+            })
+            .flatten()
+    }
 
-                let current_heights = items
-                    .iter()
-                    .filter_map(|item| item.top().and_then(get_window_frame))
-                    .map(|frame| frame.height())
-                    .collect::<Vec<_>>();
-
-                let heights =
-                    binpack_heights(&current_heights, MIN_WINDOW_HEIGHT, layout_strip_height)?;
-
-                // Every window in a column shares the master's (top item's)
-                // width, so a window stacked onto a master of a different width
-                // resizes to match it instead of keeping its own width. This
-                // also matches the column slot width from column_positions,
-                // which is the widest member.
-                let column_width = items
-                    .first()
-                    .and_then(StackItem::top)
-                    .and_then(&get_window_frame)
-                    .map(|frame| frame.width())?;
-
-                let mut next_y = 0;
-                let frames = items
-                    .into_iter()
-                    .zip(heights)
-                    .filter_map(|(item, height)| {
-                        let entity = item.top()?;
-                        let mut frame = get_window_frame(entity)?;
-                        frame.min.x = position;
-                        frame.max.x = frame.min.x + column_width;
-
-                        frame.min.y = next_y;
-                        frame.max.y = frame.min.y + height;
-
-                        next_y = frame.max.y;
-
-                        // Return ALL windows in the item with the same frame
-                        let results = item.window_iter().map(|e| (e, frame)).collect::<Vec<_>>();
-                        Some(results)
-                    })
-                    .flatten()
-                    .collect::<Vec<_>>();
+    /// Like `relative_positions_with`, but reuses the frames `cache` holds for the leading
+    /// columns which are unchanged since the previous call. Only the first changed column and
+    /// the ones after it, whose left edges may have shifted, are laid out again.
+    /// `get_min_height` returns the smallest height the application of a window accepts, if
+    /// known, so stacked windows are never packed below it.
+    pub fn relative_positions_cached<W, M>(
+        &self,
+        cache: &mut StripLayoutCache,
+        layout_strip_height: i32,
+        stack_layout: StackLayout,
+        get_window_frame: &W,
+        get_min_height: &M,
+    ) -> Vec<(Entity, IRect)>
+    where
+        W: Fn(Entity) -> Option<IRect>,
+        M: Fn(Entity) -> Option<i32>,
+    {
+        if cache.height != layout_strip_height || cache.stack_layout != stack_layout {
+            cache.height = layout_strip_height;
+            cache.stack_layout = stack_layout;
+            cache.columns.clear();
+        }
 
-                Some(frames)
+        let sizes_of = |column: &Column| {
+            column
+                .window_iter()
+                .map(|entity| get_window_frame(entity).map(|frame| frame.size()))
+                .collect::<Vec<_>>()
+        };
+        let min_heights_of =
+            |column: &Column| column.window_iter().map(get_min_height).collect::<Vec<_>>();
+        let dirty = self
+            .columns
+            .iter()
+            .zip(&cache.columns)
+            .position(|(column, cached)| {
+                *column != cached.column
+                    || sizes_of(column) != cached.sizes
+                    || min_heights_of(column) != cached.min_heights
             })
-            .flatten()
+            .unwrap_or_else(|| self.columns.len().min(cache.columns.len()));
+        trace!("reusing {dirty} of {} columns", self.columns.len());
+        cache.columns.truncate(dirty);
+
+        let next_edge = |left_edge: i32, width: Option<i32>| {
+            width.map_or(left_edge, |width| left_edge + width + stack_layout.gap)
+        };
+        let mut left_edge = cache
+            .columns
+            .last()
+            .map_or(0, |cached| next_edge(cached.left_edge, cached.width));
+        for column in &self.columns[dirty..] {
+            let width = column.width(get_window_frame);
+            let frames = width
+                .and_then(|_| {
+                    column_frames(
+                        column,
+                        left_edge,
+                        layout_strip_height,
+                        stack_layout,
+                        get_window_frame,
+                        get_min_height,
+                    )
+                })
+                .unwrap_or_default();
+            cache.columns.push(CachedColumn {
+                column: column.clone(),
+                sizes: sizes_of(column),
+                min_heights: min_heights_of(column),
+                left_edge,
+                width,
+                frames,
+            });
+            left_edge = next_edge(left_edge, width);
+        }
+
+        cache
+            .columns
+            .iter()
+            .flat_map(|cached| cached.frames.iter().copied())
+            .collect()
     }
 
+    /// Returns the columns with the left edges of their slots, `gap` pixels apart.
     #[instrument(level = Level::TRACE, skip_all)]
-    pub fn column_positions<W>(&self, get_window_frame: &W) -> impl Iterator<Item = (&Column, i32)>
+    pub fn column_positions<W>(
+        &self,
+        gap: i32,
+        get_window_frame: &W,
+    ) -> impl Iterator<Item = (&Column, i32)>
     where
         W: Fn(Entity) -> Option<IRect>,
     {
@@ -736,7 +1017,7 @@ impl LayoutStrip {
 
             width.map(|width| {
                 let temp = left_edge;
-                left_edge += width;
+                left_edge += width + gap;
                 (column, temp)
             })
         })
@@ -747,7 +1028,7 @@ impl LayoutStrip {
         let column = self.get(index).ok()?;
         match column {
             Column::Single(_) | Column::Tabs(_) | Column::Fullscren(_) => None,
-            Column::Stack(items) => {
+            Column::Stack(items) | Column::Accordion(items) => {
                 let pos = items.iter().position(|item| item.contains(entity))?;
                 (pos > 0).then(|| items[pos - 1].top()).flatten()
             }
@@ -759,7 +1040,7 @@ impl LayoutStrip {
             .and_then(|idx| self.get(idx))
             .map(|col| match col {
                 Column::Tabs(tabs) => tabs.contains(&entity),
-                Column::Stack(items) => items.iter().any(|item| {
+                Column::Stack(items) | Column::Accordion(items) => items.iter().any(|item| {
                     if let StackItem::Tabs(tabs) = item {
                         tabs.contains(&entity)
                     } else {
@@ -774,12 +1055,14 @@ impl LayoutStrip {
     pub fn tab_group(&self, entity: Entity) -> Option<Vec<Entity>> {
         self.columns.iter().find_map(|column| match column {
             Column::Tabs(tabs) if tabs.contains(&entity) && tabs.len() > 1 => Some(tabs.clone()),
-            Column::Stack(items) => items.iter().find_map(|item| match item {
-                StackItem::Tabs(tabs) if tabs.contains(&entity) && tabs.len() > 1 => {
-                    Some(tabs.clone())
-                }
-                StackItem::Single(_) | StackItem::Tabs(_) => None,
-            }),
+            Column::Stack(items) | Column::Accordion(items) => {
+                items.iter().find_map(|item| match item {
+                    StackItem::Tabs(tabs) if tabs.contains(&entity) && tabs.len() > 1 => {
+                        Some(tabs.clone())
+                    }
+                    StackItem::Single(_) | StackItem::Tabs(_) => None,
+                })
+            }
             Column::Single(_) | Column::Fullscren(_) | Column::Tabs(_) => None,
         })
     }
@@ -803,6 +1086,118 @@ impl std::fmt::Display for LayoutStrip {
     }
 }
 
+/// Lays out the windows of `column`, whose left edge sits at `position` within the strip.
+fn column_frames<W, M>(
+    column: &Column,
+    position: i32,
+    layout_strip_height: i32,
+    stack_layout: StackLayout,
+    get_window_frame: &W,
+    get_min_height: &M,
+) -> Option<Vec<(Entity, IRect)>>
+where
+    W: Fn(Entity) -> Option<IRect>,
+    M: Fn(Entity) -> Option<i32>,
+{
+    const MIN_WINDOW_HEIGHT: i32 = 200;
+
+    let items: Vec<StackItem> = match column {
+        Column::Single(entity) | Column::Fullscren(entity) => {
+            vec![StackItem::Single(*entity)]
+        }
+        Column::Stack(stack) | Column::Accordion(stack) => stack.clone(),
+        Column::Tabs(tabs) => vec![StackItem::Tabs(tabs.clone())],
+    };
+    let accordion = matches!(column, Column::Accordion(_));
+    let stacked = matches!(column, Column::Stack(_));
+    let tab_bar = if stacked {
+        stack_layout.tab_bar_height
+    } else {
+        0
+    };
+    // Accordion windows overlap anyway, only the windows of a stack are kept apart.
+    let gap = if stacked { stack_layout.gap } else { 0 };
+    let gaps = i32::try_from(items.len().saturating_sub(1)).ok()? * gap;
+    // Apps which refused a smaller height keep it as their minimum.
+    let min_height = |item: &StackItem| {
+        item.window_iter()
+            .filter_map(get_min_height)
+            .fold(MIN_WINDOW_HEIGHT, i32::max)
+    };
+    let largest_min_height = items
+        .iter()
+        .map(min_height)
+        .max()
+        .unwrap_or(MIN_WINDOW_HEIGHT);
+
+    let heights = if accordion {
+        accordion_heights(items.len(), largest_min_height, layout_strip_height)?
+    } else if let Some(ratio) = stack_layout
+        .main_ratio
+        .filter(|_| stacked && items.len() > 1)
+    {
+        main_biased_heights(
+            items.len(),
+            ratio,
+            largest_min_height,
+            layout_strip_height - tab_bar - gaps,
+        )?
+    } else {
+        let (current_heights, min_heights): (Vec<_>, Vec<_>) = items
+            .iter()
+            .filter_map(|item| {
+                let frame = item.top().and_then(get_window_frame)?;
+                Some((frame.height(), min_height(item)))
+            })
+            .unzip();
+        binpack_heights(
+            &current_heights,
+            &min_heights,
+            layout_strip_height - tab_bar - gaps,
+        )?
+    };
+
+    // Every window in a column shares the master's (top item's)
+    // width, so a window stacked onto a master of a different width
+    // resizes to match it instead of keeping its own width. This
+    // also matches the column slot width from column_positions,
+    // which is the widest member.
+    let column_width = items
+        .first()
+        .and_then(StackItem::top)
+        .and_then(&get_window_frame)
+        .map(|frame| frame.width())?;
+
+    let mut next_y = tab_bar;
+    let frames = items
+        .into_iter()
+        .zip(heights)
+        .filter_map(|(item, height)| {
+            let entity = item.top()?;
+            let mut frame = get_window_frame(entity)?;
+            frame.min.x = position;
+            frame.max.x = frame.min.x + column_width;
+
+            frame.min.y = next_y;
+            frame.max.y = frame.min.y + height;
+
+            // Accordion windows overlap, each one starting a sliver below the last.
+            next_y = if accordion {
+                frame.min.y + ACCORDION_SLIVER_HEIGHT
+            } else {
+                frame.max.y + gap
+            };
+
+            // Return ALL windows in the item with the same frame
+            let results = item.window_iter().map(|e| (e, frame)).collect::<Vec<_>>();
+            Some(results)
+        })
+        .flatten()
+        .collect::<Vec<_>>();
+
+    Some(frames)
+}
+
 /// Deduplicates `entities`, preserving first-seen order.
 fn dedup_entities(entities: &[Entity]) -> Vec<Entity> {
     let mut seen = EntityHashSet::default();
@@ -813,7 +1208,43 @@ fn dedup_entities(entities: &[Entity]) -> Vec<Entity> {
         .collect()
 }
 
-fn binpack_heights(heights: &[i32], min_height: i32, total_height: i32) -> Option<Vec<i32>> {
+/// Every accordion window gets the same height: the strip height minus one sliver for each of
+/// the other windows. Returns `None` if that leaves less than `min_height`.
+fn accordion_heights(count: usize, min_height: i32, total_height: i32) -> Option<Vec<i32>> {
+    let slivers = i32::try_from(count.saturating_sub(1)).ok()? * ACCORDION_SLIVER_HEIGHT;
+    let height = total_height - slivers;
+    (height >= min_height).then(|| vec![height; count])
+}
+
+/// The top window of a stack gets `ratio` of the height and the others split the rest evenly.
+/// The top share is reduced if the others would end up shorter than `min_height`.
+fn main_biased_heights(
+    count: usize,
+    ratio: f64,
+    min_height: i32,
+    total_height: i32,
+) -> Option<Vec<i32>> {
+    let others = i32::try_from(count.checked_sub(1)?).ok()?;
+    let main = (f64::from(total_height) * ratio.clamp(0.0, 1.0)).round() as i32;
+    let main = main.min(total_height - others * min_height);
+    if main < min_height || others == 0 {
+        return None;
+    }
+    let rest = total_height - main;
+    let height = rest / others;
+    let mut heights = vec![main];
+    heights.extend(std::iter::repeat_n(height, count - 1));
+    // The last window absorbs the rounding remainder.
+    if let Some(last) = heights.last_mut() {
+        *last += rest - height * others;
+    }
+    Some(heights)
+}
+
+/// Fits windows of the current `heights` into `total_height`, keeping as many leading windows at
+/// their height as possible. Each window gets at least its entry of `min_heights`; returns `None`
+/// if they do not fit.
+fn binpack_heights(heights: &[i32], min_heights: &[i32], total_height: i32) -> Option<Vec<i32>> {
     let mut count = heights.len();
     let mut output = vec![];
 
@@ -822,8 +1253,6 @@ fn binpack_heights(heights: &[i32], min_height: i32, total_height: i32) -> Optio
 
         let mut remaining = total_height;
         while idx < count {
-            let remaining_windows = heights.len() - idx;
-
             if heights[idx] < remaining {
                 if idx + 1 == count {
                     output.push(remaining);
@@ -831,7 +1260,7 @@ fn binpack_heights(heights: &[i32], min_height: i32, total_height: i32) -> Optio
                     output.push(heights[idx]);
                 }
                 remaining -= heights[idx];
-            } else if remaining >= min_height * i32::try_from(remaining_windows).ok()? {
+            } else if remaining >= min_heights.iter().skip(idx).sum::<i32>() {
                 output.push(remaining);
                 remaining = 0;
             } else {
@@ -851,16 +1280,14 @@ fn binpack_heights(heights: &[i32], min_height: i32, total_height: i32) -> Optio
     if remaining > 0 && count > 0 {
         count -= 1;
         output.truncate(count);
-        let sum = output.iter().sum::<i32>();
-        let avg_height = (f64::from(total_height - sum) / f64::from(remaining + 1)) as i32;
-        if avg_height < min_height {
+        // The windows which did not fit share what is left evenly on top of their minimums.
+        let tail = min_heights.get(count..)?;
+        let spare = total_height - output.iter().sum::<i32>() - tail.iter().sum::<i32>();
+        if spare < 0 {
             return None;
         }
-
-        while count < heights.len() {
-            output.push(avg_height);
-            count += 1;
-        }
+        let share = spare / (remaining + 1);
+        output.extend(tail.iter().map(|min_height| min_height + share));
     }
 
     Some(output)
@@ -938,7 +1365,7 @@ fn strip_has_changed_window(strip: &LayoutStrip, changed_entities: &EntityHashSe
 fn column_has_changed_window(column: &Column, changed_entities: &EntityHashSet) -> bool {
     match column {
         Column::Single(entity) | Column::Fullscren(entity) => changed_entities.contains(entity),
-        Column::Stack(stack) => stack
+        Column::Stack(stack) | Column::Accordion(stack) => stack
             .iter()
             .any(|item| stack_item_has_changed_window(item, changed_entities)),
         Column::Tabs(entities) => entities
@@ -956,34 +1383,88 @@ fn stack_item_has_changed_window(item: &StackItem, changed_entities: &EntityHash
     }
 }
 
+/// Lays out every strip again when the `[padding]` or `[displays]` sections or `smart_gaps`
+/// change, so a new gap takes effect right away instead of with the next change to a strip.
+fn relayout_on_padding_change(
+    mut messages: MessageReader<Event>,
+    mut strips: Query<&mut LayoutStrip>,
+) {
+    let changed = messages.read().any(|event| match event {
+        Event::ConfigReloaded(changes) => {
+            changes.options.iter().any(|option| option == "smart_gaps")
+                || changes
+                    .sections
+                    .iter()
+                    .any(|section| matches!(section.as_str(), "padding" | "displays"))
+        }
+        _ => false,
+    });
+    if changed {
+        for mut strip in &mut strips {
+            strip.set_changed();
+        }
+    }
+}
+
 /// Watches for changes to `LayoutStrip` (i.e. a window added or window order changed) and
-/// re-calculates the logical positions of all the windows in the layout strip.
+/// re-calculates the logical positions of the windows in the layout strip. Columns ahead of the
+/// first changed one keep the positions cached from the previous pass.
 #[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
 #[instrument(level = Level::DEBUG, skip_all)]
 fn layout_strip_changed(
-    changed_strips: Populated<(&LayoutStrip, &ChildOf), Changed<LayoutStrip>>,
+    changed_strips: Populated<
+        (Entity, &LayoutStrip, &ChildOf),
+        (Changed<LayoutStrip>, Without<OverviewMarker>),
+    >,
+    all_strips: Query<(), With<LayoutStrip>>,
     mut windows: Query<
         (&Position, &mut Bounds, &mut LayoutPosition),
         (Without<LayoutStrip>, With<Window>),
     >,
+    min_heights: Query<&MinimumHeight>,
     displays: Query<(&Display, Option<&DockPosition>)>,
     config: Res<Config>,
+    mut caches: Local<EntityHashMap<StripLayoutCache>>,
 ) {
+    caches.retain(|strip_entity, _| all_strips.contains(*strip_entity));
+
+    let stack_layout = StackLayout {
+        tab_bar_height: if config.stack_tabs() {
+            STACK_TAB_BAR_HEIGHT
+        } else {
+            0
+        },
+        main_ratio: config.stack_main_ratio(),
+        gap: 0,
+    };
     let get_window_frame = |entity| {
         windows
             .get(entity)
             .map(|(position, bounds, _)| IRect::from_corners(position.0, position.0 + bounds.0))
             .ok()
     };
+    let get_min_height = |entity| min_heights.get(entity).ok().map(|min| min.0);
 
     let changed = changed_strips
         .into_iter()
-        .filter_map(|(layout_strip, child_of)| {
+        .filter_map(|(strip_entity, layout_strip, child_of)| {
             displays
                 .get(child_of.parent())
                 .map(|(display, dock)| {
-                    let height = display.actual_display_bounds(dock, &config).height();
-                    layout_strip.relative_positions(height, &get_window_frame)
+                    let height = display
+                        .workspace_bounds(dock, &config, Some(layout_strip))
+                        .height();
+                    let stack_layout = StackLayout {
+                        gap: config.gap_for(display.uuid()),
+                        ..stack_layout
+                    };
+                    layout_strip.relative_positions_cached(
+                        caches.entry(strip_entity).or_default(),
+                        height,
+                        stack_layout,
+                        &get_window_frame,
+                        &get_min_height,
+                    )
                 })
                 .ok()
         })
@@ -1016,17 +1497,32 @@ fn reshuffle_layout_strip(
     displays: Query<(&Display, Option<&DockPosition>)>,
     windows: Windows,
     config: Res<Config>,
+    time: Res<Time>,
+    mut last_reshuffle: Local<EntityHashMap<Duration>>,
     mut commands: Commands,
 ) {
+    let now = time.elapsed();
+    let debounce = config.reshuffle_debounce();
+    last_reshuffle.retain(|_, at| now.saturating_sub(*at) < debounce);
+
     markers.into_iter().for_each(|(entity, layout_position)| {
+        let strip = strips.into_iter().find(|strip| strip.0.contains(entity));
+        // A strip reshuffled in an earlier frame moments ago keeps the marker until the debounce
+        // passes, so a burst of resize events ends in a single layout pass with the final frames.
+        if strip.as_ref().is_some_and(|(_, strip_entity, _, _, _)| {
+            last_reshuffle.get(strip_entity).is_some_and(|at| *at < now)
+        }) {
+            return;
+        }
         if let Ok(mut cmd) = commands.get_entity(entity) {
             cmd.try_remove::<ReshuffleAroundMarker>();
         }
-        let Some((strip, strip_entity, active_strip, child, active_marker)) =
-            strips.into_iter().find(|strip| strip.0.contains(entity))
-        else {
+        let Some((strip, strip_entity, active_strip, child, active_marker)) = strip else {
             return;
         };
+        if !debounce.is_zero() {
+            last_reshuffle.insert(strip_entity, now);
+        }
 
         if active_marker.is_some_and(|m| m.is_added()) {
             trace!("reshuffle_layout_strip: skipping newly active workspace {strip_entity}");
@@ -1035,7 +1531,7 @@ fn reshuffle_layout_strip(
         let Ok((active_display, dock)) = displays.get(child.parent()) else {
             return;
         };
-        let display_bounds = active_display.actual_display_bounds(dock, &config);
+        let display_bounds = active_display.workspace_bounds(dock, &config, Some(strip));
         let Some(mut frame) = windows.moving_frame(entity) else {
             return;
         };
@@ -1052,7 +1548,7 @@ fn reshuffle_layout_strip(
         // Enforce the edge invariant when auto-center is off: the leftmost
         // window must touch the left edge and the rightmost the right edge
         // if more than 1 windows in workspace.
-        if !config.auto_center()
+        if !config.auto_center_on(Some(strip.id()))
             && !config.continuous_swipe()
             && let Some(total_strip_width) = strip
                 .last()
@@ -1082,7 +1578,8 @@ fn reshuffle_layout_strip(
         // portion. If the hidden fraction is within the allowed ratio, skip.
         let hidden_ratio = config.window_hidden_ratio();
         if hidden_ratio > 0.0 {
-            let meaningful = (visible_width - config.sliver_width()).max(0);
+            let meaningful =
+                (visible_width - config.sliver_width_for(active_display.uuid())).max(0);
             let visible_fraction = f64::from(meaningful) / f64::from(frame.width().max(1));
             let hidden_fraction = 1.0 - visible_fraction;
 
@@ -1125,7 +1622,7 @@ fn ensure_visible_in_strip(
         if let Ok(mut cmd) = commands.get_entity(entity) {
             cmd.try_remove::<EnsureVisibleMarker>();
         }
-        let Some((_, strip_entity, strip_position, child, active_marker)) =
+        let Some((strip, strip_entity, strip_position, child, active_marker)) =
             strips.into_iter().find(|s| s.0.contains(entity))
         else {
             return;
@@ -1141,7 +1638,7 @@ fn ensure_visible_in_strip(
         let Some(size) = windows.size(entity) else {
             return;
         };
-        let viewport = display.actual_display_bounds(dock, &config);
+        let viewport = display.workspace_bounds(dock, &config, Some(strip));
 
         // Where the entity would appear if the strip stays put.
         let candidate_min = layout_position.0 + strip_position.0;
@@ -1175,151 +1672,197 @@ fn position_layout_strips(
 }
 
 #[derive(Clone, Copy)]
-struct StripWindowContext {
+struct StripWindowContext<'a> {
+    strip: &'a LayoutStrip,
     strip_position: Origin,
     swiping: bool,
     display_entity: Entity,
     stacked: bool,
+    /// Which side of the strip's zen column the window's column lies on, if zen mode is on.
+    zen: Option<Ordering>,
 }
 
-fn insert_strip_window_contexts(
-    contexts: &mut EntityHashMap<StripWindowContext>,
-    strip: &LayoutStrip,
+fn insert_strip_window_contexts<'a>(
+    contexts: &mut EntityHashMap<StripWindowContext<'a>>,
+    strip: &'a LayoutStrip,
     strip_position: Origin,
     swiping: bool,
     display_entity: Entity,
+    zen_column: Option<usize>,
 ) {
-    for column in &strip.columns {
-        insert_column_window_contexts(
-            contexts,
-            column,
+    for (index, column) in strip.columns.iter().enumerate() {
+        let context = StripWindowContext {
+            strip,
             strip_position,
             swiping,
             display_entity,
-            matches!(column, Column::Stack(_)),
-        );
+            stacked: matches!(column, Column::Stack(_) | Column::Accordion(_)),
+            zen: zen_column.map(|zen| index.cmp(&zen)),
+        };
+        insert_column_window_contexts(contexts, column, context);
     }
 }
 
-fn insert_column_window_contexts(
-    contexts: &mut EntityHashMap<StripWindowContext>,
+fn insert_column_window_contexts<'a>(
+    contexts: &mut EntityHashMap<StripWindowContext<'a>>,
     column: &Column,
-    strip_position: Origin,
-    swiping: bool,
-    display_entity: Entity,
-    stacked: bool,
+    context: StripWindowContext<'a>,
 ) {
     match column {
         Column::Single(entity) | Column::Fullscren(entity) => {
-            contexts.insert(
-                *entity,
-                StripWindowContext {
-                    strip_position,
-                    swiping,
-                    display_entity,
-                    stacked,
-                },
-            );
+            contexts.insert(*entity, context);
         }
-        Column::Stack(items) => {
+        Column::Stack(items) | Column::Accordion(items) => {
             for item in items {
-                insert_stack_item_window_contexts(
-                    contexts,
-                    item,
-                    strip_position,
-                    swiping,
-                    display_entity,
-                    stacked,
-                );
+                insert_stack_item_window_contexts(contexts, item, context);
             }
         }
         Column::Tabs(entities) => {
             for entity in entities {
-                contexts.insert(
-                    *entity,
-                    StripWindowContext {
-                        strip_position,
-                        swiping,
-                        display_entity,
-                        stacked,
-                    },
-                );
+                contexts.insert(*entity, context);
             }
         }
     }
 }
 
-fn insert_stack_item_window_contexts(
-    contexts: &mut EntityHashMap<StripWindowContext>,
+fn insert_stack_item_window_contexts<'a>(
+    contexts: &mut EntityHashMap<StripWindowContext<'a>>,
     item: &StackItem,
-    strip_position: Origin,
-    swiping: bool,
-    display_entity: Entity,
-    stacked: bool,
+    context: StripWindowContext<'a>,
 ) {
     match item {
         StackItem::Single(entity) => {
-            contexts.insert(
-                *entity,
-                StripWindowContext {
-                    strip_position,
-                    swiping,
-                    display_entity,
-                    stacked,
-                },
-            );
+            contexts.insert(*entity, context);
         }
         StackItem::Tabs(entities) => {
             for entity in entities {
-                contexts.insert(
-                    *entity,
-                    StripWindowContext {
-                        strip_position,
-                        swiping,
-                        display_entity,
-                        stacked,
-                    },
-                );
+                contexts.insert(*entity, context);
+            }
+        }
+    }
+}
+
+/// Marks windows which were just pinned or unpinned as requiring re-positioning.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+#[instrument(level = Level::DEBUG, skip_all)]
+fn pin_layout_windows(
+    pinned: Query<Entity, (Changed<PinnedMarker>, With<Window>)>,
+    mut unpinned: RemovedComponents<PinnedMarker>,
+    mut windows: Query<&mut LayoutPosition, (With<Window>, Without<LayoutStrip>)>,
+) {
+    for entity in pinned.iter().chain(unpinned.read()) {
+        if let Ok(mut position) = windows.get_mut(entity) {
+            position.set_changed();
+        }
+    }
+}
+
+/// Marks windows leaving the overview as requiring re-positioning, as their layout was frozen
+/// while the overview showed them shrunk.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+#[instrument(level = Level::DEBUG, skip_all)]
+fn overview_layout_windows(
+    mut restored: RemovedComponents<OverviewFrame>,
+    mut windows: Query<&mut LayoutPosition, (With<Window>, Without<LayoutStrip>)>,
+) {
+    for entity in restored.read() {
+        if let Ok(mut position) = windows.get_mut(entity) {
+            position.set_changed();
+        }
+    }
+}
+
+/// Marks the windows of strips entering or leaving zen mode as requiring re-positioning.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+#[instrument(level = Level::DEBUG, skip_all)]
+fn zen_layout_windows(
+    entered: Query<Entity, (Added<ZenMarker>, With<Window>)>,
+    mut left: RemovedComponents<ZenMarker>,
+    strips: Query<&LayoutStrip>,
+    mut windows: Query<&mut LayoutPosition, (With<Window>, Without<LayoutStrip>)>,
+) {
+    for entity in entered.iter().chain(left.read()) {
+        let Some(strip) = strips.iter().find(|strip| strip.contains(entity)) else {
+            continue;
+        };
+        for entity in strip.all_windows() {
+            if let Ok(mut position) = windows.get_mut(entity) {
+                position.set_changed();
             }
         }
     }
 }
 
+/// Leaves zen mode when focus moves to another window, giving the zen window its width back.
+#[allow(clippy::needless_pass_by_value)]
+#[instrument(level = Level::DEBUG, skip_all)]
+fn leave_zen_on_focus_change(
+    focused: Query<Entity, (Added<FocusedMarker>, With<Window>)>,
+    zens: Query<(Entity, &ZenMarker)>,
+    mut commands: Commands,
+) {
+    for focused in &focused {
+        for (entity, marker) in zens.iter().filter(|(entity, _)| *entity != focused) {
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.try_remove::<ZenMarker>();
+            }
+            commands.resize_entity(entity, marker.frame.size());
+        }
+    }
+}
+
 /// Reacts to changes of logical window layout in the strip and any have been changed, reposition
 /// the layout strip against the current display viewport.
 #[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
 #[instrument(level = Level::DEBUG, skip_all)]
 fn position_layout_windows(
     positioned_windows: Populated<
-        (Entity, &Window, &LayoutPosition, &mut Position, &mut Bounds),
-        (Changed<LayoutPosition>, With<Window>, Without<LayoutStrip>),
+        (
+            Entity,
+            &Window,
+            &LayoutPosition,
+            &mut Position,
+            &mut Bounds,
+            Option<&PinnedMarker>,
+        ),
+        (
+            Changed<LayoutPosition>,
+            With<Window>,
+            Without<LayoutStrip>,
+            Without<OverviewFrame>,
+        ),
     >,
     workspaces: Query<(&LayoutStrip, &Position, Has<Scrolling>, &ChildOf), With<LayoutStrip>>,
+    zens: Query<Entity, With<ZenMarker>>,
     displays: Query<(&Display, Option<&DockPosition>)>,
     config: Res<Config>,
     mut commands: Commands,
 ) {
-    let offscreen_sliver_width = config.sliver_width();
-    let (_, pad_right, _, pad_left) = config.edge_padding();
     let mut strip_contexts = EntityHashMap::default();
     for (layout_strip, Position(strip_position), swiping, child_of) in &workspaces {
+        let zen_column = zens
+            .iter()
+            .find_map(|entity| layout_strip.index_of(entity).ok());
         insert_strip_window_contexts(
             &mut strip_contexts,
             layout_strip,
             *strip_position,
             swiping,
             child_of.parent(),
+            zen_column,
         );
     }
 
-    for (entity, window, layout_position, mut position, mut bounds) in positioned_windows {
+    for (entity, window, layout_position, mut position, mut bounds, pinned) in positioned_windows {
         let Some(context) = strip_contexts.get(&entity) else {
             return;
         };
         let Ok((display, dock)) = displays.get(context.display_entity) else {
             return;
         };
-        let viewport = display.actual_display_bounds(dock, &config);
+        let viewport = display.workspace_bounds(dock, &config, Some(context.strip));
+        let offscreen_sliver_width = config.sliver_width_for(display.uuid());
+        let (_, pad_right, _, pad_left) = display.edge_padding(&config, Some(context.strip));
         // Gets 80% of the display height as threshold.
         let Ok(vertical_move_threshold) = u32::try_from(viewport.height() * 8 / 10) else {
             continue;
@@ -1335,7 +1878,20 @@ fn position_layout_windows(
         frame.max += context.strip_position;
 
         let mut offscreen = false;
-        if frame.max.x <= viewport.min.x + h_pad {
+        if let Some(pinned) = pinned {
+            // Pinned columns stay at their edge while the strip scrolls past them.
+            frame.min.x = match pinned {
+                PinnedMarker::Left => viewport.min.x,
+                PinnedMarker::Right => viewport.max.x - width,
+            };
+        } else if let Some(side @ (Ordering::Less | Ordering::Greater)) = context.zen {
+            // Zen mode slides the other columns fully past the display edges, without slivers.
+            frame.min.x = if side == Ordering::Less {
+                viewport.min.x - pad_left - width
+            } else {
+                viewport.max.x + pad_right - h_pad
+            };
+        } else if frame.max.x <= viewport.min.x + h_pad {
             // Window hidden to the left — position so exactly
             // sliver_width CG pixels are visible from the real
             // display edge.  The +h_pad accounts for the gap that
@@ -1361,8 +1917,9 @@ fn position_layout_windows(
             // column scrolls back on-screen, binpack_heights makes the
             // last window absorb all remaining space.
             if !context.stacked {
-                let inset =
-                    (f64::from(viewport.height()) * (1.0 - config.sliver_height()) / 2.0) as i32;
+                let inset = (f64::from(viewport.height())
+                    * (1.0 - config.sliver_height_for(display.uuid()))
+                    / 2.0) as i32;
                 frame.min.y += inset;
                 frame.max.y += inset;
             }
@@ -1396,6 +1953,148 @@ fn position_layout_windows(
     }
 }
 
+/// A tab in the tab bar drawn above a stacked column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackTab {
+    /// The top window of the stack item the tab stands for.
+    pub entity: Entity,
+    pub title: String,
+    /// Whether the focused window belongs to this stack item.
+    pub active: bool,
+}
+
+/// The tab bar of a stacked column in the active strip, in absolute display coordinates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StackTabBar {
+    pub frame: IRect,
+    pub tabs: Vec<StackTab>,
+}
+
+impl StackTabBar {
+    /// Returns the window whose tab lies under `point`. Tabs share the bar width evenly.
+    pub fn tab_at(&self, point: Origin) -> Option<Entity> {
+        if self.tabs.is_empty() || !self.frame.contains(point) || point.x == self.frame.max.x {
+            return None;
+        }
+        let count = i32::try_from(self.tabs.len()).ok()?;
+        let index = (point.x - self.frame.min.x) * count / self.frame.width().max(1);
+        self.tabs
+            .get(usize::try_from(index).ok()?)
+            .map(|tab| tab.entity)
+    }
+}
+
+/// Tab bars of the stacked columns visible in the active strip. Empty unless `stack_tabs` is
+/// enabled.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct StackTabBars(pub Vec<StackTabBar>);
+
+impl StackTabBars {
+    /// Returns the window whose tab lies under `point`, in any of the tab bars.
+    pub fn tab_at(&self, point: Origin) -> Option<Entity> {
+        self.0.iter().find_map(|bar| bar.tab_at(point))
+    }
+}
+
+/// Recomputes the tab bars of the stacked columns in the active strip, whenever the strip, its
+/// position, the focus or a window title changes.
+#[allow(
+    clippy::needless_pass_by_value,
+    clippy::type_complexity,
+    clippy::too_many_arguments
+)]
+#[instrument(level = Level::TRACE, skip_all)]
+fn update_stack_tab_bars(
+    active_strip: Query<
+        (Ref<LayoutStrip>, Ref<Position>, &ChildOf),
+        (With<ActiveWorkspaceMarker>, Without<Window>),
+    >,
+    layout_positions: Query<(&LayoutPosition, &Bounds), With<Window>>,
+    focus_gained: Query<(), Added<FocusedMarker>>,
+    mut focus_lost: RemovedComponents<FocusedMarker>,
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    displays: Query<(&Display, Option<&DockPosition>)>,
+    config: Res<Config>,
+    mut tab_bars: ResMut<StackTabBars>,
+) {
+    if !config.stack_tabs() {
+        if !tab_bars.0.is_empty() {
+            tab_bars.0.clear();
+        }
+        return;
+    }
+    let focus_lost = focus_lost.read().count() > 0;
+    let focus_changed = focus_lost || !focus_gained.is_empty();
+    let title_changed = messages
+        .read()
+        .any(|event| matches!(event, Event::WindowTitleChanged { .. }));
+    let Ok((strip, strip_position, child_of)) = active_strip.single() else {
+        return;
+    };
+    if !(strip.is_changed()
+        || strip_position.is_changed()
+        || focus_changed
+        || title_changed
+        || config.is_changed())
+    {
+        return;
+    }
+    let Ok((display, dock)) = displays.get(child_of.parent()) else {
+        return;
+    };
+    let viewport = display.workspace_bounds(dock, &config, Some(&strip));
+    let focused = windows.focused().map(|(_, entity)| entity);
+
+    let bars = strip
+        .columns
+        .iter()
+        .filter_map(|column| {
+            let Column::Stack(items) = column else {
+                return None;
+            };
+            let top = items.first().and_then(StackItem::top)?;
+            let (layout_position, bounds) = layout_positions.get(top).ok()?;
+            let min = layout_position.0 + strip_position.0;
+            let mut frame = IRect::new(
+                min.x,
+                min.y - STACK_TAB_BAR_HEIGHT,
+                min.x + bounds.0.x,
+                min.y,
+            );
+            // Only the on-screen part of a column gets a tab bar.
+            frame.min.x = frame.min.x.max(viewport.min.x);
+            frame.max.x = frame.max.x.min(viewport.max.x);
+            if frame.width() <= 0 {
+                return None;
+            }
+
+            let tabs = items
+                .iter()
+                .filter_map(|item| {
+                    let entity = item.top()?;
+                    let title = windows
+                        .get(entity)
+                        .and_then(|window| window.title().ok())
+                        .unwrap_or_default();
+                    let active =
+                        focused.is_some_and(|focused| item.window_iter().any(|e| e == focused));
+                    Some(StackTab {
+                        entity,
+                        title,
+                        active,
+                    })
+                })
+                .collect();
+            Some(StackTabBar { frame, tabs })
+        })
+        .collect::<Vec<_>>();
+
+    if tab_bars.0 != bars {
+        tab_bars.0 = bars;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1468,7 +2167,14 @@ mod tests {
         let strip_position = Origin::new(10, 20);
         let mut contexts = EntityHashMap::default();
 
-        insert_strip_window_contexts(&mut contexts, &strip, strip_position, true, display_entity);
+        insert_strip_window_contexts(
+            &mut contexts,
+            &strip,
+            strip_position,
+            true,
+            display_entity,
+            None,
+        );
 
         let stacked_leader = contexts.get(&entities[0]).unwrap();
         let stacked_follower = contexts.get(&entities[1]).unwrap();
@@ -1515,7 +2221,7 @@ mod tests {
                 assert_eq!(stack[0], StackItem::Single(entities[0]));
                 assert_eq!(stack[1], StackItem::Single(entities[1]));
             }
-            Column::Single(_) | Column::Fullscren(_) | Column::Tabs(_) => {
+            Column::Single(_) | Column::Fullscren(_) | Column::Tabs(_) | Column::Accordion(_) => {
                 panic!("Expected a stack")
             }
         }
@@ -1528,25 +2234,126 @@ mod tests {
         assert_eq!(strip.index_of(entities[2]).unwrap(), 2);
     }
 
+    #[test]
+    fn test_stack_onto_non_adjacent_column() {
+        let (_world, mut strip, entities) = setup_world_and_strip();
+
+        // Stack [2] onto [0], skipping over [1].
+        strip.stack_onto(entities[2], entities[0]).unwrap();
+        assert_eq!(strip.len(), 2);
+        assert_eq!(strip.index_of(entities[2]).unwrap(), 0);
+        assert_eq!(strip.index_of(entities[1]).unwrap(), 1);
+        assert_eq!(strip.above(entities[2]), Some(entities[0]));
+
+        assert!(strip.stack_onto(entities[1], entities[1]).is_err());
+    }
+
+    #[test]
+    fn test_move_column() {
+        let (_world, mut strip, entities) = setup_world_and_strip();
+
+        strip.move_column(0, 2);
+        assert_eq!(strip.index_of(entities[1]).unwrap(), 0);
+        assert_eq!(strip.index_of(entities[2]).unwrap(), 1);
+        assert_eq!(strip.index_of(entities[0]).unwrap(), 2);
+
+        // Out of range destinations clamp to the end.
+        strip.move_column(0, 10);
+        assert_eq!(strip.index_of(entities[1]).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_reverse_and_rotate() {
+        let (_world, mut strip, entities) = setup_world_and_strip();
+
+        strip.reverse();
+        assert_eq!(strip.index_of(entities[2]).unwrap(), 0);
+        assert_eq!(strip.index_of(entities[0]).unwrap(), 2);
+
+        strip.rotate(1);
+        assert_eq!(strip.index_of(entities[0]).unwrap(), 0);
+        assert_eq!(strip.index_of(entities[2]).unwrap(), 1);
+        assert_eq!(strip.index_of(entities[1]).unwrap(), 2);
+
+        // Negative steps rotate to the left and wrap around the strip length.
+        strip.rotate(-4);
+        assert_eq!(strip.index_of(entities[2]).unwrap(), 0);
+        assert_eq!(strip.index_of(entities[1]).unwrap(), 1);
+        assert_eq!(strip.index_of(entities[0]).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_accordion_toggle_and_positions() {
+        let (_world, mut strip, entities) = setup_world_and_strip();
+        strip.stack(entities[1]).unwrap();
+        strip.stack(entities[2]).unwrap();
+
+        strip.toggle_accordion(entities[1]).unwrap();
+        assert!(matches!(strip.get(0).unwrap(), Column::Accordion(ref items) if items.len() == 3));
+
+        // Windows overlap, each offset by a sliver and sharing the remaining height.
+        let get_window_frame = |_| Some(IRect::new(0, 0, 100, 300));
+        let frames = strip
+            .relative_positions(400, &get_window_frame)
+            .collect::<Vec<_>>();
+        let height = 400 - 2 * ACCORDION_SLIVER_HEIGHT;
+        for (row, entity) in entities.iter().enumerate() {
+            let (_, frame) = frames.iter().find(|(e, _)| e == entity).unwrap();
+            assert_eq!(
+                frame.min.y,
+                i32::try_from(row).unwrap() * ACCORDION_SLIVER_HEIGHT
+            );
+            assert_eq!(frame.height(), height);
+        }
+
+        // Removing a window keeps the accordion; toggling again restores a plain stack.
+        strip.remove(entities[2]);
+        assert!(matches!(strip.get(0).unwrap(), Column::Accordion(_)));
+        strip.toggle_accordion(entities[0]).unwrap();
+        assert!(matches!(strip.get(0).unwrap(), Column::Stack(_)));
+    }
+
     #[test]
     fn test_binpack() {
         const MIN_HEIGHT: i32 = 100;
         let heights = [300, 300, 300, 300];
 
-        let out = binpack_heights(&heights, MIN_HEIGHT, 1500).unwrap();
+        let out = binpack_heights(&heights, &[MIN_HEIGHT; 4], 1500).unwrap();
         assert_eq!(out, vec![300, 300, 300, 600]);
 
-        let out = binpack_heights(&heights, MIN_HEIGHT, 1024).unwrap();
+        let out = binpack_heights(&heights, &[MIN_HEIGHT; 4], 1024).unwrap();
         assert_eq!(out, vec![300, 300, 300, 124]);
 
-        let out = binpack_heights(&heights, MIN_HEIGHT, 800).unwrap();
+        let out = binpack_heights(&heights, &[MIN_HEIGHT; 4], 800).unwrap();
         assert_eq!(out, vec![300, 300, 100, 100]);
 
-        let out = binpack_heights(&heights, MIN_HEIGHT, 440).unwrap();
+        let out = binpack_heights(&heights, &[MIN_HEIGHT; 4], 440).unwrap();
         assert_eq!(out, vec![110, 110, 110, 110]);
 
-        let out = binpack_heights(&heights, MIN_HEIGHT, 390);
+        let out = binpack_heights(&heights, &[MIN_HEIGHT; 4], 390);
         assert_eq!(out, None);
+
+        // A window with a larger minimum keeps it, the others share the rest.
+        let out = binpack_heights(&heights, &[MIN_HEIGHT; 4], 1000).unwrap();
+        assert_eq!(out, vec![300, 300, 300, 100]);
+        let out = binpack_heights(&heights, &[100, 100, 100, 250], 1000).unwrap();
+        assert_eq!(out, vec![300, 300, 125, 275]);
+        assert_eq!(binpack_heights(&heights, &[100, 100, 100, 800], 1000), None);
+    }
+
+    #[test]
+    fn main_biased_heights_reserve_top_share() {
+        assert_eq!(
+            main_biased_heights(3, 0.6, 100, 1000),
+            Some(vec![600, 200, 200])
+        );
+        assert_eq!(main_biased_heights(2, 0.5, 100, 1001), Some(vec![501, 500]));
+        // The top share shrinks so the others keep their minimum height.
+        assert_eq!(
+            main_biased_heights(4, 0.9, 100, 1000),
+            Some(vec![700, 100, 100, 100])
+        );
+        assert_eq!(main_biased_heights(3, 0.6, 200, 500), None);
     }
 
     #[test]
@@ -1874,6 +2681,179 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_shows_single_window() {
+        let mut world = World::new();
+        let e1 = world.spawn_empty().id();
+        let e2 = world.spawn_empty().id();
+
+        let mut strip = LayoutStrip::default();
+        assert!(!strip.shows_single_window());
+        strip.append(e1);
+        assert!(strip.shows_single_window());
+        strip.append(e2);
+        assert!(!strip.shows_single_window());
+        strip.stack(e2).unwrap();
+        assert!(!strip.shows_single_window());
+        strip.remove(e2);
+        assert!(strip.shows_single_window());
+    }
+
+    #[test]
+    fn test_positions_with_gap() {
+        let mut world = World::new();
+        let e1 = world.spawn_empty().id();
+        let e2 = world.spawn_empty().id();
+        let e3 = world.spawn_empty().id();
+
+        let mut strip = LayoutStrip::default();
+        strip.append(e1);
+        strip.append(e2);
+        strip.append(e3);
+        strip.stack(e2).unwrap();
+
+        let get_window_frame = |_| Some(IRect::new(0, 0, 300, 300));
+        let stack_layout = StackLayout {
+            gap: 10,
+            ..StackLayout::default()
+        };
+
+        let out: Vec<_> = strip
+            .relative_positions_with(600, stack_layout, &get_window_frame)
+            .collect();
+        assert_eq!(
+            out,
+            vec![
+                (e1, IRect::new(0, 0, 300, 300)),
+                (e2, IRect::new(0, 310, 300, 600)),
+                (e3, IRect::new(310, 0, 610, 600)),
+            ]
+        );
+
+        let mut cache = StripLayoutCache::default();
+        let cached = strip.relative_positions_cached(
+            &mut cache,
+            600,
+            stack_layout,
+            &get_window_frame,
+            &|_| None,
+        );
+        assert_eq!(cached, out);
+    }
+
+    #[test]
+    fn test_cached_positions_recompute_from_changed_column() {
+        let mut world = World::new();
+        let entities = world.spawn_batch(vec![(); 4]).collect::<Vec<Entity>>();
+
+        let mut strip = LayoutStrip::default();
+        for &e in &entities {
+            strip.append(e);
+        }
+
+        let widths = std::cell::RefCell::new(vec![300, 400, 500, 600]);
+        let get_window_frame = |e: Entity| {
+            let index = entities.iter().position(|&other| other == e)?;
+            Some(IRect::new(0, 0, widths.borrow()[index], 600))
+        };
+        let full = |strip: &LayoutStrip| {
+            strip
+                .relative_positions_with(600, StackLayout::default(), &get_window_frame)
+                .collect::<Vec<_>>()
+        };
+
+        let mut cache = StripLayoutCache::default();
+        let out = strip.relative_positions_cached(
+            &mut cache,
+            600,
+            StackLayout::default(),
+            &get_window_frame,
+            &|_| None,
+        );
+        assert_eq!(out, full(&strip));
+
+        // Widening the second column shifts every column after it.
+        widths.borrow_mut()[1] = 450;
+        let out = strip.relative_positions_cached(
+            &mut cache,
+            600,
+            StackLayout::default(),
+            &get_window_frame,
+            &|_| None,
+        );
+        assert_eq!(out, full(&strip));
+        let xs: Vec<_> = out.iter().map(|(_, f)| f.min.x).collect();
+        assert_eq!(xs, vec![0, 300, 750, 1250]);
+
+        // Reordering columns invalidates the cache from the first moved column.
+        strip.swap(2, 3);
+        let out = strip.relative_positions_cached(
+            &mut cache,
+            600,
+            StackLayout::default(),
+            &get_window_frame,
+            &|_| None,
+        );
+        assert_eq!(out, full(&strip));
+
+        // A different strip height lays out every column again.
+        let out = strip.relative_positions_cached(
+            &mut cache,
+            500,
+            StackLayout::default(),
+            &get_window_frame,
+            &|_| None,
+        );
+        let expected = strip
+            .relative_positions_with(500, StackLayout::default(), &get_window_frame)
+            .collect::<Vec<_>>();
+        assert_eq!(out, expected);
+    }
+
+    #[test]
+    fn test_cached_positions_follow_min_height() {
+        let mut world = World::new();
+        let e1 = world.spawn_empty().id();
+        let e2 = world.spawn_empty().id();
+
+        let mut strip = LayoutStrip::default();
+        strip.append(e1);
+        strip.append(e2);
+        strip.stack(e2).unwrap();
+
+        let get_window_frame = |_| Some(IRect::new(0, 0, 300, 300));
+        let min_height = std::cell::Cell::new(None);
+        let get_min_height = |e: Entity| (e == e2).then(|| min_height.get()).flatten();
+
+        let mut cache = StripLayoutCache::default();
+        let before = strip.relative_positions_cached(
+            &mut cache,
+            600,
+            StackLayout::default(),
+            &get_window_frame,
+            &get_min_height,
+        );
+
+        // Learning a minimum height lays the column out again, although no size changed.
+        min_height.set(Some(400));
+        let out = strip.relative_positions_cached(
+            &mut cache,
+            600,
+            StackLayout::default(),
+            &get_window_frame,
+            &get_min_height,
+        );
+        let expected = strip.relative_positions_cached(
+            &mut StripLayoutCache::default(),
+            600,
+            StackLayout::default(),
+            &get_window_frame,
+            &get_min_height,
+        );
+        assert_eq!(out, expected);
+        assert_ne!(out, before);
+    }
+
     #[test]
     fn test_convert_to_tabs() {
         let mut world = World::new();
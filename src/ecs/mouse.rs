@@ -3,21 +3,24 @@ use bevy::ecs::entity::Entity;
 use bevy::ecs::message::MessageReader;
 use bevy::ecs::query::With;
 use bevy::ecs::schedule::IntoScheduleConfigs as _;
-use bevy::ecs::system::{Commands, Local, Query, Res, Single};
+use bevy::ecs::system::{Commands, Local, Query, Res, ResMut, Single};
+use bevy::math::IRect;
 use std::time::{Duration, Instant};
 use tracing::{debug, trace, warn};
 
 use super::{MouseHeldMarker, Timeout};
 use crate::config::Config;
-use crate::ecs::layout::LayoutStrip;
-use crate::ecs::params::{GlobalState, Windows};
+use crate::config::hot_corners::HotCornerRegion;
+use crate::ecs::layout::{Column, LayoutStrip, StackTabBars, clamp_origin_to_viewport};
+use crate::ecs::params::{ActiveDisplay, GlobalState, Windows};
 use crate::ecs::{
     ActiveWorkspaceMarker, DockPosition, MissionControlActive, Position, Scrolling,
-    SpawnCommandsExt,
+    SendMessageTrigger, SpawnCommandsExt, Wakeup,
 };
 use crate::events::Event;
 use crate::manager::{Display, Origin, WindowManager, origin_from};
 use crate::platform::WinID;
+use crate::platform::input::{COLUMN_EDGE_GRAB_PX, set_column_frames};
 
 /// Bottom-right corner region (`NxN` pixels) where focus events are suppressed.
 /// Sized to a representative macOS title bar height — see karinushka/paneru#233:
@@ -25,6 +28,9 @@ use crate::platform::WinID;
 /// so the parked sliver of a hidden virtual workspace lives within this region.
 const CORNER_DEAD_ZONE_PX: i32 = 30;
 
+/// Distance from both edges within which the pointer counts as being in a hot corner.
+const HOT_CORNER_SIZE_PX: i32 = 5;
+
 pub struct MouseEventsPlugin;
 
 impl Plugin for MouseEventsPlugin {
@@ -39,7 +45,13 @@ impl Plugin for MouseEventsPlugin {
                 (
                     mouse_moved_trigger,
                     mouse_resize_trigger,
+                    publish_column_frames
+                        .run_if(|config: Res<Config>| config.mouse_resize_modifier().is_some()),
                     mouse_down_trigger,
+                    stack_tab_click_trigger.run_if(|config: Res<Config>| config.stack_tabs()),
+                    hot_corner_trigger.run_if(|config: Res<Config>| config.has_hot_corners()),
+                    sliver_peek_trigger
+                        .run_if(|config: Res<Config>| config.sliver_peek_delay().is_some()),
                 )
                     .run_if(mission_control_inactive),
                 mouse_up_trigger,
@@ -61,6 +73,177 @@ fn is_in_corner_dead_zone(
     point.x >= bounds.max.x - CORNER_DEAD_ZONE_PX && point.y >= bounds.max.y - CORNER_DEAD_ZONE_PX
 }
 
+/// Returns the hot corner or edge of `bounds` under `point`. Corners span a small square,
+/// edges only the outermost pixel, so that ordinary pointer movement does not trigger them.
+fn hot_corner_region(point: Origin, bounds: IRect) -> Option<HotCornerRegion> {
+    let near_left = point.x < bounds.min.x + HOT_CORNER_SIZE_PX;
+    let near_right = point.x >= bounds.max.x - HOT_CORNER_SIZE_PX;
+    let near_top = point.y < bounds.min.y + HOT_CORNER_SIZE_PX;
+    let near_bottom = point.y >= bounds.max.y - HOT_CORNER_SIZE_PX;
+
+    let region = match (near_left, near_right, near_top, near_bottom) {
+        (true, _, true, _) => HotCornerRegion::TopLeft,
+        (_, true, true, _) => HotCornerRegion::TopRight,
+        (true, _, _, true) => HotCornerRegion::BottomLeft,
+        (_, true, _, true) => HotCornerRegion::BottomRight,
+        _ if point.x <= bounds.min.x => HotCornerRegion::Left,
+        _ if point.x >= bounds.max.x - 1 => HotCornerRegion::Right,
+        _ if point.y <= bounds.min.y => HotCornerRegion::Top,
+        _ if point.y >= bounds.max.y - 1 => HotCornerRegion::Bottom,
+        _ => return None,
+    };
+    Some(region)
+}
+
+#[derive(Default)]
+pub(super) struct HotCornerState {
+    /// The region under the pointer and when the pointer entered it.
+    entered: Option<(HotCornerRegion, Instant)>,
+    /// Set once the command of the current region ran, so it fires once per visit.
+    fired: bool,
+}
+
+/// Runs the command bound in `[hot_corners]` once the pointer rested in a screen corner or on
+/// an edge for the configured dwell time. The dwell is checked every frame, because a resting
+/// pointer produces no further move events.
+#[allow(clippy::needless_pass_by_value)]
+fn hot_corner_trigger(
+    mut messages: MessageReader<Event>,
+    displays: Query<&Display>,
+    config: Res<Config>,
+    mut state: Local<HotCornerState>,
+    mut wakeup: ResMut<Wakeup>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        let Event::MouseMoved { point, .. } = event else {
+            continue;
+        };
+        let cursor = origin_from(*point);
+        let region = displays
+            .iter()
+            .find(|display| display.screen_bounds().contains(cursor))
+            .and_then(|display| hot_corner_region(cursor, display.screen_bounds()));
+
+        if state.entered.map(|(entered, _)| entered) != region {
+            state.entered = region.map(|region| (region, Instant::now()));
+            state.fired = false;
+        }
+    }
+
+    let Some((region, entered)) = state.entered else {
+        return;
+    };
+    if state.fired {
+        return;
+    }
+    let Some((command, dwell)) = config.hot_corner(region) else {
+        return;
+    };
+    if entered.elapsed() < dwell {
+        wakeup.at(entered + dwell);
+    } else {
+        debug!("hot corner {region:?}: {command:?}");
+        state.fired = true;
+        commands.trigger(SendMessageTrigger(Event::Command { command }));
+    }
+}
+
+#[derive(Default)]
+pub(super) struct SliverPeekState {
+    /// The window whose sliver is under the pointer and when the pointer entered it.
+    hovered: Option<(Entity, Instant)>,
+    /// The window slid into view and the strip offset to return to once the pointer leaves it.
+    peeking: Option<(Entity, Origin)>,
+}
+
+/// Slides the column of an off-screen sliver into view, without focusing it, once the pointer
+/// rested on the sliver for `sliver_peek_delay_ms`. When the pointer leaves the window again,
+/// the strip slides back, unless the window was focused in the meantime. Like hot corners, the
+/// dwell is checked every frame.
+#[allow(clippy::needless_pass_by_value)]
+fn sliver_peek_trigger(
+    mut messages: MessageReader<Event>,
+    windows: Windows,
+    active_display: ActiveDisplay,
+    strips: Query<&Position, With<LayoutStrip>>,
+    config: Res<Config>,
+    mut state: Local<SliverPeekState>,
+    mut wakeup: ResMut<Wakeup>,
+    mut commands: Commands,
+) {
+    let Some(delay) = config.sliver_peek_delay() else {
+        return;
+    };
+    let display_bounds = active_display.bounds();
+    let sliver_width = config.sliver_width_for(active_display.display().uuid());
+    let strip = active_display.active_strip();
+    let strip_entity = active_display.active_strip_entity();
+
+    for event in messages.read() {
+        let Event::MouseMoved { point, .. } = event else {
+            continue;
+        };
+        let cursor = origin_from(*point);
+
+        if let Some((entity, offset)) = state.peeking {
+            if windows
+                .moving_frame(entity)
+                .is_some_and(|frame| frame.contains(cursor))
+            {
+                continue;
+            }
+            state.peeking = None;
+            if windows
+                .focused()
+                .is_none_or(|(_, focused)| focused != entity)
+            {
+                debug!("sliver peek of {entity} ended.");
+                commands.reposition_entity(strip_entity, offset);
+            }
+        }
+
+        let hovered = strip.all_windows().into_iter().find(|&entity| {
+            let h_pad = windows
+                .get(entity)
+                .map_or(0, |window| window.horizontal_padding());
+            windows.frame(entity).is_some_and(|frame| {
+                let visible = display_bounds.intersect(frame).width();
+                frame.contains(cursor) && visible < frame.width() && visible <= sliver_width + h_pad
+            })
+        });
+        if state.hovered.map(|(entity, _)| entity) != hovered {
+            state.hovered = hovered.map(|entity| (entity, Instant::now()));
+        }
+    }
+
+    let Some((entity, entered)) = state.hovered else {
+        return;
+    };
+    if state.peeking.is_some() {
+        return;
+    }
+    if entered.elapsed() < delay {
+        wakeup.at(entered + delay);
+        return;
+    }
+    state.hovered = None;
+    let (Some(size), Some(layout_position), Ok(Position(offset))) = (
+        windows.size(entity),
+        windows.layout_position(entity),
+        strips.get(strip_entity),
+    ) else {
+        return;
+    };
+
+    let viewport = active_display.actual_bounds(&config);
+    let origin = clamp_origin_to_viewport(layout_position.0 + *offset, size, viewport);
+    let strip_target = (origin - layout_position.0).with_y(offset.y);
+    debug!("sliver peek of {entity}, scrolling strip to {strip_target}.");
+    state.peeking = Some((entity, *offset));
+    commands.reposition_entity(strip_entity, strip_target);
+}
+
 /// Handles mouse moved events.
 ///
 /// If "focus follows mouse" is enabled, this function finds the window under the cursor and
@@ -229,12 +412,35 @@ fn mouse_down_trigger(
     }
 }
 
+/// Focuses the window whose tab in a stacked column's tab bar was clicked. The tab bar overlay
+/// lets clicks through, so they are matched against the tab bar geometry here.
+#[allow(clippy::needless_pass_by_value)]
+fn stack_tab_click_trigger(
+    mut messages: MessageReader<Event>,
+    tab_bars: Res<StackTabBars>,
+    mut commands: Commands,
+) {
+    for event in messages.read() {
+        let Event::MouseDown { point, .. } = event else {
+            continue;
+        };
+        if let Some(entity) = tab_bars.tab_at(origin_from(*point)) {
+            debug!("clicked stack tab of {entity}.");
+            commands.focus_entity(entity, true);
+            commands.reshuffle_around(entity);
+        }
+    }
+}
+
 /// Handles mouse-up events. Triggers the deferred reshuffle so the clicked
-/// window slides into view after the user releases the button.
+/// window slides into view after the user releases the button. A tiled window
+/// dragged past the midpoint of a neighbouring column takes that column's slot.
 #[allow(clippy::needless_pass_by_value)]
 fn mouse_up_trigger(
     mut messages: MessageReader<Event>,
     mouse_held: Query<(Entity, &MouseHeldMarker)>,
+    mut active_strip: Query<&mut LayoutStrip, With<ActiveWorkspaceMarker>>,
+    windows: Windows,
     mut commands: Commands,
 ) {
     for event in messages.read() {
@@ -243,6 +449,12 @@ fn mouse_up_trigger(
         }
 
         for (held_entity, marker) in &mouse_held {
+            if let Ok(mut strip) = active_strip.single_mut()
+                && let Some((from, to)) = dropped_column_slot(&strip, marker.0, &windows)
+            {
+                debug!("dropped {} from column {from} to {to}.", marker.0);
+                strip.move_column(from, to);
+            }
             commands.reshuffle_around(marker.0);
             if let Ok(mut entity_commands) = commands.get_entity(held_entity) {
                 entity_commands.try_despawn();
@@ -251,10 +463,107 @@ fn mouse_up_trigger(
     }
 }
 
+/// Returns the `(from, to)` column indices when `entity`, a single-window column, was dragged
+/// so that its center passed the center of one or more neighbouring columns.
+fn dropped_column_slot(
+    strip: &LayoutStrip,
+    entity: Entity,
+    windows: &Windows,
+) -> Option<(usize, usize)> {
+    let from = strip.index_of(entity).ok()?;
+    if !matches!(strip.get(from).ok()?, Column::Single(_)) {
+        return None;
+    }
+    let center = windows.frame(entity)?.center().x;
+
+    let to = strip
+        .columns()
+        .enumerate()
+        .filter(|(index, _)| *index != from)
+        .filter_map(|(_, column)| column.top().and_then(|top| windows.frame(top)))
+        .filter(|frame| frame.center().x < center)
+        .count();
+    (to != from).then_some((from, to))
+}
+
+/// Edge of a column moved by a mouse resize drag.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ResizeEdge {
+    Left,
+    Right,
+}
+
+/// A column edge grabbed by a left drag with the `mouse_resize_modifier` held. Tracks the target
+/// width and strip offset itself, so several drag events within one frame do not build on stale
+/// window frames.
+#[derive(Debug)]
+struct ResizeDrag {
+    entity: Entity,
+    edge: ResizeEdge,
+    last_x: i32,
+    width: i32,
+    strip_x: i32,
+}
+
 #[derive(Default)]
 pub(super) struct MouseResizeState {
     last_point: Option<Origin>,
     window_id: Option<WinID>,
+    drag: Option<ResizeDrag>,
+}
+
+/// Finds the tiled column edge within `COLUMN_EDGE_GRAB_PX` of the pointer. The right edge wins
+/// over the left one, so grabbing the gap between two columns leaves the strip in place.
+fn grab_column_edge(
+    pointer: Origin,
+    windows: &Windows,
+    strip: &LayoutStrip,
+    strip_x: i32,
+) -> Option<ResizeDrag> {
+    let near = |x: i32| (pointer.x - x).abs() <= COLUMN_EDGE_GRAB_PX;
+    let frames = strip
+        .all_windows()
+        .into_iter()
+        .filter_map(|entity| windows.frame(entity).map(|frame| (entity, frame)))
+        .filter(|(_, frame)| (frame.min.y..frame.max.y).contains(&pointer.y))
+        .collect::<Vec<_>>();
+    let (entity, frame, edge) = frames
+        .iter()
+        .find(|(_, frame)| near(frame.max.x))
+        .map(|(entity, frame)| (*entity, *frame, ResizeEdge::Right))
+        .or_else(|| {
+            frames
+                .iter()
+                .find(|(_, frame)| near(frame.min.x))
+                .map(|(entity, frame)| (*entity, *frame, ResizeEdge::Left))
+        })?;
+    debug!("resize drag of {entity} on the {edge:?} edge.");
+    Some(ResizeDrag {
+        entity,
+        edge,
+        last_x: pointer.x,
+        width: frame.width(),
+        strip_x,
+    })
+}
+
+/// Shares the frames of the active strip's tiled windows with the event tap, which only intercepts
+/// modifier clicks next to a column edge.
+#[allow(clippy::needless_pass_by_value)]
+fn publish_column_frames(
+    windows: Windows,
+    active_strip: Single<&LayoutStrip, With<ActiveWorkspaceMarker>>,
+    mut published: Local<Vec<IRect>>,
+) {
+    let frames = active_strip
+        .all_windows()
+        .into_iter()
+        .filter_map(|entity| windows.frame(entity))
+        .collect::<Vec<_>>();
+    if *published != frames {
+        set_column_frames(frames.clone());
+        *published = frames;
+    }
 }
 
 #[allow(clippy::needless_pass_by_value)]
@@ -267,9 +576,57 @@ fn mouse_resize_trigger(
     mut state: Local<MouseResizeState>,
     mut commands: Commands,
 ) {
+    const MIN_RESIZE_DRAG_WIDTH: i32 = 200;
+
+    let (strip_entity, strip, strip_position) = *active_workspace;
+
     for event in messages.read() {
-        let Event::MouseMoved { point, modifiers } = event else {
-            continue;
+        let (point, modifiers) = match event {
+            Event::MouseResizeGrab { point } => {
+                state.drag =
+                    grab_column_edge(origin_from(*point), &windows, strip, strip_position.0.x);
+                continue;
+            }
+            Event::MouseDragged { point, .. } => {
+                let Some(drag) = state.drag.as_mut() else {
+                    continue;
+                };
+                let pointer = origin_from(*point);
+                let dx = pointer.x - drag.last_x;
+                drag.last_x = pointer.x;
+                let width = match drag.edge {
+                    ResizeEdge::Left => drag.width - dx,
+                    ResizeEdge::Right => drag.width + dx,
+                }
+                .max(MIN_RESIZE_DRAG_WIDTH);
+                if width == drag.width {
+                    continue;
+                }
+                if drag.edge == ResizeEdge::Left {
+                    // Keep the right edge anchored by shifting the strip along with the pointer.
+                    drag.strip_x += drag.width - width;
+                    commands.reposition_entity(strip_entity, strip_position.0.with_x(drag.strip_x));
+                }
+                drag.width = width;
+
+                let column = strip
+                    .index_of(drag.entity)
+                    .and_then(|index| strip.get(index));
+                for window in column.iter().flat_map(Column::window_iter) {
+                    if let Some(size) = windows.size(window) {
+                        commands.resize_entity(window, size.with_x(width));
+                    }
+                }
+                continue;
+            }
+            Event::MouseUp { .. } => {
+                if let Some(drag) = state.drag.take() {
+                    commands.reshuffle_around(drag.entity);
+                }
+                continue;
+            }
+            Event::MouseMoved { point, modifiers } => (point, modifiers),
+            _ => continue,
         };
 
         if config
@@ -306,7 +663,6 @@ fn mouse_resize_trigger(
         let Some((window, entity)) = windows.find(window_id) else {
             continue;
         };
-        let (strip_entity, strip, strip_position) = *active_workspace;
         let floating = !strip.contains(entity);
 
         let mut frame = window.frame();
@@ -521,6 +877,26 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn hot_corner_regions() {
+        let bounds = make_display().screen_bounds();
+        let region = |x, y| hot_corner_region(Origin::new(x, y), bounds);
+
+        assert_eq!(region(0, 0), Some(HotCornerRegion::TopLeft));
+        assert_eq!(region(1020, 3), Some(HotCornerRegion::TopRight));
+        assert_eq!(region(2, 767), Some(HotCornerRegion::BottomLeft));
+        assert_eq!(region(1023, 767), Some(HotCornerRegion::BottomRight));
+        assert_eq!(region(0, 400), Some(HotCornerRegion::Left));
+        assert_eq!(region(1023, 400), Some(HotCornerRegion::Right));
+        assert_eq!(region(500, 0), Some(HotCornerRegion::Top));
+        assert_eq!(region(500, 767), Some(HotCornerRegion::Bottom));
+
+        // Close to an edge, but not on it.
+        assert_eq!(region(1, 400), None);
+        assert_eq!(region(500, 1), None);
+        assert_eq!(region(500, 400), None);
+    }
+
     #[test]
     fn corner_dead_zone_with_bottom_dock() {
         let display = make_display();
@@ -147,7 +147,8 @@ impl ActiveDisplay<'_, '_> {
     /// Returns the `IRect` representing the bounds of the active display, correctly padded by
     /// potential dock position and or padding configuration.
     pub fn actual_bounds(&self, config: &Config) -> IRect {
-        self.display().actual_display_bounds(self.dock(), config)
+        self.display()
+            .workspace_bounds(self.dock(), config, Some(self.active_strip()))
     }
 }
 
@@ -193,7 +194,8 @@ impl ActiveDisplayMut<'_, '_> {
     /// Returns the `IRect` representing the bounds of the active display, correctly padded by
     /// potential dock position and or padding configuration.
     pub fn actual_bounds(&self, config: &Config) -> IRect {
-        self.display().actual_display_bounds(self.dock(), config)
+        self.display()
+            .workspace_bounds(self.dock(), config, Some(&**self.strip))
     }
 }
 
@@ -292,6 +294,21 @@ impl Windows<'_, '_> {
             })
     }
 
+    /// Iterates over the other floating windows belonging to the same application as `entity`.
+    pub fn floating_siblings(&self, entity: Entity) -> impl Iterator<Item = Entity> {
+        let parent = self
+            .get_all(entity)
+            .map(|(_, _, childof, _)| childof.parent());
+        self.all
+            .iter()
+            .filter_map(move |(_, other, childof, unmanaged)| {
+                (other != entity
+                    && Some(childof.parent()) == parent
+                    && matches!(unmanaged, Some(Unmanaged::Floating)))
+                .then_some(other)
+            })
+    }
+
     pub fn full_width(&self, entity: Entity) -> Option<&FullWidthMarker> {
         self.previous_size
             .get(entity)
@@ -342,11 +359,11 @@ impl Windows<'_, '_> {
                 let mut frame = IRect::from_corners(origin.0, origin.0 + size);
 
                 if let Some(reposition) = reposition {
-                    frame.min = reposition.0;
+                    frame.min = reposition.target;
                     frame.max = frame.min + size;
                 }
                 if let Some(resize) = resize {
-                    frame.max = frame.min + resize.0;
+                    frame.max = frame.min + resize.target;
                 }
                 frame
             })
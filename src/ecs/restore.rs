@@ -33,7 +33,7 @@ pub(crate) struct SessionRestore {
 }
 
 impl SessionRestore {
-    fn new(state: PaneruState, grace: Duration) -> Self {
+    pub(crate) fn new(state: PaneruState, grace: Duration) -> Self {
         let saved_hard_keys = saved_hard_match_keys(&state);
         Self {
             state,
@@ -69,6 +69,7 @@ pub(crate) struct CurrentWindowIdentity {
     pub bundle_id: String,
     pub title: String,
     pub identifier: String,
+    pub document: String,
     pub role: String,
     pub subrole: String,
 }
@@ -87,6 +88,7 @@ impl CurrentWindowIdentity {
             bundle_id: bundle_id.to_string(),
             title: title.to_string(),
             identifier: "main".to_string(),
+            document: String::new(),
             role: "AXWindow".to_string(),
             subrole: "AXStandardWindow".to_string(),
         }
@@ -166,6 +168,20 @@ impl<'a> RestorePlanner<'a> {
         plan
     }
 
+    /// Matches a single list of saved columns against `current`, keeping one entry per saved
+    /// column so callers can line up per-column data such as widths.
+    pub(crate) fn plan_columns(
+        &self,
+        columns: &[SavedColumn],
+        current: &[CurrentWindowIdentity],
+    ) -> Vec<Option<PlannedColumn>> {
+        let mut plan = RestorePlan::default();
+        columns
+            .iter()
+            .map(|column| self.plan_column(column, current, &mut plan))
+            .collect()
+    }
+
     fn plan_workspace(
         &self,
         workspace: &SavedWorkspace,
@@ -351,12 +367,20 @@ impl SavedWindow {
     }
 
     fn fallback_match(&self, current: &CurrentWindowIdentity) -> bool {
+        if self.bundle_id != current.bundle_id
+            || self.role != current.role
+            || self.subrole != current.subrole
+        {
+            return false;
+        }
+        // A document path identifies editor-like windows better than their titles, which
+        // often carry unsaved markers or change with the selected tab.
+        if !self.document.is_empty() && !current.document.is_empty() {
+            return self.document == current.document;
+        }
         !self.title.is_empty()
-            && self.bundle_id == current.bundle_id
             && self.title == current.title
             && self.identifier == current.identifier
-            && self.role == current.role
-            && self.subrole == current.subrole
     }
 }
 
@@ -367,7 +391,8 @@ fn saved_hard_match_keys(state: &PaneruState) -> HashSet<WindowHardMatchKey> {
 }
 
 fn has_saved_fallback_windows(state: &PaneruState) -> bool {
-    saved_windows_in_state(state).any(|window| !window.title.is_empty())
+    saved_windows_in_state(state)
+        .any(|window| !window.title.is_empty() || !window.document.is_empty())
 }
 
 pub(crate) fn matches_startup_restore_state(
@@ -566,7 +591,7 @@ fn layout_strip_from_plan(planned: &PlannedStrip) -> LayoutStrip {
     strip
 }
 
-fn current_window_identities(
+pub(crate) fn current_window_identities(
     windows: &Windows,
     apps: &Query<&Application>,
     restoration: &PaneruState,
@@ -582,6 +607,7 @@ fn current_window_identities(
                 bundle_id: app.bundle_id().unwrap_or_default().clone(),
                 title: String::new(),
                 identifier: String::new(),
+                document: String::new(),
                 role: String::new(),
                 subrole: String::new(),
             })
@@ -609,7 +635,8 @@ fn hydrate_fallback_identities(
             continue;
         };
         identity.title = window.title().unwrap_or_default();
-        if identity.title.is_empty() {
+        identity.document = window.document().unwrap_or_default();
+        if identity.title.is_empty() && identity.document.is_empty() {
             continue;
         }
         identity.identifier = window.identifier().unwrap_or_default();
@@ -663,7 +690,7 @@ fn select_display<'a>(
         .map(|(entity, display, _)| (entity, display))
 }
 
-fn apply_planned_columns(strip: &mut LayoutStrip, columns: &[PlannedColumn]) {
+pub(crate) fn apply_planned_columns(strip: &mut LayoutStrip, columns: &[PlannedColumn]) {
     for column in columns {
         match column {
             PlannedColumn::Single(entity) | PlannedColumn::Fullscreen(entity) => {
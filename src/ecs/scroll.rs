@@ -219,7 +219,7 @@ fn apply_snap_force(
     const CENTER_MAGNETIC_FORCE: f64 = 10.0;
     const SNAP_DISPLAY_RATIO: f64 = 0.45;
 
-    if !config.auto_center() {
+    if !config.auto_center_on(Some(active_display.active_strip().id())) {
         return;
     }
 
@@ -310,7 +310,7 @@ fn apply_scrolling_constraints(
 }
 
 #[instrument(level = Level::TRACE, skip_all)]
-fn clamp_viewport_offset<W>(
+pub(crate) fn clamp_viewport_offset<W>(
     current_offset: i32,
     layout_strip: &LayoutStrip,
     windows: &Windows,
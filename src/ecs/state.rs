@@ -9,19 +9,27 @@ use bevy::ecs::hierarchy::ChildOf;
 use bevy::ecs::message::MessageReader;
 use bevy::ecs::query::Has;
 use bevy::ecs::resource::Resource;
-use bevy::ecs::system::Query;
+use bevy::ecs::system::{Commands, Query, Res, ResMut};
 use bevy::math::IRect;
 use objc2_core_graphics::CGDirectDisplayID;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
+use crate::config::Config;
+use crate::ecs::focus::{FocusHistory, TierMemory};
 use crate::ecs::layout::{Column, LayoutStrip, StackItem};
 use crate::ecs::params::Windows;
 use crate::ecs::{ActiveDisplayMarker, ActiveWorkspaceMarker, SelectedVirtualMarker, Unmanaged};
 use crate::manager::{Application, Display, WindowManager};
 use crate::platform::{Pid, ProcessSerialNumber, WinID, WorkspaceId};
 
+pub use paneru_ipc::{
+    PaneruActiveState, PaneruQueryState, PaneruVirtualWorkspaceState, PaneruWindowState,
+    StateQueryKind,
+};
+
 pub const STATE_FILE_NAME: &str = "state.json";
+pub const RUNTIME_STATE_FILE_NAME: &str = "runtime.json";
 const SUPPORTED_STATE_VERSION: u32 = 2;
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Resource)]
@@ -29,11 +37,42 @@ pub struct PaneruState {
     pub version: u32,
     pub timestamp: u64,
     pub active_display_id: Option<CGDirectDisplayID>,
+    /// Windows outside of the tiling layout, e.g. floating ones.
+    #[serde(default)]
+    pub unmanaged: Vec<SavedUnmanaged>,
     #[serde(default)]
     pub displays: Vec<SavedDisplay>,
     pub workspaces: Vec<SavedWorkspace>,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SavedUnmanaged {
+    pub window: SavedWindow,
+    pub kind: SavedUnmanagedKind,
+    pub frame: SavedRect,
+}
+
+/// Mirrors `Unmanaged`, the reason a window is kept out of the layout.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SavedUnmanagedKind {
+    Floating,
+    Minimized,
+    Hidden,
+    Unresponsive,
+}
+
+impl From<&Unmanaged> for SavedUnmanagedKind {
+    fn from(unmanaged: &Unmanaged) -> Self {
+        match unmanaged {
+            Unmanaged::Floating => Self::Floating,
+            Unmanaged::Minimized => Self::Minimized,
+            Unmanaged::Hidden => Self::Hidden,
+            Unmanaged::Unresponsive => Self::Unresponsive,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SavedDisplay {
     pub display_id: CGDirectDisplayID,
@@ -89,54 +128,13 @@ pub struct SavedWindow {
     pub bundle_id: String,
     pub title: String,
     pub identifier: String,
+    /// Document path or URL (`AXDocument`); empty when the app does not report one.
+    #[serde(default)]
+    pub document: String,
     pub role: String,
     pub subrole: String,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum StateQueryKind {
-    State,
-    VirtualWorkspaces,
-    Active,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-pub struct PaneruQueryState {
-    pub version: u32,
-    pub timestamp: u64,
-    pub active: PaneruActiveState,
-    pub virtual_workspaces: Vec<PaneruVirtualWorkspaceState>,
-}
-
-#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
-pub struct PaneruActiveState {
-    pub display_id: Option<CGDirectDisplayID>,
-    pub native_workspace_id: Option<WorkspaceId>,
-    pub virtual_workspace_number: Option<u32>,
-    pub focused_window_id: Option<WinID>,
-    pub focused_bundle_id: Option<String>,
-    pub focused_app_name: Option<String>,
-    pub focused_window_title: Option<String>,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-pub struct PaneruVirtualWorkspaceState {
-    pub number: u32,
-    pub native_workspace_id: WorkspaceId,
-    pub active: bool,
-    pub windows: Vec<PaneruWindowState>,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
-pub struct PaneruWindowState {
-    pub window_id: WinID,
-    pub bundle_id: String,
-    pub app_name: String,
-    pub title: String,
-    pub focused: bool,
-    pub floating: bool,
-}
-
 impl From<IRect> for SavedRect {
     fn from(rect: IRect) -> Self {
         Self {
@@ -148,6 +146,12 @@ impl From<IRect> for SavedRect {
     }
 }
 
+impl From<SavedRect> for IRect {
+    fn from(rect: SavedRect) -> Self {
+        IRect::new(rect.min_x, rect.min_y, rect.max_x, rect.max_y)
+    }
+}
+
 impl SavedWindow {
     pub fn from_entity(
         entity: Entity,
@@ -165,6 +169,7 @@ impl SavedWindow {
             bundle_id: app.bundle_id().unwrap_or_default().clone(),
             title: window.title().unwrap_or_default(),
             identifier: window.identifier().unwrap_or_default(),
+            document: window.document().unwrap_or_default(),
             role: window.role().unwrap_or_default(),
             subrole: window.subrole().unwrap_or_default(),
         })
@@ -176,7 +181,77 @@ impl SavedWindow {
     }
 }
 
+impl SavedColumn {
+    /// Converts a layout column into its saved form, dropping windows that can no longer be
+    /// resolved. Returns `None` if no window of the column survives.
+    pub fn from_column(
+        column: &Column,
+        windows: &Windows,
+        apps: &Query<&Application>,
+    ) -> Option<Self> {
+        match column {
+            Column::Single(entity) => {
+                SavedWindow::from_entity(*entity, windows, apps).map(SavedColumn::Single)
+            }
+            // Accordion mode is not persisted; the column restores as a plain stack.
+            Column::Stack(items) | Column::Accordion(items) => {
+                let saved_items = items
+                    .iter()
+                    .filter_map(|item| match item {
+                        StackItem::Single(entity) => {
+                            SavedWindow::from_entity(*entity, windows, apps)
+                                .map(SavedStackItem::Single)
+                        }
+                        StackItem::Tabs(tabs) => {
+                            let saved_tabs: Vec<_> = tabs
+                                .iter()
+                                .filter_map(|&e| SavedWindow::from_entity(e, windows, apps))
+                                .collect();
+                            if saved_tabs.is_empty() {
+                                None
+                            } else {
+                                Some(SavedStackItem::Tabs(saved_tabs))
+                            }
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                if saved_items.is_empty() {
+                    None
+                } else {
+                    Some(SavedColumn::Stack(saved_items))
+                }
+            }
+            Column::Tabs(tabs) => {
+                let saved_tabs: Vec<_> = tabs
+                    .iter()
+                    .filter_map(|&e| SavedWindow::from_entity(e, windows, apps))
+                    .collect();
+                if saved_tabs.is_empty() {
+                    None
+                } else {
+                    Some(SavedColumn::Tabs(saved_tabs))
+                }
+            }
+            Column::Fullscren(entity) => {
+                SavedWindow::from_entity(*entity, windows, apps).map(SavedColumn::Fullscreen)
+            }
+        }
+    }
+}
+
 impl PaneruState {
+    /// Builds a state holding only `workspaces`, e.g. the layout of a single display.
+    pub fn from_workspaces(workspaces: Vec<SavedWorkspace>) -> Self {
+        Self {
+            version: SUPPORTED_STATE_VERSION,
+            timestamp: now_timestamp(),
+            active_display_id: None,
+            unmanaged: Vec::new(),
+            displays: Vec::new(),
+            workspaces,
+        }
+    }
+
     #[allow(clippy::type_complexity, clippy::too_many_lines)]
     pub fn extract(
         workspaces: &Query<(Option<&ChildOf>, &LayoutStrip, Has<ActiveWorkspaceMarker>)>,
@@ -208,58 +283,10 @@ impl PaneruState {
                 workspace_ids.push(strip.id());
             }
 
-            let mut saved_columns = Vec::new();
-            for col in strip.columns() {
-                let saved_col = match col {
-                    Column::Single(entity) => {
-                        SavedWindow::from_entity(*entity, windows, apps).map(SavedColumn::Single)
-                    }
-                    Column::Stack(items) => {
-                        let saved_items = items
-                            .iter()
-                            .filter_map(|item| match item {
-                                StackItem::Single(entity) => {
-                                    SavedWindow::from_entity(*entity, windows, apps)
-                                        .map(SavedStackItem::Single)
-                                }
-                                StackItem::Tabs(tabs) => {
-                                    let saved_tabs: Vec<_> = tabs
-                                        .iter()
-                                        .filter_map(|&e| SavedWindow::from_entity(e, windows, apps))
-                                        .collect();
-                                    if saved_tabs.is_empty() {
-                                        None
-                                    } else {
-                                        Some(SavedStackItem::Tabs(saved_tabs))
-                                    }
-                                }
-                            })
-                            .collect::<Vec<_>>();
-                        if saved_items.is_empty() {
-                            None
-                        } else {
-                            Some(SavedColumn::Stack(saved_items))
-                        }
-                    }
-                    Column::Tabs(tabs) => {
-                        let saved_tabs: Vec<_> = tabs
-                            .iter()
-                            .filter_map(|&e| SavedWindow::from_entity(e, windows, apps))
-                            .collect();
-                        if saved_tabs.is_empty() {
-                            None
-                        } else {
-                            Some(SavedColumn::Tabs(saved_tabs))
-                        }
-                    }
-                    Column::Fullscren(entity) => SavedWindow::from_entity(*entity, windows, apps)
-                        .map(SavedColumn::Fullscreen),
-                };
-
-                if let Some(sc) = saved_col {
-                    saved_columns.push(sc);
-                }
-            }
+            let saved_columns = strip
+                .columns()
+                .filter_map(|column| SavedColumn::from_column(column, windows, apps))
+                .collect();
 
             let workspace =
                 workspace_map
@@ -303,10 +330,25 @@ impl PaneruState {
             })
             .collect();
 
+        let unmanaged = windows
+            .iter()
+            .filter_map(|(_, entity)| {
+                let (_, _, Some(unmanaged)) = windows.get_managed(entity)? else {
+                    return None;
+                };
+                Some(SavedUnmanaged {
+                    window: SavedWindow::from_entity(entity, windows, apps)?,
+                    kind: unmanaged.into(),
+                    frame: windows.frame(entity)?.into(),
+                })
+            })
+            .collect();
+
         Self {
             version: SUPPORTED_STATE_VERSION,
             timestamp: now_timestamp(),
             active_display_id,
+            unmanaged,
             displays,
             workspaces,
         }
@@ -414,6 +456,124 @@ impl PaneruState {
     }
 }
 
+/// Daemon state changed at runtime rather than in the configuration file, kept next to the
+/// layout state so a restart does not lose it.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Resource)]
+pub struct RuntimeState {
+    /// Options set with `config set`, re-applied in order on startup.
+    #[serde(default)]
+    pub options: Vec<SavedOption>,
+    /// Windows last focused on each workspace.
+    #[serde(default)]
+    pub focus_history: Vec<SavedFocus>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SavedOption {
+    pub key: String,
+    pub value: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SavedFocus {
+    pub workspace_id: WorkspaceId,
+    pub last_managed: Option<WinID>,
+    pub last_floating: Option<WinID>,
+}
+
+impl RuntimeState {
+    pub fn extract(config: &Config, focus_history: &FocusHistory, windows: &Windows) -> Self {
+        let window_id = |entity: Option<Entity>| {
+            entity
+                .and_then(|entity| windows.get(entity))
+                .map(|window| window.id())
+        };
+        let options = config
+            .runtime_overrides()
+            .into_iter()
+            .map(|(key, value)| SavedOption { key, value })
+            .collect();
+        let focus_history = focus_history
+            .workspaces()
+            .map(|(workspace_id, memory)| SavedFocus {
+                workspace_id,
+                last_managed: window_id(memory.last_managed),
+                last_floating: window_id(memory.last_floating),
+            })
+            .filter(|focus| focus.last_managed.is_some() || focus.last_floating.is_some())
+            .collect();
+        Self {
+            options,
+            focus_history,
+        }
+    }
+
+    pub fn save_to_file(&self, path: &Path) -> Result<(), std::io::Error> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        fs::write(&tmp_path, json)?;
+        fs::rename(tmp_path, path)
+    }
+
+    pub fn load_from_file(path: &Path) -> Option<Self> {
+        let data = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&data)
+            .inspect_err(|err| warn!("Ignoring runtime state {}: {err}", path.display()))
+            .ok()
+    }
+
+    pub fn default_file_path() -> PathBuf {
+        xdg::BaseDirectories::with_prefix("paneru")
+            .get_state_file(RUNTIME_STATE_FILE_NAME)
+            .expect("XDG state directory should be available")
+    }
+
+    /// Re-applies the saved `config set` options to `config`.
+    pub fn apply_options(&self, config: &mut Config) {
+        for SavedOption { key, value } in &self.options {
+            if let Err(err) = config.set_option(key, value) {
+                warn!("Unable to restore option '{key}' = '{value}': {err}");
+            }
+        }
+    }
+}
+
+/// Restores the persisted focus history once the initial windows exist, then drops the
+/// loaded `RuntimeState`.
+#[allow(clippy::needless_pass_by_value)]
+pub fn restore_focus_history(
+    runtime: Res<RuntimeState>,
+    windows: Windows,
+    mut focus_history: ResMut<FocusHistory>,
+    mut commands: Commands,
+) {
+    let entity = |window_id: Option<WinID>| {
+        window_id
+            .and_then(|window_id| windows.find(window_id))
+            .map(|(_, entity)| entity)
+    };
+    for saved in &runtime.focus_history {
+        let memory = TierMemory {
+            last_managed: entity(saved.last_managed),
+            last_floating: entity(saved.last_floating),
+        };
+        if memory.last_managed.is_some() || memory.last_floating.is_some() {
+            focus_history.restore(saved.workspace_id, memory);
+        }
+    }
+    commands.remove_resource::<RuntimeState>();
+}
+
+fn save_runtime_state(config: &Config, focus_history: &FocusHistory, windows: &Windows) {
+    let path = RuntimeState::default_file_path();
+    if let Err(e) = RuntimeState::extract(config, focus_history, windows).save_to_file(&path) {
+        warn!("Failed to save runtime state: {e}");
+    }
+}
+
 #[derive(Default)]
 struct SavedWorkspaceBuilder {
     display_id: Option<CGDirectDisplayID>,
@@ -421,40 +581,39 @@ struct SavedWorkspaceBuilder {
     strips: Vec<SavedStrip>,
 }
 
-impl PaneruQueryState {
-    #[allow(clippy::too_many_lines, clippy::type_complexity)]
-    pub fn extract(
-        workspaces: &Query<(
-            &ChildOf,
-            &LayoutStrip,
-            Has<ActiveWorkspaceMarker>,
-            Has<SelectedVirtualMarker>,
-        )>,
-        displays: &Query<(&Display, Entity, Has<ActiveDisplayMarker>)>,
-        windows: &Windows,
-        apps: &Query<&Application>,
-        window_manager: &WindowManager,
-    ) -> crate::errors::Result<Self> {
-        let focused_entity = windows.focused().map(|(_, entity)| entity);
-
-        let active_display = displays
-            .iter()
-            .find_map(|(display, entity, active)| active.then_some((display.id(), entity)));
-        let active_workspace_id = workspaces
-            .iter()
-            .find_map(|(_, strip, active, _)| active.then_some(strip.id()));
-
-        let mut virtual_workspaces = Vec::new();
-        let mut workspace_max_numbers: HashMap<WorkspaceId, u32> = HashMap::new();
-        let mut active = PaneruActiveState {
-            display_id: active_display.map(|(display_id, _)| display_id),
-            ..PaneruActiveState::default()
-        };
-
-        for (child, strip, active_workspace, selected_workspace) in workspaces {
-            let floating = if active_workspace
-                || selected_workspace && active_workspace_id != Some(strip.id())
-            {
+/// Builds the state document answered to `query` requests and used for subscriber events.
+#[allow(clippy::too_many_lines, clippy::type_complexity)]
+pub fn extract_query_state(
+    workspaces: &Query<(
+        &ChildOf,
+        &LayoutStrip,
+        Has<ActiveWorkspaceMarker>,
+        Has<SelectedVirtualMarker>,
+    )>,
+    displays: &Query<(&Display, Entity, Has<ActiveDisplayMarker>)>,
+    windows: &Windows,
+    apps: &Query<&Application>,
+    window_manager: &WindowManager,
+) -> crate::errors::Result<PaneruQueryState> {
+    let focused_entity = windows.focused().map(|(_, entity)| entity);
+
+    let active_display = displays
+        .iter()
+        .find_map(|(display, entity, active)| active.then_some((display.id(), entity)));
+    let active_workspace_id = workspaces
+        .iter()
+        .find_map(|(_, strip, active, _)| active.then_some(strip.id()));
+
+    let mut virtual_workspaces = Vec::new();
+    let mut workspace_max_numbers: HashMap<WorkspaceId, u32> = HashMap::new();
+    let mut active = PaneruActiveState {
+        display_id: active_display.map(|(display_id, _)| display_id),
+        ..PaneruActiveState::default()
+    };
+
+    for (child, strip, active_workspace, selected_workspace) in workspaces {
+        let floating =
+            if active_workspace || selected_workspace && active_workspace_id != Some(strip.id()) {
                 window_manager.windows_in_workspace(strip.id())?
             } else {
                 Vec::new()
@@ -466,97 +625,85 @@ impl PaneruQueryState {
                 (matches!(unmanaged, Some(Unmanaged::Floating)) && !strip.contains(entity))
                     .then_some(entity)
             });
-            let row_windows = strip
-                .all_windows()
-                .into_iter()
-                .chain(floating)
-                .filter_map(|entity| {
-                    let (window, _, unmanaged) = windows.get_managed(entity)?;
-                    let (_, _, app_entity) = windows.find_parent(window.id())?;
-                    let app = apps.get(app_entity).ok()?;
-                    let bundle_id = app.bundle_id().unwrap_or_default().clone();
-                    let app_name = app.name().to_string();
-                    let title = window.title().unwrap_or_default();
-                    Some(PaneruWindowState {
-                        window_id: window.id(),
-                        bundle_id,
-                        app_name,
-                        title,
-                        focused: focused_entity == Some(entity),
-                        floating: matches!(unmanaged, Some(Unmanaged::Floating)),
-                    })
+        let row_windows = strip
+            .all_windows()
+            .into_iter()
+            .chain(floating)
+            .filter_map(|entity| {
+                let (window, _, unmanaged) = windows.get_managed(entity)?;
+                let (_, _, app_entity) = windows.find_parent(window.id())?;
+                let app = apps.get(app_entity).ok()?;
+                let bundle_id = app.bundle_id().unwrap_or_default().clone();
+                let app_name = app.name().to_string();
+                let title = window.title().unwrap_or_default();
+                Some(PaneruWindowState {
+                    window_id: window.id(),
+                    bundle_id,
+                    app_name,
+                    title,
+                    focused: focused_entity == Some(entity),
+                    floating: matches!(unmanaged, Some(Unmanaged::Floating)),
                 })
-                .collect::<Vec<_>>();
-
-            let number = strip.virtual_index + 1;
-            workspace_max_numbers
-                .entry(strip.id())
-                .and_modify(|max| *max = (*max).max(number))
-                .or_insert(number);
-            if active_workspace {
-                active.native_workspace_id = Some(strip.id());
-                active.virtual_workspace_number = Some(number);
-            }
-
-            if active_workspace
-                && let Some(window) = row_windows.iter().find(|window| window.focused)
-            {
-                active.focused_window_id = Some(window.window_id);
-                active.focused_bundle_id = Some(window.bundle_id.clone());
-                active.focused_app_name = Some(window.app_name.clone());
-                active.focused_window_title = Some(window.title.clone());
-            }
-
-            virtual_workspaces.push(PaneruVirtualWorkspaceState {
-                number,
-                native_workspace_id: strip.id(),
-                active: active_workspace,
-                windows: row_windows,
-            });
-
-            if active_workspace
-                && let Some((display_id, display_entity)) = active_display
-                && child.parent() == display_entity
-            {
-                active.display_id = Some(display_id);
-            }
+            })
+            .collect::<Vec<_>>();
+
+        let number = strip.virtual_index + 1;
+        workspace_max_numbers
+            .entry(strip.id())
+            .and_modify(|max| *max = (*max).max(number))
+            .or_insert(number);
+        if active_workspace {
+            active.native_workspace_id = Some(strip.id());
+            active.virtual_workspace_number = Some(number);
         }
 
-        let present_numbers = virtual_workspaces
-            .iter()
-            .map(|workspace| (workspace.native_workspace_id, workspace.number))
-            .collect::<HashSet<_>>();
-        for (workspace_id, max_number) in workspace_max_numbers {
-            for number in 1..=max_number {
-                if !present_numbers.contains(&(workspace_id, number)) {
-                    virtual_workspaces.push(PaneruVirtualWorkspaceState {
-                        number,
-                        native_workspace_id: workspace_id,
-                        active: false,
-                        windows: Vec::new(),
-                    });
-                }
-            }
+        if active_workspace && let Some(window) = row_windows.iter().find(|window| window.focused) {
+            active.focused_window_id = Some(window.window_id);
+            active.focused_bundle_id = Some(window.bundle_id.clone());
+            active.focused_app_name = Some(window.app_name.clone());
+            active.focused_window_title = Some(window.title.clone());
         }
 
-        virtual_workspaces
-            .sort_by_key(|workspace| (workspace.native_workspace_id, workspace.number));
-
-        Ok(Self {
-            version: 1,
-            timestamp: now_timestamp(),
-            active,
-            virtual_workspaces,
-        })
+        virtual_workspaces.push(PaneruVirtualWorkspaceState {
+            number,
+            native_workspace_id: strip.id(),
+            active: active_workspace,
+            windows: row_windows,
+        });
+
+        if active_workspace
+            && let Some((display_id, display_entity)) = active_display
+            && child.parent() == display_entity
+        {
+            active.display_id = Some(display_id);
+        }
     }
 
-    pub fn to_query_json(&self, kind: StateQueryKind) -> serde_json::Result<String> {
-        match kind {
-            StateQueryKind::State => serde_json::to_string(self),
-            StateQueryKind::VirtualWorkspaces => serde_json::to_string(&self.virtual_workspaces),
-            StateQueryKind::Active => serde_json::to_string(&self.active),
+    let present_numbers = virtual_workspaces
+        .iter()
+        .map(|workspace| (workspace.native_workspace_id, workspace.number))
+        .collect::<HashSet<_>>();
+    for (workspace_id, max_number) in workspace_max_numbers {
+        for number in 1..=max_number {
+            if !present_numbers.contains(&(workspace_id, number)) {
+                virtual_workspaces.push(PaneruVirtualWorkspaceState {
+                    number,
+                    native_workspace_id: workspace_id,
+                    active: false,
+                    windows: Vec::new(),
+                });
+            }
         }
     }
+
+    virtual_workspaces.sort_by_key(|workspace| (workspace.native_workspace_id, workspace.number));
+
+    Ok(PaneruQueryState {
+        version: 1,
+        timestamp: now_timestamp(),
+        active,
+        virtual_workspaces,
+    })
 }
 
 fn now_timestamp() -> u64 {
@@ -572,7 +719,10 @@ pub fn periodic_state_save(
     displays: Query<(&Display, Entity, Has<ActiveDisplayMarker>)>,
     windows: Windows,
     apps: Query<&Application>,
+    config: Res<Config>,
+    focus_history: Res<FocusHistory>,
 ) {
+    save_runtime_state(&config, &focus_history, &windows);
     let state = PaneruState::extract(&workspaces, &displays, &windows, &apps);
     let path = PaneruState::default_state_file_path();
     if let Err(e) = state.save_to_file(&path) {
@@ -589,9 +739,12 @@ pub fn cleanup_on_exit(
     displays: Query<(&Display, Entity, Has<ActiveDisplayMarker>)>,
     windows: Windows,
     apps: Query<&Application>,
+    config: Res<Config>,
+    focus_history: Res<FocusHistory>,
 ) {
     if exit_events.read().next().is_some() {
         info!("Exiting, saving state...");
+        save_runtime_state(&config, &focus_history, &windows);
         let state = PaneruState::extract(&workspaces, &displays, &windows, &apps);
         let path = PaneruState::default_state_file_path();
         if let Err(e) = state.save_to_file(&path) {
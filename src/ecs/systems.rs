@@ -1,49 +1,58 @@
 use bevy::app::AppExit;
-use bevy::ecs::change_detection::{DetectChanges, DetectChangesMut};
+use bevy::ecs::change_detection::{DetectChanges, DetectChangesMut, Ref};
 use bevy::ecs::entity::Entity;
 use bevy::ecs::hierarchy::{ChildOf, Children};
+use bevy::ecs::lifecycle::RemovedComponents;
 use bevy::ecs::message::{MessageReader, MessageWriter};
 use bevy::ecs::query::{Added, Changed, Has, Or, With, Without};
 use bevy::ecs::system::{
     Commands, Local, NonSend, NonSendMut, Populated, Query, Res, ResMut, Single,
 };
-use bevy::math::IRect;
+use bevy::math::{IRect, IVec2};
 use bevy::tasks::AsyncComputeTaskPool;
 use bevy::tasks::futures_lite::future;
 use bevy::time::Time;
+use notify::Watcher;
 use objc2_foundation::NSPoint;
 use std::collections::HashSet;
 use std::pin::Pin;
-use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tracing::{Level, debug, error, info, instrument, trace, warn};
 
 use super::{
-    ActiveDisplayMarker, BProcess, ExistingMarker, FreshMarker, RepositionMarker, ResizeMarker,
-    RetryFrontSwitch, SpawnWindowTrigger, Timeout, VerifyWindowPosition,
+    ActiveDisplayMarker, BProcess, ConvergenceFailures, ExistingMarker, ExternallyMovedMarker,
+    FreshMarker, MouseHeldMarker, NoAnimationMarker, OriginalFrame, RepositionMarker,
+    ReshuffleAroundMarker, ResizeMarker, RetryFrontSwitch, SpawnWindowTrigger, Timeout,
+    VerifyWindowPosition, Wakeup, WidthLimits, WindowOpacity,
 };
 
-use crate::config::{Config, decorations::BorderRadiusOption};
+use crate::commands::JumpBadges;
+use crate::config::{AnimationEasing, Config, ExternalMovePolicy, decorations::BorderRadiusOption};
 use crate::ecs::display::FloatingLayer;
-use crate::ecs::layout::LayoutStrip;
-use crate::ecs::params::{ActiveDisplay, Windows};
+use crate::ecs::layout::{LayoutStrip, StackTabBars};
+use crate::ecs::params::{ActiveDisplay, ActiveDisplayMut, Windows};
+use crate::ecs::state::RuntimeState;
+use crate::ecs::triggers::watch_included_files;
 use crate::ecs::{
     ActiveWorkspaceMarker, Bounds, BruteforceWindows, FlashMessage, FocusedMarker, Initializing,
     LowPowerMode, MissionControlActive, Position, ReadDisplayProperties, RestoreWindowState,
     Scrolling, SendMessageTrigger, SpawnCommandsExt, Unmanaged, WidthRatio, WindowProperties,
 };
-use crate::events::Event;
+use crate::events::{Event, push_coalesced};
 use crate::manager::{
-    Application, Display, Process, Window, WindowManager, WindowOS, bruteforce_windows,
+    Application, Display, Process, Window, WindowManager, WindowOS, bruteforce_chunks,
+    bruteforce_windows,
 };
-use crate::overlay::{FlashMessageManager, OverlayManager};
+use crate::overlay::{FlashMessageManager, JumpBadgesManager, OverlayManager, StackTabsManager};
 use crate::platform::{PlatformCallbacks, WinID};
 
 const ANIAMTE_SNAP_THRESHOLD: f32 = 5.0;
 const LOOP_MAX_TIMEOUT_FRAME_ACTIVE_MS: u32 = 16;
-const LOOP_MAX_TIMEOUT_LOWPOWER_MS: u32 = 500;
-const LOOP_MAX_TIMEOUT_MS: u32 = 50;
-const LOOP_TIMEOUT_STEP: u32 = 1;
+const LOOP_MAX_TIMEOUT_LOWPOWER_MS: u32 = 5000;
+const LOOP_MAX_TIMEOUT_MS: u32 = 1000;
+const LOOP_MIN_TIMEOUT_MS: u32 = 1;
 
 /// Gathers all present displays and spawns them as entities in the Bevy world.
 /// The currently active display (identified by `window_manager.active_display_id()`) is marked with `ActiveDisplayMarker`.
@@ -151,12 +160,24 @@ pub(crate) fn add_existing_application(
 
         if !offscreen_windows.is_empty() {
             let pid = app.pid();
-            let bundle_id = app.bundle_id();
-            let config = config.clone();
-            let bruteforce_task = thread_pool.spawn(async move {
-                bruteforce_windows(pid, bundle_id.as_deref(), offscreen_windows, &config)
-            });
-            commands.spawn(BruteforceWindows(bruteforce_task));
+            debug!("{pid} has unresolved window on other desktops, bruteforcing them.");
+            // The chunks share the unresolved list, so all of them stop once it is empty.
+            let window_list = Arc::new(Mutex::new(offscreen_windows));
+            for element_ids in bruteforce_chunks() {
+                let bundle_id = app.bundle_id();
+                let window_list = window_list.clone();
+                let config = config.clone();
+                let bruteforce_task = thread_pool.spawn(async move {
+                    bruteforce_windows(
+                        pid,
+                        bundle_id.as_deref(),
+                        element_ids,
+                        &window_list,
+                        &config,
+                    )
+                });
+                commands.spawn(BruteforceWindows(bruteforce_task));
+            }
         }
     }
 }
@@ -191,7 +212,9 @@ pub(crate) fn finish_setup(
     if !bruteforce_tasks.is_empty() {
         for (entity, mut job) in &mut bruteforce_tasks {
             if let Some(found_windows) = future::block_on(future::poll_once(&mut job.0)) {
-                commands.trigger(SpawnWindowTrigger(found_windows));
+                if !found_windows.is_empty() {
+                    commands.trigger(SpawnWindowTrigger(found_windows));
+                }
                 if let Ok(mut entity_commands) = commands.get_entity(entity) {
                     entity_commands.try_despawn();
                 }
@@ -300,6 +323,17 @@ pub(super) fn add_launched_process(
             continue;
         }
 
+        if config.should_ignore_process(process) {
+            debug!(
+                "Ignoring launched process '{}' matching ignore_apps or an unmanaged rule.",
+                process.name()
+            );
+            if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                entity_commands.try_remove::<FreshMarker>();
+            }
+            continue;
+        }
+
         if config.should_force_manage_process(process) {
             debug!(
                 "Forcing management of launched process '{}' despite unobservable policy.",
@@ -489,42 +523,50 @@ pub(super) fn retry_front_switch(
 ///
 /// * `windows` - A `Populated` query for `(&mut Window, Entity, &RepositionMarker)` components.
 /// * `displays` - A query for all `Display` entities, used to get display bounds and menubar height.
+/// * `active_workspace` - The active `LayoutStrip`, whose `[workspaces.<id>]` animation speed applies.
 /// * `time` - The Bevy `Time` resource for calculating delta time.
 /// * `config` - The `Config` resource, used for animation speed.
 /// * `commands` - Bevy commands to remove the `RepositionMarker` when animation is complete.
 #[allow(clippy::needless_pass_by_value)]
 #[instrument(level = Level::TRACE, skip_all)]
 pub(super) fn animate_entities(
-    animate: Populated<(&mut Position, Entity, &RepositionMarker)>,
+    animate: Populated<(
+        &mut Position,
+        Entity,
+        &mut RepositionMarker,
+        Has<NoAnimationMarker>,
+    )>,
+    active_workspace: Query<&LayoutStrip, With<ActiveWorkspaceMarker>>,
     time: Res<Time>,
     config: Res<Config>,
     mut commands: Commands,
 ) {
-    // Frame-rate-independent exponential smoothing (ease-out).
-    // `animation_speed` is the decay rate (per second); higher = snappier.
-    // t = 1 - e^(-rate*dt) is the fraction of remaining distance consumed this frame.
-    let rate = config.animation_speed();
-    let t = (1.0 - (-rate * time.delta_secs_f64()).exp()).clamp(0.0, 1.0) as f32;
+    let easing = config.animation_easing();
+    let rate = config.animation_speed_on(active_workspace.single().ok().map(LayoutStrip::id));
+    let dt = time.delta_secs_f64();
 
     animate
         .into_iter()
-        .for_each(|(mut position, entity, RepositionMarker(origin))| {
-            let target = origin.as_vec2();
-            let current = position.0.as_vec2();
-            let lerped = current.lerp(target, t);
-
-            // Snap once we're within a pixel of the target (or after one effectively-
-            // complete tick), so the marker is dropped promptly.
-            let finished = (target - lerped).length() <= ANIAMTE_SNAP_THRESHOLD;
-            let new_pos = if finished {
-                *origin
+        .for_each(|(mut position, entity, mut marker, instant)| {
+            let start = *marker.start.get_or_insert(position.0);
+            marker.elapsed += dt;
+            let (new_pos, finished) = if instant {
+                (marker.target, true)
             } else {
-                lerped.round().as_ivec2()
+                animation_step(
+                    easing,
+                    rate,
+                    dt,
+                    marker.elapsed,
+                    start,
+                    position.0,
+                    marker.target,
+                )
             };
 
             trace!(
-                "entity {entity} source {} dest {origin} t {t:.3} moving to {new_pos}",
-                position.0,
+                "entity {entity} source {} dest {} moving to {new_pos}",
+                position.0, marker.target,
             );
             position.0 = new_pos;
             if finished && let Ok(mut entity_commands) = commands.get_entity(entity) {
@@ -541,37 +583,54 @@ pub(super) fn animate_entities(
 /// # Arguments
 ///
 /// * `windows` - A `Populated` query for `(&mut Window, Entity, &ResizeMarker)` components.
+///   Targets are clamped to the window's `WidthLimits`, if any.
 /// * `active_display` - An `ActiveDisplay` system parameter providing immutable access to the active display.
+/// * `active_workspace` - The active `LayoutStrip`, whose `[workspaces.<id>]` animation speed applies.
 /// * `commands` - Bevy commands to remove the `ResizeMarker` when resizing is complete.
 #[allow(clippy::needless_pass_by_value)]
 #[instrument(level = Level::TRACE, skip_all)]
 pub(super) fn animate_resize_entities(
-    animate: Populated<(&mut Bounds, Entity, &ResizeMarker)>,
+    animate: Populated<(
+        &mut Bounds,
+        Entity,
+        &mut ResizeMarker,
+        Has<NoAnimationMarker>,
+        Option<&WidthLimits>,
+    )>,
+    active_workspace: Query<&LayoutStrip, With<ActiveWorkspaceMarker>>,
     time: Res<Time>,
     config: Res<Config>,
     mut commands: Commands,
 ) {
-    // Matches animate_entities: exponential ease-out, frame-rate independent.
-    let rate = config.animation_speed();
-    let t = (1.0 - (-rate * time.delta_secs_f64()).exp()).clamp(0.0, 1.0) as f32;
+    let easing = config.animation_easing();
+    let rate = config.animation_speed_on(active_workspace.single().ok().map(LayoutStrip::id));
+    let dt = time.delta_secs_f64();
 
     animate
         .into_iter()
-        .for_each(|(mut bounds, entity, ResizeMarker(size))| {
-            let target = size.as_vec2();
-            let current = bounds.0.as_vec2();
-            let lerped = current.lerp(target, t);
-
-            let finished = (target - lerped).length() <= ANIAMTE_SNAP_THRESHOLD;
-            let new_size = if finished {
-                *size
+        .for_each(|(mut bounds, entity, mut marker, instant, limits)| {
+            if let Some(limits) = limits {
+                marker.target.x = limits.clamp(marker.target.x);
+            }
+            let start = *marker.start.get_or_insert(bounds.0);
+            marker.elapsed += dt;
+            let (new_size, finished) = if instant {
+                (marker.target, true)
             } else {
-                lerped.round().as_ivec2()
+                animation_step(
+                    easing,
+                    rate,
+                    dt,
+                    marker.elapsed,
+                    start,
+                    bounds.0,
+                    marker.target,
+                )
             };
 
             trace!(
-                "entity {entity} source {} dest {size} t {t:.3} resizing to {new_size}",
-                bounds.0,
+                "entity {entity} source {} dest {} resizing to {new_size}",
+                bounds.0, marker.target,
             );
             bounds.0 = new_size;
             if finished && let Ok(mut entity_commands) = commands.get_entity(entity) {
@@ -580,6 +639,71 @@ pub(super) fn animate_resize_entities(
         });
 }
 
+/// Advances an animation from `current` towards `target` by one frame of `dt` seconds and
+/// returns the new value and whether the animation finished.
+///
+/// `EaseOut` is frame-rate-independent exponential smoothing, where `rate` (the
+/// `animation_speed`) is the decay per second. The other curves interpolate from `start` over
+/// a fixed duration, derived from the same rate so that every curve settles in similar time.
+fn animation_step(
+    easing: AnimationEasing,
+    rate: f64,
+    dt: f64,
+    elapsed: f64,
+    start: IVec2,
+    current: IVec2,
+    target: IVec2,
+) -> (IVec2, bool) {
+    let target_f = target.as_vec2();
+    let value = if easing == AnimationEasing::EaseOut {
+        // t = 1 - e^(-rate*dt) is the fraction of remaining distance consumed this frame.
+        let t = (1.0 - (-rate * dt).exp()).clamp(0.0, 1.0) as f32;
+        current.as_vec2().lerp(target_f, t)
+    } else {
+        // Exponential smoothing covers 95% of the distance after 3 / rate seconds.
+        let duration = 3.0 / rate.max(f64::EPSILON);
+        let progress = (elapsed / duration).min(1.0);
+        if progress >= 1.0 {
+            return (target, true);
+        }
+        start
+            .as_vec2()
+            .lerp(target_f, eased_progress(easing, progress) as f32)
+    };
+
+    // Snap once we're within a pixel of the target (or after one effectively-
+    // complete tick), so the marker is dropped promptly.
+    let finished =
+        easing == AnimationEasing::EaseOut && (target_f - value).length() <= ANIAMTE_SNAP_THRESHOLD;
+    if finished {
+        (target, true)
+    } else {
+        (value.round().as_ivec2(), false)
+    }
+}
+
+/// Maps linear animation progress in `[0, 1]` onto the easing curve.
+fn eased_progress(easing: AnimationEasing, progress: f64) -> f64 {
+    match easing {
+        AnimationEasing::EaseOut => 1.0 - (1.0 - progress).powi(3),
+        AnimationEasing::EaseInOut => {
+            if progress < 0.5 {
+                4.0 * progress.powi(3)
+            } else {
+                1.0 - (-2.0 * progress + 2.0).powi(3) / 2.0
+            }
+        }
+        // A damped oscillation which overshoots once and settles on the target.
+        AnimationEasing::Spring => {
+            1.0 - (-6.0 * progress).exp() * (progress * 3.0 * std::f64::consts::PI).cos()
+        }
+    }
+}
+
+/// Sleeps until the next platform event, then forwards the received events to Bevy.
+/// While windows animate the loop wakes up at frame rate, otherwise the sleep backs off up to a
+/// second (or longer in low power mode), cut short by the next pending `Timeout`, periodic
+/// system or dwell registered in `Wakeup`.
 #[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 pub(super) fn pump_events(
     mut exit: MessageWriter<AppExit>,
@@ -591,52 +715,72 @@ pub(super) fn pump_events(
     resizing: Query<(), With<ResizeMarker>>,
     scrolling: Query<(), With<Scrolling>>,
     flash_messages: Query<(), With<FlashMessage>>,
+    polling: Query<
+        (),
+        Or<(
+            With<VerifyWindowPosition>,
+            With<RetryFrontSwitch>,
+            With<ReshuffleAroundMarker>,
+        )>,
+    >,
+    timeouts: Query<&Timeout>,
+    mut wakeup: ResMut<Wakeup>,
     mut timeout: Local<u32>,
 ) {
+    let wakeup_limit = wakeup.take_limit().map_or(u32::MAX, |limit| {
+        u32::try_from(limit.as_millis()).unwrap_or(u32::MAX)
+    });
     let Some((ref mut platform, incoming_events)) = platform.zip(incoming_events) else {
         // No platform interface or incoming event pipe - probably executing in a unit test.
         return;
     };
 
-    platform.pump_cocoa_event_loop(f64::from(*timeout) / 1000.0);
+    let frame_active = !repositioning.is_empty()
+        || !resizing.is_empty()
+        || !scrolling.is_empty()
+        || !flash_messages.is_empty()
+        || !polling.is_empty();
+    let low_power = low_power_mode.is_some_and(|low_power| low_power.0);
+    let timeout_limit = if frame_active {
+        LOOP_MAX_TIMEOUT_FRAME_ACTIVE_MS
+    } else if low_power {
+        LOOP_MAX_TIMEOUT_LOWPOWER_MS
+    } else {
+        LOOP_MAX_TIMEOUT_MS
+    };
+    let next_timeout = timeouts
+        .iter()
+        .map(|timeout| u32::try_from(timeout.timer.remaining().as_millis()).unwrap_or(u32::MAX))
+        .min()
+        .unwrap_or(u32::MAX);
+    let wait = (*timeout)
+        .min(timeout_limit)
+        .min(next_timeout)
+        .min(wakeup_limit);
+    platform.pump_cocoa_event_loop(f64::from(wait) / 1000.0);
+
     let mut received_events = Vec::new();
-    let mut pending_mouse = None;
     loop {
-        // Repeatedly drain the events until timeout.
-        match incoming_events.recv_timeout(Duration::from_millis(1)) {
-            Ok(Event::Exit) | Err(RecvTimeoutError::Disconnected) => {
+        match incoming_events.try_recv() {
+            Ok(Event::Exit) | Err(TryRecvError::Disconnected) => {
                 exit.write(AppExit::Success);
-                break;
-            }
-            Ok(event) => {
-                if matches!(event, Event::MouseMoved { .. }) {
-                    pending_mouse = Some(event);
-                } else {
-                    received_events.extend(pending_mouse.take());
-                    received_events.push(event);
-                }
-                *timeout = LOOP_TIMEOUT_STEP;
-            }
-            Err(RecvTimeoutError::Timeout) => {
-                received_events.extend(pending_mouse.take());
-                messages.write_batch(received_events);
-                let frame_active = !repositioning.is_empty()
-                    || !resizing.is_empty()
-                    || !scrolling.is_empty()
-                    || !flash_messages.is_empty();
-                let low_power = low_power_mode.is_some_and(|low_power| low_power.0);
-                let timeout_limit = if frame_active {
-                    LOOP_MAX_TIMEOUT_FRAME_ACTIVE_MS
-                } else if low_power {
-                    LOOP_MAX_TIMEOUT_LOWPOWER_MS
-                } else {
-                    LOOP_MAX_TIMEOUT_MS
-                };
-                *timeout = timeout.min(timeout_limit) + LOOP_TIMEOUT_STEP;
-                break;
+                return;
             }
+            Ok(event) => push_coalesced(&mut received_events, event),
+            Err(TryRecvError::Empty) => break,
         }
     }
+
+    // Follow-up work of an event usually lands within a few frames, so wake up quickly at
+    // first and double the sleep for every quiet frame.
+    *timeout = if received_events.is_empty() {
+        timeout
+            .saturating_mul(2)
+            .clamp(LOOP_MIN_TIMEOUT_MS, timeout_limit)
+    } else {
+        LOOP_MIN_TIMEOUT_MS
+    };
+    messages.write_batch(received_events);
 }
 
 #[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
@@ -666,6 +810,7 @@ pub(super) fn window_resized_update_frame(
         else {
             continue;
         };
+        window.invalidate_frame();
         if matches!(unmanaged, Some(Unmanaged::Minimized | Unmanaged::Hidden)) {
             continue;
         }
@@ -718,26 +863,54 @@ pub(super) fn window_resized_update_frame(
     }
 }
 
+/// Drops the cached title of windows the application renamed.
+pub(super) fn window_title_changed(
+    mut messages: MessageReader<Event>,
+    mut windows: Query<&mut Window>,
+) {
+    for event in messages.read() {
+        let Event::WindowTitleChanged { window_id } = event else {
+            continue;
+        };
+        if let Some(mut window) = windows.iter_mut().find(|window| window.id() == *window_id) {
+            window.invalidate_title();
+        }
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 #[instrument(level = Level::TRACE, skip_all)]
 pub(super) fn window_moved_update_frame(
     mut messages: MessageReader<Event>,
     mut windows: Query<
-        (&mut Window, &mut Position, &Bounds, Option<&Unmanaged>),
+        (
+            Entity,
+            &mut Window,
+            &mut Position,
+            &Bounds,
+            Option<&Unmanaged>,
+            Has<RepositionMarker>,
+            Has<VerifyWindowPosition>,
+        ),
         Without<LayoutStrip>,
     >,
+    busy: Query<(), Or<(With<MouseHeldMarker>, With<Scrolling>)>>,
+    config: Res<Config>,
+    mut commands: Commands,
 ) {
     for event in messages.read() {
         let Event::WindowMoved { window_id } = event else {
             continue;
         };
 
-        let Some((mut window, mut position, bounds, unmanaged)) = windows
-            .iter_mut()
-            .find(|window| window.0.id() == *window_id)
+        let Some((entity, mut window, mut position, bounds, unmanaged, repositioning, verifying)) =
+            windows
+                .iter_mut()
+                .find(|window| window.1.id() == *window_id)
         else {
             continue;
         };
+        window.invalidate_frame();
         if matches!(unmanaged, Some(Unmanaged::Minimized | Unmanaged::Hidden)) {
             continue;
         }
@@ -748,13 +921,98 @@ pub(super) fn window_moved_update_frame(
         let old_frame = IRect::from_corners(position.0, position.0 + bounds.0);
         if old_frame.min != new_frame.min {
             position.0 = new_frame.min;
+
+            // Nothing of ours is moving the window: no animation, no pending verification, no
+            // mouse interaction and no swipe. Another tool must have moved it.
+            let external = unmanaged.is_none() && !repositioning && !verifying && busy.is_empty();
+            if external
+                && config.external_move_policy().is_some()
+                && let Ok(mut entity_commands) = commands.get_entity(entity)
+            {
+                entity_commands.try_insert(ExternallyMovedMarker);
+            }
+        }
+    }
+}
+
+/// Applies the configured `external_move_policy` to managed windows moved by another tool.
+#[allow(clippy::needless_pass_by_value)]
+#[instrument(level = Level::DEBUG, skip_all)]
+pub(super) fn apply_external_move_policy(
+    moved: Populated<Entity, (With<ExternallyMovedMarker>, With<Window>)>,
+    windows: Windows,
+    mut active_display: ActiveDisplayMut,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let Some(policy) = config.external_move_policy() else {
+        return;
+    };
+
+    for entity in &moved {
+        let Ok(mut entity_commands) = commands.get_entity(entity) else {
+            continue;
+        };
+        if let Some(window) = windows.get(entity) {
+            warn!(
+                "window {} was moved by another application, applying {policy:?}.",
+                window.id()
+            );
+        }
+
+        match policy {
+            ExternalMovePolicy::SnapBack => {
+                entity_commands.try_remove::<ExternallyMovedMarker>();
+                commands.reshuffle_around(entity);
+            }
+            // The marker is removed after floating, so the unmanaged trigger keeps the new frame.
+            ExternalMovePolicy::Float => {
+                entity_commands
+                    .try_insert(Unmanaged::Floating)
+                    .try_remove::<ExternallyMovedMarker>();
+            }
+            ExternalMovePolicy::Reslot => {
+                entity_commands.try_remove::<ExternallyMovedMarker>();
+                let strip = active_display.active_strip();
+                if let Some(frame) = windows.frame(entity)
+                    && strip.contains(entity)
+                {
+                    strip.remove(entity);
+                    let index = nearest_column_slot(strip, frame.center().x, &windows);
+                    strip.insert_at(index, entity);
+                }
+                commands.reshuffle_around(entity);
+            }
         }
     }
 }
 
+/// Returns the strip index at which a column centered at `center_x` should be inserted: next to
+/// the column whose center is closest, on the side the point falls.
+fn nearest_column_slot(strip: &LayoutStrip, center_x: i32, windows: &Windows) -> usize {
+    strip
+        .columns()
+        .enumerate()
+        .filter_map(|(index, column)| {
+            let frame = column.top().and_then(|entity| windows.frame(entity))?;
+            let column_center = frame.center().x;
+            Some((index, column_center, (column_center - center_x).abs()))
+        })
+        .min_by_key(|(_, _, distance)| *distance)
+        .map_or(strip.len(), |(index, column_center, _)| {
+            if center_x < column_center {
+                index
+            } else {
+                index + 1
+            }
+        })
+}
+
 #[allow(clippy::needless_pass_by_value)]
 pub(crate) fn gather_initial_processes(
     receiver: Option<NonSendMut<Receiver<Event>>>,
+    watcher: Option<NonSendMut<Box<dyn Watcher>>>,
+    runtime_state: Option<Res<RuntimeState>>,
     mut displays: Query<&mut Display>,
     mut commands: Commands,
 ) {
@@ -783,6 +1041,16 @@ pub(crate) fn gather_initial_processes(
         }
     }
     while let Some(mut process) = initial_processes.pop() {
+        if initial_config
+            .as_ref()
+            .is_some_and(|c| c.should_ignore_process(&**process))
+        {
+            debug!(
+                "Existing application '{}' matches ignore_apps or an unmanaged rule, ignoring it.",
+                process.name(),
+            );
+            continue;
+        }
         let forced = initial_config
             .as_ref()
             .is_some_and(|c| c.should_force_manage_process(&**process));
@@ -806,7 +1074,14 @@ pub(crate) fn gather_initial_processes(
         }
     }
 
-    if let Some(config) = initial_config {
+    if let Some(mut config) = initial_config {
+        // Options changed with `config set` before the last restart.
+        if let Some(runtime_state) = runtime_state {
+            runtime_state.apply_options(&mut config);
+        }
+        if let Some(mut watcher) = watcher {
+            watch_included_files(&mut **watcher, &config);
+        }
         commands.insert_resource(config);
     }
 }
@@ -928,28 +1203,86 @@ pub(super) fn update_overlays(
     );
 }
 
+/// Applies the moves and resizes of a frame to the OS windows in a single pass. Screen updates
+/// are held back meanwhile, so a scrolling strip shifts as a whole instead of window by window.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
 #[instrument(level = Level::TRACE, skip_all)]
-pub(super) fn commit_window_position(
-    mut moved_windows: Populated<(&mut Window, &Position), Changed<Position>>,
+pub(super) fn commit_window_frames(
+    active_display: ActiveDisplay,
+    mut changed_windows: Populated<
+        (
+            &mut Window,
+            Ref<Position>,
+            Ref<Bounds>,
+            Option<&mut WidthRatio>,
+        ),
+        Or<(Changed<Position>, Changed<Bounds>)>,
+    >,
+    window_manager: Res<WindowManager>,
 ) {
-    moved_windows
+    let display_bounds = active_display.bounds();
+    window_manager.disable_updates();
+    changed_windows
         .par_iter_mut()
-        .for_each(|(mut window, position)| window.reposition(position.0));
+        .for_each(|(mut window, position, size, width_ratio)| {
+            if position.is_changed() {
+                window.reposition(position.0);
+            }
+            if size.is_changed() {
+                if let Some(mut width_ratio) = width_ratio {
+                    width_ratio.0 = f64::from(size.0.x) / f64::from(display_bounds.width());
+                }
+                window.resize(size.0);
+            }
+        });
+    window_manager.reenable_updates();
+}
+
+/// Schedules a position verification for managed windows which just finished moving, so that
+/// applications ignoring reposition requests are noticed by `verify_window_position`.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+#[instrument(level = Level::TRACE, skip_all)]
+pub(super) fn watch_window_convergence(
+    mut finished: RemovedComponents<RepositionMarker>,
+    windows: Query<
+        (),
+        (
+            With<Window>,
+            Without<Unmanaged>,
+            Without<VerifyWindowPosition>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for entity in finished.read() {
+        if windows.contains(entity)
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.try_insert(VerifyWindowPosition::default());
+        }
+    }
 }
 
 #[allow(clippy::needless_pass_by_value)]
 #[instrument(level = Level::TRACE, skip_all)]
 pub(super) fn verify_window_position(
-    mut windows: Populated<(Entity, &mut Window, &Position, &mut VerifyWindowPosition)>,
+    mut windows: Populated<(
+        Entity,
+        &mut Window,
+        &Position,
+        &mut VerifyWindowPosition,
+        Option<&ConvergenceFailures>,
+        Has<Unmanaged>,
+    )>,
     mut commands: Commands,
 ) {
-    for (entity, mut window, position, mut verification) in &mut windows {
-        if window
-            .update_frame()
-            .is_ok_and(|frame| frame.min == position.0)
-        {
+    for (entity, mut window, position, mut verification, failures, unmanaged) in &mut windows {
+        let frame = window.update_frame();
+        if frame.as_ref().is_ok_and(|frame| frame.min == position.0) {
             if let Ok(mut entity_commands) = commands.get_entity(entity) {
-                entity_commands.try_remove::<VerifyWindowPosition>();
+                entity_commands
+                    .try_remove::<VerifyWindowPosition>()
+                    .try_remove::<ConvergenceFailures>();
             }
             continue;
         }
@@ -959,40 +1292,70 @@ pub(super) fn verify_window_position(
             && let Ok(mut entity_commands) = commands.get_entity(entity)
         {
             entity_commands.try_remove::<VerifyWindowPosition>();
+
+            let mut failures = failures.cloned().unwrap_or_default();
+            if failures.record(frame.ok().map(|frame| frame.min)) && !unmanaged {
+                warn!(
+                    "window {} is not responding to reposition requests, removing it from the layout.",
+                    window.id()
+                );
+                entity_commands.try_insert(Unmanaged::Unresponsive);
+            }
+            entity_commands.try_insert(failures);
         }
     }
 }
 
-#[allow(clippy::needless_pass_by_value)]
+/// Periodically retries moving windows marked `Unmanaged::Unresponsive` to their last known
+/// position. Once a window follows the request again, it is handed back to the layout.
 #[instrument(level = Level::TRACE, skip_all)]
-pub(super) fn commit_window_size(
-    active_display: ActiveDisplay,
-    mut resized_windows: Populated<(&mut Window, &Bounds, &mut WidthRatio), Changed<Bounds>>,
+pub(super) fn probe_unresponsive_windows(
+    mut windows: Populated<(Entity, &mut Window, &Position, &Unmanaged)>,
+    mut commands: Commands,
 ) {
-    let display_bounds = active_display.bounds();
-    resized_windows
-        .par_iter_mut()
-        .for_each(|(mut window, size, mut width_ratio)| {
-            width_ratio.0 = f64::from(size.0.x) / f64::from(display_bounds.width());
-            window.resize(size.0);
-        });
+    for (entity, mut window, position, unmanaged) in &mut windows {
+        if !matches!(unmanaged, Unmanaged::Unresponsive) {
+            continue;
+        }
+        window.reposition(position.0);
+        if window
+            .update_frame()
+            .is_ok_and(|frame| frame.min == position.0)
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            info!("window {} is responding again, managing it.", window.id());
+            entity_commands
+                .try_remove::<Unmanaged>()
+                .try_remove::<ConvergenceFailures>();
+        }
+    }
 }
 
 /// Restores user-visible window state before Paneru shuts down: clears any
-/// brightness dim, removes the dim/border overlay window, and centers every
-/// managed window on the display its frame center falls in.
+/// brightness dim and opacity, removes the dim/border overlay window, and moves every
+/// window back to its original frame. Windows whose original frame is no longer on any
+/// display are centered on the display their frame center falls in.
 #[allow(clippy::needless_pass_by_value)]
 pub(super) fn cleanup_on_exit(
     mut exit_events: MessageReader<AppExit>,
-    mut all_windows: Query<&mut Window>,
+    mut all_windows: Query<(&mut Window, Has<WindowOpacity>, Option<&OriginalFrame>)>,
     displays: Query<&Display>,
     window_manager: Res<WindowManager>,
+    config: Res<Config>,
     mut overlay_mgr: Option<NonSendMut<OverlayManager>>,
 ) {
     for _ in exit_events.read() {
-        let ids = all_windows.iter().map(|w| w.id()).collect::<Vec<_>>();
+        let ids = all_windows
+            .iter()
+            .map(|(w, _, _)| w.id())
+            .collect::<Vec<_>>();
         info!("exit cleanup: restoring {} window(s)", ids.len());
         window_manager.dim_windows(&ids, 0.0);
+        for (window, opacity, _) in &all_windows {
+            if opacity || config.unfocused_opacity().is_some() {
+                window_manager.set_window_opacity(window.id(), 1.0);
+            }
+        }
 
         if let Some(ref mut overlay_mgr) = overlay_mgr {
             overlay_mgr.remove_all();
@@ -1003,17 +1366,27 @@ pub(super) fn cleanup_on_exit(
             return;
         }
 
-        for mut window in &mut all_windows {
+        let on_display = |point: IVec2| {
+            display_bounds.iter().find(|b| {
+                point.x >= b.min.x && point.x <= b.max.x && point.y >= b.min.y && point.y <= b.max.y
+            })
+        };
+
+        for (mut window, _, original) in &mut all_windows {
+            if let Some(OriginalFrame(original)) = original
+                && on_display(original.center()).is_some()
+            {
+                info!(
+                    "exit cleanup: window {} -> original frame {original:?}",
+                    window.id()
+                );
+                window.resize(original.size());
+                window.reposition(original.min);
+                continue;
+            }
+
             let frame = window.frame();
-            let center = frame.center();
-            let bounds = display_bounds
-                .iter()
-                .find(|b| {
-                    center.x >= b.min.x
-                        && center.x <= b.max.x
-                        && center.y >= b.min.y
-                        && center.y <= b.max.y
-                })
+            let bounds = on_display(frame.center())
                 .copied()
                 .unwrap_or(display_bounds[0]);
 
@@ -1104,6 +1477,78 @@ pub(crate) fn update_flash_messages(
     }
 }
 
+/// Draws the tab bars of stacked columns, hiding them while swiping or in mission control.
+#[allow(clippy::needless_pass_by_value)]
+pub(super) fn update_stack_tabs(
+    tab_bars: Res<StackTabBars>,
+    active_workspace: Query<(Has<Scrolling>, &LayoutStrip), With<ActiveWorkspaceMarker>>,
+    mission_control_active: Res<MissionControlActive>,
+    tabs_mgr: Option<NonSendMut<StackTabsManager>>,
+) {
+    use crate::overlay::TabBarParams;
+    use objc2_foundation::{NSPoint, NSRect, NSSize};
+
+    let Some(mut tabs_mgr) = tabs_mgr else {
+        return;
+    };
+
+    let hidden = mission_control_active.0
+        || active_workspace
+            .iter()
+            .any(|(swiping, strip)| swiping || strip.is_fullscreen());
+    if hidden {
+        tabs_mgr.remove_all();
+        return;
+    }
+
+    let bars = tab_bars
+        .0
+        .iter()
+        .map(|bar| TabBarParams {
+            frame: NSRect::new(
+                NSPoint::new(f64::from(bar.frame.min.x), f64::from(bar.frame.min.y)),
+                NSSize::new(f64::from(bar.frame.width()), f64::from(bar.frame.height())),
+            ),
+            tabs: bar
+                .tabs
+                .iter()
+                .map(|tab| (tab.title.clone(), tab.active))
+                .collect(),
+        })
+        .collect::<Vec<_>>();
+    tabs_mgr.update(&bars);
+}
+
+/// Draws the column labels of an in-progress `window jump`, removing them once it ends.
+#[allow(clippy::needless_pass_by_value)]
+pub(super) fn update_jump_badges(
+    badges: Res<JumpBadges>,
+    badges_mgr: Option<NonSendMut<JumpBadgesManager>>,
+) {
+    use crate::overlay::JumpBadgeParams;
+    use objc2_foundation::{NSPoint, NSRect, NSSize};
+
+    let Some(mut badges_mgr) = badges_mgr else {
+        return;
+    };
+
+    let badges = badges
+        .0
+        .iter()
+        .map(|badge| JumpBadgeParams {
+            frame: NSRect::new(
+                NSPoint::new(f64::from(badge.frame.min.x), f64::from(badge.frame.min.y)),
+                NSSize::new(
+                    f64::from(badge.frame.width()),
+                    f64::from(badge.frame.height()),
+                ),
+            ),
+            label: badge.label.to_uppercase(),
+        })
+        .collect::<Vec<_>>();
+    badges_mgr.update(&badges);
+}
+
 pub(crate) fn update_low_power_state(low_power_mode: Option<ResMut<LowPowerMode>>) {
     let Some(mut state) = low_power_mode else {
         return;
@@ -1209,3 +1654,106 @@ pub(crate) fn detect_tabbed_windows(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::world::World;
+
+    use super::*;
+
+    #[test]
+    fn eased_curves_start_and_end_on_target() {
+        for easing in [
+            AnimationEasing::EaseOut,
+            AnimationEasing::EaseInOut,
+            AnimationEasing::Spring,
+        ] {
+            assert!(eased_progress(easing, 0.0).abs() < 1e-9, "{easing:?}");
+            assert!(
+                (eased_progress(easing, 1.0) - 1.0).abs() < 0.01,
+                "{easing:?}"
+            );
+        }
+        assert!((eased_progress(AnimationEasing::EaseInOut, 0.5) - 0.5).abs() < 1e-9);
+        // The spring overshoots before settling.
+        assert!(eased_progress(AnimationEasing::Spring, 1.0 / 3.0) > 1.0);
+    }
+
+    #[test]
+    fn duration_based_animation_interpolates_from_start() {
+        let start = IVec2::new(0, 0);
+        let target = IVec2::new(300, 0);
+        // animation_speed 10 gives a duration of 0.3s.
+        let step = |elapsed| {
+            animation_step(
+                AnimationEasing::EaseInOut,
+                10.0,
+                0.1,
+                elapsed,
+                start,
+                start,
+                target,
+            )
+        };
+
+        let (halfway, finished) = step(0.15);
+        assert_eq!(halfway, IVec2::new(150, 0));
+        assert!(!finished);
+        assert_eq!(step(0.3), (target, true));
+    }
+
+    #[test]
+    fn retargeting_keeps_animation_progress() {
+        let mut world = World::new();
+        let entity = world
+            .spawn((
+                RepositionMarker {
+                    target: IVec2::new(100, 0),
+                    start: Some(IVec2::new(0, 0)),
+                    elapsed: 0.2,
+                },
+                ResizeMarker {
+                    target: IVec2::new(400, 300),
+                    start: Some(IVec2::new(200, 300)),
+                    elapsed: 0.1,
+                },
+            ))
+            .id();
+
+        world
+            .commands()
+            .reposition_entity(entity, IVec2::new(200, 0));
+        world.commands().resize_entity(entity, IVec2::new(500, 300));
+        world.flush();
+
+        let marker = world
+            .get::<RepositionMarker>(entity)
+            .expect("reposition marker");
+        assert_eq!(marker.target, IVec2::new(200, 0));
+        assert_eq!(marker.start, Some(IVec2::new(0, 0)));
+        assert!((marker.elapsed - 0.2).abs() < f64::EPSILON);
+        let marker = world.get::<ResizeMarker>(entity).expect("resize marker");
+        assert_eq!(marker.target, IVec2::new(500, 300));
+        assert_eq!(marker.start, Some(IVec2::new(200, 300)));
+        assert!((marker.elapsed - 0.1).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn wakeup_limits_sleep_to_interval_and_deadline() {
+        let mut wakeup = Wakeup::default();
+        assert_eq!(wakeup.take_limit(), None);
+
+        wakeup.every(Duration::from_secs(2));
+        wakeup.every(Duration::from_secs(1));
+        assert_eq!(wakeup.take_limit(), Some(Duration::from_secs(1)));
+
+        wakeup.at(std::time::Instant::now() + Duration::from_millis(200));
+        assert!(
+            wakeup
+                .take_limit()
+                .is_some_and(|limit| limit <= Duration::from_millis(200))
+        );
+        // The deadline is consumed, the periodic interval stays.
+        assert_eq!(wakeup.take_limit(), Some(Duration::from_secs(1)));
+    }
+}
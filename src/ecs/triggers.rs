@@ -7,25 +7,31 @@ use bevy::ecs::query::{Added, Has, With};
 use bevy::ecs::system::{Commands, NonSendMut, Populated, Query, Res, ResMut, Single};
 use bevy::math::IRect;
 use notify::event::{DataChange, MetadataKind, ModifyKind};
-use notify::{EventKind, Watcher};
+use notify::{EventKind, RecursiveMode, Watcher};
 use std::cmp::Ordering;
 use std::time::Duration;
 use tracing::{Level, debug, error, info, instrument, trace, warn};
 
 use super::{
-    ActiveDisplayMarker, BProcess, FocusedMarker, FreshMarker, MissionControlActive,
-    PreviousManagedStrip, RetryFrontSwitch, SpawnWindowTrigger, StrayFocusEvent, SystemTheme,
-    Timeout, Unmanaged,
+    ActiveDisplayMarker, BProcess, ExternallyMovedMarker, FocusedMarker, FreshMarker,
+    MissionControlActive, NewWindowMarker, OriginalFrame, PreviousManagedStrip, RetryFrontSwitch,
+    SelectedVirtualMarker, SpawnWindowTrigger, StrayFocusEvent, SystemTheme, Timeout, Unmanaged,
 };
-use crate::config::Config;
-use crate::ecs::focus::FocusHistory;
+use crate::commands::{Command, InsertNext, Operation};
+use crate::config::{
+    CONFIGURATION_FILE, Config, DisplaySelector, EmptyWorkspaceBehavior, InsertPosition,
+    MaxColumnsPolicy,
+};
+use crate::ecs::focus::{FocusHistory, window_opacity};
 use crate::ecs::layout::LayoutStrip;
 use crate::ecs::params::{ActiveDisplay, GlobalState, Windows};
 use crate::ecs::state::PaneruState;
 use crate::ecs::{
-    ActiveWorkspaceMarker, Bounds, DockPosition, Initializing, LayoutPosition, Position,
-    ResizeMarker, RestoreWindowState, Scrolling, SendMessageTrigger, SpawnCommandsExt,
-    VerifyWindowPosition, WidthRatio, WindowProperties,
+    ActiveWorkspaceMarker, Bounds, DialogMarker, DockPosition, Initializing, InsertNextMarker,
+    InsertPointMarker, LayoutPosition, MinimumHeight, NextInsertion, NoAnimationMarker, PipMarker,
+    Position, ResizeMarker, ResizeRetries, RestoreWindowState, Scrolling, SendMessageTrigger,
+    SpawnCommandsExt, StickyMarker, VerifyWindowPosition, WidthRatio, WindowOpacity,
+    WindowProperties, looks_like_dialog,
 };
 use crate::events::Event;
 use crate::manager::{
@@ -497,6 +503,8 @@ pub(super) fn window_unmanaged_trigger(
     apps: Query<(Entity, &Application)>,
     workspaces: Query<&mut LayoutStrip>,
     active_display: Single<(&Display, Option<&DockPosition>), With<ActiveDisplayMarker>>,
+    externally_moved: Query<(), With<ExternallyMovedMarker>>,
+    dialogs: Query<(), With<DialogMarker>>,
     config: Res<Config>,
     initializing: Option<Res<Initializing>>,
     mut commands: Commands,
@@ -540,6 +548,28 @@ pub(super) fn window_unmanaged_trigger(
         frame
     }
 
+    /// Shifts `frame` diagonally by `step` while it would open exactly on top of a sibling,
+    /// as long as the shifted frame still fits within `bounds`.
+    fn cascade_frame_within_bounds(
+        frame: IRect,
+        siblings: &[Origin],
+        bounds: IRect,
+        step: i32,
+    ) -> IRect {
+        let mut frame = frame;
+        while step > 0 && siblings.contains(&frame.min) {
+            let moved = IRect::from_corners(
+                Origin::new(frame.min.x + step, frame.min.y + step),
+                Origin::new(frame.max.x + step, frame.max.y + step),
+            );
+            if moved.max.x > bounds.max.x || moved.max.y > bounds.max.y {
+                break;
+            }
+            frame = moved;
+        }
+        frame
+    }
+
     let entity = trigger.event().entity;
     let Some((_, _, Some(Unmanaged::Floating))) = windows.get_managed(entity) else {
         return;
@@ -572,19 +602,36 @@ pub(super) fn window_unmanaged_trigger(
         let h = (f64::from(display_bounds.height()) * rh) as i32;
         commands.reposition_entity(entity, Origin::new(x, y));
         commands.resize_entity(entity, Size::new(w, h));
-    } else if initializing.is_none() && !properties.floating() {
-        let max_width = display_bounds.width() * UNMANAGED_MAX_SCREEN_RATIO_NUM
-            / UNMANAGED_MAX_SCREEN_RATIO_DEN;
-        let max_height = display_bounds.height() * UNMANAGED_MAX_SCREEN_RATIO_NUM
-            / UNMANAGED_MAX_SCREEN_RATIO_DEN;
-        let new_width = frame.width().min(max_width);
-        let new_height = frame.height().min(max_height);
-
-        let mut target_frame =
-            IRect::from_corners(frame.min, frame.min + Origin::new(new_width, new_height));
-        target_frame = clamp_origin_to_bounds(target_frame, target_frame.size(), display_bounds);
-        target_frame =
-            offset_frame_within_bounds(target_frame, display_bounds, UNMANAGED_POP_OFFSET);
+    } else if initializing.is_none() && !externally_moved.contains(entity) {
+        let mut target_frame = frame;
+        if !properties.floating(dialogs.contains(entity)) {
+            let max_width = display_bounds.width() * UNMANAGED_MAX_SCREEN_RATIO_NUM
+                / UNMANAGED_MAX_SCREEN_RATIO_DEN;
+            let max_height = display_bounds.height() * UNMANAGED_MAX_SCREEN_RATIO_NUM
+                / UNMANAGED_MAX_SCREEN_RATIO_DEN;
+            let new_width = frame.width().min(max_width);
+            let new_height = frame.height().min(max_height);
+
+            target_frame =
+                IRect::from_corners(frame.min, frame.min + Origin::new(new_width, new_height));
+            target_frame =
+                clamp_origin_to_bounds(target_frame, target_frame.size(), display_bounds);
+            target_frame =
+                offset_frame_within_bounds(target_frame, display_bounds, UNMANAGED_POP_OFFSET);
+        }
+
+        // Windows of the same app tend to open at the same spot, cascade them so none is hidden.
+        let siblings = windows
+            .floating_siblings(entity)
+            .filter_map(|sibling| windows.moving_frame(sibling))
+            .map(|frame| frame.min)
+            .collect::<Vec<_>>();
+        target_frame = cascade_frame_within_bounds(
+            target_frame,
+            &siblings,
+            display_bounds,
+            config.floating_cascade_step(),
+        );
 
         if target_frame.size() != frame.size() {
             commands.resize_entity(
@@ -599,17 +646,25 @@ pub(super) fn window_unmanaged_trigger(
 
     workspaces.into_iter().for_each(|mut strip| {
         if strip.contains(entity) {
+            let width_ratio = windows.width_ratio(entity);
+            remember_managed_strip(entity, &strip, width_ratio, &mut commands);
             strip.remove(entity);
         }
     });
 }
 
-fn remember_managed_strip(entity: Entity, strip: &LayoutStrip, commands: &mut Commands) {
+fn remember_managed_strip(
+    entity: Entity,
+    strip: &LayoutStrip,
+    width_ratio: Option<f64>,
+    commands: &mut Commands,
+) {
     if let Ok(mut entity_commands) = commands.get_entity(entity) {
         entity_commands.try_insert(PreviousManagedStrip {
             workspace_id: strip.id(),
             virtual_index: strip.virtual_index,
             index: strip.index_of(entity).unwrap_or(strip.len()),
+            width_ratio,
         });
     }
 }
@@ -625,14 +680,18 @@ pub(super) fn window_minimized_trigger(
     mut commands: Commands,
 ) {
     let entity = trigger.event().entity;
-    if let Some((_, _, Some(Unmanaged::Minimized | Unmanaged::Hidden))) =
-        windows.get_managed(entity)
+    if let Some((
+        _,
+        _,
+        Some(unmanaged @ (Unmanaged::Minimized | Unmanaged::Hidden | Unmanaged::Unresponsive)),
+    )) = windows.get_managed(entity)
     {
-        debug!("Entity {entity} is minimized or hidden.");
+        debug!("Entity {entity} is {unmanaged:?}.");
         let display_bounds = active_display.bounds();
 
         for (mut strip, active) in workspaces {
-            if active {
+            // An unresponsive window keeps its focus, the user may still be interacting with it.
+            if active && !matches!(unmanaged, Unmanaged::Unresponsive) {
                 give_away_focus(
                     entity,
                     &windows,
@@ -643,7 +702,7 @@ pub(super) fn window_minimized_trigger(
                 );
             }
             if strip.contains(entity) {
-                remember_managed_strip(entity, &strip, &mut commands);
+                remember_managed_strip(entity, &strip, None, &mut commands);
                 strip.remove(entity);
             }
         }
@@ -679,11 +738,25 @@ pub(super) fn window_managed_trigger(
 
     debug!("Entity {entity} is managed again.");
     let (display, dock) = *active_display;
-    let display_bounds = display.actual_display_bounds(dock, &config);
-    let mut insert_at = previous_strips
+    let active_strip = workspaces
+        .iter()
+        .find_map(|(strip, active)| active.then_some(strip));
+    let workspace = active_strip.map(LayoutStrip::id);
+    let display_bounds = display.workspace_bounds(dock, &config, active_strip);
+    // A floated window rejoins the active strip, so its old slot only counts while that strip
+    // is still the active one.
+    let active_slot = workspaces
+        .iter()
+        .find_map(|(strip, active)| active.then(|| (strip.id(), strip.virtual_index)));
+    let previous = previous_strips
         .get(entity)
         .ok()
-        .map(|previous| previous.index);
+        .copied()
+        .filter(|previous| {
+            previous.width_ratio.is_none()
+                || active_slot == Some((previous.workspace_id, previous.virtual_index))
+        });
+    let mut insert_at = previous.map(|previous| previous.index);
 
     if let Some(window) = windows.get(entity)
         && let Some((_, app)) = windows
@@ -693,17 +766,21 @@ pub(super) fn window_managed_trigger(
         let properties = WindowProperties::new(app, window, &config);
 
         if let Some(width_ratio) = properties.width_ratio() {
-            let (_, pad_right, _, pad_left) = config.edge_padding();
+            let (_, pad_right, _, pad_left) = config.edge_padding_for(display.uuid(), workspace);
             let padded_width = display_bounds.width() - pad_left - pad_right;
             let width = (f64::from(padded_width) * width_ratio).round() as i32;
             let height = display_bounds.height();
             commands.resize_entity(entity, Size::new(width, height));
+        } else if let Some(width_ratio) = previous.and_then(|previous| previous.width_ratio) {
+            // Give a floated window back the width it had while tiled.
+            let width = (f64::from(display.bounds().width()) * width_ratio).round() as i32;
+            let height = display_bounds.height();
+            commands.resize_entity(entity, Size::new(width, height));
         }
 
         insert_at = properties.insertion().or(insert_at);
     }
 
-    let previous = previous_strips.get(entity).ok().copied();
     for (mut strip, _) in &mut workspaces {
         strip.remove(entity);
     }
@@ -768,8 +845,10 @@ pub(super) fn window_destroyed_trigger(
     mut messages: MessageReader<Event>,
     windows: Windows,
     active_display: ActiveDisplay,
+    workspaces: Query<&LayoutStrip>,
     mut apps: Query<&mut Application>,
-    mut config: GlobalState,
+    mut global_state: GlobalState,
+    config: Res<Config>,
     mut focus_history: ResMut<FocusHistory>,
     mut commands: Commands,
 ) {
@@ -793,14 +872,23 @@ pub(super) fn window_destroyed_trigger(
         };
         app.unobserve_window(window);
 
+        let active_strip = active_display.active_strip();
         give_away_focus(
             entity,
             &windows,
-            active_display.active_strip(),
+            active_strip,
             &active_display.bounds(),
-            &mut config,
+            &mut global_state,
             &mut commands,
         );
+        if active_strip.contains(entity)
+            && active_strip
+                .all_windows()
+                .iter()
+                .all(|&other| other == entity)
+        {
+            leave_empty_workspace(active_strip, &workspaces, &config, &mut commands);
+        }
         focus_history.forget(entity);
 
         if let Ok(mut entity_commands) = commands.get_entity(entity) {
@@ -811,6 +899,48 @@ pub(super) fn window_destroyed_trigger(
     }
 }
 
+/// Applies the `empty_workspace` option after the last window of `current` closed, switching
+/// to another virtual workspace of the same native space if configured.
+fn leave_empty_workspace(
+    current: &LayoutStrip,
+    workspaces: &Query<&LayoutStrip>,
+    config: &Config,
+    commands: &mut Commands,
+) {
+    let mut rows = workspaces
+        .iter()
+        .filter(|strip| strip.id() == current.id() && strip.virtual_index != current.virtual_index)
+        .collect::<Vec<_>>();
+    rows.sort_by_key(|strip| strip.virtual_index);
+
+    let target = match config.empty_workspace() {
+        EmptyWorkspaceBehavior::Stay => None,
+        EmptyWorkspaceBehavior::Previous => rows
+            .iter()
+            .rev()
+            .find(|strip| strip.virtual_index < current.virtual_index),
+        EmptyWorkspaceBehavior::NextNonEmpty => {
+            let (before, after): (Vec<_>, Vec<_>) = rows
+                .iter()
+                .partition(|strip| strip.virtual_index < current.virtual_index);
+            after
+                .into_iter()
+                .chain(before)
+                .find(|strip| strip.len() > 0)
+        }
+    };
+
+    if let Some(strip) = target {
+        debug!(
+            "virtual workspace {} is empty, switching to {}",
+            current.virtual_index, strip.virtual_index
+        );
+        commands.trigger(SendMessageTrigger(Event::Command {
+            command: Command::Window(Operation::VirtualNumber(strip.virtual_index)),
+        }));
+    }
+}
+
 /// Moves the focus away to a neighbour window.
 fn give_away_focus(
     entity: Entity,
@@ -870,14 +1000,17 @@ fn give_away_focus(
 /// * `apps` - A query for all applications.
 /// * `active_display` - A query for the active display.
 /// * `main_cid` - The main connection ID resource.
+/// * `config` - The configuration, deciding whether dialog-like windows are recognized.
 /// * `commands` - Bevy commands to manage components and trigger events.
-#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 #[instrument(level = Level::DEBUG, skip_all)]
 pub(super) fn spawn_window_trigger(
     mut trigger: On<SpawnWindowTrigger>,
     windows: Query<&Window>,
     mut apps: Query<(Entity, &mut Application)>,
     active_display: ActiveDisplay,
+    mut next_insertion: ResMut<NextInsertion>,
+    config: Res<Config>,
     initializing: Option<Res<Initializing>>,
     restore: Option<Res<crate::ecs::restore::SessionRestore>>,
     mut commands: Commands,
@@ -927,17 +1060,29 @@ pub(super) fn spawn_window_trigger(
         let width_ratio =
             WidthRatio(f64::from(frame.width()) / f64::from(active_display.bounds().width()));
         let layout_position = LayoutPosition::default();
+        // Querying the window for its subrole, buttons and size is slow, so it is done once.
+        let dialog = config.auto_float_dialogs() && looks_like_dialog(&window);
 
         // Insert the window into the internal Bevy state.
         // This insertion triggers window attributes observer.
-        commands.spawn((
+        let mut entity_commands = commands.spawn((
             position,
             bounds,
             width_ratio,
             window,
             layout_position,
+            OriginalFrame(frame),
             ChildOf(app_entity),
         ));
+        if dialog {
+            entity_commands.insert(DialogMarker);
+        }
+        if initializing.is_none() {
+            entity_commands.insert(NewWindowMarker);
+            if let Some(directive) = next_insertion.0.take() {
+                entity_commands.insert(InsertNextMarker(directive));
+            }
+        }
     }
 
     if initializing.is_none() && restore.is_some() {
@@ -945,15 +1090,28 @@ pub(super) fn spawn_window_trigger(
     }
 }
 
-#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 pub(super) fn apply_window_defaults(
-    added: Populated<(&mut Window, &mut Position, &mut Bounds, &ChildOf), Added<Window>>,
+    added: Populated<
+        (
+            Entity,
+            &mut Window,
+            &mut Position,
+            &mut Bounds,
+            &ChildOf,
+            Has<FocusedMarker>,
+        ),
+        Added<Window>,
+    >,
     apps: Query<(Entity, &Application)>,
+    dialogs: Query<(), With<DialogMarker>>,
     active_display: ActiveDisplay,
+    window_manager: Res<WindowManager>,
     config: Res<Config>,
     initializing: Option<Res<Initializing>>,
+    mut commands: Commands,
 ) {
-    for (ref mut window, mut position, mut bounds, child) in added {
+    for (entity, ref mut window, mut position, mut bounds, child, focused) in added {
         let Ok((_, app)) = apps.get(child.parent()) else {
             continue;
         };
@@ -961,10 +1119,28 @@ pub(super) fn apply_window_defaults(
         let properties = WindowProperties::new(app, window, &config);
         debug!("Applying window defaults for '{}'", window.id());
 
+        if !properties.animate()
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.try_insert(NoAnimationMarker);
+        }
+
+        let rule_opacity = properties.opacity();
+        if let Some(opacity) = rule_opacity
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.try_insert(WindowOpacity(opacity));
+        }
+        if let Some(opacity) = window_opacity(&config, rule_opacity, focused) {
+            window_manager.set_window_opacity(window.id(), opacity);
+        }
+
+        window.set_disable_enhanced_ui(properties.disable_enhanced_ui());
+
         let initializing = initializing.is_some();
 
         // Do not add padding to floating windows.
-        if properties.floating() {
+        if properties.floating(dialogs.contains(entity)) {
             // Skip grid_ratios during init: we don't know this window's display.
             if !initializing && let Some((rx, ry, rw, rh)) = properties.grid_ratios() {
                 let bounds = active_display.actual_bounds(&config);
@@ -990,12 +1166,27 @@ pub(super) fn apply_window_defaults(
         // Use padded display width (matching window_resize command behavior).
         // Safe during init: this only resizes, it doesn't reposition, so a
         // window on an inactive display stays put.
-        if let Some(width) = properties.width_ratio() {
+        let display_bounds = active_display.actual_bounds(&config);
+        let (_, pad_right, _, pad_left) = config.edge_padding_for(
+            active_display.display().uuid(),
+            Some(active_display.active_strip().id()),
+        );
+        let padded_width = display_bounds.width() - pad_left - pad_right;
+        let limits = properties.width_limits(padded_width);
+        if let Some(limits) = limits
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.try_insert(limits);
+        }
+        let new_width = properties
+            .width_ratio()
+            .map(|width| (f64::from(padded_width) * width).round() as i32)
+            .or_else(|| limits.map(|_| window.frame().width()))
+            .map(|width| limits.map_or(width, |limits| limits.clamp(width)));
+        if let Some(new_width) = new_width
+            && new_width != window.frame().width()
+        {
             _ = window.update_frame().inspect_err(|err| error!("{err}"));
-            let bounds = active_display.actual_bounds(&config);
-            let (_, pad_right, _, pad_left) = config.edge_padding();
-            let padded_width = bounds.width() - pad_left - pad_right;
-            let new_width = (f64::from(padded_width) * width).round() as i32;
             let height = window.frame().height();
             window.resize(Size::new(new_width, height));
             // Re-read the actual OS size: the app may enforce a minimum width
@@ -1009,9 +1200,18 @@ pub(super) fn apply_window_defaults(
 #[instrument(level = Level::DEBUG, skip_all)]
 pub(super) fn apply_window_positions(
     added: Populated<Entity, Added<Window>>,
-    mut workspaces: Query<(&mut LayoutStrip, Has<ActiveWorkspaceMarker>)>,
+    mut workspaces: Query<(
+        &mut LayoutStrip,
+        Has<ActiveWorkspaceMarker>,
+        Has<SelectedVirtualMarker>,
+    )>,
     windows: Windows,
     apps: Query<&Application>,
+    dialogs: Query<(), With<DialogMarker>>,
+    displays: Query<&Display>,
+    insert_points: Query<Entity, With<InsertPointMarker>>,
+    directives: Query<&InsertNextMarker>,
+    window_manager: Res<WindowManager>,
     config: Res<Config>,
     initializing: Option<Res<Initializing>>,
     restore: Option<Res<crate::ecs::restore::SessionRestore>>,
@@ -1019,7 +1219,7 @@ pub(super) fn apply_window_positions(
     mut commands: Commands,
 ) {
     for entity in added {
-        if workspaces.iter().any(|(strip, _)| strip.tabbed(entity)) {
+        if workspaces.iter().any(|(strip, _, _)| strip.tabbed(entity)) {
             debug!("Ignoring tabbed {entity} attributes.");
             continue;
         }
@@ -1046,53 +1246,125 @@ pub(super) fn apply_window_positions(
 
         let properties = WindowProperties::new(app, window, &config);
 
-        if properties.floating() {
+        if properties.floating(dialogs.contains(entity)) {
             if let Some(mut strip) = workspaces
                 .iter_mut()
-                .find_map(|(strip, _)| strip.contains(entity).then_some(strip))
+                .find_map(|(strip, _, _)| strip.contains(entity).then_some(strip))
             {
                 strip.remove(entity);
             }
             if let Ok(mut entity_commands) = commands.get_entity(entity) {
                 // Avoid managing window if it's floating.
                 entity_commands.try_insert(Unmanaged::Floating);
+                if properties.sticky() {
+                    entity_commands.try_insert(StickyMarker);
+                }
+                if properties.pip() {
+                    entity_commands.try_insert(PipMarker);
+                }
             }
             continue;
         }
 
         // During startup, the window is already inserted into some strip.
         let allready_inserted = workspaces
-            .iter_mut()
-            .find_map(|(strip, _)| strip.contains(entity).then_some(strip));
-        if allready_inserted.is_none()
+            .iter()
+            .any(|(strip, _, _)| strip.contains(entity));
+
+        // A window pinned to another display goes into that display's selected strip.
+        if !allready_inserted
+            && let Some(display) = properties
+                .display()
+                .and_then(|selector| pinned_display(&selector, &displays))
+            && let Ok(space_id) = window_manager.active_display_space(display.id())
+            && let Some(mut strip) = workspaces.iter_mut().find_map(|(strip, active, selected)| {
+                (!active && selected && strip.id() == space_id).then_some(strip)
+            })
+        {
+            debug!("New window {entity} pinned to display {}", display.id());
+            strip.append(entity);
+            commands.reposition_entity(entity, display.bounds().min);
+            commands.reshuffle_around(entity);
+        } else if !allready_inserted
             && let Some(mut strip) = workspaces
                 .iter_mut()
-                .find_map(|(strip, active)| active.then_some(strip))
+                .find_map(|(strip, active, _)| active.then_some(strip))
         {
-            // Attempt inserting the window at a pre-defined position.
-            let insert_at = properties.insertion().map_or_else(
-                || {
-                    // Otherwise attempt inserting it after the current focus.
-                    let focused_window = windows.focused();
-                    // Insert to the right of the currently focused window
-                    focused_window
-                        .and_then(|(_, entity)| strip.index_of(entity).ok())
-                        .and_then(|insert_at| {
-                            (insert_at + 1 < strip.len()).then_some(insert_at + 1)
-                        })
-                },
-                Some,
-            );
+            let focused = windows
+                .focused()
+                .and_then(|(_, focused)| strip.index_of(focused).ok());
+            // New windows of the focused application join its column with `auto_stack_same_app`.
+            let auto_stack = properties.insertion().is_none()
+                && properties.auto_stack_same_app(&config)
+                && windows
+                    .focused()
+                    .and_then(|(focused, _)| windows.find_parent(focused.id()))
+                    .and_then(|(_, _, parent)| apps.get(parent).ok())
+                    .and_then(|focused_app| focused_app.bundle_id())
+                    .is_some_and(|bundle_id| app.bundle_id() == Some(bundle_id));
+            let directive = directives
+                .get(entity)
+                .ok()
+                .map(|InsertNextMarker(directive)| *directive)
+                .or(auto_stack.then_some(InsertNext::Stacked))
+                .zip(focused);
 
             debug!("New window {entity} adding at {}", *strip);
-            match insert_at {
-                Some(after) => {
-                    debug!("New window inserted at {after}");
-                    strip.insert_at(after, entity);
+            if let Some((directive, focused)) = directive {
+                // An `insert next` directive overrides every other placement.
+                debug!("New window placed {directive:?} of column {focused}");
+                match directive {
+                    InsertNext::Left => strip.insert_at(focused, entity),
+                    InsertNext::Right => strip.insert_at(focused + 1, entity),
+                    InsertNext::Stacked => {
+                        strip.insert_at(focused + 1, entity);
+                        _ = strip.stack(entity);
+                    }
+                }
+            } else if config.max_columns_policy() == MaxColumnsPolicy::StackLast
+                && config.max_columns().is_some_and(|max| strip.len() >= max)
+            {
+                // A full strip takes new windows into its last column instead of growing.
+                debug!(
+                    "New window stacked onto the last of {} columns",
+                    strip.len()
+                );
+                strip.append(entity);
+                _ = strip.stack(entity);
+            } else {
+                // Attempt inserting the window at a pre-defined position, then after a marked
+                // insertion point, and otherwise relative to the current focus.
+                let insert_point = insert_points
+                    .iter()
+                    .find_map(|marked| strip.index_of(marked).ok().map(|index| (marked, index)));
+                let insert_at = properties
+                    .insertion()
+                    .or_else(|| {
+                        let (marked, index) = insert_point?;
+                        if let Ok(mut entity_commands) = commands.get_entity(marked) {
+                            entity_commands.try_remove::<InsertPointMarker>();
+                        }
+                        Some(index + 1)
+                    })
+                    .or_else(|| {
+                        let focused = windows.focused().map(|(_, entity)| entity);
+                        insertion_index(config.insert_position(), &strip, focused)
+                    });
+
+                match insert_at {
+                    Some(after) => {
+                        debug!("New window inserted at {after}");
+                        strip.insert_at(after, entity);
+                    }
+                    None => strip.append(entity),
                 }
-                None => strip.append(entity),
             }
         }
+        if directives.contains(entity)
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            entity_commands.try_remove::<InsertNextMarker>();
+        }
 
         // During init, skip per-window reshuffles. finish_setup does a single
         // reshuffle after all windows are added.
@@ -1115,15 +1387,53 @@ pub(super) fn apply_window_positions(
     }
 }
 
+/// Returns the strip index at which a new window opens according to `position`, or `None` to
+/// append it to the end of the strip.
+fn insertion_index(
+    position: InsertPosition,
+    strip: &LayoutStrip,
+    focused: Option<Entity>,
+) -> Option<usize> {
+    let focused = focused.and_then(|entity| strip.index_of(entity).ok());
+    match position {
+        InsertPosition::AfterFocused => focused
+            .map(|index| index + 1)
+            .filter(|index| *index < strip.len()),
+        InsertPosition::BeforeFocused => focused,
+        InsertPosition::Start => Some(0),
+        InsertPosition::End => None,
+    }
+}
+
+/// Finds the display selected by a window rule's `display` option. Indices count from 1 in the
+/// display arrangement, left to right, then top to bottom.
+fn pinned_display<'a>(
+    selector: &DisplaySelector,
+    displays: &'a Query<&Display>,
+) -> Option<&'a Display> {
+    match selector {
+        DisplaySelector::Index(index) => {
+            let mut arranged = displays.iter().collect::<Vec<_>>();
+            arranged.sort_by_key(|display| (display.bounds().min.x, display.bounds().min.y));
+            index
+                .checked_sub(1)
+                .and_then(|index| arranged.get(index).copied())
+        }
+        DisplaySelector::Uuid(uuid) => displays.iter().find(|display| {
+            display
+                .uuid()
+                .is_some_and(|display_uuid| display_uuid.eq_ignore_ascii_case(uuid))
+        }),
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 pub(super) fn refresh_configuration_trigger(
     mut messages: MessageReader<Event>,
     window_manager: Res<WindowManager>,
     mut config: ResMut<Config>,
     mut watcher: Option<NonSendMut<Box<dyn Watcher>>>,
-    windows: Windows,
-    mut displays: Query<&mut Display>,
-    applications: Query<&Application>,
+    mut commands: Commands,
 ) {
     for event in messages.read() {
         let Event::ConfigRefresh(event) = event else {
@@ -1168,25 +1478,67 @@ pub(super) fn refresh_configuration_trigger(
                     **watcher = new_watcher;
                 }
             }
-            info!("Reloading configuration file; {}", path.display());
-            _ = config.reload_config(path.as_path()).inspect_err(|err| {
-                error!("loading config '{}': {err}", path.display());
-            });
+            // A change to an included file reloads the including configuration.
+            let source = if config.included_files().contains(path) {
+                CONFIGURATION_FILE.as_path()
+            } else {
+                path.as_path()
+            };
+            info!("Reloading configuration file; {}", source.display());
+            match config.reload_config(source) {
+                Ok(changes) => {
+                    watch_included_files(&mut ***watcher, &config);
+                    info!("Configuration reloaded: {changes}");
+                    commands.trigger(SendMessageTrigger(Event::ConfigReloaded(changes)));
+                }
+                Err(err) => error!("loading config '{}': {err}", path.display()),
+            }
         }
+    }
+}
 
-        let height = config.menubar_height();
-        for mut display in &mut displays {
-            display.set_menubar_height_override(height);
-        }
+/// Applies the parts of a changed configuration which live outside the ECS, whether it was
+/// reloaded from its file or changed by `config set` and `gaps`.
+#[allow(clippy::needless_pass_by_value)]
+pub(super) fn config_reloaded_trigger(
+    mut messages: MessageReader<Event>,
+    config: Res<Config>,
+    windows: Windows,
+    mut displays: Query<&mut Display>,
+    applications: Query<&Application>,
+) {
+    if !messages
+        .read()
+        .any(|event| matches!(event, Event::ConfigReloaded(_)))
+    {
+        return;
+    }
 
-        // Recompute passthrough keys for the currently focused window.
-        if let Some((window, _, parent)) = windows
-            .focused()
-            .and_then(|(w, e)| windows.find_parent(w.id()).map(|(w, _, p)| (w, e, p)))
-            && let Ok(app) = applications.get(parent)
-        {
-            update_passthrough(window, app, &config);
-        }
+    let height = config.menubar_height();
+    for mut display in &mut displays {
+        display.set_menubar_height_override(height);
+    }
+
+    // Recompute passthrough keys for the currently focused window.
+    if let Some((window, _, parent)) = windows
+        .focused()
+        .and_then(|(w, e)| windows.find_parent(w.id()).map(|(w, _, p)| (w, e, p)))
+        && let Ok(app) = applications.get(parent)
+    {
+        update_passthrough(window, app, &config);
+    }
+
+    // A mode removed from the configuration would otherwise keep swallowing every key.
+    crate::platform::input::revalidate_active_mode(&config);
+}
+
+/// Adds the files included by the configuration to the config watcher, so changes to them
+/// reload the configuration too.
+pub(super) fn watch_included_files(watcher: &mut dyn Watcher, config: &Config) {
+    for path in config.included_files() {
+        _ = watcher
+            .watch(&path, RecursiveMode::NonRecursive)
+            .inspect_err(|err| error!("watching included config '{}': {err}", path.display()));
     }
 }
 
@@ -1229,9 +1581,14 @@ pub(super) fn cleanup_timeout_trigger(
     }
 }
 
+/// Checks that a window reached the size of a finished resize. A window which did not is asked
+/// again up to `ResizeRetries::LIMIT` times, after which its actual size is adopted so that the
+/// layout works with accurate dimensions.
 pub(super) fn window_resize_verifier(
     mut removed: RemovedComponents<ResizeMarker>,
     mut windows: Query<(&mut Window, &Position, &mut Bounds)>,
+    retries: Query<&ResizeRetries>,
+    min_heights: Query<&MinimumHeight>,
     layout_strips: Query<&LayoutStrip>,
     mut commands: Commands,
 ) {
@@ -1251,9 +1608,37 @@ pub(super) fn window_resize_verifier(
         let width_ord = fuzzy_equal(actual_size.x, expected_size.x);
         let height_ord = fuzzy_equal(actual_size.y, expected_size.y);
 
+        // The window went shorter than its learned minimum, so the application no longer needs it.
+        if min_heights
+            .get(entity)
+            .is_ok_and(|min_height| fuzzy_equal(actual_size.y, min_height.0) == Ordering::Greater)
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            debug!("window {} no longer needs a minimum height.", window.id());
+            entity_commands.try_remove::<MinimumHeight>();
+        }
+
+        let retried = retries.get(entity).map_or(0, |retries| retries.0);
         if width_ord == Ordering::Equal && height_ord == Ordering::Equal {
+            if retried > 0
+                && let Ok(mut entity_commands) = commands.get_entity(entity)
+            {
+                entity_commands.try_remove::<ResizeRetries>();
+            }
             continue;
         }
+        if let Ok(mut entity_commands) = commands.get_entity(entity) {
+            if retried < ResizeRetries::LIMIT {
+                debug!(
+                    "window {} is {actual_size} instead of {expected_size}, retrying.",
+                    window.id()
+                );
+                entity_commands.try_insert(ResizeRetries(retried + 1));
+                commands.resize_entity(entity, expected_size);
+                continue;
+            }
+            entity_commands.try_remove::<ResizeRetries>();
+        }
         debug!(
             "window '{}'({}) did not fully resized to {}, was {} instead",
             window.title().unwrap_or_default(),
@@ -1263,6 +1648,18 @@ pub(super) fn window_resize_verifier(
         );
         bounds.0 = actual_size;
 
+        // The app refused to go any shorter, which is its minimum height.
+        if height_ord == Ordering::Less
+            && let Ok(mut entity_commands) = commands.get_entity(entity)
+        {
+            debug!(
+                "window {} needs a height of {}.",
+                window.id(),
+                actual_size.y
+            );
+            entity_commands.try_insert(MinimumHeight(actual_size.y));
+        }
+
         // we may hitting minimum width constraint on this window or this window isn't resizable.
         // if this window is a part of a column, other windows in the column might have resized(shrunk) successfully,
         // which leaves an empty space next to those windows.
@@ -6,7 +6,7 @@ use bevy::ecs::hierarchy::ChildOf;
 use bevy::ecs::lifecycle::Add;
 use bevy::ecs::message::MessageReader;
 use bevy::ecs::observer::On;
-use bevy::ecs::query::{Added, Has, With, Without};
+use bevy::ecs::query::{Added, Changed, Has, With, Without};
 use bevy::ecs::schedule::IntoScheduleConfigs as _;
 use bevy::ecs::schedule::common_conditions::{not, resource_exists};
 use bevy::ecs::system::{Commands, Local, ParamSet, Populated, Query, Res, ResMut, Single};
@@ -17,14 +17,14 @@ use tracing::{Level, debug, error, instrument, warn};
 
 use super::{ActiveDisplayMarker, SpawnWindowTrigger};
 use crate::commands::{Direction, MoveFocus, Operation, filter_window_operations};
-use crate::config::Config;
+use crate::config::{Config, MaxColumnsPolicy};
 use crate::ecs::focus::FocusHistory;
-use crate::ecs::layout::LayoutStrip;
+use crate::ecs::layout::{Column, LayoutStrip};
 use crate::ecs::params::{ActiveDisplay, Windows};
 use crate::ecs::{
-    ActiveWorkspaceMarker, Bounds, DockPosition, Initializing, NativeFullscreenMarker, Position,
-    RefreshWindowSizes, RepositionMarker, Scrolling, SelectedVirtualMarker, SpawnCommandsExt,
-    Timeout, Unmanaged,
+    ActiveWorkspaceMarker, Bounds, DockPosition, FocusedAt, Initializing, NativeFullscreenMarker,
+    Position, RefreshWindowSizes, RepositionMarker, RescaleWidthsMarker, Scrolling,
+    SelectedVirtualMarker, SpawnCommandsExt, StickyMarker, Timeout, Unmanaged, Wakeup, WidthRatio,
 };
 use crate::errors::Result;
 use crate::events::Event;
@@ -38,6 +38,12 @@ impl Plugin for WorkspaceEventsPlugin {
         const REFRESH_WINDOW_CHECK_FREQ_MS: u64 = 1000;
         const DISPLAY_CHANGE_CHECK_FREQ_MS: u64 = 1000;
 
+        app.world_mut()
+            .get_resource_or_init::<Wakeup>()
+            .every(Duration::from_millis(
+                REFRESH_WINDOW_CHECK_FREQ_MS.min(DISPLAY_CHANGE_CHECK_FREQ_MS),
+            ));
+
         let reap_workspaces = |config: Option<Res<Config>>| {
             config.is_some_and(|config| config.reap_empty_workspaces())
         };
@@ -52,9 +58,13 @@ impl Plugin for WorkspaceEventsPlugin {
                 renumber_virtual_indexes,
                 reap_empty_virtual_workspaces.run_if(reap_workspaces),
                 workspace_change_handler,
+                follow_sticky_windows,
                 workspace_created_handler,
                 show_active_workspace,
                 handle_virtual_window_moves,
+                enforce_max_columns
+                    .after(handle_virtual_window_moves)
+                    .run_if(not(resource_exists::<Initializing>)),
                 detect_moved_windows.run_if(not(resource_exists::<Initializing>)),
                 refresh_workspace_window_sizes.run_if(on_timer(Duration::from_millis(
                     REFRESH_WINDOW_CHECK_FREQ_MS,
@@ -205,6 +215,43 @@ fn workspace_change_handler(
     }
 }
 
+/// Moves sticky windows of the active display onto its newly active space, so they stay
+/// visible when switching workspaces. Native fullscreen spaces are skipped.
+#[allow(clippy::needless_pass_by_value)]
+fn follow_sticky_windows(
+    mut messages: MessageReader<Event>,
+    sticky_windows: Query<&Window, With<StickyMarker>>,
+    active_display: Single<&Display, With<ActiveDisplayMarker>>,
+    window_manager: Res<WindowManager>,
+) {
+    if !messages
+        .read()
+        .any(|event| matches!(event, Event::SpaceChanged))
+        || sticky_windows.is_empty()
+    {
+        return;
+    }
+    let display_id = active_display.id();
+    if window_manager.is_fullscreen_space(display_id) {
+        return;
+    }
+    let Ok(workspace_id) = window_manager.active_display_space(display_id) else {
+        return;
+    };
+
+    let bounds = active_display.bounds();
+    for window in sticky_windows
+        .iter()
+        .filter(|window| bounds.contains(window.frame().center()))
+    {
+        debug!(
+            "moving sticky window {} to space {workspace_id}",
+            window.id()
+        );
+        window_manager.move_window_to_space(window.id(), workspace_id);
+    }
+}
+
 #[allow(clippy::needless_pass_by_value, clippy::too_many_arguments)]
 #[instrument(level = Level::DEBUG, skip_all, fields(trigger))]
 fn detect_moved_windows(
@@ -481,15 +528,29 @@ fn find_orphaned_workspaces(
     }
 }
 
-#[allow(clippy::needless_pass_by_value)]
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
 fn refresh_workspace_window_sizes(
-    layout_strip: Single<(&LayoutStrip, Entity, &RefreshWindowSizes), With<ActiveWorkspaceMarker>>,
-    mut windows: Query<(Entity, &mut Window, &mut Bounds, Option<&Unmanaged>)>,
+    layout_strip: Single<
+        (
+            &LayoutStrip,
+            Entity,
+            &RefreshWindowSizes,
+            Has<RescaleWidthsMarker>,
+        ),
+        With<ActiveWorkspaceMarker>,
+    >,
+    mut windows: Query<(
+        Entity,
+        &mut Window,
+        &mut Bounds,
+        Option<&Unmanaged>,
+        Option<&WidthRatio>,
+    )>,
     active_display: ActiveDisplay,
     window_manager: Res<WindowManager>,
     mut commands: Commands,
 ) {
-    let (strip, strip_entity, marker) = *layout_strip;
+    let (strip, strip_entity, marker, rescale) = *layout_strip;
     if !marker.ready() {
         return;
     }
@@ -504,13 +565,20 @@ fn refresh_workspace_window_sizes(
 
     // Resize windows for the new display dimensions.
     for entity in strip.all_windows() {
-        let Ok((_, ref mut window, ref mut bounds, _)) = windows.get_mut(entity) else {
+        let Ok((_, ref mut window, ref mut bounds, _, width_ratio)) = windows.get_mut(entity)
+        else {
             continue;
         };
+        // The display change may have moved the window without telling us.
+        window.invalidate_frame();
         let Ok(frame) = window.update_frame() else {
             continue;
         };
         bounds.0 = frame.size();
+        if rescale && let Some(width_ratio) = width_ratio {
+            bounds.0.x =
+                (width_ratio.0 * f64::from(active_display.bounds().width())).round() as i32;
+        }
         debug!("refreshing window {} frame {:?}", window.id(), frame);
 
         in_workspace.retain(|window_id| *window_id != window.id());
@@ -522,7 +590,7 @@ fn refresh_workspace_window_sizes(
         .filter_map(|window_id| {
             windows
                 .iter()
-                .find_map(|(entity, window, _, unmanaged)| {
+                .find_map(|(entity, window, _, unmanaged, _)| {
                     (window_id == window.id()).then_some(unmanaged.zip(Some(entity)))
                 })
                 .flatten()
@@ -536,7 +604,7 @@ fn refresh_workspace_window_sizes(
     }
 
     if let Ok(mut cmds) = commands.get_entity(strip_entity) {
-        cmds.try_remove::<RefreshWindowSizes>();
+        cmds.try_remove::<(RefreshWindowSizes, RescaleWidthsMarker)>();
     }
 }
 
@@ -891,6 +959,96 @@ fn switch_virtual_workspace_bind(
     );
 }
 
+/// Keeps the active strip within `max_columns` by stacking its least recently focused column
+/// onto a neighbour, or by moving it to the next virtual workspace. With `stack_last`, the last
+/// column is stacked instead.
+#[allow(clippy::needless_pass_by_value, clippy::type_complexity)]
+fn enforce_max_columns(
+    mut active_strip: Single<&mut LayoutStrip, (With<ActiveWorkspaceMarker>, Changed<LayoutStrip>)>,
+    windows: Windows,
+    focused_at: Query<&FocusedAt>,
+    pending_moves: Query<(), With<VirtualMoveMarker>>,
+    config: Res<Config>,
+    mut commands: Commands,
+) {
+    let Some(max_columns) = config.max_columns() else {
+        return;
+    };
+    // Wait for earlier overflow moves to land before picking another column.
+    if active_strip.len() <= max_columns || !pending_moves.is_empty() {
+        return;
+    }
+    let focused = windows.focused().map(|(_, entity)| entity);
+
+    while active_strip.len() > max_columns {
+        let index = match config.max_columns_policy() {
+            MaxColumnsPolicy::StackLast => active_strip.len().checked_sub(1),
+            _ => least_recently_focused_column(&active_strip, focused, &focused_at),
+        };
+        let Some(index) = index else {
+            break;
+        };
+        let Ok(Some(entity)) = active_strip.get(index).map(|column| column.top()) else {
+            break;
+        };
+
+        match config.max_columns_policy() {
+            MaxColumnsPolicy::Stack | MaxColumnsPolicy::StackLast => {
+                let strip = &mut *active_strip;
+                let columns = strip.len();
+                // The leftmost column has nothing to stack onto, so it goes below its neighbour.
+                if index == 0 {
+                    strip.move_column(0, 1);
+                }
+                if strip.stack(entity).is_err() || strip.len() == columns {
+                    break;
+                }
+                debug!("stacked column of {entity} to stay within {max_columns} columns.");
+            }
+            MaxColumnsPolicy::Overflow => {
+                if let Ok(mut entity_commands) = commands.get_entity(entity) {
+                    entity_commands.try_insert(VirtualMoveMarker {
+                        target_virtual_index: active_strip.virtual_index + 1,
+                        move_focus: MoveFocus::Stay,
+                    });
+                }
+                debug!(
+                    "moving {entity} to the next virtual workspace, over {max_columns} columns."
+                );
+                return;
+            }
+        }
+    }
+
+    if let Some(entity) = focused {
+        commands.reshuffle_around(entity);
+    }
+}
+
+/// Finds the column whose windows were focused the longest time ago, never-focused columns first.
+/// The column holding the focused window and fullscreen columns are never picked.
+fn least_recently_focused_column(
+    strip: &LayoutStrip,
+    focused: Option<Entity>,
+    focused_at: &Query<&FocusedAt>,
+) -> Option<usize> {
+    strip
+        .columns()
+        .enumerate()
+        .filter(|(_, column)| {
+            !matches!(column, Column::Fullscren(_))
+                && focused.is_none_or(|focused| !column.window_iter().any(|e| e == focused))
+        })
+        .min_by_key(|(_, column)| {
+            column
+                .window_iter()
+                .filter_map(|entity| focused_at.get(entity).ok())
+                .map(|focused_at| focused_at.0)
+                .max()
+        })
+        .map(|(index, _)| index)
+}
+
 /// Handles the keybinding to move windows between virtual workspaces.
 #[instrument(level = Level::DEBUG, skip_all)]
 #[allow(clippy::needless_pass_by_value)]
@@ -1009,7 +1167,7 @@ pub(crate) fn show_active_workspace(
             }
 
             let origin = match &moving {
-                Some(RepositionMarker(destination)) => *destination,
+                Some(marker) => marker.target,
                 None => position.0,
             };
             if let Ok(mut entity_commands) = commands.get_entity(entity) {
@@ -1,13 +1,18 @@
 use bevy::ecs::message::Message;
 use objc2::rc::Retained;
-use objc2_core_foundation::{CFRetained, CGPoint};
+use objc2_core_foundation::{
+    CFRetained, CFRunLoop, CFRunLoopSource, CFRunLoopSourceContext, CGPoint, kCFRunLoopCommonModes,
+};
 use objc2_core_graphics::CGDirectDisplayID;
+use std::ffi::c_void;
 use std::os::unix::net::UnixStream;
+use std::ptr::null_mut;
+use std::sync::atomic::{AtomicPtr, Ordering};
 use std::sync::mpsc::{Receiver, Sender, channel};
 use std::sync::{Arc, Mutex};
 
 use crate::commands::Command;
-use crate::config::Config;
+use crate::config::{Config, ConfigChanges};
 use crate::ecs::state::StateQueryKind;
 use crate::errors::Result;
 use crate::platform::{Modifiers, ProcessSerialNumber, WinID, WorkspaceId, WorkspaceObserver};
@@ -27,6 +32,8 @@ pub enum Event {
     InitialConfig(Config),
     /// Signals that the configuration should be reloaded.
     ConfigRefresh(notify::Event),
+    /// The configuration was reloaded, with a summary of what changed.
+    ConfigReloaded(ConfigChanges),
 
     /// An application has been launched.
     ApplicationLaunched {
@@ -79,6 +86,10 @@ pub enum Event {
         point: CGPoint,
         modifiers: Modifiers,
     },
+    /// The left mouse button went down next to a tiled column edge with the
+    /// `mouse_resize_modifier` held. The click is kept from the window, and the drag that follows
+    /// resizes the column.
+    MouseResizeGrab { point: CGPoint },
     /// A mouse move event has occurred.
     MouseMoved {
         point: CGPoint,
@@ -101,6 +112,11 @@ pub enum Event {
     TouchpadDown,
     /// All fingers are up from the touchpad.
     TouchpadUp,
+    /// The modifiers of the keybinding driving a `window cycle-recent` session or a `window peek`
+    /// were released.
+    CycleModifiersReleased,
+    /// The key pressed while a `window jump` waited for a column label.
+    JumpKey { keycode: u8 },
 
     /// A new space (virtual desktop) has been created.
     SpaceCreated { space_id: WorkspaceId },
@@ -161,6 +177,74 @@ pub enum Event {
     StateSubscribe { stream: Arc<Mutex<UnixStream>> },
 }
 
+impl Event {
+    /// Returns true if this event makes an `earlier` one redundant, because handling the latest
+    /// of them gives the same result.
+    fn supersedes(&self, earlier: &Event) -> bool {
+        match (self, earlier) {
+            (Event::MouseMoved { .. }, Event::MouseMoved { .. })
+            | (Event::SpaceChanged, Event::SpaceChanged) => true,
+            (Event::WindowMoved { window_id }, Event::WindowMoved { window_id: earlier }) => {
+                window_id == earlier
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Appends `event` to a batch of received events, or stores it in place of an earlier event it
+/// supersedes, so the batch keeps the order the events first arrived in. Mouse moves only
+/// collapse with a directly preceding move, so clicks and drags still see the pointer where it
+/// was at the time.
+pub fn push_coalesced(events: &mut Vec<Event>, event: Event) {
+    let earlier = if matches!(event, Event::MouseMoved { .. }) {
+        events
+            .len()
+            .checked_sub(1)
+            .filter(|&last| event.supersedes(&events[last]))
+    } else {
+        events.iter().rposition(|earlier| event.supersedes(earlier))
+    };
+    match earlier {
+        Some(index) => events[index] = event,
+        None => events.push(event),
+    }
+}
+
+/// Run loop source on the main thread, signalled by `EventSender::send`. A signal stays pending
+/// until the run loop handles it, so an event sent while the main thread is busy still cuts
+/// its next sleep short.
+static WAKE_SOURCE: AtomicPtr<CFRunLoopSource> = AtomicPtr::new(null_mut());
+
+/// Adds the run loop source which wakes the main thread for events from other threads.
+/// Has to be called on the main thread, before it sleeps in `pump_cocoa_event_loop`.
+pub fn install_wake_source() {
+    // Nothing to do, handling the source is what makes the run loop return.
+    unsafe extern "C-unwind" fn perform(_info: *mut c_void) {}
+
+    let mut context = CFRunLoopSourceContext {
+        version: 0,
+        info: null_mut(),
+        retain: None,
+        release: None,
+        copyDescription: None,
+        equal: None,
+        hash: None,
+        schedule: None,
+        cancel: None,
+        perform: Some(perform),
+    };
+    let Some(main_loop) = CFRunLoop::main() else {
+        return;
+    };
+    let Some(source) = (unsafe { CFRunLoopSource::new(None, 0, &raw mut context) }) else {
+        return;
+    };
+    CFRunLoop::add_source(&main_loop, Some(&source), unsafe { kCFRunLoopCommonModes });
+    // The source lives as long as the process, so its reference is never released.
+    WAKE_SOURCE.store(CFRetained::into_raw(source).as_ptr(), Ordering::Release);
+}
+
 /// `EventSender` is a thin wrapper around a `std::sync::mpsc::Sender` for `Event`s.
 /// It provides a convenient way to send events to the main event loop from various parts of the application.
 #[derive(Clone, Debug)]
@@ -190,6 +274,72 @@ impl EventSender {
     ///
     /// `Ok(())` if the event is sent successfully, otherwise `Err(Error)` if the receiver has disconnected.
     pub fn send(&self, event: Event) -> Result<()> {
-        Ok(self.tx.send(event)?)
+        self.tx.send(event)?;
+        // Wakes the main thread when it sleeps in `pump_cocoa_event_loop`. Signalling a source
+        // and waking the run loop are both safe from any thread.
+        if let Some(source) = unsafe { WAKE_SOURCE.load(Ordering::Acquire).as_ref() }
+            && let Some(main_loop) = CFRunLoop::main()
+        {
+            source.signal();
+            main_loop.wake_up();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mouse_moved(x: f64) -> Event {
+        Event::MouseMoved {
+            point: CGPoint::new(x, 0.0),
+            modifiers: Modifiers::empty(),
+        }
+    }
+
+    #[test]
+    fn coalesces_window_moves_and_space_changes_in_place() {
+        let mut events = Vec::new();
+        for event in [
+            Event::WindowMoved { window_id: 1 },
+            Event::SpaceChanged,
+            Event::WindowMoved { window_id: 2 },
+            Event::WindowMoved { window_id: 1 },
+            Event::SpaceChanged,
+        ] {
+            push_coalesced(&mut events, event);
+        }
+
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::WindowMoved { window_id: 1 },
+                Event::SpaceChanged,
+                Event::WindowMoved { window_id: 2 },
+            ]
+        ));
+    }
+
+    #[test]
+    fn coalesces_only_consecutive_mouse_moves() {
+        let mut events = Vec::new();
+        for event in [
+            mouse_moved(1.0),
+            mouse_moved(2.0),
+            Event::TouchpadDown,
+            mouse_moved(3.0),
+        ] {
+            push_coalesced(&mut events, event);
+        }
+
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::MouseMoved { point, .. },
+                Event::TouchpadDown,
+                Event::MouseMoved { .. },
+            ] if *point == CGPoint::new(2.0, 0.0)
+        ));
     }
 }
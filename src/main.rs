@@ -2,11 +2,12 @@
 
 use std::sync::mpsc::{Receiver, TryRecvError};
 
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, Args, Parser, Subcommand, ValueEnum};
 use tracing::{error, warn};
 use tracing_subscriber::{EnvFilter, fmt, prelude::*};
 
 mod accessibility_prompt;
+mod announcer;
 mod commands;
 mod config;
 mod ecs;
@@ -26,7 +27,7 @@ embed_plist::embed_info_plist!("../assets/Info.plist");
 
 use events::{Event, EventSender};
 
-use ecs::state::StateQueryKind;
+use ecs::state::{PaneruWindowState, StateQueryKind};
 use errors::Result;
 use platform::service;
 use reader::CommandReader;
@@ -61,22 +62,33 @@ pub enum SubCmd {
     Launch,
 
     /// Installs the `paneru` daemon as a background service.
-    Install,
+    Install(InstallArgs),
 
     /// Uninstalls the `paneru` background service.
-    Uninstall,
+    Uninstall(ServiceArgs),
 
     /// Reinstalls the `paneru` background service.
-    Reinstall,
+    Reinstall(InstallArgs),
 
     /// Starts the `paneru` background service.
-    Start,
+    Start(ServiceArgs),
 
     /// Stops the `paneru` background service.
-    Stop,
+    Stop(ServiceArgs),
 
     /// Restarts the `paneru` background service.
-    Restart,
+    Restart(ServiceArgs),
+
+    /// Reports whether the background service is loaded, its PID and uptime, and whether the
+    /// daemon answers on the command socket.
+    Status(ServiceArgs),
+
+    /// Writes a commented default configuration to `$XDG_CONFIG_HOME/paneru/paneru.toml`.
+    InitConfig {
+        /// Replace an existing configuration file.
+        #[arg(long)]
+        force: bool,
+    },
 
     /// Sends a command via a Unix socket to the running `paneru` daemon.
     SendCmd {
@@ -97,6 +109,46 @@ pub enum SubCmd {
     },
 }
 
+/// Selects the launchd job managed by a service subcommand.
+#[derive(Clone, Debug, Args)]
+pub struct ServiceArgs {
+    /// The launchd label of the service.
+    #[arg(long, default_value = service::ID)]
+    label: String,
+}
+
+impl ServiceArgs {
+    fn service(&self) -> Result<service::Service> {
+        Ok(service::Service::try_new(&self.label)?)
+    }
+}
+
+/// Options for the launchd plist generated by `install` and `reinstall`.
+#[derive(Clone, Debug, Args)]
+pub struct InstallArgs {
+    /// Redirects stdout and stderr of the service to this file.
+    #[arg(long)]
+    log_path: Option<std::path::PathBuf>,
+    #[command(flatten)]
+    service: ServiceArgs,
+    /// Restarts the service after a crash.
+    #[arg(long, default_value_t = true, action = ArgAction::Set)]
+    keep_alive: bool,
+    /// Scheduling priority of the service, from -20 (highest) to 20.
+    #[arg(long, default_value_t = -20, allow_negative_numbers = true)]
+    nice: i32,
+}
+
+impl InstallArgs {
+    fn options(&self) -> service::InstallOptions {
+        service::InstallOptions {
+            log_path: self.log_path.clone(),
+            keep_alive: self.keep_alive,
+            nice: self.nice,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Subcommand)]
 pub enum QueryCmd {
     /// Prints the complete state document.
@@ -114,6 +166,20 @@ pub enum QueryCmd {
         #[arg(long)]
         json: bool,
     },
+    /// Prints the windows of all virtual workspaces.
+    Windows {
+        #[arg(long)]
+        json: bool,
+        /// Prints one tab separated `id`, `app`, `title` line per window instead of JSON,
+        /// for pickers like `fzf`.
+        #[arg(long, value_enum)]
+        format: Option<WindowsFormat>,
+    },
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum WindowsFormat {
+    Fzf,
 }
 
 /// The main entry point of the `paneru` application.
@@ -123,8 +189,25 @@ pub enum QueryCmd {
 ///
 /// `Ok(())` if the application runs successfully, otherwise `Err(Error)`.
 fn main() -> Result<()> {
+    let subcmd = Paneru::parse().subcmd.unwrap_or_default();
+
+    // Only the daemon writes log files. The other subcommands are short-lived clients, which
+    // would otherwise open, and possibly rotate, the files of a running daemon.
+    let (log_file, log_file_error) = match matches!(subcmd, SubCmd::Launch)
+        .then(config::discover_configuration_file)
+        .flatten()
+        .and_then(|path| config::Config::new(&path).ok())
+        .map(|config| util::log_file_layer(&config))
+        .transpose()
+    {
+        Ok(layer) => (layer.flatten(), None),
+        Err(err) => (None, Some(err)),
+    };
+
     tracing_subscriber::registry()
-        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with(util::reloadable_log_filter(
+            EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")),
+        ))
         .with(
             fmt::layer()
                 .with_level(true)
@@ -135,11 +218,12 @@ fn main() -> Result<()> {
                 .with_writer(std::io::stderr)
                 .compact(),
         )
+        .with(log_file)
         .init();
+    if let Some(err) = log_file_error {
+        warn!("{err}");
+    }
 
-    let service = || service::Service::try_new(service::ID);
-
-    let subcmd = Paneru::parse().subcmd.unwrap_or_default();
     maybe_warn_deprecated_options_for_service(&subcmd);
 
     match subcmd {
@@ -156,27 +240,48 @@ fn main() -> Result<()> {
                 return Ok(());
             }
             match setup_bevy_app(sender, receiver) {
-                Ok(mut app) => {
+                Ok(app) => {
+                    // A panic never reaches the exit cleanup, so put the windows back while
+                    // unwinding instead of leaving them scrolled off-screen.
+                    let mut app = scopeguard::guard_on_unwind(app, |mut app| {
+                        ecs::restore_original_frames(app.world_mut());
+                    });
                     app.run();
                 }
                 Err(err) => {
                     error!(
                         "Error launching Paneru: {err}.\nStopping the service for now. You can restart it again with 'paneru restart'."
                     );
-                    service()?.stop()?;
+                    service::Service::try_new(&service::running_label())?.stop()?;
                 }
             }
         }
-        SubCmd::Install => service()?.install()?,
-        SubCmd::Uninstall => service()?.uninstall()?,
-        SubCmd::Reinstall => service()?.reinstall()?,
-        SubCmd::Start => service()?.start()?,
-        SubCmd::Stop => service()?.stop()?,
-        SubCmd::Restart => service()?.restart()?,
+        SubCmd::Install(args) => args.service.service()?.install(&args.options())?,
+        SubCmd::Uninstall(args) => args.service()?.uninstall()?,
+        SubCmd::Reinstall(args) => args.service.service()?.reinstall(&args.options())?,
+        SubCmd::Start(args) => args.service()?.start()?,
+        SubCmd::Stop(args) => args.service()?.stop()?,
+        SubCmd::Restart(args) => args.service()?.restart()?,
+        SubCmd::Status(args) => print_status(&args.service()?),
+        SubCmd::InitConfig { force } => {
+            let path = config::write_default_configuration(force)?;
+            println!("Wrote default configuration to {}", path.display());
+        }
         SubCmd::SendCmd { cmd } => CommandReader::send_command(cmd)?,
         SubCmd::Query { query } => {
             let output = CommandReader::send_query(query.kind())?;
-            print!("{output}");
+            if let QueryCmd::Windows {
+                format: Some(WindowsFormat::Fzf),
+                ..
+            } = query
+            {
+                let windows: Vec<PaneruWindowState> = serde_json::from_str(&output)?;
+                for window in windows {
+                    println!("{}", window.picker_line());
+                }
+            } else {
+                print!("{output}");
+            }
         }
         SubCmd::Subscribe { json: _ } => CommandReader::subscribe_json()?,
     }
@@ -222,12 +327,38 @@ fn wait_for_accessibility(sender: EventSender, receiver: &Receiver<Event>) -> bo
     }
 }
 
+/// Prints the state of the launchd job and the command socket for `paneru status`.
+fn print_status(service: &service::Service) {
+    let status = service.status();
+    let job = match (status.installed, status.loaded) {
+        (_, true) => "loaded",
+        (true, false) => "installed, not loaded",
+        (false, false) => "not installed",
+    };
+    let responsive = CommandReader::ping(std::time::Duration::from_secs(3));
+    println!("service: {job} ({})", service.raw.name);
+    println!(
+        "pid:     {}",
+        status.pid.map_or("-".to_string(), |pid| pid.to_string())
+    );
+    println!("uptime:  {}", status.uptime.as_deref().unwrap_or("-"));
+    println!(
+        "socket:  {}",
+        if responsive {
+            "responsive"
+        } else {
+            "not responding"
+        }
+    );
+}
+
 impl QueryCmd {
     fn kind(&self) -> StateQueryKind {
         match self {
             QueryCmd::State { json: _ } => StateQueryKind::State,
             QueryCmd::VirtualWorkspaces { json: _ } => StateQueryKind::VirtualWorkspaces,
             QueryCmd::Active { json: _ } => StateQueryKind::Active,
+            QueryCmd::Windows { .. } => StateQueryKind::Windows,
         }
     }
 }
@@ -235,7 +366,11 @@ impl QueryCmd {
 fn should_check_deprecated_options(subcmd: &SubCmd) -> bool {
     matches!(
         subcmd,
-        SubCmd::Install | SubCmd::Uninstall | SubCmd::Start | SubCmd::Stop | SubCmd::Restart
+        SubCmd::Install(_)
+            | SubCmd::Uninstall(_)
+            | SubCmd::Start(_)
+            | SubCmd::Stop(_)
+            | SubCmd::Restart(_)
     )
 }
 
@@ -12,13 +12,15 @@ use objc2_core_foundation::{
     CGPoint, CGRect, CGSize, kCFBooleanTrue,
 };
 use objc2_core_graphics::{
-    CGAssociateMouseAndMouseCursorPosition, CGDirectDisplayID, CGDisplayBounds,
+    CGAssociateMouseAndMouseCursorPosition, CGDirectDisplayID, CGDisplayBounds, CGEventFlags,
     CGGetActiveDisplayList, CGWarpMouseCursorPosition, CGWindowListCopyWindowInfo,
     CGWindowListOption, kCGNullWindowID, kCGWindowNumber,
 };
+use std::ops::Range;
 use std::path::Path;
 use std::ptr::null_mut;
 use std::slice::from_raw_parts_mut;
+use std::sync::{Mutex, PoisonError};
 use std::time::Duration;
 use stdext::function_name;
 use tracing::{Level, debug, error, instrument, trace, warn};
@@ -26,7 +28,12 @@ use tracing::{Level, debug, error, instrument, trace, warn};
 use crate::config::Config;
 use crate::errors::{Error, Result};
 use crate::events::{Event, EventSender};
-use crate::manager::skylight::SLSSetWindowListBrightness;
+use crate::manager::skylight::{
+    SLSDisableUpdate, SLSHideSpaces, SLSManagedDisplaySetCurrentSpace,
+    SLSMoveWindowsToManagedSpace, SLSReenableUpdate, SLSSetWindowAlpha, SLSSetWindowListBrightness,
+    SLSShowSpaces,
+};
+use crate::platform::input::post_keystroke;
 use crate::platform::{ConnID, Pid, ProcessSerialNumber, WinID, WorkspaceId};
 use crate::util::{AXUIWrapper, MacResult, create_array, symlink_target};
 use app::ApplicationOS;
@@ -59,6 +66,10 @@ mod windows;
 pub type Origin = IVec2;
 pub type Size = IVec2;
 
+/// Virtual keycodes of the digit keys `1` to `9` and `0`, which switch to desktops 1 to 10 in
+/// the default Mission Control shortcuts.
+const DESKTOP_KEYCODES: [u16; 10] = [0x12, 0x13, 0x14, 0x15, 0x17, 0x16, 0x1a, 0x1c, 0x19, 0x1d];
+
 pub fn origin_from(point: CGPoint) -> Origin {
     Origin::new(point.x as i32, point.y as i32)
 }
@@ -183,7 +194,27 @@ pub trait WindowManagerApi: Send + Sync {
 
     fn dim_windows(&self, windows: &[WinID], level: f32);
 
+    /// Sets the opacity of a window, from 0.0 (transparent) to 1.0 (opaque).
+    fn set_window_opacity(&self, window: WinID, opacity: f32);
+
+    /// Moves a window to another space of its display.
+    fn move_window_to_space(&self, window: WinID, space_id: WorkspaceId);
+
+    /// Returns the desktops of a display in Mission Control order, leaving out fullscreen spaces.
+    /// `space focus <n>` and `window tospace <n>` count these from one.
+    fn display_desktops(&self, display_id: CGDirectDisplayID) -> Result<Vec<WorkspaceId>>;
+
+    /// Switches the display to one of its spaces.
+    fn focus_space(&self, display_id: CGDirectDisplayID, space_id: WorkspaceId) -> Result<()>;
+
     fn windows_on_screen(&self) -> Option<Vec<WinID>>;
+
+    /// Holds back screen updates until `reenable_updates`, so a batch of window moves and
+    /// resizes appears at once instead of one window at a time.
+    fn disable_updates(&self);
+
+    /// Shows all window changes made since `disable_updates`.
+    fn reenable_updates(&self);
 }
 
 /// `WindowManager` is a Bevy resource that holds a boxed `WindowManagerApi` trait object.
@@ -300,6 +331,39 @@ impl WindowManagerOS {
         }
     }
 
+    /// Returns `true` if the space is a regular desktop rather than a fullscreen space.
+    fn is_desktop(&self, space_id: WorkspaceId) -> bool {
+        unsafe { SLSSpaceGetType(self.main_cid, space_id) == 0 }
+    }
+
+    /// Returns the Mission Control desktop number of a space, counting the desktops of all
+    /// displays from one. Fullscreen spaces have no number, the same as in `display_desktops`.
+    fn desktop_number(&self, space_id: WorkspaceId) -> Option<usize> {
+        let display_spaces = NonNull::new(unsafe { SLSCopyManagedDisplaySpaces(self.main_cid) })
+            .map(|ptr| unsafe { CFRetained::from_raw(ptr) })?;
+        display_spaces
+            .iter()
+            .filter_map(|display| {
+                let display = unsafe {
+                    display.cast_unchecked::<CFString, CFArray<CFDictionary<CFString, CFNumber>>>()
+                };
+                display.get(&CFString::from_static_str("Spaces"))
+            })
+            .flat_map(|spaces| {
+                spaces
+                    .iter()
+                    .filter_map(|space| {
+                        space
+                            .get(&CFString::from_static_str("id64"))
+                            .and_then(|id| id.as_i64().and_then(|value| u64::try_from(value).ok()))
+                    })
+                    .collect::<Vec<WorkspaceId>>()
+            })
+            .filter(|id| self.is_desktop(*id))
+            .position(|id| id == space_id)
+            .map(|index| index + 1)
+    }
+
     /// Returns the connection ID (`ConnID`) for a given process serial number (`PSN`).
     ///
     /// # Arguments
@@ -560,6 +624,71 @@ impl WindowManagerApi for WindowManagerOS {
         .inspect_err(|err| debug!("{err}"));
     }
 
+    fn set_window_opacity(&self, window: WinID, opacity: f32) {
+        _ = unsafe { SLSSetWindowAlpha(self.main_cid, window, opacity.clamp(0.0, 1.0)) }
+            .to_result(function_name!())
+            .inspect_err(|err| debug!("{err}"));
+    }
+
+    fn move_window_to_space(&self, window: WinID, space_id: WorkspaceId) {
+        match create_array(&[window], CFNumberType::SInt32Type) {
+            Ok(window_list) => unsafe {
+                SLSMoveWindowsToManagedSpace(self.main_cid, &raw const *window_list, space_id);
+            },
+            Err(err) => debug!("{}: {err}", function_name!()),
+        }
+    }
+
+    fn display_desktops(&self, display_id: CGDirectDisplayID) -> Result<Vec<WorkspaceId>> {
+        let uuid = Display::uuid_from_id(display_id)?;
+        let mut spaces = self.display_space_list(uuid.as_ref())?;
+        spaces.retain(|id| self.is_desktop(*id));
+        Ok(spaces)
+    }
+
+    /// Switches spaces through `SkyLight`. Without the Dock's cooperation the switch does not
+    /// always stick, in which case the Mission Control shortcut `ctrl - <number>` is typed instead.
+    /// macOS only has these shortcuts for the first ten desktops.
+    fn focus_space(&self, display_id: CGDirectDisplayID, space_id: WorkspaceId) -> Result<()> {
+        let uuid = Display::uuid_from_id(display_id)?;
+        let current = self.active_display_space(display_id)?;
+        if current == space_id {
+            return Ok(());
+        }
+        let shown = create_array(&[space_id], CFNumberType::SInt64Type)?;
+        let hidden = create_array(&[current], CFNumberType::SInt64Type)?;
+        unsafe {
+            SLSShowSpaces(self.main_cid, &raw const *shown);
+            SLSHideSpaces(self.main_cid, &raw const *hidden);
+            SLSManagedDisplaySetCurrentSpace(self.main_cid, &raw const *uuid, space_id);
+        }
+        if self.active_display_space(display_id)? == space_id {
+            return Ok(());
+        }
+
+        let keycode = self
+            .desktop_number(space_id)
+            .and_then(|number| DESKTOP_KEYCODES.get(number - 1))
+            .ok_or(Error::NotFound(format!(
+                "{}: no desktop shortcut for space {space_id}.",
+                function_name!()
+            )))?;
+        debug!("switching to space {space_id} with a keystroke.");
+        post_keystroke(*keycode, CGEventFlags::MaskControl)
+    }
+
+    fn disable_updates(&self) {
+        _ = unsafe { SLSDisableUpdate(self.main_cid) }
+            .to_result(function_name!())
+            .inspect_err(|err| debug!("{err}"));
+    }
+
+    fn reenable_updates(&self) {
+        _ = unsafe { SLSReenableUpdate(self.main_cid) }
+            .to_result(function_name!())
+            .inspect_err(|err| debug!("{err}"));
+    }
+
     fn windows_on_screen(&self) -> Option<Vec<WinID>> {
         let options =
             CGWindowListOption::OptionOnScreenOnly | CGWindowListOption::ExcludeDesktopElements;
@@ -714,6 +843,19 @@ fn existing_application_window_list(
     space_window_list_for_connection(cid, spaces, app.connection(), true)
 }
 
+/// Element ids probed by `bruteforce_windows`.
+const BRUTEFORCE_ELEMENT_IDS: u64 = 0x7fff;
+/// Number of element ids probed by a single bruteforce task.
+const BRUTEFORCE_CHUNK_SIZE: u64 = 0x800;
+
+/// Splits the bruteforced element id space into chunks, which can be probed in parallel.
+pub fn bruteforce_chunks() -> impl Iterator<Item = Range<u64>> {
+    (0..BRUTEFORCE_ELEMENT_IDS.div_ceil(BRUTEFORCE_CHUNK_SIZE)).map(|chunk| {
+        let start = chunk * BRUTEFORCE_CHUNK_SIZE;
+        start..(start + BRUTEFORCE_CHUNK_SIZE).min(BRUTEFORCE_ELEMENT_IDS)
+    })
+}
+
 /// Attempts to find and add unresolved windows for a given application by brute-forcing `element_id` values.
 /// This is a workaround for macOS API limitations that do not return `AXUIElementRef` for windows on inactive spaces.
 ///
@@ -721,18 +863,19 @@ fn existing_application_window_list(
 ///
 /// * `pid` - The process ID of the application whose windows are to be brute-forced.
 /// * `bundle_id` - The bundle identifier of the application, if known.
-/// * `window_list` - A mutable vector of `WinID`s representing the expected global window list; found windows are removed from this list.
+/// * `element_ids` - The chunk of element ids to probe, see `bruteforce_chunks`.
+/// * `window_list` - The window ids still unresolved, shared between the chunks of one application; found windows are removed from this list and probing stops once it is empty.
 /// * `config` - The current Paneru configuration, used to evaluate window rules.
 pub fn bruteforce_windows(
     pid: Pid,
     bundle_id: Option<&str>,
-    mut window_list: Vec<WinID>,
+    element_ids: Range<u64>,
+    window_list: &Mutex<Vec<WinID>>,
     config: &Config,
 ) -> Vec<Window> {
     const MAGIC: u32 = 0x636f_636f;
     const BUFSIZE: isize = 0x14;
     let mut found_windows = Vec::new();
-    debug!("{pid} has unresolved window on other desktops, bruteforcing them.");
 
     //
     // NOTE: MacOS API does not return AXUIElementRef of windows on inactive spaces. However,
@@ -758,7 +901,15 @@ pub fn bruteforce_windows(
     let bytes = MAGIC.to_ne_bytes();
     data[0x8..0x8 + bytes.len()].copy_from_slice(&bytes);
 
-    for element_id in 0..0x7fffu64 {
+    for element_id in element_ids {
+        if window_list
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .is_empty()
+        {
+            // Another chunk found the remaining windows.
+            break;
+        }
         let bytes = element_id.to_ne_bytes();
         data[0xc..0xc + bytes.len()].copy_from_slice(&bytes);
 
@@ -771,8 +922,15 @@ pub fn bruteforce_windows(
             continue;
         };
 
-        if let Some(index) = window_list.iter().position(|&id| id == window_id) {
-            window_list.remove(index);
+        let found = {
+            let mut window_list = window_list.lock().unwrap_or_else(PoisonError::into_inner);
+            window_list
+                .iter()
+                .position(|&id| id == window_id)
+                .map(|index| window_list.remove(index))
+                .is_some()
+        };
+        if found {
             debug!("Found window {window_id:?}");
             if let Ok(window) = WindowOS::new_with_config(&element_ref, config, bundle_id)
                 .inspect_err(|err| warn!("{err}"))
@@ -841,3 +999,20 @@ impl notify::EventHandler for ConfigHandler {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bruteforce_chunks_cover_all_element_ids() {
+        let chunks = bruteforce_chunks().collect::<Vec<_>>();
+
+        assert_eq!(chunks.first().map(|chunk| chunk.start), Some(0));
+        assert_eq!(
+            chunks.last().map(|chunk| chunk.end),
+            Some(BRUTEFORCE_ELEMENT_IDS)
+        );
+        assert!(chunks.windows(2).all(|pair| pair[0].end == pair[1].start));
+    }
+}
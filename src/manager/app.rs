@@ -36,7 +36,6 @@ pub static AX_NOTIFICATIONS: LazyLock<Vec<&str>> = LazyLock::new(|| {
         accessibility_sys::kAXFocusedUIElementChangedNotification,
         accessibility_sys::kAXWindowMovedNotification,
         accessibility_sys::kAXWindowResizedNotification,
-        accessibility_sys::kAXTitleChangedNotification,
         accessibility_sys::kAXMenuOpenedNotification,
         accessibility_sys::kAXMenuClosedNotification,
     ]
@@ -44,10 +43,13 @@ pub static AX_NOTIFICATIONS: LazyLock<Vec<&str>> = LazyLock::new(|| {
 
 /// A static `LazyLock` that holds a list of `AXNotification` strings to be observed for window-specific events.
 /// These notifications are related to individual window lifecycle events,
-/// such as a window being destroyed, miniaturized (minimized), or deminiaturized (restored).
+/// such as a window being destroyed, miniaturized (minimized), deminiaturized (restored) or retitled.
+/// Title changes are observed per window, because the application-level notification also fires
+/// for other elements and does not carry a usable window reference.
 pub static AX_WINDOW_NOTIFICATIONS: LazyLock<Vec<&str>> = LazyLock::new(|| {
     vec![
         accessibility_sys::kAXUIElementDestroyedNotification,
+        accessibility_sys::kAXTitleChangedNotification,
         accessibility_sys::kAXWindowMiniaturizedNotification,
         accessibility_sys::kAXWindowDeminiaturizedNotification,
     ]
@@ -183,11 +185,7 @@ impl ApplicationOS {
             let ptr = AXUIElementCreateApplication(process.pid());
             AXUIWrapper::retain(ptr)?
         };
-        let bundle_id = process
-            .application()
-            .as_ref()
-            .and_then(|app| app.bundleIdentifier())
-            .map(|id| id.to_string());
+        let bundle_id = process.bundle_id();
         Ok(Self {
             element: refer,
             psn: process.psn(),
@@ -374,10 +372,6 @@ impl ObserverContext {
     /// * `element` - The `AXUIElementRef` associated with the notification.
     fn notify_app(&self, notification: &str, element: AXUIElementRef) {
         match notification {
-            accessibility_sys::kAXTitleChangedNotification => {
-                // TODO: WindowTitleChanged does not have a valid window as its element reference.
-                return;
-            }
             accessibility_sys::kAXCreatedNotification => {
                 let Ok(element) = AXUIWrapper::retain(element).inspect_err(|err| {
                     error!("invalid element {element:?}: {err}");
@@ -430,6 +424,9 @@ impl ObserverContext {
             accessibility_sys::kAXUIElementDestroyedNotification => {
                 Event::WindowDestroyed { window_id }
             }
+            accessibility_sys::kAXTitleChangedNotification => {
+                Event::WindowTitleChanged { window_id }
+            }
 
             _ => {
                 error!("unhandled window notification: {notification:?}");
@@ -8,7 +8,7 @@ use tracing::{Level, instrument};
 use super::skylight::{CGDisplayCreateUUIDFromDisplayID, CGDisplayGetDisplayIDFromUUID};
 use crate::{
     config::Config,
-    ecs::DockPosition,
+    ecs::{DockPosition, layout::LayoutStrip},
     errors::{Error, Result},
 };
 
@@ -25,6 +25,8 @@ pub struct Display {
     /// Optional config override for the menubar height.
     menubar_height_override: Option<i32>,
     notch_height: i32,
+    /// The UUID of the display, used to look up its `[displays."<uuid>"]` overrides.
+    uuid: Option<String>,
 }
 
 impl Display {
@@ -47,6 +49,7 @@ impl Display {
             menubar_height,
             menubar_height_override: None,
             notch_height: 0,
+            uuid: Self::uuid_from_id(id).ok().map(|uuid| uuid.to_string()),
         }
     }
 
@@ -122,6 +125,11 @@ impl Display {
         bounds
     }
 
+    /// Returns the full bounds of the display, including the menubar.
+    pub fn screen_bounds(&self) -> IRect {
+        self.bounds
+    }
+
     pub fn width(&self) -> i32 {
         self.bounds().width()
     }
@@ -140,9 +148,38 @@ impl Display {
         self.notch_height = height;
     }
 
-    #[instrument(level = Level::TRACE, skip_all, ret)]
+    /// Returns the UUID of the display, if Core Graphics could provide one.
+    pub fn uuid(&self) -> Option<&str> {
+        self.uuid.as_deref()
+    }
+
     pub fn actual_display_bounds(&self, dock: Option<&DockPosition>, config: &Config) -> IRect {
-        let (pad_top, pad_right, pad_bottom, pad_left) = config.edge_padding();
+        self.workspace_bounds(dock, config, None)
+    }
+
+    /// Returns the `(top, right, bottom, left)` edge padding of `strip` on this display. With
+    /// `smart_gaps`, a strip showing a single window is not padded.
+    pub fn edge_padding(
+        &self,
+        config: &Config,
+        strip: Option<&LayoutStrip>,
+    ) -> (i32, i32, i32, i32) {
+        if config.smart_gaps() && strip.is_some_and(LayoutStrip::shows_single_window) {
+            return (0, 0, 0, 0);
+        }
+        config.edge_padding_for(self.uuid(), strip.map(LayoutStrip::id))
+    }
+
+    /// Returns the display bounds as `actual_display_bounds`, but padded by the
+    /// `[workspaces.<id>]` overrides of the workspace of `strip`.
+    #[instrument(level = Level::TRACE, skip_all, ret)]
+    pub fn workspace_bounds(
+        &self,
+        dock: Option<&DockPosition>,
+        config: &Config,
+        strip: Option<&LayoutStrip>,
+    ) -> IRect {
+        let (pad_top, pad_right, pad_bottom, pad_left) = self.edge_padding(config, strip);
         let mut viewport = self.bounds();
         viewport.min.x += pad_left;
         viewport.min.y += pad_top;
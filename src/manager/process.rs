@@ -8,7 +8,7 @@ use objc2_foundation::{
 use std::pin::Pin;
 use std::ptr::NonNull;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tracing::debug;
+use tracing::{debug, warn};
 
 use crate::ecs::BProcess;
 use crate::platform::{OSStatus, Pid, ProcessSerialNumber, WorkspaceObserver};
@@ -84,6 +84,9 @@ pub trait ProcessApi: Send + Sync {
     fn pid(&self) -> Pid;
     /// Returns the process serial number (`ProcessSerialNumber`) of the process.
     fn psn(&self) -> ProcessSerialNumber;
+    /// Returns the bundle identifier of the process, resolved when the process was launched.
+    /// Available before the application is observed, so bundle-only rules can be applied early.
+    fn bundle_id(&self) -> Option<String>;
     /// Returns an optional `NSRunningApplication` instance associated with this process.
     /// This provides access to higher-level application properties.
     ///
@@ -106,6 +109,15 @@ pub trait ProcessApi: Send + Sync {
     ///
     /// * `force` - If `true`, skip the observable check in `ready()`.
     fn force_manage(&mut self, force: bool);
+    /// Checks whether the application is currently hidden.
+    fn is_hidden(&self) -> bool;
+    /// Hides or unhides the application. macOS reports the outcome through the usual
+    /// hidden and visible workspace notifications.
+    ///
+    /// # Arguments
+    ///
+    /// * `hidden` - If `true`, hide the application, otherwise unhide it.
+    fn set_hidden(&self, hidden: bool);
 }
 
 /// `ProcessOS` is a concrete implementation of the `ProcessApi` trait for macOS.
@@ -136,6 +148,11 @@ impl ProcessApi for ProcessOS {
         self.inner.psn
     }
 
+    /// Returns the bundle identifier of the inner `Process`.
+    fn bundle_id(&self) -> Option<String> {
+        self.inner.bundle_id.clone()
+    }
+
     /// Returns the `NSRunningApplication` instance of the inner `Process`.
     fn application(&self) -> Option<Retained<NSRunningApplication>> {
         self.inner.application.clone()
@@ -150,6 +167,22 @@ impl ProcessApi for ProcessOS {
     fn force_manage(&mut self, force: bool) {
         self.inner.force_manage(force);
     }
+
+    /// Reads the `isHidden` property of the `NSRunningApplication`.
+    fn is_hidden(&self) -> bool {
+        self.application().is_some_and(|app| app.isHidden())
+    }
+
+    /// Hides or unhides the application through its `NSRunningApplication`.
+    fn set_hidden(&self, hidden: bool) {
+        let Some(app) = self.application() else {
+            return;
+        };
+        let sent = if hidden { app.hide() } else { app.unhide() };
+        if !sent {
+            warn!("unable to change hidden state of {}.", self.name());
+        }
+    }
 }
 
 impl From<Pin<Box<Process>>> for BProcess {
@@ -169,6 +202,8 @@ pub struct Process {
     pub pid: Pid,
     /// The name of the application.
     pub name: String,
+    /// The bundle identifier of the application, if it has one.
+    pub bundle_id: Option<String>,
     /// An optional `NSRunningApplication` instance, providing access to Cocoa-level application properties.
     pub application: Option<Retained<NSRunningApplication>>,
     /// The current activation policy of the application.
@@ -194,7 +229,7 @@ impl Drop for Process {
 }
 
 impl Process {
-    /// Creates a new `Process` instance. It retrieves process information (PID, name) and attempts to get an `NSRunningApplication` instance.
+    /// Creates a new `Process` instance. It retrieves process information (PID, name, bundle ID) and attempts to get an `NSRunningApplication` instance.
     /// It also initializes the observation flags for application launch and activation policy.
     ///
     /// # Arguments
@@ -218,10 +253,15 @@ impl Process {
 
         // [[NSRunningApplication runningApplicationWithProcessIdentifier:process->pid] retain];
         let apps = NSRunningApplication::runningApplicationWithProcessIdentifier(pid);
+        let bundle_id = apps
+            .as_ref()
+            .and_then(|app| app.bundleIdentifier())
+            .map(|id| id.to_string());
 
         Box::pin(Process {
             psn: *psn,
             name,
+            bundle_id,
             pid,
             application: apps,
             policy: NSApplicationActivationPolicy::Prohibited,
@@ -140,6 +140,42 @@ unsafe extern "C" {
     /// extern `uint64_t` SLSManagedDisplayGetCurrentSpace(int cid, `CFStringRef` uuid);
     pub fn SLSManagedDisplayGetCurrentSpace(cid: ConnID, uuid: CFStringRef) -> u64;
 
+    /// Makes a space the current space of a managed display. The Dock is not told about the
+    /// switch, so the spaces also have to be shown and hidden with `SLSShowSpaces` and
+    /// `SLSHideSpaces`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The `ConnID` of the connection.
+    /// * `uuid` - A `CFStringRef` representing the display's UUID.
+    /// * `sid` - The ID of the space to switch to.
+    ///
+    /// # Original signature
+    /// extern void SLSManagedDisplaySetCurrentSpace(int cid, `CFStringRef` uuid, `uint64_t` sid);
+    pub fn SLSManagedDisplaySetCurrentSpace(cid: ConnID, uuid: CFStringRef, sid: u64);
+
+    /// Shows the windows of the spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The `ConnID` of the connection.
+    /// * `spaces` - A raw pointer to a `CFArray` of space IDs.
+    ///
+    /// # Original signature
+    /// extern void SLSShowSpaces(int cid, `CFArrayRef` spaces);
+    pub fn SLSShowSpaces(cid: ConnID, spaces: *const CFArray);
+
+    /// Hides the windows of the spaces.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The `ConnID` of the connection.
+    /// * `spaces` - A raw pointer to a `CFArray` of space IDs.
+    ///
+    /// # Original signature
+    /// extern void SLSHideSpaces(int cid, `CFArrayRef` spaces);
+    pub fn SLSHideSpaces(cid: ConnID, spaces: *const CFArray);
+
     /// Returns the type of a space.
     ///
     /// # Returns
@@ -507,6 +543,23 @@ unsafe extern "C" {
         value: &CFType,
     ) -> OSStatus;
 
+    /// Checks whether an accessibility attribute of a UI element can be modified.
+    ///
+    /// # Arguments
+    ///
+    /// * `element` - An `AXUIElementRef` pointing to the UI element.
+    /// * `attribute` - A reference to a `CFString` representing the attribute name.
+    /// * `settable` - A mutable reference receiving a non-zero value if the attribute is settable.
+    ///
+    /// # Returns
+    ///
+    /// An `i32` indicating success or failure (`kAXErrorSuccess` for success).
+    pub fn AXUIElementIsAttributeSettable(
+        element: AXUIElementRef,
+        attribute: &CFString,
+        settable: &mut u8,
+    ) -> OSStatus;
+
     /// Performs an action on an accessibility UI element.
     ///
     /// # Arguments
@@ -534,6 +587,34 @@ unsafe extern "C" {
     /// extern `AXUIElementRef` _AXUIElementCreateWithRemoteToken(CFDataRef data);
     pub fn _AXUIElementCreateWithRemoteToken(data: &CFMutableData) -> AXUIElementRef;
 
+    /// Sets the opacity of a window owned by any process.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The connection ID.
+    /// * `wid` - The ID of the window.
+    /// * `alpha` - The opacity, from 0.0 (transparent) to 1.0 (opaque).
+    ///
+    /// # Returns
+    ///
+    /// A `CGError` indicating success or failure.
+    ///
+    /// # Original signature
+    /// extern `CGError` SLSSetWindowAlpha(int cid, `uint32_t` wid, float alpha);
+    pub fn SLSSetWindowAlpha(cid: ConnID, wid: WinID, alpha: c_float) -> CGError;
+
+    /// Moves windows owned by any process to a managed space.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The connection ID.
+    /// * `window_list` - A raw pointer to a `CFArray` of window IDs.
+    /// * `sid` - The ID of the destination space.
+    ///
+    /// # Original signature
+    /// extern void SLSMoveWindowsToManagedSpace(int cid, `CFArrayRef` `window_list`, `uint64_t` sid);
+    pub fn SLSMoveWindowsToManagedSpace(cid: ConnID, window_list: *const CFArray, sid: u64);
+
     // The SLS version of the brightness function
     pub fn SLSSetWindowListBrightness(
         cid: ConnID,
@@ -541,4 +622,33 @@ unsafe extern "C" {
         brightness_levels: *const c_float,
         count: isize,
     ) -> CGError;
+
+    /// Freezes screen updates of the connection, so the following window changes are
+    /// composited together. The Window Server lifts the freeze by itself after about a second.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The connection ID.
+    ///
+    /// # Returns
+    ///
+    /// A `CGError` indicating success or failure.
+    ///
+    /// # Original signature
+    /// extern `CGError` SLSDisableUpdate(int cid);
+    pub fn SLSDisableUpdate(cid: ConnID) -> CGError;
+
+    /// Lifts a freeze started by `SLSDisableUpdate` and flushes the pending changes to the screen.
+    ///
+    /// # Arguments
+    ///
+    /// * `cid` - The connection ID.
+    ///
+    /// # Returns
+    ///
+    /// A `CGError` indicating success or failure.
+    ///
+    /// # Original signature
+    /// extern `CGError` SLSReenableUpdate(int cid);
+    pub fn SLSReenableUpdate(cid: ConnID) -> CGError;
 }
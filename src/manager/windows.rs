@@ -1,8 +1,9 @@
 use accessibility_sys::{
     AXUIElementCreateApplication, AXUIElementRef, AXValueCreate, AXValueGetValue,
-    kAXFloatingWindowSubrole, kAXPositionAttribute, kAXRaiseAction, kAXSizeAttribute,
-    kAXStandardWindowSubrole, kAXUnknownSubrole, kAXValueTypeCGPoint, kAXValueTypeCGSize,
-    kAXWindowRole,
+    kAXCloseButtonAttribute, kAXErrorAttributeUnsupported, kAXErrorNoValue,
+    kAXFloatingWindowSubrole, kAXMinimizedAttribute, kAXPositionAttribute, kAXRaiseAction,
+    kAXSizeAttribute, kAXStandardWindowSubrole, kAXUnknownSubrole, kAXValueTypeCGPoint,
+    kAXValueTypeCGSize, kAXWindowRole,
 };
 use bevy::ecs::component::Component;
 use bevy::math::IRect;
@@ -23,8 +24,8 @@ use tracing::{Level, debug, instrument, trace, warn};
 
 use super::skylight::{
     _AXUIElementGetWindow, _SLPSSetFrontProcessWithOptions, AXUIElementCopyAttributeValue,
-    AXUIElementPerformAction, AXUIElementSetAttributeValue, SLPSPostEventRecordTo,
-    SLSWindowIteratorAdvance,
+    AXUIElementIsAttributeSettable, AXUIElementPerformAction, AXUIElementSetAttributeValue,
+    SLPSPostEventRecordTo, SLSWindowIteratorAdvance,
 };
 use crate::config::Config;
 use crate::errors::{Error, Result};
@@ -70,14 +71,26 @@ pub trait WindowApi: Send + Sync {
     fn element(&self) -> Option<CFRetained<AXUIWrapper>>;
     fn title(&self) -> Result<String>;
     fn identifier(&self) -> Result<String>;
+    /// The document path or URL the window is showing (`AXDocument`), if the app reports one.
+    fn document(&self) -> Result<String>;
     fn child_role(&self) -> Result<bool>;
     fn role(&self) -> Result<String>;
     fn subrole(&self) -> Result<String>;
     fn is_minimized(&self) -> bool;
+    /// Minimizes the window into the Dock, or brings it back, through `AXMinimized`.
+    fn set_minimized(&self, minimized: bool);
     fn is_full_screen(&self) -> bool;
+    /// Returns `false` if the application does not allow resizing the window.
+    fn is_resizable(&self) -> bool;
+    /// Returns `false` if the window has no close button, as is common for panels and popups.
+    fn has_close_button(&self) -> bool;
     fn reposition(&mut self, origin: Origin);
     fn resize(&mut self, size: Size);
     fn update_frame(&mut self) -> Result<IRect>;
+    /// Marks the cached frame as outdated, e.g. after the OS reported a move or resize.
+    fn invalidate_frame(&mut self);
+    /// Drops the cached title, so the next `title()` asks the application again.
+    fn invalidate_title(&mut self);
     fn focus_without_raise(
         &self,
         psn: ProcessSerialNumber,
@@ -91,6 +104,9 @@ pub trait WindowApi: Send + Sync {
     fn raise_without_focus(&self);
     fn pid(&self) -> Result<Pid>;
     fn set_padding(&mut self, padding: WindowPadding);
+    /// Sets whether the application's `AXEnhancedUserInterface` is turned off while the window
+    /// is moved or resized.
+    fn set_disable_enhanced_ui(&mut self, disable: bool);
     fn horizontal_padding(&self) -> i32;
     fn vertical_padding(&self) -> i32;
     fn border_radius(&self) -> Option<f64>;
@@ -130,6 +146,19 @@ pub fn ax_window_id(element_ref: AXUIElementRef) -> Result<WinID> {
     Ok(window_id)
 }
 
+/// Returns the cached value of an attribute, querying it on first use. Errors are not cached, so
+/// a transient Accessibility failure is retried on the next call.
+fn cached_attribute(
+    cache: &OnceLock<String>,
+    query: impl FnOnce() -> Result<String>,
+) -> Result<String> {
+    if let Some(value) = cache.get() {
+        return Ok(value.clone());
+    }
+    let value = query()?;
+    Ok(cache.get_or_init(|| value).clone())
+}
+
 // const CPS_ALL_WINDOWS: u32 = 0x100;
 const CPS_USER_GENERATED: u32 = 0x200;
 // const CPS_NO_WINDOWS: u32 = 0x400;
@@ -139,8 +168,15 @@ pub struct WindowOS {
     id: WinID,
     ax_element: CFRetained<AXUIWrapper>,
     frame: IRect,
+    /// Set when `frame` may no longer match the OS, so `update_frame` has to query it.
+    frame_stale: bool,
+    title: OnceLock<String>,
+    role: OnceLock<String>,
+    subrole: OnceLock<String>,
     vertical_padding: i32,
     horizontal_padding: i32,
+    /// Whether `AXEnhancedUserInterface` is turned off around moves and resizes.
+    enhanced_ui_workaround: bool,
     border_radius: OnceLock<Option<f64>>,
     pid: OnceLock<Result<Pid>>,
     app_reference: OnceLock<Option<CFRetained<AXUIWrapper>>>,
@@ -184,8 +220,13 @@ impl WindowOS {
             id,
             ax_element: element.clone(),
             frame: IRect::default(),
+            frame_stale: true,
+            title: OnceLock::new(),
+            role: OnceLock::new(),
+            subrole: OnceLock::new(),
             vertical_padding: 0,
             horizontal_padding: 0,
+            enhanced_ui_workaround: true,
             border_radius: OnceLock::new(),
             pid: OnceLock::new(),
             app_reference: OnceLock::new(),
@@ -227,8 +268,10 @@ impl WindowOS {
         let Ok(title) = self.title() else {
             return false;
         };
+        let role = self.role().unwrap_or_default();
+        let subrole = self.subrole().unwrap_or_default();
         config
-            .find_window_properties(&title, bundle_id.unwrap_or_default())
+            .find_window_properties(&title, bundle_id.unwrap_or_default(), &role, &subrole)
             .iter()
             .any(|params| params.manage.is_some_and(|manage| manage))
     }
@@ -277,17 +320,24 @@ impl WindowOS {
     ///
     /// This avoids animated move/resize that breaks window management for apps like Chrome,
     /// Firefox, and Zen Browser when accessibility clients (e.g. Kindavim) enable enhanced UI.
-    fn disable_enhanced_ui(&self) {
-        let Ok(pid) = self.pid() else { return };
+    ///
+    /// # Returns
+    ///
+    /// `true` if this call holds a count, which has to be handed back to `reenable_enhanced_ui`.
+    fn disable_enhanced_ui(&self) -> bool {
+        if !self.enhanced_ui_workaround {
+            return false;
+        }
+        let Ok(pid) = self.pid() else { return false };
         let mut counts = ENHANCED_UI_REFCOUNT
             .lock()
             .unwrap_or_else(std::sync::PoisonError::into_inner);
         if let Some(count) = counts.get_mut(&pid) {
             *count += 1;
-            return;
+            return true;
         }
         let Some(app_element) = self.app_reference() else {
-            return;
+            return false;
         };
         let attr = CFString::from_static_str("AXEnhancedUserInterface");
         let enabled = app_element
@@ -303,11 +353,16 @@ impl WindowOS {
             }
             counts.insert(pid, 1);
         }
+        enabled
     }
 
     /// Re-enables `AXEnhancedUserInterface` on this window's app once the last concurrent
-    /// caller has finished. Pairs with [`disable_enhanced_ui`].
-    fn reenable_enhanced_ui(&self) {
+    /// caller has finished. Pairs with [`disable_enhanced_ui`], whose result is passed as
+    /// `applied`, so the count stays balanced even if the workaround was toggled in between.
+    fn reenable_enhanced_ui(&self, applied: bool) {
+        if !applied {
+            return;
+        }
         let Ok(pid) = self.pid() else { return };
         let mut counts = ENHANCED_UI_REFCOUNT
             .lock()
@@ -354,6 +409,8 @@ impl WindowOS {
             let size = self.frame.size();
             self.frame.min = origin;
             self.frame.max = origin + size;
+            // The application may clamp or ignore the request, only the OS knows for sure.
+            self.frame_stale = true;
         }
     }
 
@@ -379,6 +436,7 @@ impl WindowOS {
                 )
             };
             self.frame.max = self.frame.min + size;
+            self.frame_stale = true;
         }
     }
 
@@ -444,13 +502,17 @@ impl WindowApi for WindowOS {
     ///
     /// `Ok(String)` with the window title if successful, otherwise `Err(Error)`.
     fn title(&self) -> Result<String> {
-        self.ax_element.title()
+        cached_attribute(&self.title, || self.ax_element.title())
     }
 
     fn identifier(&self) -> Result<String> {
         self.ax_element.identifier()
     }
 
+    fn document(&self) -> Result<String> {
+        self.ax_element.document()
+    }
+
     /// Returns true if the window has a child role.
     fn child_role(&self) -> Result<bool> {
         let role = self.role()?;
@@ -465,7 +527,7 @@ impl WindowApi for WindowOS {
     ///
     /// `Ok(String)` with the window role if successful, otherwise `Err(Error)`.
     fn role(&self) -> Result<String> {
-        self.ax_element.role()
+        cached_attribute(&self.role, || self.ax_element.role())
     }
 
     /// Retrieves the subrole of the window (e.g., "`AXStandardWindow`").
@@ -474,7 +536,7 @@ impl WindowApi for WindowOS {
     ///
     /// `Ok(String)` with the window subrole if successful, otherwise `Err(Error)`.
     fn subrole(&self) -> Result<String> {
-        self.ax_element.subrole()
+        cached_attribute(&self.subrole, || self.ax_element.subrole())
     }
 
     #[instrument(level = Level::DEBUG, ret)]
@@ -482,19 +544,65 @@ impl WindowApi for WindowOS {
         self.ax_element.minimized().is_ok_and(|minimized| minimized)
     }
 
+    fn set_minimized(&self, minimized: bool) {
+        let value = if minimized {
+            kCFBooleanTrue
+        } else {
+            kCFBooleanFalse
+        };
+        unsafe {
+            AXUIElementSetAttributeValue(
+                self.ax_element.as_ptr(),
+                CFString::from_static_str(kAXMinimizedAttribute).as_ref(),
+                value.unwrap(),
+            );
+        }
+    }
+
     fn is_full_screen(&self) -> bool {
         self.ax_element.full_screen().unwrap_or(false)
     }
 
+    /// A window counts as resizable unless the Accessibility API reports its size as read-only.
+    fn is_resizable(&self) -> bool {
+        let mut settable = 0;
+        let result = unsafe {
+            AXUIElementIsAttributeSettable(
+                self.ax_element.as_ptr(),
+                CFString::from_static_str(kAXSizeAttribute).as_ref(),
+                &mut settable,
+            )
+        }
+        .to_result(function_name!());
+        result.is_err() || settable != 0
+    }
+
+    /// Only a definite "no value" or "unsupported" answer counts as a missing close button. Other
+    /// errors, like `kAXErrorCannotComplete` from a busy application, keep the window a document.
+    fn has_close_button(&self) -> bool {
+        let mut button: *mut CFType = null_mut();
+        let result = unsafe {
+            AXUIElementCopyAttributeValue(
+                self.ax_element.as_ptr(),
+                CFString::from_static_str(kAXCloseButtonAttribute).as_ref(),
+                &mut button,
+            )
+        };
+        if let Some(button) = NonNull::new(button) {
+            drop(unsafe { CFRetained::from_raw(button) });
+        }
+        !matches!(result, kAXErrorNoValue | kAXErrorAttributeUnsupported)
+    }
+
     #[instrument(level = Level::TRACE)]
     fn reposition(&mut self, origin: Origin) {
         if self.frame.min == origin {
             trace!("already in position.");
             return;
         }
-        self.disable_enhanced_ui();
+        let applied = self.disable_enhanced_ui();
         self.set_ax_position(origin);
-        self.reenable_enhanced_ui();
+        self.reenable_enhanced_ui(applied);
     }
 
     #[instrument(level = Level::TRACE)]
@@ -505,7 +613,7 @@ impl WindowApi for WindowOS {
         }
         let previous_frame = self.frame;
         let target_origin = previous_frame.min;
-        self.disable_enhanced_ui();
+        let applied = self.disable_enhanced_ui();
         self.set_ax_size(size);
 
         let mut previous_observed_frame = previous_frame;
@@ -542,20 +650,20 @@ impl WindowApi for WindowOS {
             }
             self.set_ax_position(target_origin);
         }
-        self.reenable_enhanced_ui();
+        self.reenable_enhanced_ui(applied);
     }
 
     /// Updates the internal `frame` of the window by querying its current position and size from the Accessibility API.
-    /// It also updates the `width_ratio`.
-    ///
-    /// # Arguments
-    ///
-    /// * `display_bounds` - An optional `CGRect` representing the bounds of the display the window is on.
+    /// The query is skipped while the cached frame is still valid, i.e. nothing was moved or
+    /// resized since the last one.
     ///
     /// # Returns
     ///
-    /// `Ok(())` if the frame is updated successfully, otherwise `Err(Error)`.
+    /// `Ok(IRect)` with the current frame, otherwise `Err(Error)`.
     fn update_frame(&mut self) -> Result<IRect> {
+        if !self.frame_stale {
+            return Ok(self.frame);
+        }
         let window_ref = self.ax_element.as_ptr();
 
         let position = unsafe {
@@ -601,10 +709,19 @@ impl WindowApi for WindowOS {
         self.frame.min.y -= self.vertical_padding;
         self.frame.max.x += self.horizontal_padding;
         self.frame.max.y += self.vertical_padding;
+        self.frame_stale = false;
 
         Ok(self.frame)
     }
 
+    fn invalidate_frame(&mut self) {
+        self.frame_stale = true;
+    }
+
+    fn invalidate_title(&mut self) {
+        self.title.take();
+    }
+
     /// Focuses the window without raising it. This involves sending specific events to the process.
     ///
     /// # Arguments
@@ -684,12 +801,21 @@ impl WindowApi for WindowOS {
     }
 
     fn set_padding(&mut self, padding: WindowPadding) {
-        match padding {
-            WindowPadding::Vertical(padding) => self.vertical_padding = padding,
-            WindowPadding::Horizontal(padding) => self.horizontal_padding = padding,
+        let (current, padding) = match padding {
+            WindowPadding::Vertical(padding) => (&mut self.vertical_padding, padding),
+            WindowPadding::Horizontal(padding) => (&mut self.horizontal_padding, padding),
+        };
+        if *current != padding {
+            *current = padding;
+            // The cached frame includes the padding.
+            self.frame_stale = true;
         }
     }
 
+    fn set_disable_enhanced_ui(&mut self, disable: bool) {
+        self.enhanced_ui_workaround = disable;
+    }
+
     fn horizontal_padding(&self) -> i32 {
         self.horizontal_padding
     }
@@ -320,7 +320,7 @@ pub fn update_menu_bar(
     menu_bar.update(
         strip.virtual_index,
         config.workspace_menu_status(),
-        &config.preset_column_widths(),
+        &config.preset_column_widths_for(viewport.width(), active_display.display().uuid()),
         focused_window.is_some(),
         focused_width_ratio,
     );
@@ -235,6 +235,72 @@ fn make_overlay_window(mtm: MainThreadMarker, cocoa_frame: NSRect) -> Retained<N
 
     window
 }
+
+// ── Text drawing ────────────────────────────────────────────────────────
+
+/// Draws `text` centered within `bounds`. With `truncate` the text is laid out across the width of
+/// `bounds` and its tail is cut off if it does not fit, otherwise it is drawn at its natural size.
+fn draw_centered_label(
+    bounds: NSRect,
+    text: &NSString,
+    font: &NSFont,
+    color: &NSColor,
+    truncate: bool,
+) {
+    let paragraph_style = unsafe {
+        let style = NSParagraphStyle::defaultParagraphStyle().mutableCopy();
+        let _: () = msg_send![&style, setAlignment: 1isize]; // Center (NSTextAlignmentCenter = 1)
+        if truncate {
+            let _: () = msg_send![&style, setLineBreakMode: 4isize]; // NSLineBreakByTruncatingTail
+        }
+        style
+    };
+
+    // Using manual attribute keys as they might be missing from the crate's high-level API
+    let attr_str: Retained<NSAttributedString> = unsafe {
+        let font_key = NSString::from_str("NSFont");
+        let color_key = NSString::from_str("NSColor");
+        let para_key = NSString::from_str("NSParagraphStyle");
+
+        let keys = [&*font_key, &*color_key, &*para_key];
+        let objects = [
+            font as &AnyObject,
+            color as &AnyObject,
+            &*paragraph_style as &AnyObject,
+        ];
+        let attributes = NSDictionary::from_slices(&keys, &objects);
+
+        // Using raw msg_send as the high-level wrapper might have trait bound issues
+        let alloc = NSAttributedString::alloc();
+        msg_send![alloc, initWithString: text, attributes: &*attributes]
+    };
+
+    let text_size = unsafe {
+        let size: NSSize = msg_send![&attr_str, size];
+        size
+    };
+    let text_rect = if truncate {
+        NSRect::new(
+            NSPoint::new(
+                bounds.origin.x,
+                bounds.origin.y + (bounds.size.height - text_size.height) / 2.0,
+            ),
+            NSSize::new(bounds.size.width.max(0.0), text_size.height),
+        )
+    } else {
+        NSRect::new(
+            NSPoint::new(
+                bounds.origin.x + (bounds.size.width - text_size.width) / 2.0,
+                bounds.origin.y + (bounds.size.height - text_size.height) / 2.0,
+            ),
+            text_size,
+        )
+    };
+    unsafe {
+        let _: () = msg_send![&attr_str, drawInRect: text_rect];
+    };
+}
+
 // ── OverlayManager ──────────────────────────────────────────────────────
 
 pub struct OverlayManager {
@@ -346,6 +412,249 @@ impl OverlayManager {
     }
 }
 
+// ── StackTabs ───────────────────────────────────────────────────────────
+
+/// A tab bar drawn above a stacked column.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TabBarParams {
+    /// The tab bar rect in absolute CG coordinates.
+    pub frame: NSRect,
+    /// Window titles, paired with whether the tab is the active one.
+    pub tabs: Vec<(String, bool)>,
+}
+
+#[derive(Debug, Clone)]
+struct TabBarViewIvars {
+    titles: Vec<Retained<NSString>>,
+    active: Option<usize>,
+}
+
+define_class!(
+    #[unsafe(super(NSView))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "PaneruTabBarView"]
+    #[ivars = TabBarViewIvars]
+    #[derive(Debug)]
+    struct TabBarView;
+
+    impl TabBarView {
+        #[unsafe(method(drawRect:))]
+        #[allow(clippy::cast_precision_loss)]
+        fn draw_rect(&self, _dirty_rect: NSRect) {
+            let ivars = self.ivars();
+            let bounds = self.bounds();
+            if ivars.titles.is_empty() {
+                return;
+            }
+
+            let background = NSColor::colorWithSRGBRed_green_blue_alpha(0.12, 0.12, 0.12, 0.85);
+            background.setFill();
+            NSBezierPath::fillRect(bounds);
+
+            let tab_width = bounds.size.width / ivars.titles.len() as f64;
+            let font = NSFont::systemFontOfSize(bounds.size.height * 0.55);
+
+            for (index, title) in ivars.titles.iter().enumerate() {
+                let tab = NSRect::new(
+                    NSPoint::new(bounds.origin.x + tab_width * index as f64, bounds.origin.y),
+                    NSSize::new(tab_width, bounds.size.height),
+                );
+                let active = ivars.active == Some(index);
+                if active {
+                    let highlight = NSColor::colorWithSRGBRed_green_blue_alpha(0.3, 0.45, 0.75, 0.9);
+                    highlight.setFill();
+                    NSBezierPath::fillRect(tab);
+                }
+
+                let alpha = if active { 1.0 } else { 0.7 };
+                let color = NSColor::colorWithSRGBRed_green_blue_alpha(1.0, 1.0, 1.0, alpha);
+                let label = NSRect::new(
+                    NSPoint::new(tab.origin.x + 4.0, tab.origin.y),
+                    NSSize::new(tab.size.width - 8.0, tab.size.height),
+                );
+                draw_centered_label(label, title, &font, &color, true);
+            }
+        }
+
+        #[unsafe(method(isFlipped))]
+        fn is_flipped(&self) -> bool {
+            true
+        }
+    }
+);
+
+impl TabBarView {
+    fn new(mtm: MainThreadMarker, frame: NSRect, params: &TabBarParams) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(TabBarViewIvars {
+            titles: params
+                .tabs
+                .iter()
+                .map(|(title, _)| NSString::from_str(title))
+                .collect(),
+            active: params.tabs.iter().position(|(_, active)| *active),
+        });
+        unsafe { msg_send![super(this), initWithFrame: frame] }
+    }
+}
+
+/// Draws the tab bars of stacked columns. Clicks go through to the windows below and are
+/// matched against the tab geometry by the mouse handlers instead.
+pub struct StackTabsManager {
+    mtm: MainThreadMarker,
+    bars: Vec<(Retained<NSWindow>, TabBarParams)>,
+}
+
+impl StackTabsManager {
+    pub fn new(mtm: MainThreadMarker) -> Self {
+        Self {
+            mtm,
+            bars: Vec::new(),
+        }
+    }
+
+    /// Shows one tab bar per entry in `bars`, reusing existing overlay windows where possible.
+    pub fn update(&mut self, bars: &[TabBarParams]) {
+        let screen_h = primary_screen_height(self.mtm);
+
+        // Columns which scrolled away or were unstacked lose their bars.
+        let kept = bars.len().min(self.bars.len());
+        for (window, _) in self.bars.drain(kept..) {
+            window.orderOut(None::<&AnyObject>);
+        }
+
+        for (index, params) in bars.iter().enumerate() {
+            let frame = cg_abs_to_cocoa(params.frame, screen_h);
+            let local = NSRect::new(NSPoint::new(0.0, 0.0), frame.size);
+            if let Some((window, stored)) = self.bars.get_mut(index) {
+                if stored == params {
+                    continue;
+                }
+                let view = TabBarView::new(self.mtm, local, params);
+                window.setContentView(Some(&view));
+                window.setFrame_display(frame, true);
+                window.orderFront(None::<&AnyObject>);
+                *stored = params.clone();
+            } else {
+                let window = make_overlay_window(self.mtm, frame);
+                let view = TabBarView::new(self.mtm, local, params);
+                window.setContentView(Some(&view));
+                window.orderFront(None::<&AnyObject>);
+                self.bars.push((window, params.clone()));
+            }
+        }
+    }
+
+    pub fn remove_all(&mut self) {
+        for (window, _) in self.bars.drain(..) {
+            window.orderOut(None::<&AnyObject>);
+        }
+    }
+}
+
+// ── JumpBadges ──────────────────────────────────────────────────────────
+
+/// A badge drawn over a column while `window jump` waits for a key.
+#[derive(Clone, Debug, PartialEq)]
+pub struct JumpBadgeParams {
+    /// The badge rect in absolute CG coordinates.
+    pub frame: NSRect,
+    pub label: String,
+}
+
+#[derive(Debug, Clone)]
+struct JumpBadgeViewIvars {
+    label: Retained<NSString>,
+}
+
+define_class!(
+    #[unsafe(super(NSView))]
+    #[thread_kind = MainThreadOnly]
+    #[name = "PaneruJumpBadgeView"]
+    #[ivars = JumpBadgeViewIvars]
+    #[derive(Debug)]
+    struct JumpBadgeView;
+
+    impl JumpBadgeView {
+        #[unsafe(method(drawRect:))]
+        fn draw_rect(&self, _dirty_rect: NSRect) {
+            let ivars = self.ivars();
+            let bounds = self.bounds();
+
+            let background = NSColor::colorWithSRGBRed_green_blue_alpha(0.3, 0.45, 0.75, 0.9);
+            background.setFill();
+            let radius = bounds.size.height * 0.2;
+            let path = NSBezierPath::bezierPathWithRoundedRect_xRadius_yRadius(
+                bounds, radius, radius,
+            );
+            path.fill();
+
+            let font = NSFont::boldSystemFontOfSize(bounds.size.height * 0.6);
+            let color = NSColor::colorWithSRGBRed_green_blue_alpha(1.0, 1.0, 1.0, 1.0);
+            draw_centered_label(bounds, &ivars.label, &font, &color, false);
+        }
+
+        #[unsafe(method(isFlipped))]
+        fn is_flipped(&self) -> bool {
+            true
+        }
+    }
+);
+
+impl JumpBadgeView {
+    fn new(mtm: MainThreadMarker, frame: NSRect, params: &JumpBadgeParams) -> Retained<Self> {
+        let this = Self::alloc(mtm).set_ivars(JumpBadgeViewIvars {
+            label: NSString::from_str(&params.label),
+        });
+        unsafe { msg_send![super(this), initWithFrame: frame] }
+    }
+}
+
+/// Draws the column labels of `window jump` above all windows.
+pub struct JumpBadgesManager {
+    mtm: MainThreadMarker,
+    badges: Vec<(Retained<NSWindow>, JumpBadgeParams)>,
+}
+
+impl JumpBadgesManager {
+    pub fn new(mtm: MainThreadMarker) -> Self {
+        Self {
+            mtm,
+            badges: Vec::new(),
+        }
+    }
+
+    /// Shows one badge per entry in `badges`, reusing existing overlay windows where possible.
+    pub fn update(&mut self, badges: &[JumpBadgeParams]) {
+        let screen_h = primary_screen_height(self.mtm);
+
+        let kept = badges.len().min(self.badges.len());
+        for (window, _) in self.badges.drain(kept..) {
+            window.orderOut(None::<&AnyObject>);
+        }
+
+        for (index, params) in badges.iter().enumerate() {
+            let frame = cg_abs_to_cocoa(params.frame, screen_h);
+            let local = NSRect::new(NSPoint::new(0.0, 0.0), frame.size);
+            let view = JumpBadgeView::new(self.mtm, local, params);
+            if let Some((window, stored)) = self.badges.get_mut(index) {
+                if stored == params {
+                    continue;
+                }
+                window.setContentView(Some(&view));
+                window.setFrame_display(frame, true);
+                window.orderFront(None::<&AnyObject>);
+                *stored = params.clone();
+            } else {
+                let window = make_overlay_window(self.mtm, frame);
+                window.setLevel(NSFloatingWindowLevel + 1);
+                window.setContentView(Some(&view));
+                window.orderFront(None::<&AnyObject>);
+                self.badges.push((window, params.clone()));
+            }
+        }
+    }
+}
+
 // ── FlashMessage ────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone)]
@@ -384,49 +693,7 @@ define_class!(
             let font_size = bounds.size.height * 0.8; // Scale font with bezel
             let font = NSFont::systemFontOfSize(font_size);
             let color = NSColor::colorWithSRGBRed_green_blue_alpha(1.0, 1.0, 1.0, CGFloat::from(ivars.opacity));
-
-            let paragraph_style = unsafe {
-                let style = NSParagraphStyle::defaultParagraphStyle().mutableCopy();
-                let _: () = msg_send![&style, setAlignment: 1isize]; // Center (NSTextAlignmentCenter = 1)
-                style
-            };
-
-            // Using manual attribute keys as they might be missing from the crate's high-level API
-            let attr_str: Retained<NSAttributedString> = unsafe {
-                let font_key = NSString::from_str("NSFont");
-                let color_key = NSString::from_str("NSColor");
-                let para_key = NSString::from_str("NSParagraphStyle");
-
-                let keys = [&*font_key, &*color_key, &*para_key];
-                let objects = [
-                    &*font as &AnyObject,
-                    &*color as &AnyObject,
-                    &*paragraph_style as &AnyObject,
-                ];
-
-                let attributes = NSDictionary::from_slices(&keys, &objects);
-
-                // Using raw msg_send as the high-level wrapper might have trait bound issues
-                let alloc = NSAttributedString::alloc();
-                msg_send![alloc, initWithString: &*ivars.message, attributes: &*attributes]
-            };
-
-            let text_size = unsafe {
-                let size: NSSize = msg_send![&attr_str, size];
-                size
-            };
-
-            let text_rect = NSRect::new(
-                NSPoint::new(
-                    bounds.origin.x + (bounds.size.width - text_size.width) / 2.0,
-                    bounds.origin.y + (bounds.size.height - text_size.height) / 2.0,
-                ),
-                text_size
-            );
-
-            unsafe {
-                let _: () = msg_send![&attr_str, drawInRect: text_rect];
-            };
+            draw_centered_label(bounds, &ivars.message, &font, &color, false);
         }
     }
 );
@@ -4,15 +4,15 @@ use accessibility_sys::{AXError, AXObserverRef, AXUIElementRef};
 use objc2::MainThreadMarker;
 use objc2::rc::{Retained, autoreleasepool};
 use objc2_app_kit::{NSApplication, NSApplicationActivationPolicy, NSEventMask};
-use objc2_core_foundation::CFString;
-use objc2_foundation::{NSDate, NSDefaultRunLoopMode, NSProcessInfo};
+use objc2_core_foundation::{CFRunLoop, CFString, kCFRunLoopDefaultMode};
+use objc2_foundation::{NSDefaultRunLoopMode, NSProcessInfo};
 use std::ffi::c_void;
 use std::pin::Pin;
-use tracing::error;
+use tracing::{error, warn};
 
 use crate::config::{CONFIGURATION_FILE, Config};
 use crate::errors::{Error, Result};
-use crate::events::{Event, EventSender};
+use crate::events::{Event, EventSender, install_wake_source};
 use crate::manager::{check_ax_privilege, check_separate_spaces};
 use crate::platform::display::PinnedDisplayHandler;
 use crate::platform::input::PinnedInputHandler;
@@ -177,6 +177,7 @@ impl PlatformCallbacks {
         cocoa_app.setActivationPolicy(NSApplicationActivationPolicy::Accessory);
         cocoa_app.finishLaunching();
         NSApplication::load();
+        install_wake_source();
 
         let workspace_observer = WorkspaceObserver::new(events.clone());
         Box::pin(PlatformCallbacks {
@@ -219,7 +220,16 @@ impl PlatformCallbacks {
             ));
         }
 
-        let config = Config::new(CONFIGURATION_FILE.as_path())?;
+        let path = CONFIGURATION_FILE.as_path();
+        let config = if path.exists() {
+            Config::new(path)?
+        } else {
+            warn!(
+                "configuration file {} does not exist, using built-in defaults.",
+                path.display()
+            );
+            Config::default()
+        };
         self.events.send(Event::InitialConfig(config.clone()))?;
         self.event_handler = Some(InputHandler::new(self.events.clone(), config).start()?);
 
@@ -234,17 +244,21 @@ impl PlatformCallbacks {
         self.events.send(Event::ProcessesLoaded)
     }
 
+    /// Sleeps on the main run loop until a source fires, `EventSender` signals its wake source,
+    /// or `timeout` seconds pass. Afterwards dispatches the Cocoa events queued in the meantime.
     pub fn pump_cocoa_event_loop(&mut self, timeout: f64) {
         autoreleasepool(|_| {
-            let until_date = NSDate::dateWithTimeIntervalSinceNow(timeout);
+            // Accessibility observers, the event tap and the Cocoa event port are all run loop
+            // sources, so this returns as soon as any of them has something for us.
+            CFRunLoop::run_in_mode(unsafe { kCFRunLoopDefaultMode }, timeout, true);
 
             // nextEventMatchingMask:untilDate:inMode:dequeue:
-            // This is the core of the Cocoa event loop.
+            // A nil date does not wait, it only drains what is already queued.
             while let Some(event) = unsafe {
                 self.cocoa_app
                     .nextEventMatchingMask_untilDate_inMode_dequeue(
                         NSEventMask::Any,
-                        Some(&until_date),
+                        None,
                         NSDefaultRunLoopMode,
                         true, // Dequeue so we can handle it
                     )
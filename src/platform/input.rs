@@ -1,9 +1,10 @@
 use arc_swap::ArcSwap;
+use bevy::math::IRect;
 use core::ptr::NonNull;
 use objc2::msg_send;
 use objc2::rc::Retained;
 use objc2_app_kit::{NSEvent, NSEventType, NSTouch, NSTouchPhase};
-use objc2_core_foundation::{CFMachPort, CFRetained, CFRunLoop, kCFRunLoopCommonModes};
+use objc2_core_foundation::{CFMachPort, CFRetained, CFRunLoop, CGPoint, kCFRunLoopCommonModes};
 use objc2_core_graphics::{
     CGEvent, CGEventField, CGEventFlags, CGEventTapLocation, CGEventTapOptions,
     CGEventTapPlacement, CGEventTapProxy, CGEventType,
@@ -14,17 +15,25 @@ use std::ffi::c_void;
 use std::marker::PhantomPinned;
 use std::pin::Pin;
 use std::ptr::null_mut;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, LazyLock};
 use std::time::{Duration, Instant};
 use stdext::function_name;
-use tracing::{error, info};
+use tracing::{debug, error, info};
 
-use crate::config::Config;
+use crate::commands::{Command, Operation};
+use crate::config::{Config, DEFAULT_MODE};
 use crate::errors::{Error, Result};
 use crate::events::{Event, EventSender};
+use crate::manager::origin_from;
 use crate::platform::Modifiers;
 
 const NX_DEVICEFNKEYMASK: u64 = 0x0080_0100;
+/// Virtual keycode of the escape key (`kVK_Escape`), which always leaves a keybinding mode.
+const KEYCODE_ESCAPE: u8 = 0x35;
+/// Marks keystrokes posted by `post_keystroke`, so the event tap does not treat them as
+/// keybindings of its own.
+const SYNTHETIC_EVENT_TAG: i64 = 0x7061_6e65;
 
 /// The currently active set of passthrough keybindings, shared lock-free with
 /// the `CGEvent` tap callback thread via `ArcSwap`.
@@ -37,6 +46,89 @@ pub fn set_focused_passthrough(keys: Vec<(u8, Modifiers)>) {
     FOCUSED_PASSTHROUGH.store(Arc::new(keys));
 }
 
+/// Posts a key down and key up of `keycode` with the modifier `flags` held, as if typed on the
+/// keyboard. The event tap passes these keystrokes through, even when they match a keybinding.
+pub fn post_keystroke(keycode: u16, flags: CGEventFlags) -> Result<()> {
+    for key_down in [true, false] {
+        let event = CGEvent::new_keyboard_event(None, keycode, key_down).ok_or(
+            Error::InvalidInput(format!("{}: keycode {keycode}", function_name!())),
+        )?;
+        CGEvent::set_flags(Some(&event), flags);
+        CGEvent::set_integer_value_field(
+            Some(&event),
+            CGEventField::EventSourceUserData,
+            SYNTHETIC_EVENT_TAG,
+        );
+        CGEvent::post(CGEventTapLocation::HIDEventTap, Some(&event));
+    }
+    Ok(())
+}
+
+/// The active keybinding mode (`None` for the default `[bindings]`), shared lock-free
+/// between the ECS thread and the `CGEvent` tap callback.
+static ACTIVE_MODE: LazyLock<ArcSwap<Option<String>>> =
+    LazyLock::new(|| ArcSwap::from_pointee(None));
+
+/// Switches the active keybinding mode, where `default` returns to `[bindings]`.
+/// Returns `false` and keeps the current mode if no such mode is configured.
+pub fn set_active_mode(mode: &str, config: &Config) -> bool {
+    let mode = if mode == DEFAULT_MODE {
+        None
+    } else if config.has_mode(mode) {
+        Some(mode.to_string())
+    } else {
+        return false;
+    };
+    ACTIVE_MODE.store(Arc::new(mode));
+    true
+}
+
+/// Returns to the default `[bindings]` if the active keybinding mode is no longer configured.
+pub fn revalidate_active_mode(config: &Config) {
+    let mode = ACTIVE_MODE.load();
+    if let Some(mode) = mode.as_deref()
+        && !config.has_mode(mode)
+    {
+        debug!("keybinding mode '{mode}' was removed, returning to the default bindings.");
+        ACTIVE_MODE.store(Arc::new(None));
+    }
+}
+
+/// Set while a `window jump` shows its column labels, so the event tap hands the next key press
+/// to the ECS instead of matching it against the keybindings.
+static JUMP_PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Makes the event tap take the next key press as the label of a `window jump`.
+pub fn set_jump_pending(pending: bool) {
+    JUMP_PENDING.store(pending, Ordering::Release);
+}
+
+/// Distance in pixels from a tiled column edge within which a left drag with the
+/// `mouse_resize_modifier` grabs that edge.
+pub const COLUMN_EDGE_GRAB_PX: i32 = 6;
+
+/// Frames of the tiled windows on the active strip, shared lock-free with the `CGEvent` tap
+/// callback, so it can tell whether a modifier click grabs a column edge.
+static COLUMN_FRAMES: LazyLock<ArcSwap<Vec<IRect>>> =
+    LazyLock::new(|| ArcSwap::from_pointee(Vec::new()));
+
+/// Replaces the tiled window frames the event tap checks for column edges. Called from the ECS
+/// thread whenever the active strip moves.
+pub fn set_column_frames(frames: Vec<IRect>) {
+    COLUMN_FRAMES.store(Arc::new(frames));
+}
+
+/// Returns `true` if the point lies within `COLUMN_EDGE_GRAB_PX` of the left or right edge of a
+/// tiled window.
+fn near_column_edge(point: CGPoint) -> bool {
+    let point = origin_from(point);
+    COLUMN_FRAMES.load().iter().any(|frame| {
+        (frame.min.y..frame.max.y).contains(&point.y)
+            && ((point.x - frame.min.x).abs() <= COLUMN_EDGE_GRAB_PX
+                || (point.x - frame.max.x).abs() <= COLUMN_EDGE_GRAB_PX)
+    })
+}
+
 /// How long to suppress scroll wheel events after a vertical swipe gesture,
 /// covering macOS momentum scroll that continues after finger lift.
 const VERTICAL_GESTURE_SCROLL_SUPPRESS: Duration = Duration::from_millis(1200);
@@ -44,6 +136,51 @@ const VERTICAL_GESTURE_SCROLL_SUPPRESS: Duration = Duration::from_millis(1200);
 const SWIPE_THRESHOLD: f64 = 0.001;
 const GESTURE_MINIMAL_FINGERS: usize = 3;
 
+/// Tracks a pressed leader key until the next key press, which completes the chord if it arrives
+/// within the configured timeout.
+#[derive(Debug, Default)]
+struct LeaderState {
+    pressed_at: Option<Instant>,
+}
+
+impl LeaderState {
+    fn press(&mut self, now: Instant) {
+        self.pressed_at = Some(now);
+    }
+
+    /// Consumes the pending leader press, returning `true` if it has not timed out yet.
+    fn take(&mut self, now: Instant, timeout: Duration) -> bool {
+        self.pressed_at
+            .take()
+            .is_some_and(|pressed_at| now.saturating_duration_since(pressed_at) <= timeout)
+    }
+}
+
+/// Tracks the modifiers held while a `window cycle-recent` binding steps through windows, so
+/// their release can commit the selection. `window peek` reuses it to end the peek.
+#[derive(Debug, Default)]
+struct CycleState {
+    held: Option<Modifiers>,
+}
+
+impl CycleState {
+    fn start(&mut self, modifiers: Modifiers) {
+        if !modifiers.is_empty() {
+            self.held = Some(modifiers);
+        }
+    }
+
+    /// Ends the cycle once none of its modifiers are pressed anymore, returning `true` if the
+    /// selection should be committed.
+    fn release(&mut self, modifiers: Modifiers) -> bool {
+        let released = self.held.is_some_and(|held| !modifiers.intersects(held));
+        if released {
+            self.held = None;
+        }
+        released
+    }
+}
+
 /// `InputHandler` manages low-level input events from the macOS `CGEventTap`.
 /// It intercepts keyboard and mouse events, processes gestures, and dispatches them as higher-level `Event`s.
 pub(super) struct InputHandler {
@@ -59,6 +196,12 @@ pub(super) struct InputHandler {
     /// are suppressed for a short window after this to prevent the OS from
     /// scrolling windows underneath (including momentum scroll after finger lift).
     last_swipe_time: Option<Instant>,
+    /// Pending leader key press of a two-step keybinding.
+    leader: LeaderState,
+    /// Set while a mouse resize drag is in progress, so its events are kept away from the window.
+    resize_drag: bool,
+    /// Modifiers held by an in-progress `window cycle-recent` session.
+    cycle: CycleState,
     // Prevents from being Unpin automatically
     _pin: PhantomPinned,
 }
@@ -84,6 +227,9 @@ impl InputHandler {
             finger_position: None,
             tap_port: None,
             last_swipe_time: None,
+            leader: LeaderState::default(),
+            resize_drag: false,
+            cycle: CycleState::default(),
             _pin: PhantomPinned,
         }
     }
@@ -103,7 +249,8 @@ impl InputHandler {
             | (1 << CGEventType::RightMouseDragged.0)
             | (1 << CGEventType::ScrollWheel.0)
             | (1 << NSEventType::Gesture.0)
-            | (1 << CGEventType::KeyDown.0);
+            | (1 << CGEventType::KeyDown.0)
+            | (1 << CGEventType::FlagsChanged.0);
 
         let mut pinned = Box::pin(self);
         let this = unsafe { NonNull::new_unchecked(pinned.as_mut().get_unchecked_mut()) }.as_ptr();
@@ -201,6 +348,7 @@ impl InputHandler {
 
         let flags = CGEvent::flags(Some(event));
         let modifiers = get_modifiers(flags);
+        let mut intercept = false;
 
         let result = match event_type {
             CGEventType::TapDisabledByTimeout | CGEventType::TapDisabledByUserInput => {
@@ -212,26 +360,56 @@ impl InputHandler {
             }
             CGEventType::LeftMouseDown | CGEventType::RightMouseDown => {
                 let point = CGEvent::location(Some(event));
-                events.send(Event::MouseDown { point, modifiers })
+                // A left drag with the resize modifier next to a column edge resizes that column,
+                // the window itself must not see the click.
+                self.resize_drag = event_type == CGEventType::LeftMouseDown
+                    && self
+                        .config
+                        .mouse_resize_modifier()
+                        .is_some_and(|modifier| modifier.matches(modifiers))
+                    && near_column_edge(point);
+                intercept = self.resize_drag;
+                if self.resize_drag {
+                    events.send(Event::MouseResizeGrab { point })
+                } else {
+                    events.send(Event::MouseDown { point, modifiers })
+                }
             }
             CGEventType::LeftMouseUp | CGEventType::RightMouseUp => {
                 let point = CGEvent::location(Some(event));
+                if event_type == CGEventType::LeftMouseUp {
+                    intercept = std::mem::take(&mut self.resize_drag);
+                }
                 events.send(Event::MouseUp { point, modifiers })
             }
             CGEventType::LeftMouseDragged | CGEventType::RightMouseDragged => {
                 let point = CGEvent::location(Some(event));
+                intercept = self.resize_drag && event_type == CGEventType::LeftMouseDragged;
                 events.send(Event::MouseDragged { point, modifiers })
             }
             CGEventType::MouseMoved => {
                 let point = CGEvent::location(Some(event));
                 events.send(Event::MouseMoved { point, modifiers })
             }
+            CGEventType::KeyDown
+                if CGEvent::integer_value_field(Some(event), CGEventField::EventSourceUserData)
+                    == SYNTHETIC_EVENT_TAG =>
+            {
+                return false;
+            }
             CGEventType::KeyDown => {
                 let keycode =
                     CGEvent::integer_value_field(Some(event), CGEventField::KeyboardEventKeycode);
                 // handle_keypress can intercept the event, so it may return true.
                 return self.handle_keypress(keycode, flags);
             }
+            CGEventType::FlagsChanged => {
+                if self.cycle.release(modifiers) {
+                    events.send(Event::CycleModifiersReleased)
+                } else {
+                    Ok(())
+                }
+            }
             CGEventType::ScrollWheel => {
                 return self.handle_scroll_wheel(event);
             }
@@ -247,8 +425,8 @@ impl InputHandler {
             // Trigger cleanup destructor, unregistering the handler.
             self.events = None;
         }
-        // Do not intercept this event, let it fall through.
-        false
+        // Only resize drags are intercepted, everything else falls through.
+        intercept
     }
 
     /// Handles scroll wheel events. If configured modifier is held, it transforms the scroll into a swipe event.
@@ -419,6 +597,8 @@ impl InputHandler {
 
     /// Handles key press events. It determines the modifier mask and attempts to find a matching keybinding in the configuration.
     /// If a binding is found, it sends a `Command` event and intercepts the key press.
+    /// A press of the leader key is intercepted and remembered, so the next key is looked up
+    /// among the two-step bindings instead.
     ///
     /// # Arguments
     ///
@@ -428,29 +608,79 @@ impl InputHandler {
     /// # Returns
     ///
     /// `true` if the key press was handled and should be intercepted, `false` otherwise.
-    fn handle_keypress(&self, keycode: i64, eventflags: CGEventFlags) -> bool {
+    fn handle_keypress(&mut self, keycode: i64, eventflags: CGEventFlags) -> bool {
         let Some(events) = &self.events else {
             return false;
         };
+        let Ok(keycode) = u8::try_from(keycode) else {
+            return false;
+        };
+
+        // A pending jump swallows the next key, whichever it is: a label picks its column and
+        // anything else cancels the jump.
+        if JUMP_PENDING.swap(false, Ordering::AcqRel) {
+            if let Err(err) = events.send(Event::JumpKey { keycode }) {
+                error!("Error sending jump key: {err}");
+            }
+            return true;
+        }
 
         let mask = get_modifiers(eventflags);
+        let now = Instant::now();
+
+        let mode = ACTIVE_MODE.load();
+        let command = if let Some(mode) = mode.as_deref() {
+            // While a mode is active its table replaces `[bindings]` and unbound keys are
+            // swallowed, so plain keys can be used without typing into the focused application.
+            let command = self.config.find_mode_keybind(mode, keycode, mask);
+            if command.is_none() && keycode == KEYCODE_ESCAPE && mask.is_empty() {
+                Some(Command::Mode(DEFAULT_MODE.to_string()))
+            } else if command.is_none() {
+                return true;
+            } else {
+                command
+            }
+        } else if self.leader.take(now, self.config.leader_timeout()) {
+            // The key after the leader is always swallowed, so a mistyped chord does not leak
+            // into the focused application.
+            let command = self.config.find_leader_keybind(keycode, mask);
+            if command.is_none() {
+                debug!("no leader binding for keycode {keycode} with {mask:?}");
+                return true;
+            }
+            command
+        } else {
+            // On a native fullscreen space, keybindings are still intercepted so
+            // that paneru can actively switch back to the previous workspace.
+            // Non-paneru keys pass through naturally (find_keybind returns None).
+            let passthrough = FOCUSED_PASSTHROUGH.load();
+            if passthrough
+                .iter()
+                .any(|(c, m)| *c == keycode && m.matches(mask))
+            {
+                return false;
+            }
+            if self
+                .config
+                .leader_key()
+                .is_some_and(|(code, modifiers)| code == keycode && modifiers.matches(mask))
+            {
+                self.leader.press(now);
+                return true;
+            }
+            self.config.find_keybind(keycode, mask)
+        };
 
-        // On a native fullscreen space, keybindings are still intercepted so
-        // that paneru can actively switch back to the previous workspace.
-        // Non-paneru keys pass through naturally (find_keybind returns None).
-
-        let keycode = keycode.try_into().ok();
-        keycode
-            .and_then(|keycode| {
-                let passthrough = FOCUSED_PASSTHROUGH.load();
-                if passthrough
-                    .iter()
-                    .any(|(c, m)| *c == keycode && m.matches(mask))
-                {
-                    return None;
-                }
-                self.config.find_keybind(keycode, mask)
-            })
+        if let Some(Command::Mode(mode)) = &command {
+            // Switch right away, so keys typed before the ECS handles the command already
+            // use the new mode.
+            set_active_mode(mode, &self.config);
+        }
+        if let Some(Command::Window(Operation::CycleRecent | Operation::Peek(_))) = &command {
+            self.cycle.start(mask);
+        }
+
+        command
             .and_then(|command| {
                 events
                     .send(Event::Command { command })
@@ -510,6 +740,40 @@ mod tests {
     const NX_DEVICELCTLKEYMASK: u64 = 0x0000_0001;
     const NX_DEVICERCTLKEYMASK: u64 = 0x0000_2000;
 
+    #[test]
+    fn leader_chord_completes_within_timeout() {
+        let timeout = Duration::from_millis(500);
+        let start = Instant::now();
+        let mut leader = LeaderState::default();
+
+        assert!(!leader.take(start, timeout));
+
+        leader.press(start);
+        assert!(leader.take(start + Duration::from_millis(200), timeout));
+        // The pending press is consumed by the second key.
+        assert!(!leader.take(start + Duration::from_millis(300), timeout));
+
+        leader.press(start);
+        assert!(!leader.take(start + Duration::from_millis(600), timeout));
+        assert!(!leader.take(start + Duration::from_millis(700), timeout));
+    }
+
+    #[test]
+    fn cycle_commits_when_modifiers_are_released() {
+        let mut cycle = CycleState::default();
+        assert!(!cycle.release(Modifiers::empty()));
+
+        // Without modifiers there is nothing to release, e.g. a command sent over the socket.
+        cycle.start(Modifiers::empty());
+        assert!(!cycle.release(Modifiers::empty()));
+
+        cycle.start(Modifiers::LALT | Modifiers::LSHIFT);
+        // Still holding one of the binding's modifiers keeps the cycle going.
+        assert!(!cycle.release(Modifiers::LALT));
+        assert!(cycle.release(Modifiers::empty()));
+        assert!(!cycle.release(Modifiers::empty()));
+    }
+
     #[test]
     fn no_modifiers() {
         assert_eq!(get_modifiers(CGEventFlags(0)), Modifiers::empty());
@@ -12,6 +12,53 @@ use crate::util::exe_path;
 /// The bundle identifier for the `paneru` service.
 pub const ID: &str = "com.github.karinushka.paneru";
 
+/// Environment variable through which the generated plist hands its launchd label to the daemon.
+const LABEL_VAR: &str = "PANERU_SERVICE_LABEL";
+
+/// Returns the launchd label the daemon was started under, or the default `ID` when it was not
+/// started from a plist which records one.
+#[must_use]
+pub fn running_label() -> String {
+    env::var(LABEL_VAR)
+        .ok()
+        .filter(|label| !label.is_empty())
+        .unwrap_or_else(|| ID.to_string())
+}
+
+/// Settings for the generated launchd plist, chosen with `paneru install`.
+#[derive(Clone, Debug)]
+pub struct InstallOptions {
+    /// Redirects both stdout and stderr of the daemon to this file instead of the default logs.
+    pub log_path: Option<PathBuf>,
+    /// Restarts the daemon by launchd after it crashed.
+    pub keep_alive: bool,
+    /// Scheduling priority of the daemon, from -20 (highest) to 20.
+    pub nice: i32,
+}
+
+impl Default for InstallOptions {
+    fn default() -> Self {
+        Self {
+            log_path: None,
+            keep_alive: true,
+            nice: -20,
+        }
+    }
+}
+
+/// State of the launchd job, as reported by `paneru status`.
+#[derive(Debug, Default)]
+pub struct ServiceStatus {
+    /// Whether the launch agent plist is installed.
+    pub installed: bool,
+    /// Whether the job is loaded into launchd.
+    pub loaded: bool,
+    /// Process id of the running daemon, if launchd reports one.
+    pub pid: Option<u32>,
+    /// Elapsed running time of the daemon, as formatted by `ps`.
+    pub uptime: Option<String>,
+}
+
 /// `Service` manages the installation, uninstallation, starting, and stopping of the `paneru` application as a launchd service.
 /// It encapsulates the `launchctl::Service` and the path to the executable.
 #[derive(Debug)]
@@ -72,10 +119,14 @@ impl Service {
     /// Installs the service as a launch agent by writing its plist file.
     /// If the service is already installed, a warning is logged, and installation is skipped.
     ///
+    /// # Arguments
+    ///
+    /// * `options` - The `InstallOptions` used to generate the plist.
+    ///
     /// # Returns
     ///
     /// `Ok(())` if the service is installed successfully or already exists, otherwise `Err(Error)` if a file system error occurs.
-    pub fn install(&self) -> Result<()> {
+    pub fn install(&self, options: &InstallOptions) -> Result<()> {
         let plist_path = self.plist_path();
         let dir = plist_path.parent().ok_or(Error::last_os_error())?;
         if !dir.exists() {
@@ -91,9 +142,16 @@ impl Service {
         }
 
         let mut plist = fs::File::create(plist_path)?;
-        plist.write_all(self.launchd_plist().as_bytes())?;
+        plist.write_all(self.launchd_plist(options).as_bytes())?;
         info!("installed launch agent to `{}`", plist_path.display());
-        info!("check logfile /tmp/com.github.karinushka.paneru*.log for potential error messages");
+        let (out_log_path, error_log_path) = self.log_paths(options);
+        if out_log_path == error_log_path {
+            info!("check logfile {out_log_path} for potential error messages");
+        } else {
+            info!(
+                "check logfiles {out_log_path} and {error_log_path} for potential error messages"
+            );
+        }
         Ok(())
     }
 
@@ -128,23 +186,27 @@ impl Service {
 
     /// Reinstalls the service by first uninstalling it and then installing it again.
     ///
+    /// # Arguments
+    ///
+    /// * `options` - The `InstallOptions` used to generate the new plist.
+    ///
     /// # Returns
     ///
     /// `Ok(())` if the service is reinstalled successfully, otherwise `Err(Error)` from underlying install/uninstall operations.
-    pub fn reinstall(&self) -> Result<()> {
+    pub fn reinstall(&self, options: &InstallOptions) -> Result<()> {
         self.uninstall()?;
-        self.install()
+        self.install(options)
     }
 
     /// Starts the service using `launchctl`.
-    /// If the service is not installed, it will be installed first.
+    /// If the service is not installed, it will be installed first with the default `InstallOptions`.
     ///
     /// # Returns
     ///
     /// `Ok(())` if the service starts successfully, otherwise `Err(Error)` from `launchctl`.
     pub fn start(&self) -> Result<()> {
         if !self.is_installed() {
-            self.install()?;
+            self.install(&InstallOptions::default())?;
         }
         info!("starting service...");
         self.raw.start()?;
@@ -174,7 +236,37 @@ impl Service {
         self.start()
     }
 
-    /// Spawns a detached `paneru restart` subprocess.
+    /// Queries `launchctl` for the state of the service and `ps` for the uptime of its process.
+    ///
+    /// # Returns
+    ///
+    /// The `ServiceStatus`. Failures to run either tool are reported as a job which is not loaded
+    /// or an unknown uptime.
+    #[must_use]
+    pub fn status(&self) -> ServiceStatus {
+        let installed = self.is_installed();
+        let Some(output) = Command::new("/bin/launchctl")
+            .args(["print", &self.raw.service_target])
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+        else {
+            return ServiceStatus {
+                installed,
+                ..ServiceStatus::default()
+            };
+        };
+        let pid = parse_launchctl_pid(&String::from_utf8_lossy(&output.stdout));
+        ServiceStatus {
+            installed,
+            loaded: true,
+            pid,
+            uptime: pid.and_then(process_uptime),
+        }
+    }
+
+    /// Spawns a detached `paneru restart` subprocess for the label of the running daemon.
     /// Used by the in-daemon restart command so launchctl stop/start runs outside
     /// the process being stopped.
     pub fn request_restart() -> Result<()> {
@@ -183,7 +275,7 @@ impl Service {
             "Cannot find current executable path.",
         ))?;
         Command::new(bin_path)
-            .arg("restart")
+            .args(["restart", "--label", &running_label()])
             .stdin(Stdio::null())
             .stdout(Stdio::null())
             .stderr(Stdio::null())
@@ -191,19 +283,142 @@ impl Service {
         Ok(())
     }
 
+    /// Returns the files receiving stdout and stderr of the service: the `log_path` option if
+    /// set, otherwise files under /tmp named after the service label.
+    fn log_paths(&self, options: &InstallOptions) -> (String, String) {
+        options.log_path.as_ref().map_or_else(
+            || {
+                (
+                    self.raw.out_log_path.clone(),
+                    self.raw.error_log_path.clone(),
+                )
+            },
+            |path| (path.display().to_string(), path.display().to_string()),
+        )
+    }
+
     /// Generates the content of the launchd plist file for this service.
-    /// This string is formatted with the service name, executable path, and log paths.
+    /// This string is formatted with the service name, executable path, log paths and the
+    /// chosen `InstallOptions`.
     #[must_use]
-    pub fn launchd_plist(&self) -> String {
+    pub fn launchd_plist(&self, options: &InstallOptions) -> String {
         let xdg_config_home = env::var("XDG_CONFIG_HOME")
             .unwrap_or_else(|_| format!("{}/.config", self.home_dir.display()));
+        let (out_log_path, error_log_path) = self.log_paths(options);
+        let keep_alive = if options.keep_alive {
+            "<dict>
+      <key>Crashed</key>
+      <true />
+      <key>SuccessfulExit</key>
+      <false />
+    </dict>"
+        } else {
+            "<false />"
+        };
         format!(
             include_str!("../../assets/launchd.plist"),
             name = self.raw.name,
+            label_var = LABEL_VAR,
             bin_path = self.bin_path.display(),
-            out_log_path = self.raw.out_log_path,
-            error_log_path = self.raw.error_log_path,
+            out_log_path = out_log_path,
+            error_log_path = error_log_path,
             xdg_config_home = xdg_config_home,
+            keep_alive = keep_alive,
+            nice = options.nice.clamp(-20, 20),
         )
     }
 }
+
+/// Extracts the process id from the output of `launchctl print`, which only lists it while the
+/// job is running.
+fn parse_launchctl_pid(output: &str) -> Option<u32> {
+    output
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("pid = ")?.trim().parse().ok())
+}
+
+/// Returns the elapsed running time of a process, e.g. `02:13:45` or `3-04:05:06`.
+fn process_uptime(pid: u32) -> Option<String> {
+    let output = Command::new("/bin/ps")
+        .args(["-o", "etime=", "-p", &pid.to_string()])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let uptime = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!uptime.is_empty()).then_some(uptime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_service(name: &str) -> Service {
+        Service {
+            raw: launchctl::Service::builder()
+                .name(name)
+                .uid("501")
+                .plist_path(format!("/tmp/{name}.plist"))
+                .build(),
+            bin_path: PathBuf::from("/usr/local/bin/paneru"),
+            home_dir: PathBuf::from("/Users/me"),
+        }
+    }
+
+    #[test]
+    fn default_plist_keeps_alive_on_crash() {
+        let plist = test_service(ID).launchd_plist(&InstallOptions::default());
+        assert!(plist.contains(&format!("<string>{ID}</string>")));
+        assert!(plist.contains(&format!("<key>{LABEL_VAR}</key>")));
+        assert!(plist.contains("<key>Crashed</key>"));
+        assert!(plist.contains("<integer>-20</integer>"));
+        assert!(plist.contains("/tmp/com.github.karinushka.paneru_501.out.log"));
+    }
+
+    #[test]
+    fn plist_follows_install_options() {
+        let options = InstallOptions {
+            log_path: Some(PathBuf::from("/Users/me/Library/Logs/paneru.log")),
+            keep_alive: false,
+            nice: 5,
+        };
+        let plist = test_service("org.example.paneru").launchd_plist(&options);
+        assert!(plist.contains("<string>org.example.paneru</string>"));
+        assert!(!plist.contains("<key>Crashed</key>"));
+        assert!(plist.contains("<key>KeepAlive</key>\n    <false />"));
+        assert!(plist.contains("<integer>5</integer>"));
+        assert_eq!(
+            plist.matches("/Users/me/Library/Logs/paneru.log").count(),
+            2
+        );
+    }
+
+    #[test]
+    fn log_paths_follow_label() {
+        let service = test_service("org.example.paneru");
+        let (out, err) = service.log_paths(&InstallOptions::default());
+        assert_eq!(out, "/tmp/org.example.paneru_501.out.log");
+        assert_eq!(err, "/tmp/org.example.paneru_501.err.log");
+
+        let plist = service.launchd_plist(&InstallOptions::default());
+        assert!(plist.contains(&out));
+        assert!(!plist.contains(ID));
+    }
+
+    #[test]
+    fn launchctl_pid_is_parsed_from_print_output() {
+        let running = "gui/501/com.github.karinushka.paneru = {\n\
+            \tactive count = 1\n\
+            \tpath = /Users/me/Library/LaunchAgents/com.github.karinushka.paneru.plist\n\
+            \tstate = running\n\
+            \tpid = 4321\n\
+            \tlast exit code = (never exited)\n\
+            }\n";
+        assert_eq!(parse_launchctl_pid(running), Some(4321));
+
+        let stopped = "gui/501/com.github.karinushka.paneru = {\n\
+            \tstate = not running\n\
+            \tlast exit code = 0\n\
+            }\n";
+        assert_eq!(parse_launchctl_pid(stopped), None);
+    }
+}
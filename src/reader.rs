@@ -1,13 +1,13 @@
+use paneru_ipc::{Client, SOCKET_PATH, StateQueryKind, decode_request, is_subscribe_request};
 use std::io::{Read, Write};
 use std::os::unix::net::{UnixListener, UnixStream};
 use std::sync::mpsc::channel;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::{fs, thread};
-use tracing::{debug, error};
+use tracing::error;
 
 use crate::config::parse_command;
-use crate::ecs::state::StateQueryKind;
 use crate::errors::Result;
 use crate::events::{Event, EventSender};
 
@@ -19,9 +19,6 @@ pub struct CommandReader {
 }
 
 impl CommandReader {
-    /// The path to the Unix socket used for inter-process communication.
-    const SOCKET_PATH: &str = "/tmp/paneru.socket";
-
     /// Sends a command and its arguments to the running `paneru` application via a Unix socket.
     /// The arguments are serialized and sent as a byte stream.
     ///
@@ -33,43 +30,36 @@ impl CommandReader {
     ///
     /// `Ok(())` if the command is sent successfully, otherwise `Err(Error)` if an I/O error occurs or the connection fails.
     pub fn send_command(params: impl IntoIterator<Item = String>) -> Result<()> {
-        let _stream = Self::send_socket_request(params)?;
+        Client::new().send_command(params)?;
         Ok(())
     }
 
     pub fn send_query(kind: StateQueryKind) -> Result<String> {
-        let args = match kind {
-            StateQueryKind::State => ["query", "state", "--json"],
-            StateQueryKind::VirtualWorkspaces => ["query", "virtual-workspaces", "--json"],
-            StateQueryKind::Active => ["query", "active", "--json"],
-        };
-        let mut stream = Self::send_socket_request(args.into_iter().map(str::to_string))?;
         let mut output = String::new();
-        stream.read_to_string(&mut output)?;
+        Client::new()
+            .request(kind.args())?
+            .read_to_string(&mut output)?;
         Ok(output)
     }
 
+    /// Checks whether the running daemon answers a query on the command socket within
+    /// `timeout`.
+    pub fn ping(timeout: Duration) -> bool {
+        let Ok(mut stream) = Client::new().request(StateQueryKind::Active.args()) else {
+            return false;
+        };
+        let mut output = String::new();
+        stream.set_read_timeout(Some(timeout)).is_ok()
+            && stream.read_to_string(&mut output).is_ok()
+            && !output.trim().is_empty()
+    }
+
     pub fn subscribe_json() -> Result<()> {
-        let mut stream =
-            Self::send_socket_request(["subscribe", "--json"].into_iter().map(str::to_string))?;
+        let mut stream = Client::new().request(["subscribe", "--json"])?;
         std::io::copy(&mut stream, &mut std::io::stdout())?;
         Ok(())
     }
 
-    fn send_socket_request(params: impl IntoIterator<Item = String>) -> Result<UnixStream> {
-        let output = params
-            .into_iter()
-            .flat_map(|param| [param.as_bytes(), &[0]].concat())
-            .collect::<Vec<_>>();
-        let size: u32 = output.len().try_into()?;
-        debug!("{:?} {output:?}", size.to_le_bytes());
-
-        let mut stream = UnixStream::connect(CommandReader::SOCKET_PATH)?;
-        stream.write_all(&size.to_le_bytes())?;
-        stream.write_all(&output)?;
-        Ok(stream)
-    }
-
     /// Creates a new `CommandReader` instance.
     ///
     /// # Arguments
@@ -102,8 +92,8 @@ impl CommandReader {
     /// `Ok(())` if the runner completes successfully (though it's typically a long-running loop),
     /// otherwise `Err(Error)` if a binding or I/O error occurs.
     fn runner(&mut self) -> Result<()> {
-        _ = fs::remove_file(CommandReader::SOCKET_PATH);
-        let listener = UnixListener::bind(CommandReader::SOCKET_PATH)?;
+        _ = fs::remove_file(SOCKET_PATH);
+        let listener = UnixListener::bind(SOCKET_PATH)?;
 
         for stream in listener.incoming() {
             let Ok(mut stream) = stream.inspect_err(|err| error!("reading stream {err}")) else {
@@ -120,14 +110,10 @@ impl CommandReader {
             if !full_read(&mut stream, buffer.len(), &mut buffer) {
                 continue;
             }
-            let argv = buffer
-                .split(|c| *c == 0)
-                .filter(|s| !s.is_empty())
-                .map(|s| String::from_utf8_lossy(s).to_string())
-                .collect::<Vec<_>>();
+            let argv = decode_request(&buffer);
             let argv_ref = argv.iter().map(String::as_str).collect::<Vec<_>>();
 
-            if let Some(kind) = parse_query_request(&argv_ref) {
+            if let Some(kind) = StateQueryKind::from_args(&argv_ref) {
                 let (tx, rx) = channel();
                 _ = self
                     .events
@@ -185,21 +171,6 @@ impl CommandReader {
     }
 }
 
-fn parse_query_request(argv: &[&str]) -> Option<StateQueryKind> {
-    match argv {
-        ["query", "state", "--json"] | ["query", "state"] => Some(StateQueryKind::State),
-        ["query", "virtual-workspaces", "--json"] | ["query", "virtual-workspaces"] => {
-            Some(StateQueryKind::VirtualWorkspaces)
-        }
-        ["query", "active", "--json"] | ["query", "active"] => Some(StateQueryKind::Active),
-        _ => None,
-    }
-}
-
-fn is_subscribe_request(argv: &[&str]) -> bool {
-    matches!(argv, ["subscribe", "--json"] | ["subscribe"])
-}
-
 fn full_read(stream: &mut UnixStream, expected: usize, buffer: &mut [u8]) -> bool {
     if let Ok(count) = stream.read(buffer).inspect_err(|err| {
         error!("{err}");
@@ -1,14 +1,20 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use bevy::prelude::*;
 use bevy::time::TimeUpdateStrategy;
 
-use crate::commands::{Command, MouseMove, MoveFocus, Operation};
-use crate::config::Config;
+use crate::commands::{
+    Command, Direction, DisplayOperation, DisplayTarget, MouseMove, MoveFocus, Operation,
+    SpaceOperation, WorkspaceOperation,
+};
+use crate::config::{Config, DisplaySelector, MainOptions, WindowParams};
 use crate::ecs::layout::LayoutStrip;
-use crate::ecs::{ActiveWorkspaceMarker, DockPosition, RefreshWindowSizes, Timeout};
+use crate::ecs::{
+    ActiveWorkspaceMarker, Bounds, DockPosition, RefreshWindowSizes, RescaleWidthsMarker, Timeout,
+    WidthRatio,
+};
 use crate::events::Event;
-use crate::manager::{Display, Origin, Size};
+use crate::manager::{Display, Origin, Size, WindowManager};
 use crate::{assert_not_on_workspace, assert_on_workspace, assert_window_at, assert_window_size};
 
 use super::*;
@@ -96,6 +102,58 @@ fn test_multi_display_lifecycle() {
         .run(commands);
 }
 
+/// A monitor reattached with new space ids gets back the strips it had before it was removed,
+/// instead of leaving them orphaned until they time out.
+#[test]
+fn test_reattached_display_restores_layout() {
+    const NEW_WORKSPACE_ID: u64 = TEST_WORKSPACE_ID + 100;
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::DisplayRemoved {
+            display_id: TEST_DISPLAY_ID,
+        },
+        Event::DisplayAdded {
+            display_id: TEST_DISPLAY_ID,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(2)
+        .on_iteration(1, |_world, state| {
+            state.remove_display(TEST_DISPLAY_ID);
+        })
+        .on_iteration(2, |_world, mut state| {
+            state.add_display(
+                TEST_DISPLAY_ID,
+                IRect::new(0, 0, TEST_DISPLAY_WIDTH, TEST_DISPLAY_HEIGHT),
+                vec![NEW_WORKSPACE_ID],
+            );
+        })
+        .on_iteration(3, |world, _state| {
+            let first = find_window_entity(0, world);
+            let second = find_window_entity(1, world);
+            let display_entity = world
+                .query_filtered::<Entity, With<Display>>()
+                .single(world)
+                .expect("display should be spawned again");
+
+            let restored = world
+                .query::<(&LayoutStrip, &ChildOf)>()
+                .iter(world)
+                .find(|(strip, _)| strip.id() == NEW_WORKSPACE_ID && strip.contains(first))
+                .map(|(strip, child)| (strip.all_windows(), child.parent()));
+            let (windows, parent) =
+                restored.expect("windows should be restored onto the new space");
+            assert_eq!(parent, display_entity);
+            assert!(windows.contains(&second), "both windows should be restored");
+        })
+        .run(commands);
+}
+
 #[test]
 fn test_multi_workspace_orphaning() {
     let commands = vec![
@@ -242,6 +300,275 @@ fn test_next_display_inserts_into_target_strip() {
         .run(commands);
 }
 
+#[test]
+fn test_workspace_to_next_display_moves_active_strip() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::Workspace(WorkspaceOperation::ToNextDisplay),
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(2)
+        .with_display(
+            EXT_DISPLAY_ID,
+            IRect::new(0, -EXT_DISPLAY_HEIGHT, EXT_DISPLAY_WIDTH, 0),
+            vec![EXT_WORKSPACE_ID],
+        )
+        .on_iteration(1, move |world, _state| {
+            assert_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+            assert_on_workspace!(world, 1, TEST_WORKSPACE_ID);
+        })
+        .on_iteration(2, move |world, _state| {
+            assert_on_workspace!(world, 0, EXT_WORKSPACE_ID);
+            assert_on_workspace!(world, 1, EXT_WORKSPACE_ID);
+            assert_not_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+
+            let ext_display = world
+                .query::<(Entity, &Display)>()
+                .iter(world)
+                .find_map(|(entity, display)| (display.id() == EXT_DISPLAY_ID).then_some(entity))
+                .expect("external display");
+            let moved = world
+                .query_filtered::<(&LayoutStrip, &ChildOf), With<ActiveWorkspaceMarker>>()
+                .single(world)
+                .map(|(strip, child)| (strip.id(), child.parent()))
+                .expect("should have an active workspace");
+            assert_eq!(moved, (EXT_WORKSPACE_ID, ext_display));
+
+            // The source space keeps a strip for windows opened there later.
+            assert!(
+                world
+                    .query::<&LayoutStrip>()
+                    .iter(world)
+                    .any(|strip| strip.id() == TEST_WORKSPACE_ID),
+                "source workspace should keep a strip"
+            );
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_swap_display_workspaces_exchanges_windows() {
+    let harness = TestHarness::new().with_windows(1).with_display(
+        EXT_DISPLAY_ID,
+        IRect::new(0, -EXT_DISPLAY_HEIGHT, EXT_DISPLAY_WIDTH, 0),
+        vec![EXT_WORKSPACE_ID],
+    );
+    let ext_origin = Origin::new(0, -EXT_DISPLAY_HEIGHT + TEST_MENUBAR_HEIGHT);
+    let size = Size::new(TEST_WINDOW_WIDTH, TEST_WINDOW_HEIGHT);
+    harness.mock_state.spawn_window(
+        TEST_PROCESS_ID,
+        EXT_WORKSPACE_ID,
+        100,
+        IRect::from_corners(ext_origin, ext_origin + size),
+    );
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::Display(DisplayOperation::SwapWorkspaces),
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    harness
+        .on_iteration(1, move |world, _state| {
+            assert_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+            assert_on_workspace!(world, 100, EXT_WORKSPACE_ID);
+        })
+        .on_iteration(2, move |world, _state| {
+            assert_on_workspace!(world, 0, EXT_WORKSPACE_ID);
+            assert_on_workspace!(world, 100, TEST_WORKSPACE_ID);
+            assert_not_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+            assert_not_on_workspace!(world, 100, EXT_WORKSPACE_ID);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_space_focus_switches_active_display_space() {
+    const OTHER_WORKSPACE_ID: WorkspaceId = TEST_WORKSPACE_ID + 1;
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Space(SpaceOperation::Focus(2)),
+        },
+        Event::Command {
+            command: Command::Space(SpaceOperation::Focus(5)),
+        },
+    ];
+
+    let active_space = |world: &mut World| {
+        world
+            .resource::<WindowManager>()
+            .active_display_space(TEST_DISPLAY_ID)
+            .ok()
+    };
+    TestHarness::new()
+        .with_display(
+            TEST_DISPLAY_ID,
+            IRect::new(0, 0, TEST_DISPLAY_WIDTH, TEST_DISPLAY_HEIGHT),
+            vec![TEST_WORKSPACE_ID, OTHER_WORKSPACE_ID],
+        )
+        .with_windows(1)
+        .on_iteration(0, move |world, _state| {
+            assert_eq!(active_space(world), Some(TEST_WORKSPACE_ID));
+        })
+        .on_iteration(1, move |world, state| {
+            assert_eq!(active_space(world), Some(OTHER_WORKSPACE_ID));
+            assert_eq!(state.window_workspace(0), Some(TEST_WORKSPACE_ID));
+        })
+        .on_iteration(2, move |world, _state| {
+            // There is no fifth space, so the display stays where it is.
+            assert_eq!(active_space(world), Some(OTHER_WORKSPACE_ID));
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_to_space_follow_switches_space() {
+    const OTHER_WORKSPACE_ID: WorkspaceId = TEST_WORKSPACE_ID + 1;
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::ToSpace(2, MoveFocus::Follow)),
+        },
+    ];
+
+    TestHarness::new()
+        .with_display(
+            TEST_DISPLAY_ID,
+            IRect::new(0, 0, TEST_DISPLAY_WIDTH, TEST_DISPLAY_HEIGHT),
+            vec![TEST_WORKSPACE_ID, OTHER_WORKSPACE_ID],
+        )
+        .with_windows(2)
+        .with_focused_window(0)
+        .on_iteration(1, |world, state| {
+            assert_eq!(state.window_workspace(0), Some(OTHER_WORKSPACE_ID));
+            assert_eq!(state.window_workspace(1), Some(TEST_WORKSPACE_ID));
+            assert_eq!(
+                world
+                    .resource::<WindowManager>()
+                    .active_display_space(TEST_DISPLAY_ID)
+                    .ok(),
+                Some(OTHER_WORKSPACE_ID)
+            );
+            assert_not_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_to_display_by_direction_and_index() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::ToDisplay(
+                DisplayTarget::Direction(Direction::West),
+                MoveFocus::Stay,
+            )),
+        },
+        Event::Command {
+            command: Command::Window(Operation::ToDisplay(
+                DisplayTarget::Direction(Direction::East),
+                MoveFocus::Stay,
+            )),
+        },
+        Event::Command {
+            command: Command::Window(Operation::ToDisplay(
+                DisplayTarget::Index(2),
+                MoveFocus::Stay,
+            )),
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(2)
+        .with_focused_window(0)
+        .with_display(
+            EXT_DISPLAY_ID,
+            IRect::new(
+                TEST_DISPLAY_WIDTH,
+                0,
+                TEST_DISPLAY_WIDTH + EXT_DISPLAY_WIDTH,
+                EXT_DISPLAY_HEIGHT,
+            ),
+            vec![EXT_WORKSPACE_ID],
+        )
+        .on_iteration(1, |world, _state| {
+            // Nothing lies to the west, so the window stays put.
+            assert_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_on_workspace!(world, 0, EXT_WORKSPACE_ID);
+            assert_not_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+        })
+        .on_iteration(3, |world, _state| {
+            // Focus stayed on the source display with window 1, and the second display from
+            // the left is the external one.
+            assert_on_workspace!(world, 1, EXT_WORKSPACE_ID);
+            assert_not_on_workspace!(world, 1, TEST_WORKSPACE_ID);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_display_rule_places_new_window_on_pinned_display() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    let mut params = WindowParams::new("Window 1", None);
+    params.display = Some(DisplaySelector::Index(2));
+    let config: Config = (MainOptions::default(), vec![params]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .with_display(
+            EXT_DISPLAY_ID,
+            IRect::new(
+                TEST_DISPLAY_WIDTH,
+                0,
+                TEST_DISPLAY_WIDTH + EXT_DISPLAY_WIDTH,
+                EXT_DISPLAY_HEIGHT,
+            ),
+            vec![EXT_WORKSPACE_ID],
+        )
+        .on_iteration(0, |world, state| {
+            let frame = IRect::new(0, 0, TEST_WINDOW_WIDTH, TEST_WINDOW_HEIGHT);
+            let window = state.spawn_window(TEST_PROCESS_ID, TEST_WORKSPACE_ID, 1, frame);
+            world.trigger(crate::ecs::SpawnWindowTrigger(vec![window]));
+        })
+        .on_iteration(2, |world, _state| {
+            assert_on_workspace!(world, 1, EXT_WORKSPACE_ID);
+            assert_not_on_workspace!(world, 1, TEST_WORKSPACE_ID);
+            assert_on_workspace!(world, 0, TEST_WORKSPACE_ID);
+        })
+        .run(commands);
+}
+
 #[test]
 fn test_send_next_display_stays_on_source() {
     let mut harness = TestHarness::new();
@@ -341,7 +668,7 @@ fn test_mouse_to_next_display() {
 /// inactive displays onto the active display. `apply_window_properties`
 /// initially appends every observed window to the active strip; if the
 /// layout writers run before `finish_setup` has reassigned them, they
-/// cache active-display coordinates into `Position` and `commit_window_position`
+/// cache active-display coordinates into `Position` and `commit_window_frames`
 /// later pushes those to macOS, moving the windows.
 #[test]
 fn test_init_keeps_windows_on_their_real_displays() {
@@ -452,6 +779,69 @@ fn test_wake_reconciles_unplugged_display() {
         .run(commands);
 }
 
+/// Changing the resolution of a display keeps its windows at the same share of the display
+/// width, rather than at whatever width macOS leaves them with.
+#[test]
+fn test_display_resize_rescales_window_widths() {
+    const NEW_DISPLAY_WIDTH: i32 = TEST_DISPLAY_WIDTH * 2;
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::DisplayResized {
+            display_id: TEST_DISPLAY_ID,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(1)
+        .on_iteration(0, |_world, mut state| {
+            state.add_display(
+                TEST_DISPLAY_ID,
+                IRect::new(0, 0, NEW_DISPLAY_WIDTH, TEST_DISPLAY_HEIGHT),
+                vec![TEST_WORKSPACE_ID],
+            );
+        })
+        .on_iteration(1, |world, _state| {
+            let rescale = world
+                .query_filtered::<Has<RescaleWidthsMarker>, With<ActiveWorkspaceMarker>>()
+                .iter(world)
+                .any(|has| has);
+            assert!(rescale, "resized display should rescale its strips");
+
+            // Skip the settle delay of the refresh.
+            let strip = world
+                .query_filtered::<Entity, With<ActiveWorkspaceMarker>>()
+                .single(world)
+                .expect("should have an active workspace");
+            world
+                .entity_mut(strip)
+                .insert(RefreshWindowSizes(Instant::now() - Duration::from_secs(10)));
+            let entity = find_window_entity(0, world);
+            world.entity_mut(entity).insert(WidthRatio(0.5));
+        })
+        .on_iteration(2, |world, _state| {
+            let entity = find_window_entity(0, world);
+            let bounds = world.get::<Bounds>(entity).expect("window bounds").0;
+            assert_eq!(
+                bounds.x,
+                NEW_DISPLAY_WIDTH / 2,
+                "window should keep its share of the display width"
+            );
+            assert!(
+                world
+                    .query::<&RescaleWidthsMarker>()
+                    .iter(world)
+                    .next()
+                    .is_none(),
+                "the rescale marker should be cleared by the refresh"
+            );
+        })
+        .run(commands);
+}
+
 /// Even when the display set is unchanged, waking from sleep must force the
 /// active workspace to re-tile, because macOS relocates window frames across a
 /// sleep/wake cycle.
@@ -477,3 +867,34 @@ fn test_wake_refreshes_active_workspace() {
         })
         .run(commands);
 }
+
+/// `config set` must apply the same side effects as reloading the file, such as the menubar
+/// height override of the displays.
+#[test]
+fn test_config_set_applies_menubar_height() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::SetOption("menubar_height".to_string(), "40".to_string()),
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(1)
+        .on_iteration(2, |world, _state| {
+            let heights = world
+                .query::<&Display>()
+                .iter(world)
+                .map(Display::menubar_height)
+                .collect::<Vec<_>>();
+            assert!(!heights.is_empty());
+            assert!(
+                heights.iter().all(|&height| height == 40),
+                "config set should override the menubar height, got {heights:?}"
+            );
+        })
+        .run(commands);
+}
@@ -11,6 +11,7 @@ use crate::commands::register_commands;
 use crate::config::Config;
 use crate::ecs::display::DisplayEventsPlugin;
 use crate::ecs::focus::FocusEventsPlugin;
+use crate::ecs::journal::JournalPlugin;
 use crate::ecs::layout::LayoutEventsPlugin;
 use crate::ecs::mouse::MouseEventsPlugin;
 use crate::ecs::scroll::ScrollEventsPlugin;
@@ -233,6 +234,7 @@ fn setup_world() -> App {
         .add_plugins(LayoutEventsPlugin)
         .add_plugins(FocusEventsPlugin)
         .add_plugins(DisplayEventsPlugin)
+        .add_plugins(JournalPlugin)
         .add_plugins((register_triggers, register_systems, register_commands));
 
     bevy_app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
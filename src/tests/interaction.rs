@@ -1,20 +1,27 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
 
+use bevy::ecs::lifecycle::Insert;
 use bevy::prelude::*;
 use objc2_core_foundation::CGPoint;
 
 use crate::commands::{Command, Direction, MoveFocus, Operation};
-use crate::config::{Config, MainOptions, WindowParams};
+use crate::config::{Config, ExternalMovePolicy, MainOptions, WindowParams};
 use crate::ecs::display::FloatingLayer;
 use crate::ecs::{
-    ActiveWorkspaceMarker, FocusedMarker, NativeFullscreenMarker, Position, Unmanaged,
-    layout::LayoutStrip,
+    ActiveWorkspaceMarker, Bounds, DialogMarker, FocusedMarker, NativeFullscreenMarker, PipMarker,
+    Position, StickyMarker, Unmanaged, layout::LayoutStrip,
+};
+use crate::ecs::{
+    MinimumHeight, NewWindowMarker, RepositionMarker, ResizeRetries, Scrolling, SpawnWindowTrigger,
 };
-use crate::ecs::{RepositionMarker, Scrolling, SpawnWindowTrigger};
 use crate::events::Event;
 use crate::manager::{Origin, Size, Window};
-use crate::platform::Modifiers;
-use crate::{assert_focused, assert_window_at, assert_window_size};
+use crate::platform::{Modifiers, WinID};
+use crate::{
+    assert_focused, assert_not_on_workspace, assert_on_workspace, assert_window_at,
+    assert_window_size,
+};
 
 use super::*;
 
@@ -115,6 +122,54 @@ fn native_fullscreen_transition_removes_window_from_original_strip_without_focus
         ]);
 }
 
+#[test]
+fn native_fullscreen_exit_restores_middle_column() {
+    const FULLSCREEN_WORKSPACE_ID: WorkspaceId = TEST_WORKSPACE_ID + 100;
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(0, |_world, state| {
+            state.update_window(1, |window| {
+                window.workspace_id = FULLSCREEN_WORKSPACE_ID;
+                window.is_full_screen = true;
+            });
+            state.activate_workspace(TEST_DISPLAY_ID, FULLSCREEN_WORKSPACE_ID, true);
+        })
+        .on_iteration(1, |world, _state| {
+            let fullscreen_window = find_window_entity(1, world);
+            let mut strips = world.query::<(&LayoutStrip, Option<&NativeFullscreenMarker>)>();
+            let (original_strip, _) = strips
+                .iter(world)
+                .find(|(strip, marker)| strip.id() == TEST_WORKSPACE_ID && marker.is_none())
+                .expect("original strip");
+            assert!(!original_strip.contains(fullscreen_window));
+            assert_eq!(original_strip.len(), 2);
+
+            // The window entity survives while it lives on its own space.
+            assert!(world.get_entity(fullscreen_window).is_ok());
+        })
+        .on_iteration(2, |world, _state| {
+            let windows = [0, 1, 2].map(|id| find_window_entity(id, world));
+            let mut strips = world.query::<&LayoutStrip>();
+            let original_strip = strips
+                .iter(world)
+                .find(|strip| strip.id() == TEST_WORKSPACE_ID)
+                .expect("original strip");
+            for (index, entity) in windows.into_iter().enumerate() {
+                assert_eq!(original_strip.index_of(entity).ok(), Some(index));
+            }
+        })
+        .run(vec![
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::SpaceChanged,
+            Event::SpaceDestroyed {
+                space_id: FULLSCREEN_WORKSPACE_ID,
+            },
+        ]);
+}
+
 #[test]
 fn frontmost_floating_window_is_focused_after_setup() {
     let mut params = WindowParams::new(".*", None);
@@ -133,6 +188,156 @@ fn frontmost_floating_window_is_focused_after_setup() {
         .run(vec![Event::MenuOpened { window_id: 0 }]);
 }
 
+#[test]
+fn sticky_window_follows_active_space() {
+    const OTHER_WORKSPACE_ID: WorkspaceId = TEST_WORKSPACE_ID + 1;
+
+    let mut params = WindowParams::new(".*", None);
+    params.sticky = Some(true);
+    let config: Config = (MainOptions::default(), vec![params]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .with_focused_window(0)
+        .on_iteration(0, |world, state| {
+            let entity = find_window_entity(0, world);
+            assert!(world.entity(entity).contains::<StickyMarker>());
+            assert!(world.entity(entity).contains::<Unmanaged>());
+            state.activate_workspace(TEST_DISPLAY_ID, OTHER_WORKSPACE_ID, false);
+        })
+        .on_iteration(1, |_world, state| {
+            assert_eq!(state.window_workspace(0), Some(OTHER_WORKSPACE_ID));
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::SpaceChanged,
+        ]);
+}
+
+#[test]
+fn managing_sticky_window_unsticks_it() {
+    let mut params = WindowParams::new(".*", None);
+    params.sticky = Some(true);
+    let config: Config = (MainOptions::default(), vec![params]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .with_focused_window(0)
+        .on_iteration(0, |world, _state| {
+            let entity = find_window_entity(0, world);
+            assert!(world.entity(entity).contains::<StickyMarker>());
+        })
+        .on_iteration(1, |world, _state| {
+            let entity = find_window_entity(0, world);
+            assert!(!world.entity(entity).contains::<StickyMarker>());
+            assert!(!world.entity(entity).contains::<Unmanaged>());
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::Command {
+                command: Command::Window(Operation::Manage),
+            },
+        ]);
+}
+
+#[test]
+fn pip_window_is_thrown_between_display_corners() {
+    const MARGIN: i32 = 10;
+    const PIP_WIDTH: i32 = 200;
+    const PIP_HEIGHT: i32 = 150;
+
+    let options = MainOptions {
+        animation_speed: Some(10000.0),
+        pip_margin: Some(MARGIN),
+        ..MainOptions::default()
+    };
+    let mut params = WindowParams::new(".*", None);
+    params.pip = Some(true);
+    let config: Config = (options, vec![params]).into();
+
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+
+    TestHarness::new()
+        .with_config(config)
+        .with_window(0, |window| {
+            window.frame = IRect::new(0, 0, PIP_WIDTH, PIP_HEIGHT);
+        })
+        .with_focused_window(0)
+        .on_iteration(0, |world, _state| {
+            let entity = find_window_entity(0, world);
+            assert!(world.entity(entity).contains::<PipMarker>());
+            assert!(world.entity(entity).contains::<Unmanaged>());
+        })
+        .on_iteration(2, |world, _state| {
+            assert_window_at!(
+                world,
+                0,
+                TEST_DISPLAY_WIDTH - MARGIN - PIP_WIDTH,
+                TEST_MENUBAR_HEIGHT + MARGIN
+            );
+        })
+        .on_iteration(4, |world, _state| {
+            assert_window_at!(
+                world,
+                0,
+                TEST_DISPLAY_WIDTH - MARGIN - PIP_WIDTH,
+                TEST_DISPLAY_HEIGHT - MARGIN - PIP_HEIGHT
+            );
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            command(Operation::Swap(Direction::East)),
+            Event::Command {
+                command: Command::PrintState,
+            },
+            command(Operation::Swap(Direction::South)),
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
+#[test]
+fn dialog_like_windows_float_without_rules() {
+    // A rule setting `floating` wins over the heuristics.
+    let mut params = WindowParams::new("^Window 4$", None);
+    params.floating = Some(false);
+    let config: Config = (MainOptions::default(), vec![params]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_window(0, |_| {})
+        .with_window(1, |window| window.resizable = false)
+        .with_window(2, |window| window.subrole = "AXDialog".to_string())
+        .with_window(3, |window| window.frame = IRect::new(0, 0, 200, 100))
+        .with_window(4, |window| window.close_button = false)
+        .with_focused_window(0)
+        .on_iteration(0, |world, _state| {
+            for (id, floating) in [(0, false), (1, true), (2, true), (3, true), (4, false)] {
+                let entity = find_window_entity(id, world);
+                assert_eq!(
+                    world.entity(entity).contains::<Unmanaged>(),
+                    floating,
+                    "window {id}"
+                );
+            }
+            // The heuristics ran once when the windows were spawned, whatever the rules say.
+            for (id, dialog) in [(0, false), (2, true), (4, true)] {
+                let entity = find_window_entity(id, world);
+                assert_eq!(
+                    world.entity(entity).contains::<DialogMarker>(),
+                    dialog,
+                    "window {id}"
+                );
+            }
+        })
+        .run(vec![Event::MenuOpened { window_id: 0 }]);
+}
+
 /// Regression: a floating window placed by a grid rule must land at the active
 /// display's usable origin (menubar + padding offset), not at (0, 0). Dropping
 /// the display bounds origin previously sent grid windows to the primary
@@ -180,6 +385,431 @@ fn floating_grid_window_uses_active_display_usable_origin() {
         ]);
 }
 
+#[test]
+fn grid_command_places_floating_window_into_cell() {
+    let mut params = WindowParams::new(".*", None);
+    params.floating = Some(true);
+    let options = MainOptions {
+        animation_speed: Some(10000.0),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![params]).into();
+    let usable_height = TEST_DISPLAY_HEIGHT - TEST_MENUBAR_HEIGHT;
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .with_focused_window(0)
+        .on_iteration(2, move |world, _state| {
+            assert_window_at!(
+                world,
+                0,
+                TEST_DISPLAY_WIDTH / 2,
+                TEST_MENUBAR_HEIGHT + usable_height / 2
+            );
+            assert_window_size!(world, 0, TEST_DISPLAY_WIDTH / 2, usable_height / 2);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::Command {
+                command: Command::Window(Operation::Grid {
+                    x: 0.5,
+                    y: 0.5,
+                    width: 0.5,
+                    height: 0.5,
+                }),
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
+#[test]
+fn restore_original_moves_window_back_to_its_first_frame() {
+    let mut params = WindowParams::new(".*", None);
+    params.floating = Some(true);
+    let options = MainOptions {
+        animation_speed: Some(10000.0),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![params]).into();
+    let original = IRect::new(100, 80, 100 + TEST_WINDOW_WIDTH, 80 + 300);
+
+    TestHarness::new()
+        .with_config(config)
+        .with_window(0, move |window| window.frame = original)
+        .with_focused_window(0)
+        .on_iteration(1, |world, _state| {
+            assert_window_at!(world, 0, 0, TEST_MENUBAR_HEIGHT);
+        })
+        .on_iteration(2, move |world, _state| {
+            assert_window_at!(world, 0, original.min.x, original.min.y);
+            assert_window_size!(world, 0, original.width(), original.height());
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::Command {
+                command: Command::Window(Operation::Grid {
+                    x: 0.0,
+                    y: 0.0,
+                    width: 0.5,
+                    height: 0.5,
+                }),
+            },
+            Event::Command {
+                command: Command::Window(Operation::RestoreOriginal),
+            },
+        ]);
+}
+
+/// Floating windows of the same app opening at the same spot are cascaded by
+/// `floating_cascade_step` instead of stacking exactly on top of each other.
+#[test]
+fn floating_windows_of_same_app_cascade() {
+    fn spawn_at_same_spot(world: &mut World, state: &MockState, window_id: WinID) {
+        let origin = Origin::new(100, 100);
+        let size = Size::new(TEST_WINDOW_WIDTH / 2, TEST_WINDOW_HEIGHT / 2);
+        let frame = IRect::from_corners(origin, origin + size);
+        let window = state.spawn_window(TEST_PROCESS_ID, TEST_WORKSPACE_ID, window_id, frame);
+        world.trigger(SpawnWindowTrigger(vec![window]));
+    }
+
+    let options = MainOptions {
+        floating_cascade_step: Some(40),
+        ..MainOptions::default()
+    };
+    let mut params = WindowParams::new(".*", None);
+    params.floating = Some(true);
+    let config: Config = (options, vec![params]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .on_iteration(1, |world, state| spawn_at_same_spot(world, &state, 0))
+        .on_iteration(2, |world, state| spawn_at_same_spot(world, &state, 1))
+        .on_iteration(3, |world, state| spawn_at_same_spot(world, &state, 2))
+        .on_iteration(5, |world, _state| {
+            assert_window_at!(world, 0, 100, 100);
+            assert_window_at!(world, 1, 140, 140);
+            assert_window_at!(world, 2, 180, 180);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
+#[test]
+fn test_modifier_drag_resizes_column_edge() {
+    let options = MainOptions {
+        mouse_resize_modifier: Some(Modifiers::ALT),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![]).into();
+    let point = |x: i32| CGPoint::new(x.into(), 100.0);
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(2)
+        .on_iteration(2, |world, _state| {
+            // The pointer grabbed the right edge, so the right edge followed it.
+            assert_window_size!(world, 0, TEST_WINDOW_WIDTH + 100, 748);
+        })
+        .on_iteration(3, |world, _state| {
+            assert_window_size!(world, 0, TEST_WINDOW_WIDTH + 100, 748);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::MouseResizeGrab {
+                point: point(TEST_WINDOW_WIDTH - 2),
+            },
+            Event::MouseDragged {
+                point: point(TEST_WINDOW_WIDTH + 98),
+                modifiers: Modifiers::LALT,
+            },
+            Event::MouseUp {
+                point: point(TEST_WINDOW_WIDTH + 98),
+                modifiers: Modifiers::LALT,
+            },
+        ]);
+}
+
+#[test]
+fn test_modifier_click_without_edge_grab_does_not_resize() {
+    let options = MainOptions {
+        mouse_resize_modifier: Some(Modifiers::ALT),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![]).into();
+    let point = |x: i32| CGPoint::new(x.into(), 100.0);
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(2)
+        .on_iteration(3, |world, _state| {
+            assert_window_size!(world, 0, TEST_WINDOW_WIDTH, 748);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::MouseDown {
+                point: point(TEST_WINDOW_WIDTH / 2),
+                modifiers: Modifiers::LALT,
+            },
+            Event::MouseDragged {
+                point: point(TEST_WINDOW_WIDTH / 2 + 100),
+                modifiers: Modifiers::LALT,
+            },
+            Event::MouseUp {
+                point: point(TEST_WINDOW_WIDTH / 2 + 100),
+                modifiers: Modifiers::LALT,
+            },
+        ]);
+}
+
+#[test]
+fn test_externally_moved_window_reslots_to_nearest_column() {
+    let options = MainOptions {
+        external_move_policy: Some(ExternalMovePolicy::Reslot),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .on_iteration(1, |world, state| {
+            assert_eq!(active_strip_first_id(world), 0);
+            // Another tool drags the first window past the last column.
+            state.os_move_window(0, Origin::new(900, 20));
+        })
+        .on_iteration(4, |world, _state| {
+            assert_eq!(active_strip_first_id(world), 1);
+            assert_eq!(active_strip_last_id(world), 0);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::MenuOpened { window_id: 0 },
+            Event::MenuOpened { window_id: 0 },
+            Event::MenuOpened { window_id: 0 },
+            Event::MenuOpened { window_id: 0 },
+        ]);
+}
+
+#[test]
+fn test_dragged_window_takes_neighbouring_column_slot() {
+    let point = CGPoint::new(100.0, 100.0);
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(1, |world, state| {
+            assert_eq!(active_strip_first_id(world), 0);
+            // The user drags the first window past the middle of the second column.
+            state.os_move_window(0, Origin::new(450, 20));
+        })
+        .on_iteration(3, |world, _state| {
+            assert_eq!(active_strip_first_id(world), 1);
+            assert_eq!(active_strip_last_id(world), 2);
+            // A plain drag moves the window and never grabs a column edge.
+            assert_window_size!(world, 0, TEST_WINDOW_WIDTH, 748);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::MouseDown {
+                point,
+                modifiers: Modifiers::empty(),
+            },
+            Event::MouseUp {
+                point,
+                modifiers: Modifiers::empty(),
+            },
+            Event::MenuOpened { window_id: 0 },
+        ]);
+}
+
+#[test]
+fn test_hot_corner_runs_bound_command() {
+    let config = Config::try_from(
+        r#"
+[options]
+focus_follows_mouse = false
+
+[bindings]
+
+[hot_corners]
+top_right = { command = "window focus east", dwell = 0 }
+"#,
+    )
+    .expect("config should parse");
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .on_iteration(1, |world, _state| {
+            assert_focused!(world, 1);
+        })
+        .on_iteration(2, |world, _state| {
+            // Moving within the corner does not fire again.
+            assert_focused!(world, 1);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::MouseMoved {
+                point: CGPoint::new(1023.0, 0.0),
+                modifiers: Modifiers::empty(),
+            },
+            Event::MouseMoved {
+                point: CGPoint::new(1022.0, 1.0),
+                modifiers: Modifiers::empty(),
+            },
+        ]);
+}
+
+#[test]
+fn test_sliver_peek_slides_column_into_view_without_focus() {
+    let options = MainOptions {
+        focus_follows_mouse: Some(false),
+        sliver_peek_delay_ms: Some(0),
+        animation_speed: Some(10000.0),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![]).into();
+    let moved = |x: f64| Event::MouseMoved {
+        point: CGPoint::new(x, f64::from(TEST_DISPLAY_HEIGHT / 2)),
+        modifiers: Modifiers::empty(),
+    };
+    let frame_of = |world: &mut World, id: WinID| {
+        let entity = find_window_entity(id, world);
+        world.get::<Window>(entity).expect("need window").frame()
+    };
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(4)
+        .with_focused_window(0)
+        .on_iteration(2, move |world, _state| {
+            assert!(frame_of(world, 3).max.x <= TEST_DISPLAY_WIDTH);
+            assert_focused!(world, 0);
+        })
+        .on_iteration(3, move |world, _state| {
+            // Back to a sliver parked at the right edge.
+            assert!(frame_of(world, 3).min.x > TEST_DISPLAY_WIDTH - 10);
+            assert_focused!(world, 0);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            moved(f64::from(TEST_DISPLAY_WIDTH - 2)),
+            moved(f64::from(TEST_DISPLAY_WIDTH - 3)),
+            moved(100.0),
+        ]);
+}
+
+#[test]
+fn test_sliver_peek_waits_for_delay() {
+    const DELAY_MS: u64 = 100;
+
+    let options = MainOptions {
+        focus_follows_mouse: Some(false),
+        sliver_peek_delay_ms: Some(DELAY_MS),
+        animation_speed: Some(10000.0),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![]).into();
+    let frame_of = |world: &mut World, id: WinID| {
+        let entity = find_window_entity(id, world);
+        world.get::<Window>(entity).expect("need window").frame()
+    };
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(4)
+        .with_focused_window(0)
+        .on_iteration(1, move |world, _state| {
+            // Still a sliver parked at the right edge while the pointer dwells on it.
+            assert!(frame_of(world, 3).min.x > TEST_DISPLAY_WIDTH - 10);
+            assert_focused!(world, 0);
+            std::thread::sleep(std::time::Duration::from_millis(DELAY_MS + 50));
+        })
+        .on_iteration(2, move |world, _state| {
+            assert!(frame_of(world, 3).max.x <= TEST_DISPLAY_WIDTH);
+            assert_focused!(world, 0);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::MouseMoved {
+                point: CGPoint::new(
+                    f64::from(TEST_DISPLAY_WIDTH - 2),
+                    f64::from(TEST_DISPLAY_HEIGHT / 2),
+                ),
+                modifiers: Modifiers::empty(),
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
+#[test]
+fn test_mouse_follows_new_window_without_mouse_follows_focus() {
+    let options = MainOptions {
+        mouse_follows_focus: Some(false),
+        mouse_follows_new_window: Some(true),
+        ..MainOptions::default()
+    };
+    let config: Config = (options, vec![]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(2)
+        .on_iteration(1, |world, state| {
+            // Focus changes between existing windows do not move the cursor.
+            assert_eq!(state.cursor_position(), Origin::new(0, 0));
+
+            let origin = Origin::new(0, 0);
+            let size = Size::new(TEST_WINDOW_WIDTH, TEST_WINDOW_HEIGHT);
+            let frame = IRect::from_corners(origin, origin + size);
+            let window = state.spawn_window(TEST_PROCESS_ID, TEST_WORKSPACE_ID, 2, frame);
+            world.trigger(SpawnWindowTrigger(vec![window]));
+        })
+        .on_iteration(4, |world, state| {
+            assert_focused!(world, 2);
+            let entity = find_window_entity(2, world);
+            let window = world.get::<Window>(entity).expect("need window");
+            assert!(window.frame().contains(state.cursor_position()));
+            // The first focus spent the marker, so later focus changes do not warp.
+            let mut query = world.query_filtered::<Entity, With<NewWindowMarker>>();
+            assert_eq!(query.iter(world).count(), 0);
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 0 },
+            Event::Command {
+                command: Command::Window(Operation::Focus(Direction::East)),
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
 #[test]
 fn test_dont_focus() {
     let commands = vec![
@@ -543,25 +1173,56 @@ fn test_rapid_focus_not_swallowed() {
 }
 
 #[test]
-fn test_stale_focus_event_ignored() {
+fn test_stale_focus_event_ignored() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::East)),
+        },
+        Event::WindowFocused { window_id: 4 },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(5)
+        .on_iteration(1, |world, _state| {
+            assert_focused!(world, 1);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_focused!(world, 1);
+        })
+        .run(commands);
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn test_unfocused_windows_are_dimmed_to_unfocused_opacity() {
     let commands = vec![
         Event::MenuOpened { window_id: 0 },
         Event::Command {
             command: Command::Window(Operation::Focus(Direction::East)),
         },
-        Event::WindowFocused { window_id: 4 },
-        Event::Command {
-            command: Command::PrintState,
-        },
     ];
 
+    let config: Config = (
+        MainOptions {
+            unfocused_opacity: Some(0.5),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
     TestHarness::new()
-        .with_windows(5)
-        .on_iteration(1, |world, _state| {
-            assert_focused!(world, 1);
-        })
-        .on_iteration(2, |world, _state| {
+        .with_config(config)
+        .with_windows(3)
+        .on_iteration(1, |world, state| {
             assert_focused!(world, 1);
+            assert_eq!(state.window_opacity(0), 0.5);
+            assert_eq!(state.window_opacity(1), 1.0);
+            assert_eq!(state.window_opacity(2), 0.5);
         })
         .run(commands);
 }
@@ -596,7 +1257,7 @@ fn test_repeated_external_focus_reshuffles_already_focused_window() {
                 .expect("active strip");
             world.commands().entity(entity).insert((
                 Position(Origin::new(0, 0)),
-                RepositionMarker(Origin::new(-TEST_DISPLAY_WIDTH, 0)),
+                RepositionMarker::new(Origin::new(-TEST_DISPLAY_WIDTH, 0)),
             ));
         })
         .on_iteration(2, |_world, state| {
@@ -816,181 +1477,433 @@ fn test_external_focus_restores_app_hidden_window_to_original_virtual_strip() {
 }
 
 #[test]
-fn test_external_focus_restores_hidden_window_without_visible_event() {
-    let ignored_repositions = Arc::new(std::sync::atomic::AtomicUsize::new(0));
-
+fn test_external_focus_restores_hidden_window_without_visible_event() {
+    let ignored_repositions = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+
+    let commands = vec![
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::ApplicationHidden {
+            pid: TEST_PROCESS_ID,
+        },
+        Event::Command {
+            command: Command::Window(Operation::VirtualNumber(1)),
+        },
+        Event::WindowFocused { window_id: 0 },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(1)
+        .on_iteration(1, move |world, _state| {
+            let mut query = world.query::<&mut Window>();
+            let mut window = query
+                .iter_mut(world)
+                .find(|window| window.id() == 0)
+                .expect("window 0");
+            window.reposition(Origin::new(0, TEST_DISPLAY_HEIGHT));
+            ignored_repositions.store(1, std::sync::atomic::Ordering::SeqCst);
+        })
+        .on_iteration(4, |world, _state| {
+            let mut query = world.query::<(&LayoutStrip, Has<ActiveWorkspaceMarker>)>();
+            let active = query
+                .iter(world)
+                .find_map(|(strip, active)| active.then_some(strip.virtual_index))
+                .expect("an active virtual strip");
+            assert_eq!(active, 0);
+            assert_window_at!(world, 0, 0, TEST_MENUBAR_HEIGHT);
+            assert_focused!(world, 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn mouse_in_bottom_right_corner_does_not_change_focus() {
+    // Focus window 2 explicitly, then move cursor into the bottom-right 30x30
+    // dead zone. The corner gate should suppress the focus-follow-mouse event,
+    // so focus stays on window 2.
+    //
+    // Test display is 1024x768 with no Dock, so the dead zone is
+    // x >= 994, y >= 738. Cursor at (1010, 750) is inside it. The mock's
+    // find_window_at_point always returns window 0, so without the gate the
+    // FFM event would shift focus to window 0; with the gate it should not.
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::West)),
+        },
+        Event::MouseMoved {
+            point: CGPoint {
+                x: 1010.0,
+                y: 750.0,
+            },
+            modifiers: Modifiers::empty(),
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(2, |world, _state| {
+            // After MouseMoved into corner dead zone: focus should remain on window 2
+            // because the corner gate suppressed the focus-follow-mouse event.
+            assert_focused!(world, 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn mouse_outside_corner_still_changes_focus() {
+    use crate::events::Event;
+    use crate::platform::Modifiers;
+    use objc2_core_foundation::CGPoint;
+
+    // Cursor at (500, 400), middle of the display, outside the dead zone.
+    // FFM should fire normally and switch focus.
+    //
+    // Focus window 2 first, then move cursor away from the corner. The mock's
+    // find_window_at_point always returns window 0, so FFM lands focus on
+    // window 0.
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::West)),
+        },
+        Event::MouseMoved {
+            point: CGPoint { x: 500.0, y: 400.0 },
+            modifiers: Modifiers::empty(),
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(2, |world, _state| {
+            // After MouseMoved outside corner: FFM should have fired and changed focus.
+            // In the mock, find_window_at_point always returns window 0, so window 0
+            // should now be focused (changed from window 2).
+            assert_focused!(world, 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn toggle_floating_layer_flips_state() {
+    fn current_layer(world: &mut World) -> FloatingLayer {
+        let mut query = world.query::<&FloatingLayer>();
+        *query
+            .query(world)
+            .iter()
+            .find(|layer| layer.workspace_id == TEST_WORKSPACE_ID)
+            .expect("active workspace has FloatingLayer")
+    }
+
+    let commands = vec![
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::Window(Operation::ToggleFloatingLayer),
+        },
+        Event::Command {
+            command: Command::Window(Operation::ToggleFloatingLayer),
+        },
+    ];
+
+    TestHarness::new()
+        .with_config(Config::default())
+        .with_windows(3)
+        .on_iteration(0, |world, _state| {
+            assert!(!current_layer(world).front);
+        })
+        .on_iteration(1, |world, _state| {
+            assert!(current_layer(world).front);
+        })
+        .on_iteration(2, |world, _state| {
+            assert!(!current_layer(world).front);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_unfloat_after_virtual_switch_uses_active_workspace() {
+    let commands = vec![
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::Window(Operation::Manage),
+        },
+        Event::Command {
+            command: Command::Window(Operation::VirtualNumber(1)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Manage),
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(2)
+        .on_iteration(3, |world, _state| {
+            let entity = find_window_entity(0, world);
+            let mut query = world.query_filtered::<&LayoutStrip, With<ActiveWorkspaceMarker>>();
+            let strip = query.single(world).expect("an active virtual workspace");
+
+            assert_eq!(strip.virtual_index, 1);
+            assert!(strip.contains(entity));
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_unfloat_restores_tiled_slot_and_width() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::Focus(Direction::First)),
+        command(Operation::Manage),
+        Event::Command {
+            command: Command::PrintState,
+        },
+        command(Operation::Manage),
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, |world, _state| {
+            // Drag and shrink the floating window over the last column.
+            let entity = find_window_entity(0, world);
+            assert!(world.entity(entity).contains::<Unmanaged>());
+            world
+                .entity_mut(entity)
+                .insert((Position(Origin::new(700, 100)), Bounds(Size::new(200, 300))));
+        })
+        .on_iteration(6, |world, _state| {
+            let entity = find_window_entity(0, world);
+            let mut query = world.query_filtered::<&LayoutStrip, With<ActiveWorkspaceMarker>>();
+            let strip = query.single(world).expect("an active workspace");
+            assert_eq!(strip.index_of(entity).ok(), Some(0));
+
+            let bounds = world.get::<Bounds>(entity).expect("window bounds");
+            assert_eq!(bounds.0.x, TEST_WINDOW_WIDTH);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_minimize_and_deminimize_focused_window() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::Focus(Direction::First)),
+        command(Operation::Minimize),
+        Event::Command {
+            command: Command::PrintState,
+        },
+        command(Operation::Deminimize),
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    let strip_index = |world: &mut World, entity| {
+        let mut query = world.query_filtered::<&LayoutStrip, With<ActiveWorkspaceMarker>>();
+        let strip = query.single(world).expect("an active workspace");
+        strip.index_of(entity).ok()
+    };
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, move |world, _state| {
+            let entity = find_window_entity(0, world);
+            assert!(matches!(
+                world.get::<Unmanaged>(entity),
+                Some(Unmanaged::Minimized)
+            ));
+            assert_eq!(strip_index(world, entity), None);
+        })
+        .on_iteration(5, move |world, _state| {
+            let entity = find_window_entity(0, world);
+            assert!(world.get::<Unmanaged>(entity).is_none());
+            assert_eq!(strip_index(world, entity), Some(0));
+            assert_focused!(world, 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_hide_app_toggles_application_windows() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
     let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::HideApp),
         Event::Command {
             command: Command::PrintState,
         },
-        Event::ApplicationHidden {
-            pid: TEST_PROCESS_ID,
-        },
-        Event::Command {
-            command: Command::Window(Operation::VirtualNumber(1)),
-        },
-        Event::WindowFocused { window_id: 0 },
+        command(Operation::HideApp),
         Event::Command {
             command: Command::PrintState,
         },
     ];
 
+    let hidden = |world: &mut World, id| {
+        let entity = find_window_entity(id, world);
+        matches!(world.get::<Unmanaged>(entity), Some(Unmanaged::Hidden))
+    };
+
     TestHarness::new()
-        .with_windows(1)
-        .on_iteration(1, move |world, _state| {
-            let mut query = world.query::<&mut Window>();
-            let mut window = query
-                .iter_mut(world)
-                .find(|window| window.id() == 0)
-                .expect("window 0");
-            window.reposition(Origin::new(0, TEST_DISPLAY_HEIGHT));
-            ignored_repositions.store(1, std::sync::atomic::Ordering::SeqCst);
+        .with_windows(2)
+        .on_iteration(2, move |world, _state| {
+            assert!(hidden(world, 0));
+            assert!(hidden(world, 1));
         })
-        .on_iteration(4, |world, _state| {
-            let mut query = world.query::<(&LayoutStrip, Has<ActiveWorkspaceMarker>)>();
-            let active = query
-                .iter(world)
-                .find_map(|(strip, active)| active.then_some(strip.virtual_index))
-                .expect("an active virtual strip");
-            assert_eq!(active, 0);
-            assert_window_at!(world, 0, 0, TEST_MENUBAR_HEIGHT);
-            assert_focused!(world, 0);
+        .on_iteration(4, move |world, _state| {
+            assert!(!hidden(world, 0));
+            assert!(!hidden(world, 1));
         })
         .run(commands);
 }
 
 #[test]
-fn mouse_in_bottom_right_corner_does_not_change_focus() {
-    // Focus window 2 explicitly, then move cursor into the bottom-right 30x30
-    // dead zone. The corner gate should suppress the focus-follow-mouse event,
-    // so focus stays on window 2.
-    //
-    // Test display is 1024x768 with no Dock, so the dead zone is
-    // x >= 994, y >= 738. Cursor at (1010, 750) is inside it. The mock's
-    // find_window_at_point always returns window 0, so without the gate the
-    // FFM event would shift focus to window 0; with the gate it should not.
+fn test_focus_app_by_bundle_id_and_name() {
+    const NOTES_PID: i32 = TEST_PROCESS_ID + 1;
+
+    let focus_app = |pattern| Event::Command {
+        command: Command::Window(Operation::FocusApp {
+            matcher: regex::Regex::new(pattern).unwrap(),
+            launch: false,
+        }),
+    };
     let commands = vec![
         Event::MenuOpened { window_id: 0 },
         Event::Command {
-            command: Command::Window(Operation::Focus(Direction::West)),
-        },
-        Event::MouseMoved {
-            point: CGPoint {
-                x: 1010.0,
-                y: 750.0,
-            },
-            modifiers: Modifiers::empty(),
+            command: Command::Window(Operation::Focus(Direction::First)),
         },
+        focus_app("com.example.notes"),
+        focus_app("^Test"),
     ];
 
     TestHarness::new()
-        .with_windows(3)
+        .with_windows(2)
+        .with_app(NOTES_PID, "com.example.notes", "Notes", |_| {})
+        .on_iteration(0, |world, state| {
+            let origin = Origin::new(0, 0);
+            let size = Size::new(TEST_WINDOW_WIDTH, TEST_WINDOW_HEIGHT);
+            let frame = IRect::from_corners(origin, origin + size);
+            let window = state.spawn_window(NOTES_PID, TEST_WORKSPACE_ID, 10, frame);
+            world.trigger(SpawnWindowTrigger(vec![window]));
+        })
+        .on_iteration(1, |world, _state| {
+            assert_focused!(world, 0);
+        })
         .on_iteration(2, |world, _state| {
-            // After MouseMoved into corner dead zone: focus should remain on window 2
-            // because the corner gate suppressed the focus-follow-mouse event.
+            assert_focused!(world, 10);
+        })
+        .on_iteration(3, |world, _state| {
             assert_focused!(world, 0);
         })
         .run(commands);
 }
 
 #[test]
-fn mouse_outside_corner_still_changes_focus() {
-    use crate::events::Event;
-    use crate::platform::Modifiers;
-    use objc2_core_foundation::CGPoint;
-
-    // Cursor at (500, 400), middle of the display, outside the dead zone.
-    // FFM should fire normally and switch focus.
-    //
-    // Focus window 2 first, then move cursor away from the corner. The mock's
-    // find_window_at_point always returns window 0, so FFM lands focus on
-    // window 0.
+fn test_focus_next_and_prev_wrap_around_strip() {
+    let focus = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
     let commands = vec![
         Event::MenuOpened { window_id: 0 },
-        Event::Command {
-            command: Command::Window(Operation::Focus(Direction::West)),
-        },
-        Event::MouseMoved {
-            point: CGPoint { x: 500.0, y: 400.0 },
-            modifiers: Modifiers::empty(),
-        },
+        focus(Operation::FocusPrev),
+        focus(Operation::FocusNext),
+        focus(Operation::FocusNext),
+        focus(Operation::FocusPrev),
     ];
 
     TestHarness::new()
         .with_windows(3)
+        .on_iteration(1, |world, _state| {
+            assert_focused!(world, 2);
+        })
         .on_iteration(2, |world, _state| {
-            // After MouseMoved outside corner: FFM should have fired and changed focus.
-            // In the mock, find_window_at_point always returns window 0, so window 0
-            // should now be focused (changed from window 2).
+            assert_focused!(world, 0);
+        })
+        .on_iteration(3, |world, _state| {
+            assert_focused!(world, 1);
+        })
+        .on_iteration(4, |world, _state| {
             assert_focused!(world, 0);
         })
         .run(commands);
 }
 
 #[test]
-fn toggle_floating_layer_flips_state() {
-    fn current_layer(world: &mut World) -> FloatingLayer {
-        let mut query = world.query::<&FloatingLayer>();
-        *query
-            .query(world)
-            .iter()
-            .find(|layer| layer.workspace_id == TEST_WORKSPACE_ID)
-            .expect("active workspace has FloatingLayer")
-    }
-
+fn test_cycle_recent_steps_through_history_and_commits_on_release() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
     let commands = vec![
-        Event::Command {
-            command: Command::PrintState,
-        },
-        Event::Command {
-            command: Command::Window(Operation::ToggleFloatingLayer),
-        },
-        Event::Command {
-            command: Command::Window(Operation::ToggleFloatingLayer),
-        },
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::FocusId(0)),
+        command(Operation::FocusId(1)),
+        command(Operation::FocusId(2)),
+        command(Operation::CycleRecent),
+        command(Operation::CycleRecent),
+        Event::CycleModifiersReleased,
+        command(Operation::CycleRecent),
     ];
 
     TestHarness::new()
-        .with_config(Config::default())
         .with_windows(3)
-        .on_iteration(0, |world, _state| {
-            assert!(!current_layer(world).front);
+        .on_iteration(4, |world, _state| {
+            assert_focused!(world, 1);
         })
-        .on_iteration(1, |world, _state| {
-            assert!(current_layer(world).front);
+        .on_iteration(5, |world, _state| {
+            assert_focused!(world, 0);
         })
-        .on_iteration(2, |world, _state| {
-            assert!(!current_layer(world).front);
+        .on_iteration(6, |world, _state| {
+            assert_focused!(world, 0);
+        })
+        // Window 1 was only previewed, so window 2 is still the next most recent one.
+        .on_iteration(7, |world, _state| {
+            assert_focused!(world, 2);
         })
         .run(commands);
 }
 
 #[test]
-fn test_unfloat_after_virtual_switch_uses_active_workspace() {
+fn test_focus_id_focuses_window_by_id() {
+    let focus_id = |window_id| Event::Command {
+        command: Command::Window(Operation::FocusId(window_id)),
+    };
     let commands = vec![
-        Event::Command {
-            command: Command::PrintState,
-        },
-        Event::Command {
-            command: Command::Window(Operation::Manage),
-        },
-        Event::Command {
-            command: Command::Window(Operation::VirtualNumber(1)),
-        },
-        Event::Command {
-            command: Command::Window(Operation::Manage),
-        },
+        Event::MenuOpened { window_id: 0 },
+        focus_id(2),
+        focus_id(99),
+        focus_id(0),
     ];
 
     TestHarness::new()
-        .with_windows(2)
+        .with_windows(3)
+        .on_iteration(1, |world, _state| {
+            assert_focused!(world, 2);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_focused!(world, 2);
+        })
         .on_iteration(3, |world, _state| {
-            let entity = find_window_entity(0, world);
-            let mut query = world.query_filtered::<&LayoutStrip, With<ActiveWorkspaceMarker>>();
-            let strip = query.single(world).expect("an active virtual workspace");
-
-            assert_eq!(strip.virtual_index, 1);
-            assert!(strip.contains(entity));
+            assert_focused!(world, 0);
         })
         .run(commands);
 }
@@ -1562,6 +2475,55 @@ fn test_reshuffle_leftmost_pins_strip_to_left_edge_with_stale_frame() {
     );
 }
 
+/// Reshuffle requests arriving right after a reshuffle of the same strip are held back until
+/// `reshuffle_debounce_ms` passes, then handled in one pass.
+#[test]
+fn test_reshuffle_requests_within_debounce_are_coalesced() {
+    use crate::ecs::ReshuffleAroundMarker;
+
+    let config: Config = (
+        MainOptions {
+            reshuffle_debounce_ms: Some(250),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+    let mut h = TestHarness::new().with_config(config).with_windows(3);
+    // Let the startup layout settle, so no earlier reshuffle is within the debounce.
+    for _ in 0..10 {
+        h.app.update();
+    }
+    let entity = find_window_entity(1, h.app.world_mut());
+    let pending = |h: &TestHarness| h.app.world().get::<ReshuffleAroundMarker>(entity).is_some();
+
+    h.app
+        .world_mut()
+        .entity_mut(entity)
+        .insert(ReshuffleAroundMarker);
+    h.app.update();
+    assert!(!pending(&h), "the first reshuffle runs right away");
+
+    // Frames advance by 100ms in the harness, so the next two frames are within the debounce.
+    h.app
+        .world_mut()
+        .entity_mut(entity)
+        .insert(ReshuffleAroundMarker);
+    h.app.update();
+    assert!(
+        pending(&h),
+        "a reshuffle right after the last one is deferred"
+    );
+    h.app.update();
+    assert!(pending(&h));
+
+    h.app.update();
+    assert!(
+        !pending(&h),
+        "the deferred reshuffle runs once the debounce passed"
+    );
+}
+
 /// With `virtual_workspace_animations = true`, switching away from a scrolled
 /// strip and back must restore its saved scroll position, not reset it.
 ///
@@ -1803,3 +2765,124 @@ fn test_stack_unstack_brings_focused_window_into_view() {
         "unstacking must bring the focused window fully back into view"
     );
 }
+
+#[test]
+fn test_unresponsive_window_is_unmanaged_and_recovers() {
+    let is_unresponsive = |world: &mut World| {
+        let entity = find_window_entity(1, world);
+        matches!(
+            world.get::<Unmanaged>(entity),
+            Some(Unmanaged::Unresponsive)
+        )
+    };
+
+    let mut commands = vec![
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::East)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::West)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::East)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::West)),
+        },
+    ];
+    // Idle long enough for the unresponsive probe to run.
+    commands.extend((0..6).map(|_| Event::Command {
+        command: Command::PrintState,
+    }));
+
+    TestHarness::new()
+        .with_windows(1)
+        .with_window(1, |window| window.frozen = true)
+        .with_focused_window(0)
+        .on_iteration(4, move |world, mock_state| {
+            assert!(is_unresponsive(world), "window 1 should be unresponsive");
+            assert_not_on_workspace!(world, 1, TEST_WORKSPACE_ID);
+            mock_state.update_window(1, |window| window.frozen = false);
+        })
+        .on_iteration(9, move |world, _| {
+            let entity = find_window_entity(1, world);
+            assert!(world.get::<Unmanaged>(entity).is_none());
+            assert_on_workspace!(world, 1, TEST_WORKSPACE_ID);
+        })
+        .run(commands);
+}
+
+#[test]
+fn refused_shrink_is_learned_as_minimum_height() {
+    const MIN_HEIGHT: i32 = 500;
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 1 },
+        Event::Command {
+            command: Command::Window(Operation::Stack(true)),
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(1)
+        .with_window(1, |window| window.min_height = MIN_HEIGHT)
+        .with_focused_window(1)
+        .on_iteration(0, |world, _| {
+            let entity = find_window_entity(1, world);
+            assert!(world.get::<MinimumHeight>(entity).is_none());
+        })
+        .on_iteration(3, |world, _| {
+            // Stacking asked for half the display, which the window refused.
+            let entity = find_window_entity(1, world);
+            assert_eq!(
+                world.get::<MinimumHeight>(entity).map(|min| min.0),
+                Some(MIN_HEIGHT)
+            );
+            assert!(world.get::<Bounds>(entity).unwrap().0.y >= MIN_HEIGHT);
+        })
+        .run(commands);
+}
+
+#[test]
+fn ignored_resize_is_retried_then_adopts_actual_size() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::SetWidthPixels(600)),
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ];
+
+    let retries = Arc::new(AtomicU8::new(0));
+    let counted = retries.clone();
+
+    TestHarness::new()
+        .with_windows(2)
+        .with_focused_window(0)
+        .on_iteration(0, move |world, mock_state| {
+            // The application stops following resize requests.
+            mock_state.update_window(0, |window| window.frozen = true);
+            let counted = counted.clone();
+            world.add_observer(move |_: On<Insert, ResizeRetries>| {
+                counted.fetch_add(1, Ordering::SeqCst);
+            });
+        })
+        .on_iteration(2, move |world, _| {
+            let entity = find_window_entity(0, world);
+            // The resize was asked for again before giving up on it.
+            assert_eq!(retries.load(Ordering::SeqCst), ResizeRetries::LIMIT);
+            // The layout works with the size the window really has.
+            assert_eq!(world.get::<Bounds>(entity).unwrap().0.x, TEST_WINDOW_WIDTH);
+            assert!(world.get::<ResizeRetries>(entity).is_none());
+            assert_window_at!(world, 1, TEST_WINDOW_WIDTH, TEST_MENUBAR_HEIGHT);
+        })
+        .run(commands);
+}
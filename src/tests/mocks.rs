@@ -24,6 +24,7 @@ pub(crate) struct MockAppData {
     pub(crate) name: String,
     pub(crate) focused_window_id: Option<WinID>,
     pub(crate) is_frontmost: bool,
+    pub(crate) hidden: bool,
     pub(crate) connection: Option<crate::platform::ConnID>,
 }
 
@@ -40,11 +41,19 @@ pub(crate) struct MockWindowData {
     pub(crate) role: String,
     pub(crate) subrole: String,
     pub(crate) identifier: String,
+    pub(crate) document: String,
     pub(crate) is_full_screen: bool,
     pub(crate) border_radius: Option<f64>,
     pub(crate) horizontal_padding: i32,
     pub(crate) vertical_padding: i32,
     pub(crate) child_role: bool,
+    pub(crate) resizable: bool,
+    pub(crate) close_button: bool,
+    /// Simulates an application which ignores reposition and resize requests.
+    pub(crate) frozen: bool,
+    /// Simulates an application which does not let its window get any shorter.
+    pub(crate) min_height: i32,
+    pub(crate) opacity: f32,
 }
 
 impl Default for MockWindowData {
@@ -60,11 +69,17 @@ impl Default for MockWindowData {
             role: "AXWindow".to_string(),
             subrole: "AXStandardWindow".to_string(),
             identifier: "testid".to_string(),
+            document: String::new(),
             is_full_screen: false,
             border_radius: None,
             horizontal_padding: 0,
             vertical_padding: 0,
             child_role: false,
+            resizable: true,
+            close_button: true,
+            frozen: false,
+            min_height: 0,
+            opacity: 1.0,
         }
     }
 }
@@ -128,6 +143,7 @@ impl MockState {
                 name: name.to_string(),
                 focused_window_id: None,
                 is_frontmost: true,
+                hidden: false,
                 connection: Some(0),
             },
         );
@@ -290,6 +306,19 @@ impl MockState {
         }
     }
 
+    pub fn os_hide_app(&self, pid: Pid, hidden: bool) {
+        let mut inner = self.inner.force_write();
+        if let Some(app) = inner.apps.get_mut(&pid) {
+            app.hidden = hidden;
+            let event = if hidden {
+                Event::ApplicationHidden { pid }
+            } else {
+                Event::ApplicationVisible { pid }
+            };
+            inner.event_queue.push_back(event);
+        }
+    }
+
     // --- Interaction Helpers ---
 
     #[allow(unused)]
@@ -339,6 +368,22 @@ impl MockState {
         self.inner.force_read().cursor_position
     }
 
+    pub fn window_opacity(&self, id: WinID) -> f32 {
+        self.inner
+            .force_read()
+            .windows
+            .get(&id)
+            .map_or(1.0, |window| window.opacity)
+    }
+
+    pub fn window_workspace(&self, id: WinID) -> Option<WorkspaceId> {
+        self.inner
+            .force_read()
+            .windows
+            .get(&id)
+            .map(|window| window.workspace_id)
+    }
+
     // --- Mock Factory Methods ---
 
     #[allow(clippy::too_many_lines)]
@@ -370,15 +415,19 @@ impl MockState {
         let s = self.clone();
         mw.expect_resize().returning(move |size| {
             let mut inner = s.inner.force_write();
-            if let Some(w) = inner.windows.get_mut(&id) {
-                w.frame.max = w.frame.min + size;
+            if let Some(w) = inner.windows.get_mut(&id)
+                && !w.frozen
+            {
+                w.frame.max = w.frame.min + size.with_y(size.y.max(w.min_height));
             }
         });
 
         let s_move = self.clone();
         mw.expect_reposition().returning(move |origin| {
             let mut inner = s_move.inner.force_write();
-            if let Some(w) = inner.windows.get_mut(&id) {
+            if let Some(w) = inner.windows.get_mut(&id)
+                && !w.frozen
+            {
                 let size = w.frame.size();
                 w.frame.min = origin;
                 w.frame.max = origin + size;
@@ -409,6 +458,10 @@ impl MockState {
                 .is_some_and(|w| w.minimized)
         });
 
+        let s = self.clone();
+        mw.expect_set_minimized()
+            .returning(move |minimized| s.os_minimize_window(id, minimized));
+
         let s = self.clone();
         mw.expect_update_frame().returning(move || {
             s.inner
@@ -419,6 +472,9 @@ impl MockState {
                 .ok_or(Error::InvalidWindow)
         });
 
+        mw.expect_invalidate_frame().return_const(());
+        mw.expect_invalidate_title().return_const(());
+
         let s = self.clone();
         mw.expect_identifier().returning(move || {
             Ok(s.inner
@@ -429,6 +485,16 @@ impl MockState {
                 .unwrap_or_default())
         });
 
+        let s = self.clone();
+        mw.expect_document().returning(move || {
+            Ok(s.inner
+                .force_read()
+                .windows
+                .get(&id)
+                .map(|w| w.document.clone())
+                .unwrap_or_default())
+        });
+
         let s = self.clone();
         mw.expect_role().returning(move || {
             Ok(s.inner
@@ -458,6 +524,24 @@ impl MockState {
                 .is_some_and(|w| w.child_role))
         });
 
+        let s = self.clone();
+        mw.expect_is_resizable().returning(move || {
+            s.inner
+                .force_read()
+                .windows
+                .get(&id)
+                .is_none_or(|w| w.resizable)
+        });
+
+        let s = self.clone();
+        mw.expect_has_close_button().returning(move || {
+            s.inner
+                .force_read()
+                .windows
+                .get(&id)
+                .is_none_or(|w| w.close_button)
+        });
+
         let s = self.clone();
         mw.expect_horizontal_padding().returning(move || {
             s.inner
@@ -501,6 +585,7 @@ impl MockState {
         mw.expect_raise_without_focus().return_const(());
         mw.expect_focus_without_raise().return_const(());
         mw.expect_set_padding().return_const(());
+        mw.expect_set_disable_enhanced_ui().return_const(());
 
         Window::new(Box::new(mw))
     }
@@ -664,6 +749,46 @@ impl MockState {
         wm.expect_cursor_position()
             .returning(move || Some(origin_to(s.inner.force_read().cursor_position)));
 
+        let s = self.clone();
+        wm.expect_set_window_opacity()
+            .returning(move |id, opacity| s.update_window(id, |window| window.opacity = opacity));
+
+        let s = self.clone();
+        wm.expect_move_window_to_space()
+            .returning(move |id, space_id| {
+                s.update_window(id, |window| window.workspace_id = space_id)
+            });
+
+        let s = self.clone();
+        wm.expect_display_desktops().returning(move |display_id| {
+            let inner = s.inner.force_read();
+            inner
+                .displays
+                .get(&display_id)
+                .map(|display| {
+                    display
+                        .workspaces
+                        .iter()
+                        .copied()
+                        .filter(|id| !inner.fullscreen_spaces.contains(id))
+                        .collect()
+                })
+                .ok_or(Error::InvalidWindow)
+        });
+
+        let s = self.clone();
+        wm.expect_focus_space()
+            .returning(move |display_id, space_id| {
+                s.activate_workspace(display_id, space_id, false);
+                s.inner
+                    .force_write()
+                    .event_queue
+                    .push_back(Event::SpaceChanged);
+                Ok(())
+            });
+
+        wm.expect_disable_updates().return_const(());
+        wm.expect_reenable_updates().return_const(());
         wm.expect_get_associated_windows().return_const(vec![]);
         wm.expect_find_window_at_point().return_const(Ok(0));
 
@@ -687,10 +812,23 @@ impl MockState {
         mp.expect_psn()
             .returning(move || s.inner.force_read().apps.get(&pid).map(|a| a.psn).unwrap());
         mp.expect_is_observable().returning(|| true);
+        mp.expect_bundle_id().return_const(None);
         mp.expect_application().return_const(None);
         mp.expect_ready().return_const(true);
         mp.expect_force_manage().return_const(());
 
+        let s = self.clone();
+        mp.expect_is_hidden().returning(move || {
+            s.inner
+                .force_read()
+                .apps
+                .get(&pid)
+                .is_some_and(|a| a.hidden)
+        });
+        let s = self.clone();
+        mp.expect_set_hidden()
+            .returning(move |hidden| s.os_hide_app(pid, hidden));
+
         mp
     }
 }
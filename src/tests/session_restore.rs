@@ -24,6 +24,7 @@ fn test_startup_restore_rebuilds_virtual_workspace_layout() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![SavedDisplay {
             display_id: TEST_DISPLAY_ID,
             bounds: SavedRect {
@@ -86,6 +87,7 @@ fn test_startup_restore_replaces_consumed_empty_virtual_zero_strip() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![saved_display(TEST_DISPLAY_ID, true)],
         workspaces: vec![SavedWorkspace {
             workspace_id: TEST_WORKSPACE_ID,
@@ -138,6 +140,7 @@ fn test_startup_restore_preserves_fullscreen_strip() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![saved_display(TEST_DISPLAY_ID, true)],
         workspaces: vec![SavedWorkspace {
             workspace_id: TEST_WORKSPACE_ID,
@@ -188,6 +191,7 @@ fn test_startup_restore_prefers_existing_workspace_parent_before_saved_or_active
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(EXT_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![
             saved_display(EXT_DISPLAY_ID, true),
             saved_display(TEST_DISPLAY_ID, false),
@@ -260,6 +264,7 @@ fn test_startup_restore_preserves_saved_display_when_present() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(EXT_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![
             saved_display(EXT_DISPLAY_ID, true),
             saved_display(TEST_DISPLAY_ID, false),
@@ -329,6 +334,7 @@ fn test_startup_restore_keeps_current_native_workspace_active_across_multiple_wo
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![
             saved_display(TEST_DISPLAY_ID, true),
             saved_display(EXT_DISPLAY_ID, false),
@@ -599,6 +605,7 @@ fn test_startup_restore_keeps_one_selected_row_and_hides_inactive_rows() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![saved_display(TEST_DISPLAY_ID, true)],
         workspaces: vec![SavedWorkspace {
             workspace_id: TEST_WORKSPACE_ID,
@@ -671,6 +678,7 @@ fn state_with_strips(strips: Vec<SavedStrip>) -> PaneruState {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![saved_display(TEST_DISPLAY_ID, true)],
         workspaces: vec![SavedWorkspace {
             workspace_id: TEST_WORKSPACE_ID,
@@ -712,6 +720,7 @@ fn saved_window(window_id: i32) -> SavedWindow {
         bundle_id: "test".to_string(),
         title: String::new(),
         identifier: String::new(),
+        document: String::new(),
         role: "AXWindow".to_string(),
         subrole: "AXStandardWindow".to_string(),
     }
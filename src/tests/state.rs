@@ -1,11 +1,13 @@
 use bevy::prelude::*;
 
+use crate::ecs::focus::FocusHistory;
 use crate::ecs::layout::LayoutStrip;
 use crate::ecs::params::Windows;
 use crate::ecs::restore::CurrentWindowIdentity;
 use crate::ecs::state::{
-    PaneruQueryState, PaneruState, SavedColumn, SavedDisplay, SavedRect, SavedStackItem,
-    SavedStrip, SavedWindow, SavedWorkspace,
+    PaneruQueryState, PaneruState, RuntimeState, SavedColumn, SavedDisplay, SavedFocus,
+    SavedOption, SavedRect, SavedStackItem, SavedStrip, SavedUnmanagedKind, SavedWindow,
+    SavedWorkspace, extract_query_state,
 };
 use crate::ecs::{ActiveDisplayMarker, ActiveWorkspaceMarker, SelectedVirtualMarker};
 use crate::events::Event;
@@ -54,7 +56,7 @@ type QueryStateExtractionState<'w, 's> = SystemState<(
 fn extract_query_state(world: &mut World) -> crate::errors::Result<PaneruQueryState> {
     let mut system_state: QueryStateExtractionState<'_, '_> = SystemState::new(world);
     let (workspaces, displays, windows, apps, window_manager) = system_state.get(world);
-    PaneruQueryState::extract(&workspaces, &displays, &windows, &apps, &window_manager)
+    extract_query_state(&workspaces, &displays, &windows, &apps, &window_manager)
 }
 
 #[test]
@@ -66,6 +68,7 @@ fn test_state_serialization() {
         bundle_id: "com.apple.Finder".to_string(),
         title: "Finder".to_string(),
         identifier: "finder-main".to_string(),
+        document: String::new(),
         role: "AXWindow".to_string(),
         subrole: "AXStandardWindow".to_string(),
     };
@@ -74,6 +77,7 @@ fn test_state_serialization() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![SavedDisplay {
             display_id: TEST_DISPLAY_ID,
             bounds: SavedRect {
@@ -111,6 +115,7 @@ fn test_state_restoration() {
         bundle_id: "com.apple.Finder".to_string(),
         title: "Finder".to_string(),
         identifier: "finder-main".to_string(),
+        document: String::new(),
         role: "AXWindow".to_string(),
         subrole: "AXStandardWindow".to_string(),
     };
@@ -119,6 +124,7 @@ fn test_state_restoration() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: None,
+        unmanaged: Vec::new(),
         displays: Vec::new(),
         workspaces: vec![SavedWorkspace {
             workspace_id: TEST_WORKSPACE_ID,
@@ -175,6 +181,30 @@ fn test_state_extraction() {
     }
 }
 
+#[test]
+fn test_state_extraction_records_floating_windows() {
+    use crate::commands::{Command, Operation};
+    use crate::tests::harness::TestHarness;
+
+    let mut harness = TestHarness::new().with_windows(1);
+    harness.app.update();
+    harness.app.world_mut().write_message(Event::Command {
+        command: Command::Window(Operation::Manage),
+    });
+    harness.app.update();
+
+    let world = harness.world();
+    let mut system_state: StateExtractionState<'_, '_> = SystemState::new(world);
+    let (workspaces, displays, windows, apps) = system_state.get(world);
+
+    let state = PaneruState::extract(&workspaces, &displays, &windows, &apps);
+
+    assert_eq!(state.unmanaged.len(), 1);
+    assert_eq!(state.unmanaged[0].window.window_id, 0);
+    assert_eq!(state.unmanaged[0].kind, SavedUnmanagedKind::Floating);
+    assert!(state.workspaces[0].strips[0].columns.is_empty());
+}
+
 #[test]
 fn test_state_serializes_display_and_active_virtual_workspace() {
     use crate::tests::harness::TestHarness;
@@ -287,6 +317,7 @@ fn test_state_save_is_loadable_from_path() {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: vec![SavedDisplay {
             display_id: TEST_DISPLAY_ID,
             bounds: SavedRect {
@@ -317,6 +348,47 @@ fn test_state_save_is_loadable_from_path() {
     let _ = std::fs::remove_file(path);
 }
 
+#[test]
+fn test_runtime_state_restores_focus_history_and_options() {
+    let runtime = RuntimeState {
+        options: vec![SavedOption {
+            key: "animation_speed".to_string(),
+            value: "20".to_string(),
+        }],
+        focus_history: vec![SavedFocus {
+            workspace_id: TEST_WORKSPACE_ID,
+            last_managed: None,
+            last_floating: Some(1),
+        }],
+    };
+    let path = unique_state_path("runtime");
+    runtime
+        .save_to_file(&path)
+        .expect("runtime state should save");
+    let loaded = RuntimeState::load_from_file(&path).expect("runtime state should load");
+    assert_eq!(loaded, runtime);
+    let _ = std::fs::remove_file(path);
+
+    let mut config = crate::config::Config::default();
+    loaded.apply_options(&mut config);
+    assert_eq!(config.animation_speed(), 20.0);
+
+    let mut harness = crate::tests::harness::TestHarness::new().with_windows(2);
+    harness.world().insert_resource(loaded);
+    for _ in 0..5 {
+        harness.app.update();
+    }
+
+    let world = harness.world();
+    assert!(world.get_resource::<RuntimeState>().is_none());
+    assert_eq!(
+        world
+            .resource::<FocusHistory>()
+            .last_floating(TEST_WORKSPACE_ID),
+        Some(crate::tests::harness::find_window_entity(1, world))
+    );
+}
+
 fn unique_state_path(name: &str) -> std::path::PathBuf {
     std::env::temp_dir().join(format!(
         "paneru-{name}-{}-{}.json",
@@ -484,11 +556,94 @@ fn restore_plan_skips_ambiguous_fallback_match() {
     assert_eq!(plan.skipped_ambiguous_matches, 1);
 }
 
+#[test]
+fn restore_plan_matches_fallback_windows_by_document() {
+    use crate::ecs::restore::{CurrentWindowIdentity, PlannedColumn, RestorePlanner};
+
+    let mut world = World::new();
+    let first = world.spawn_empty().id();
+    let second = world.spawn_empty().id();
+    let mut saved_a = saved_window(20, 120, "com.example.editor", "Editor");
+    saved_a.document = "file:///tmp/a.rs".to_string();
+    let mut saved_b = saved_window(21, 120, "com.example.editor", "Editor");
+    saved_b.document = "file:///tmp/b.rs".to_string();
+    let state = restore_state(vec![SavedWorkspace {
+        workspace_id: TEST_WORKSPACE_ID,
+        display_id: None,
+        active_virtual_index: Some(0),
+        strips: vec![SavedStrip {
+            virtual_index: 0,
+            columns: vec![SavedColumn::Single(saved_a), SavedColumn::Single(saved_b)],
+        }],
+    }]);
+    // After a restart the titles carry an unsaved marker and the windows come back in
+    // reverse order; only the document path tells them apart.
+    let current = vec![
+        CurrentWindowIdentity {
+            document: "file:///tmp/b.rs".to_string(),
+            ..CurrentWindowIdentity::fallback_only(first, "com.example.editor", "Editor *")
+        },
+        CurrentWindowIdentity {
+            document: "file:///tmp/a.rs".to_string(),
+            ..CurrentWindowIdentity::fallback_only(second, "com.example.editor", "Editor *")
+        },
+    ];
+
+    let plan = RestorePlanner::new(&state).plan(&current);
+
+    assert_eq!(plan.strips.len(), 1);
+    assert_eq!(
+        plan.strips[0].columns,
+        vec![PlannedColumn::Single(second), PlannedColumn::Single(first)]
+    );
+    assert_eq!(plan.ignored_missing_windows, 0);
+    assert_eq!(plan.skipped_ambiguous_matches, 0);
+}
+
+#[test]
+fn restore_plan_columns_keeps_one_entry_per_saved_column() {
+    use crate::ecs::restore::{PlannedColumn, RestorePlanner};
+
+    let mut world = World::new();
+    let first = world.spawn_empty().id();
+    let third = world.spawn_empty().id();
+    let columns = vec![
+        SavedColumn::Single(saved_window(30, 130, "com.example.one", "One")),
+        SavedColumn::Single(saved_window(31, 131, "com.example.two", "Two")),
+        SavedColumn::Single(saved_window(32, 132, "com.example.three", "Three")),
+    ];
+    let state = restore_state(vec![SavedWorkspace {
+        workspace_id: TEST_WORKSPACE_ID,
+        display_id: None,
+        active_virtual_index: Some(0),
+        strips: vec![SavedStrip {
+            virtual_index: 0,
+            columns: columns.clone(),
+        }],
+    }]);
+    let current = vec![
+        current_window(third, 32, 132, "com.example.three", "Three"),
+        current_window(first, 30, 130, "com.example.one", "One"),
+    ];
+
+    let planned = RestorePlanner::new(&state).plan_columns(&columns, &current);
+
+    assert_eq!(
+        planned,
+        vec![
+            Some(PlannedColumn::Single(first)),
+            None,
+            Some(PlannedColumn::Single(third)),
+        ]
+    );
+}
+
 fn restore_state(workspaces: Vec<SavedWorkspace>) -> PaneruState {
     PaneruState {
         version: 2,
         timestamp: 123_456_789,
         active_display_id: Some(TEST_DISPLAY_ID),
+        unmanaged: Vec::new(),
         displays: Vec::new(),
         workspaces,
     }
@@ -502,6 +657,7 @@ fn saved_window(window_id: WinID, pid: Pid, bundle_id: &str, title: &str) -> Sav
         bundle_id: bundle_id.to_string(),
         title: title.to_string(),
         identifier: "main".to_string(),
+        document: String::new(),
         role: "AXWindow".to_string(),
         subrole: "AXStandardWindow".to_string(),
     }
@@ -521,6 +677,7 @@ fn current_window(
         bundle_id: bundle_id.to_string(),
         title: title.to_string(),
         identifier: "main".to_string(),
+        document: String::new(),
         role: "AXWindow".to_string(),
         subrole: "AXStandardWindow".to_string(),
     }
@@ -1,6 +1,14 @@
-use crate::commands::{Command, Direction, Operation, ResizeDirection};
-use crate::config::{Config, MainOptions, WindowParams};
+use crate::commands::{
+    Command, CycleDirection, Direction, InsertNext, Operation, ResizeAmount, ResizeAxis,
+    ResizeDirection, StripOperation,
+};
+use crate::config::{
+    ColumnWidth, Config, InsertPosition, MainOptions, MaxColumnsPolicy, ScrollPolicy, WindowParams,
+};
+use crate::ecs::layout::LayoutStrip;
+use crate::ecs::{InsertPointMarker, SpawnWindowTrigger};
 use crate::events::Event;
+use crate::platform::WinID;
 use crate::{assert_window_at, assert_window_size};
 use bevy::prelude::*;
 
@@ -183,7 +191,11 @@ fn test_window_resize_grow_and_shrink_cycle() {
 
     let config: Config = (
         MainOptions {
-            preset_column_widths: vec![0.25, 0.5, 0.75],
+            preset_column_widths: vec![
+                ColumnWidth::Ratio(0.25),
+                ColumnWidth::Ratio(0.5),
+                ColumnWidth::Ratio(0.75),
+            ],
             ..Default::default()
         },
         vec![],
@@ -208,6 +220,163 @@ fn test_window_resize_grow_and_shrink_cycle() {
         .run(commands);
 }
 
+#[test]
+fn test_window_resize_reverse_steps_down_first() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Resize(ResizeDirection::Grow)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Resize(ResizeDirection::Grow)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Resize(ResizeDirection::Shrink)),
+        },
+    ];
+
+    let config: Config = (
+        MainOptions {
+            preset_column_widths: vec![
+                ColumnWidth::Ratio(0.25),
+                ColumnWidth::Ratio(0.5),
+                ColumnWidth::Ratio(0.75),
+            ],
+            window_resize_reverse: Some(true),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .on_iteration(1, |world, _state| {
+            assert_window_size!(world, 0, 256, 748);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_window_size!(world, 0, 768, 748);
+        })
+        .on_iteration(3, |world, _state| {
+            assert_window_size!(world, 0, 256, 748);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_resize_by_delta() {
+    let resize_by = |amount| Event::Command {
+        command: Command::Window(Operation::ResizeBy {
+            axis: ResizeAxis::Width,
+            amount,
+        }),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        resize_by(ResizeAmount::Pixels(100)),
+        resize_by(ResizeAmount::Percent(-10.0)),
+        resize_by(ResizeAmount::Pixels(-1000)),
+    ];
+
+    TestHarness::new()
+        .with_windows(1)
+        .on_iteration(1, |world, _state| {
+            assert_window_size!(world, 0, TEST_WINDOW_WIDTH + 100, 748);
+        })
+        .on_iteration(2, |world, _state| {
+            // 10% of the 1024 pixel wide viewport.
+            assert_window_size!(world, 0, TEST_WINDOW_WIDTH - 2, 748);
+        })
+        .on_iteration(3, |world, _state| {
+            assert_window_size!(world, 0, 200, 748);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_set_exact_width() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::SetWidthPixels(900)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::SetWidth(0.25)),
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(1)
+        .on_iteration(1, |world, _state| {
+            assert_window_size!(world, 0, 900, 748);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_window_size!(world, 0, 256, 748);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_animate_false_rule_resizes_instantly() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::SetWidthPixels(900)),
+        },
+    ];
+
+    let mut params = WindowParams::new(".*", None);
+    params.animate = Some(false);
+    let config: Config = (
+        MainOptions {
+            // Slow enough that an animated resize is still far from done.
+            animation_speed: Some(1.0),
+            ..Default::default()
+        },
+        vec![params],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .on_iteration(1, |world, _state| {
+            assert_window_size!(world, 0, 900, 748);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_width_limits_rule_clamps_resizes() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::SetWidthPixels(900)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::SetWidth(0.25)),
+        },
+    ];
+
+    let mut params = WindowParams::new(".*", None);
+    params.min_width = Some(ColumnWidth::Pixels(300));
+    params.max_width = Some(ColumnWidth::Ratio(0.5));
+    let config: Config = (MainOptions::default(), vec![params]).into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(1)
+        .on_iteration(1, |world, _state| {
+            // Half of the 1024 pixel wide viewport.
+            assert_window_size!(world, 0, 512, 748);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_window_size!(world, 0, 300, 748);
+        })
+        .run(commands);
+}
+
 #[test]
 fn test_window_can_resize_to_two_display_widths_and_scroll() {
     let commands = vec![
@@ -264,3 +433,839 @@ fn assert_oversized_window_is_pannable(world: &mut World, id: i32) {
         "oversized window must stay within its pannable range, got x={x}"
     );
 }
+
+#[test]
+fn test_window_monocle_toggle_restores_stack() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::First)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::East)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Stack(true)),
+        }, // 3
+        Event::Command {
+            command: Command::Window(Operation::Monocle),
+        }, // 4
+        Event::Command {
+            command: Command::Window(Operation::Monocle),
+        }, // 5
+    ];
+
+    let stack_row = |world: &mut World| {
+        let first = find_window_entity(0, world);
+        let second = find_window_entity(1, world);
+        let mut query = world.query::<&LayoutStrip>();
+        query.iter(world).find_map(|strip| {
+            let index = strip.index_of(second).ok()?;
+            let column = strip.get(index).ok()?;
+            (strip.index_of(first).ok()? == index).then(|| column.position_of(second))?
+        })
+    };
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, move |world, _state| {
+            assert_eq!(stack_row(world), Some(1));
+        })
+        .on_iteration(4, move |world, _state| {
+            assert_eq!(stack_row(world), None);
+            assert_window_size!(
+                world,
+                1,
+                TEST_DISPLAY_WIDTH,
+                TEST_DISPLAY_HEIGHT - TEST_MENUBAR_HEIGHT
+            );
+        })
+        .on_iteration(5, move |world, _state| {
+            assert_eq!(stack_row(world), Some(1));
+            let frame = world
+                .query::<&crate::manager::Window>()
+                .iter(world)
+                .find(|window| window.id() == 1)
+                .map(crate::manager::Window::frame)
+                .expect("window not found");
+            assert_eq!(frame.width(), TEST_WINDOW_WIDTH);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_zen_hides_other_columns_and_restores() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 1 },
+        Event::Command {
+            command: Command::Window(Operation::Zen),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Zen),
+        },
+    ];
+
+    let config: Config = (
+        MainOptions {
+            animation_speed: Some(10000.0),
+            zen_width: Some(0.5),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .with_focused_window(1)
+        .on_iteration(1, move |world, _state| {
+            assert_eq!(frame_of(world, 1).width(), TEST_DISPLAY_WIDTH / 2);
+            assert!(frame_of(world, 0).max.x <= 0);
+            assert!(frame_of(world, 2).min.x >= TEST_DISPLAY_WIDTH);
+        })
+        .on_iteration(2, move |world, _state| {
+            assert_eq!(frame_of(world, 1).width(), TEST_WINDOW_WIDTH);
+            assert!(frame_of(world, 0).max.x > 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_overview_fits_strip_and_restores_picked_window() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Overview),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::Last)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Overview),
+        },
+    ];
+
+    let config: Config = (
+        MainOptions {
+            animation_speed: Some(10000.0),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(4)
+        .with_focused_window(0)
+        .on_iteration(1, move |world, _state| {
+            for id in 0..4 {
+                let frame = frame_of(world, id);
+                assert!(frame.width() < TEST_WINDOW_WIDTH);
+                assert!(frame.min.x >= 0 && frame.max.x <= TEST_DISPLAY_WIDTH);
+            }
+        })
+        .on_iteration(2, move |world, _state| {
+            crate::assert_focused!(world, 3);
+            assert!(frame_of(world, 3).width() < TEST_WINDOW_WIDTH);
+        })
+        .on_iteration(3, move |world, _state| {
+            crate::assert_focused!(world, 3);
+            let frame = frame_of(world, 3);
+            assert_eq!(frame.width(), TEST_WINDOW_WIDTH);
+            assert!(frame.max.x <= TEST_DISPLAY_WIDTH);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_jump_labels_visible_columns_and_focuses_picked_one() {
+    use crate::commands::JumpBadges;
+
+    // Keycode of the "2" key, the label of the second visible column.
+    const KEY_2: u8 = 0x13;
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Jump),
+        },
+        Event::JumpKey { keycode: KEY_2 },
+    ];
+
+    TestHarness::new()
+        .with_windows(5)
+        .with_focused_window(0)
+        .on_iteration(1, move |world, _state| {
+            let badges = world.resource::<JumpBadges>().0.clone();
+            let labels = badges.iter().map(|badge| badge.label).collect::<Vec<_>>();
+            // The two columns past the display edge show only slivers and get no badge.
+            assert_eq!(labels, vec!["1", "2", "3"]);
+            assert!(
+                badges
+                    .iter()
+                    .all(|badge| badge.frame.max.x <= TEST_DISPLAY_WIDTH)
+            );
+        })
+        .on_iteration(2, move |world, _state| {
+            crate::assert_focused!(world, 1);
+            assert!(world.resource::<JumpBadges>().0.is_empty());
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_window_peek_reveals_column_until_released() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Peek(Direction::Last)),
+        },
+        Event::CycleModifiersReleased,
+    ];
+
+    let config: Config = (
+        MainOptions {
+            animation_speed: Some(10000.0),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(4)
+        .with_focused_window(0)
+        .on_iteration(1, move |world, _state| {
+            assert!(frame_of(world, 3).max.x <= TEST_DISPLAY_WIDTH);
+            crate::assert_focused!(world, 0);
+        })
+        .on_iteration(2, move |world, _state| {
+            assert_eq!(frame_of(world, 0).min.x, 0);
+            assert!(frame_of(world, 3).min.x > TEST_DISPLAY_WIDTH - 10);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_scroll_policy_edge_aligns_focused_window_with_nearer_edge() {
+    let focus = |direction| Event::Command {
+        command: Command::Window(Operation::Focus(direction)),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        focus(Direction::First),
+        focus(Direction::East),
+        focus(Direction::East),
+        focus(Direction::East),
+        focus(Direction::West),
+    ];
+
+    let config: Config = (
+        MainOptions {
+            scroll_policy: Some(ScrollPolicy::Edge),
+            animation_speed: Some(10000.0),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(6)
+        .on_iteration(4, |world, _state| {
+            assert_window_at!(
+                world,
+                3,
+                TEST_DISPLAY_WIDTH - TEST_WINDOW_WIDTH,
+                TEST_MENUBAR_HEIGHT
+            );
+        })
+        .on_iteration(5, |world, _state| {
+            // Fully visible already, yet left of the center: it moves to the left edge.
+            assert_window_at!(world, 2, 0, TEST_MENUBAR_HEIGHT);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_strip_scroll_pans_without_changing_focus() {
+    let strip = |operation| Event::Command {
+        command: Command::Strip(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        strip(StripOperation::ScrollTo(Direction::Last)),
+        strip(StripOperation::Scroll(
+            Direction::West,
+            ResizeAmount::Pixels(100),
+        )),
+        strip(StripOperation::ScrollTo(Direction::First)),
+    ];
+
+    let config: Config = (
+        MainOptions {
+            animation_speed: Some(10000.0),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+    let last_x = TEST_DISPLAY_WIDTH - TEST_WINDOW_WIDTH;
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(4)
+        .with_focused_window(0)
+        .on_iteration(1, move |world, _state| {
+            assert_window_at!(world, 3, last_x, TEST_MENUBAR_HEIGHT);
+            crate::assert_focused!(world, 0);
+        })
+        .on_iteration(2, move |world, _state| {
+            assert_window_at!(
+                world,
+                2,
+                last_x - TEST_WINDOW_WIDTH + 100,
+                TEST_MENUBAR_HEIGHT
+            );
+            crate::assert_focused!(world, 0);
+        })
+        .on_iteration(3, |world, _state| {
+            assert_window_at!(world, 0, 0, TEST_MENUBAR_HEIGHT);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_max_columns_stacks_least_recently_focused_column() {
+    let config: Config = (
+        MainOptions {
+            max_columns: Some(2),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .with_focused_window(2)
+        .on_iteration(2, |world, _state| {
+            let first = find_window_entity(0, world);
+            let second = find_window_entity(1, world);
+            let focused = find_window_entity(2, world);
+            let mut query = world.query::<&LayoutStrip>();
+            let strip = query
+                .iter(world)
+                .find(|strip| strip.contains(focused))
+                .expect("strip with the focused window");
+
+            assert_eq!(strip.len(), 2);
+            // Windows 0 and 1 were never focused, so they end up sharing a column.
+            assert_eq!(
+                strip.index_of(first).unwrap(),
+                strip.index_of(second).unwrap()
+            );
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 2 },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
+#[test]
+fn test_max_columns_overflows_to_next_virtual_workspace() {
+    let config: Config = (
+        MainOptions {
+            max_columns: Some(2),
+            max_columns_policy: Some(MaxColumnsPolicy::Overflow),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .with_focused_window(2)
+        .on_iteration(3, |world, _state| {
+            let first = find_window_entity(0, world);
+            let focused = find_window_entity(2, world);
+            let mut query = world.query::<&LayoutStrip>();
+            let strips = query.iter(world).collect::<Vec<_>>();
+
+            let active = strips
+                .iter()
+                .find(|strip| strip.contains(focused))
+                .expect("strip with the focused window");
+            assert_eq!(active.len(), 2);
+            assert!(
+                strips
+                    .iter()
+                    .any(|strip| strip.virtual_index == active.virtual_index + 1
+                        && strip.contains(first))
+            );
+        })
+        .run(vec![
+            Event::MenuOpened { window_id: 2 },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+            Event::Command {
+                command: Command::PrintState,
+            },
+        ]);
+}
+
+#[test]
+fn test_balance_widths_splits_viewport() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::BalanceWidths(None)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::BalanceWidths(Some((0, 1)))),
+        },
+    ];
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(1, |world, _state| {
+            // All three columns are at least partly visible on the 1024 pixel wide display.
+            for id in 0..3 {
+                assert_window_size!(world, id, 341, 748);
+            }
+        })
+        .on_iteration(2, |world, _state| {
+            assert_window_size!(world, 0, 512, 748);
+            assert_window_size!(world, 1, 512, 748);
+            assert_window_size!(world, 2, 341, 748);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_stack_tabs_reserve_tab_bar_and_focus_on_click() {
+    use crate::ecs::layout::{STACK_TAB_BAR_HEIGHT, StackTabBars};
+
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::First)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::East)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Stack(true)),
+        }, // 3
+        Event::Command {
+            command: Command::PrintState,
+        }, // 4
+    ];
+
+    let config: Config = (
+        MainOptions {
+            stack_tabs: Some(true),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .on_iteration(3, move |world, state| {
+            let first = find_window_entity(0, world);
+            let second = find_window_entity(1, world);
+            let bars = world.resource::<StackTabBars>().0.clone();
+            assert_eq!(bars.len(), 1);
+            let bar = &bars[0];
+            assert_eq!(bar.frame.height(), STACK_TAB_BAR_HEIGHT);
+            assert_eq!(
+                bar.tabs
+                    .iter()
+                    .map(|tab| (tab.entity, tab.active))
+                    .collect::<Vec<_>>(),
+                vec![(first, false), (second, true)]
+            );
+
+            // The stacked windows start below the tab bar.
+            let top = world
+                .query::<&crate::manager::Window>()
+                .iter(world)
+                .find(|window| window.id() == 0)
+                .map(|window| window.frame().min.y)
+                .expect("window not found");
+            assert_eq!(top, TEST_MENUBAR_HEIGHT + STACK_TAB_BAR_HEIGHT);
+            assert_eq!(bar.frame.max.y, top);
+
+            // Click the middle of the first tab.
+            let point = IVec2::new(
+                bar.frame.min.x + bar.frame.width() / 4,
+                bar.frame.center().y,
+            );
+            assert_eq!(bar.tab_at(point), Some(first));
+            state.simulate_click(point);
+        })
+        .on_iteration(4, move |world, _state| {
+            crate::assert_focused!(world, 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_cycle_stack_wraps_around() {
+    let cycle = |direction| Event::Command {
+        command: Command::Window(Operation::CycleStack(direction)),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::First)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::East)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Stack(true)),
+        }, // 3
+        cycle(CycleDirection::Next), // 4
+        cycle(CycleDirection::Next), // 5
+        cycle(CycleDirection::Prev), // 6
+    ];
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, |world, _state| crate::assert_focused!(world, 1))
+        .on_iteration(4, |world, _state| crate::assert_focused!(world, 0))
+        .on_iteration(5, |world, _state| crate::assert_focused!(world, 1))
+        .on_iteration(6, |world, _state| crate::assert_focused!(world, 0))
+        .run(commands);
+}
+
+#[test]
+fn test_swap_north_south_reorders_stack() {
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::First)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Focus(Direction::East)),
+        },
+        Event::Command {
+            command: Command::Window(Operation::Stack(true)),
+        }, // 3
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::North)),
+        }, // 4
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::North)),
+        }, // 5
+        Event::Command {
+            command: Command::Window(Operation::Swap(Direction::South)),
+        }, // 6
+    ];
+
+    let stack_row = |world: &mut World, id| {
+        let entity = find_window_entity(id, world);
+        let mut query = world.query::<&LayoutStrip>();
+        query.iter(world).find_map(|strip| {
+            let column = strip.get(strip.index_of(entity).ok()?).ok()?;
+            column.position_of(entity)
+        })
+    };
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, move |world, _state| {
+            assert_eq!(stack_row(world, 0), Some(0));
+            assert_eq!(stack_row(world, 1), Some(1));
+        })
+        .on_iteration(4, move |world, _state| {
+            assert_eq!(stack_row(world, 1), Some(0));
+            assert_eq!(stack_row(world, 0), Some(1));
+            crate::assert_focused!(world, 1);
+        })
+        .on_iteration(5, move |world, _state| {
+            // Already at the top of the stack: nothing to swap with.
+            assert_eq!(stack_row(world, 1), Some(0));
+        })
+        .on_iteration(6, move |world, _state| {
+            assert_eq!(stack_row(world, 0), Some(0));
+            assert_eq!(stack_row(world, 1), Some(1));
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_promote_moves_window_to_top_of_stack() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::Focus(Direction::First)),
+        command(Operation::Focus(Direction::East)),
+        command(Operation::Stack(true)),
+        command(Operation::Focus(Direction::East)),
+        command(Operation::Stack(true)), // 5
+        command(Operation::Promote),     // 6
+        command(Operation::Promote),     // 7
+    ];
+
+    let stack_rows = |world: &mut World| {
+        [0, 1, 2].map(|id| {
+            let entity = find_window_entity(id, world);
+            let mut query = world.query::<&LayoutStrip>();
+            query.iter(world).find_map(|strip| {
+                let column = strip.get(strip.index_of(entity).ok()?).ok()?;
+                column.position_of(entity)
+            })
+        })
+    };
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(5, move |world, _state| {
+            assert_eq!(stack_rows(world), [Some(0), Some(1), Some(2)]);
+        })
+        .on_iteration(6, move |world, _state| {
+            assert_eq!(stack_rows(world), [Some(1), Some(2), Some(0)]);
+            crate::assert_focused!(world, 2);
+        })
+        .on_iteration(7, move |world, _state| {
+            // Already on top: the stack order stays the same.
+            assert_eq!(stack_rows(world), [Some(1), Some(2), Some(0)]);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_pinned_column_stays_at_viewport_edge() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::Focus(Direction::First)),
+        command(Operation::Pin),
+        command(Operation::Focus(Direction::Last)), // 3
+        command(Operation::Focus(Direction::First)),
+        command(Operation::Pin),
+        command(Operation::Focus(Direction::Last)), // 6
+    ];
+
+    // Three columns are wider than the display, so focusing the last one scrolls the first
+    // partly off the left edge.
+    let scrolled_left = TEST_DISPLAY_WIDTH - 3 * TEST_WINDOW_WIDTH;
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, move |world, _state| {
+            assert_window_at!(world, 0, 0, TEST_MENUBAR_HEIGHT);
+            assert_window_at!(
+                world,
+                2,
+                TEST_DISPLAY_WIDTH - TEST_WINDOW_WIDTH,
+                TEST_MENUBAR_HEIGHT
+            );
+        })
+        .on_iteration(6, move |world, _state| {
+            assert_window_at!(world, 0, scrolled_left, TEST_MENUBAR_HEIGHT);
+        })
+        .run(commands);
+}
+
+/// Returns the current frame of window `id`.
+fn frame_of(world: &mut World, id: i32) -> IRect {
+    world
+        .query::<&crate::manager::Window>()
+        .iter(world)
+        .find(|window| window.id() == id)
+        .map(crate::manager::Window::frame)
+        .expect("window not found")
+}
+
+/// Returns the column index of window `id` within its strip.
+fn column_of(world: &mut World, id: i32) -> Option<usize> {
+    let entity = find_window_entity(id, world);
+    let mut query = world.query::<&LayoutStrip>();
+    query
+        .iter(world)
+        .find_map(|strip| strip.index_of(entity).ok())
+}
+
+/// Opens window `id` with the default test frame, as if its application had just created it.
+fn spawn_new_window(world: &mut World, state: &MockState, id: WinID) {
+    let frame = IRect::new(0, 0, TEST_WINDOW_WIDTH, TEST_WINDOW_HEIGHT);
+    let window = state.spawn_window(TEST_PROCESS_ID, TEST_WORKSPACE_ID, id, frame);
+    world.trigger(SpawnWindowTrigger(vec![window]));
+}
+
+/// Events for a test which spawns a window on iteration 0 with `focused` focused, leaving two
+/// more iterations for the new window to be laid out.
+fn spawn_events(focused: WinID) -> Vec<Event> {
+    vec![
+        Event::MenuOpened { window_id: focused },
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        },
+    ]
+}
+
+#[test]
+fn test_insert_position_start_opens_new_window_first() {
+    let config: Config = (
+        MainOptions {
+            insert_position: Some(InsertPosition::Start),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(3)
+        .with_focused_window(1)
+        .on_iteration(0, |world, state| {
+            spawn_new_window(world, &state, 10);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_eq!(column_of(world, 10), Some(0));
+            assert_eq!(column_of(world, 0), Some(1));
+        })
+        .run(spawn_events(1));
+}
+
+#[test]
+fn test_insert_point_overrides_focus_once() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::Focus(Direction::First)),
+        command(Operation::SetInsertPoint),
+        command(Operation::Focus(Direction::Last)), // 3
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        }, // 5
+    ];
+
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(3, |world, state| {
+            spawn_new_window(world, &state, 10);
+        })
+        .on_iteration(5, |world, _state| {
+            // The new window opened after the marked first window, not after the focused last.
+            assert_eq!(column_of(world, 10), Some(1));
+            let mut marked = world.query_filtered::<Entity, With<InsertPointMarker>>();
+            assert_eq!(marked.iter(world).count(), 0);
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_insert_next_stacked_applies_to_one_window() {
+    let command = |operation| Event::Command {
+        command: Command::Window(operation),
+    };
+    let commands = vec![
+        Event::MenuOpened { window_id: 0 },
+        command(Operation::Focus(Direction::First)),
+        command(Operation::InsertNext(InsertNext::Stacked)), // 2
+        Event::Command {
+            command: Command::PrintState,
+        },
+        Event::Command {
+            command: Command::PrintState,
+        }, // 4
+    ];
+    TestHarness::new()
+        .with_windows(3)
+        .on_iteration(2, |world, state| spawn_new_window(world, &state, 10))
+        .on_iteration(3, |world, state| {
+            assert_eq!(column_of(world, 10), Some(0));
+            assert_eq!(column_of(world, 1), Some(1));
+            // The directive is gone, so the next window opens in a column of its own.
+            spawn_new_window(world, &state, 11);
+        })
+        .on_iteration(4, |world, _state| {
+            assert_ne!(column_of(world, 11), column_of(world, 0));
+        })
+        .run(commands);
+}
+
+#[test]
+fn test_auto_stack_same_app_joins_focused_column() {
+    let config: Config = (
+        MainOptions {
+            auto_stack_same_app: Some(true),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(2)
+        .with_focused_window(0)
+        .on_iteration(0, |world, state| {
+            spawn_new_window(world, &state, 10);
+        })
+        .on_iteration(2, |world, _state| {
+            assert_eq!(column_of(world, 10), column_of(world, 0));
+            assert_eq!(column_of(world, 1), Some(1));
+        })
+        .run(spawn_events(0));
+}
+
+#[test]
+fn test_max_columns_stack_last_stacks_new_windows_onto_last_column() {
+    let config: Config = (
+        MainOptions {
+            max_columns: Some(2),
+            max_columns_policy: Some(MaxColumnsPolicy::StackLast),
+            ..Default::default()
+        },
+        vec![],
+    )
+        .into();
+
+    TestHarness::new()
+        .with_config(config)
+        .with_windows(2)
+        .with_focused_window(0)
+        .on_iteration(0, |world, state| {
+            spawn_new_window(world, &state, 10);
+        })
+        .on_iteration(2, |world, _state| {
+            // Focus was on the first column, yet the new window joins the last one.
+            assert_eq!(column_of(world, 0), Some(0));
+            assert_eq!(column_of(world, 10), Some(1));
+            assert_eq!(column_of(world, 1), Some(1));
+        })
+        .run(spawn_events(0));
+}
@@ -16,16 +16,106 @@ use std::{
     os::unix::ffi::OsStrExt,
     path::{Path, PathBuf},
     ptr::null_mut,
+    sync::OnceLock,
 };
 use stdext::function_name;
+use tracing::Subscriber;
 use tracing::debug;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::{EnvFilter, Layer, Registry, fmt, reload};
 
 use crate::{
+    config::{Config, LogFormat, LogRotation},
     errors::{Error, Result},
     manager::{AXUIElementCopyAttributeValue, ax_window_id},
     platform::{OSStatus, WinID},
 };
 
+/// Reload handle of the log filter, so the `loglevel` command can change it at runtime.
+static LOG_FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Wraps the initial log `filter` into a reloadable layer and keeps its handle for
+/// `set_log_filter`.
+pub fn reloadable_log_filter(filter: EnvFilter) -> reload::Layer<EnvFilter, Registry> {
+    let (layer, handle) = reload::Layer::new(filter);
+    _ = LOG_FILTER.set(handle);
+    layer
+}
+
+/// Replaces the log filter with new `directives`, using the same syntax as `RUST_LOG`,
+/// e.g. `debug` or `paneru=trace`.
+///
+/// # Returns
+///
+/// `Ok(())` if the filter was swapped, otherwise `Err(Error)` for invalid directives or when no
+/// reloadable filter is installed.
+pub fn set_log_filter(directives: &str) -> Result<()> {
+    let filter = EnvFilter::try_new(directives).map_err(|err| {
+        Error::InvalidInput(format!(
+            "{}: invalid log filter '{directives}': {err}",
+            function_name!()
+        ))
+    })?;
+    LOG_FILTER
+        .get()
+        .ok_or(Error::NotFound(format!(
+            "{}: no reloadable log filter installed.",
+            function_name!()
+        )))?
+        .reload(filter)
+        .map_err(|err| Error::Generic(format!("{}: {err}", function_name!())))
+}
+
+/// Number of rotated log files kept in the log directory.
+const MAX_LOG_FILES: usize = 7;
+
+/// Builds the layer writing the log into rotating files under `~/Library/Logs/paneru/`, if the
+/// `log_file` option is enabled.
+///
+/// # Returns
+///
+/// `Ok` with the file logging layer, or `None` if file logging is disabled, otherwise
+/// `Err(Error)` if the log directory can not be set up. The error is returned rather than
+/// logged, since logging is not up yet.
+pub fn log_file_layer<S>(config: &Config) -> Result<Option<Box<dyn Layer<S> + Send + Sync>>>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    if !config.log_file() {
+        return Ok(None);
+    }
+    let Some(home) = std::env::home_dir() else {
+        return Ok(None);
+    };
+    let directory = home.join("Library/Logs/paneru");
+    let rotation = match config.log_rotation() {
+        LogRotation::Hourly => Rotation::HOURLY,
+        LogRotation::Daily => Rotation::DAILY,
+        LogRotation::Never => Rotation::NEVER,
+    };
+    let appender = RollingFileAppender::builder()
+        .rotation(rotation)
+        .filename_prefix("paneru")
+        .filename_suffix("log")
+        .max_log_files(MAX_LOG_FILES)
+        .build(&directory)
+        .map_err(|err| {
+            Error::Generic(format!("unable to log to {}: {err}", directory.display()))
+        })?;
+
+    let layer = fmt::layer()
+        .with_ansi(false)
+        .with_line_number(true)
+        .with_file(true)
+        .with_target(true)
+        .with_writer(appender);
+    Ok(Some(match config.log_format() {
+        LogFormat::Compact => layer.compact().boxed(),
+        LogFormat::Json => layer.json().boxed(),
+    }))
+}
+
 /// Returns `true` if macOS is currently in Dark Mode.
 pub fn is_dark_mode() -> bool {
     autoreleasepool(|_| {
@@ -167,6 +257,12 @@ pub trait AXUIAttributes {
             .map(|value| value.to_string())
     }
 
+    fn document(&self) -> Result<String> {
+        let axname = CFString::from_str("AXDocument");
+        self.get_attribute::<CFString>(&axname)
+            .map(|value| value.to_string())
+    }
+
     fn minimized(&self) -> Result<bool> {
         let axname = CFString::from_static_str(kAXMinimizedAttribute);
         self.get_attribute::<CFBoolean>(&axname)
@@ -322,6 +418,56 @@ pub fn exe_path() -> Option<PathBuf> {
     Some(OsStr::from_bytes(path.to_bytes()).into())
 }
 
+/// Starts `command` without waiting for it. The child is reaped on a background thread, so it
+/// does not linger as a zombie and a slow program never stalls the caller.
+pub fn spawn_and_reap(command: &mut std::process::Command) -> std::io::Result<()> {
+    let mut child = command.spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Expands a leading `~` to the home directory and `$VAR` or `${VAR}` references to the values
+/// of environment variables, so paths in the configuration are portable across machines.
+/// References to unset variables are kept as written.
+pub fn expand_env(input: &str) -> String {
+    let mut expanded = String::with_capacity(input.len());
+    let mut remaining = match (input.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => {
+            expanded.push_str(&home);
+            rest
+        }
+        _ => input,
+    };
+
+    while let Some(start) = remaining.find('$') {
+        expanded.push_str(&remaining[..start]);
+        let after = &remaining[start + 1..];
+        let (name, consumed) = match after.strip_prefix('{') {
+            Some(braced) => braced
+                .find('}')
+                .map_or(("", 0), |end| (&braced[..end], end + 2)),
+            None => {
+                let end = after
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        match std::env::var(name) {
+            Ok(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => expanded.push_str(&remaining[start..=start + consumed]),
+        }
+        remaining = &after[consumed..];
+    }
+    expanded.push_str(remaining);
+    expanded
+}
+
+/// Returns `path` with `~` and environment variables expanded, see [`expand_env`].
+pub fn expand_path(path: &str) -> PathBuf {
+    PathBuf::from(expand_env(path))
+}
+
 pub fn symlink_target(path: &Path) -> Option<PathBuf> {
     if let Ok(metadata) = std::fs::symlink_metadata(path)
         && metadata.file_type().is_symlink()